@@ -0,0 +1,351 @@
+//! The flags and arguments shared by the subcommands
+//!
+//! Each subcommand is described by a `Command`: its name, its positional arguments, and its flags.
+//! The same description is used to parse the command line and to generate the `--help`, so they can't disagree.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A flag of a command, e.g. `-i, --ignore-case`, or `--threads <N>` with a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Flag {
+    pub long: &'static str,
+    pub short: Option<char>,
+    /// Name of the value shown in the help, `None` for the flags without a value
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+/// A positional argument of a command, e.g. `QUERY`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arg {
+    pub name: &'static str,
+    pub help: &'static str,
+}
+
+/// Every command accepts `--help`, it's added to the flags by the parsing and the help
+pub const HELP: Flag = Flag {
+    long: "help",
+    short: Some('h'),
+    value: None,
+    help: "Print the help of the command",
+};
+
+/// The description of a command, used to parse its arguments and to generate its help
+#[derive(Debug, Clone, Copy)]
+pub struct Command {
+    pub name: &'static str,
+    /// One line about what the command does, shown in the help and in the list of the commands
+    pub about: &'static str,
+    pub args: &'static [Arg],
+    pub flags: &'static [Flag],
+}
+
+/// Error of the parsing, the command line doesn't match the description of the command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgsError {
+    /// `--help` was given, it's not a mistake but the command must not run
+    Help,
+    UnknownFlag(String),
+    MissingValue(&'static str),
+    /// A flag without a value was given one, e.g. `--ignore-case=yes`
+    UnexpectedValue(&'static str),
+    InvalidValue {
+        flag: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+    MissingArg(&'static str),
+    UnexpectedArg(String),
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgsError::Help => write!(f, "help requested"),
+            ArgsError::UnknownFlag(flag) => write!(f, "unknown flag `{flag}`"),
+            ArgsError::MissingValue(flag) => write!(f, "the flag `--{flag}` needs a value"),
+            ArgsError::UnexpectedValue(flag) => write!(f, "the flag `--{flag}` takes no value"),
+            ArgsError::InvalidValue {
+                flag,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value `{value}` for `--{flag}`, expected {expected}"
+            ),
+            ArgsError::MissingArg(name) => write!(f, "missing the {name} argument"),
+            ArgsError::UnexpectedArg(arg) => write!(f, "unexpected argument `{arg}`"),
+        }
+    }
+}
+
+impl Error for ArgsError {}
+
+/// The command line of a command after the parsing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Args {
+    /// The flags given, by their long name, with their values, in the order of the command line
+    pub flags: Vec<(&'static str, Option<String>)>,
+    /// The positional arguments, one for each `Arg` of the command
+    pub args: Vec<String>,
+}
+
+impl Args {
+    /// Whether the flag was given at least once
+    pub fn has(&self, flag: &str) -> bool {
+        self.flags.iter().any(|(name, _)| *name == flag)
+    }
+
+    /// The value of the flag, the last one if it was given more than once
+    pub fn value<'a>(&'a self, flag: &'a str) -> Option<&'a str> {
+        self.values(flag).last()
+    }
+
+    /// All the values given to the flag, in order
+    pub fn values<'a>(&'a self, flag: &'a str) -> impl Iterator<Item = &'a str> {
+        self.flags
+            .iter()
+            .filter(move |(name, _)| *name == flag)
+            .filter_map(|(_, value)| value.as_deref())
+    }
+
+    /// The value of the flag parsed as `T`, or `default` if the flag wasn't given
+    pub fn parse_value<T: FromStr>(
+        &self,
+        flag: &'static str,
+        default: T,
+        expected: &'static str,
+    ) -> Result<T, ArgsError> {
+        match self.value(flag) {
+            None => Ok(default),
+            Some(value) => value.parse().map_err(|_| ArgsError::InvalidValue {
+                flag,
+                value: String::from(value),
+                expected,
+            }),
+        }
+    }
+}
+
+impl Command {
+    fn flag(&self, matches: impl Fn(&Flag) -> bool) -> Option<Flag> {
+        self.flags
+            .iter()
+            .chain([&HELP])
+            .find(|flag| matches(flag))
+            .copied()
+    }
+
+    /// Parses the arguments after the name of the command
+    ///
+    /// The values follow their flag, as `--threads 4`, `--threads=4`, or `-t 4`.
+    /// After `--` everything is a positional argument, even if it starts with `-`.
+    pub fn parse(&self, args: impl IntoIterator<Item = String>) -> Result<Args, ArgsError> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                parsed.args.extend(args.by_ref());
+                break;
+            }
+            let (flag, inline) = if let Some(long) = arg.strip_prefix("--") {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(String::from(value))),
+                    None => (long, None),
+                };
+                (self.flag(|flag| flag.long == name), inline)
+            } else if let Some(short) = arg
+                .strip_prefix('-')
+                .filter(|short| short.chars().count() == 1)
+            {
+                let short = short.chars().next();
+                (self.flag(|flag| flag.short == short), None)
+            } else {
+                // A lone `-` is an argument, often meaning the standard input
+                parsed.args.push(arg);
+                continue;
+            };
+
+            let flag = flag.ok_or_else(|| ArgsError::UnknownFlag(arg.clone()))?;
+            if flag == HELP {
+                return Err(ArgsError::Help);
+            }
+            let value = match (flag.value, inline) {
+                (None, None) => None,
+                (None, Some(_)) => return Err(ArgsError::UnexpectedValue(flag.long)),
+                (Some(_), Some(value)) => Some(value),
+                (Some(_), None) => Some(args.next().ok_or(ArgsError::MissingValue(flag.long))?),
+            };
+            parsed.flags.push((flag.long, value));
+        }
+
+        if let Some(missing) = self.args.get(parsed.args.len()) {
+            return Err(ArgsError::MissingArg(missing.name));
+        }
+        if let Some(unexpected) = parsed.args.get(self.args.len()) {
+            return Err(ArgsError::UnexpectedArg(unexpected.clone()));
+        }
+        Ok(parsed)
+    }
+
+    /// The usage line, e.g. `Usage: book grep [OPTIONS] QUERY FILE`
+    pub fn usage(&self, program: &str) -> String {
+        let mut usage = format!("Usage: {program} {} [OPTIONS]", self.name);
+        for arg in self.args {
+            usage.push(' ');
+            usage.push_str(arg.name);
+        }
+        usage
+    }
+
+    /// The whole help: what the command does, the usage, and the arguments and flags with their descriptions
+    pub fn help(&self, program: &str) -> String {
+        let args: Vec<(String, &str)> = self
+            .args
+            .iter()
+            .map(|arg| (String::from(arg.name), arg.help))
+            .collect();
+        let flags: Vec<(String, &str)> = self
+            .flags
+            .iter()
+            .chain([&HELP])
+            .map(|flag| {
+                let short = flag
+                    .short
+                    .map(|short| format!("-{short}, "))
+                    .unwrap_or_default();
+                let value = flag
+                    .value
+                    .map(|value| format!(" <{value}>"))
+                    .unwrap_or_default();
+                (format!("{short:>4}--{}{value}", flag.long), flag.help)
+            })
+            .collect();
+
+        let mut help = format!("{}\n\n{}\n", self.about, self.usage(program));
+        if !args.is_empty() {
+            help.push_str("\nArguments:\n");
+            help.push_str(&table(&args));
+        }
+        help.push_str("\nOptions:\n");
+        help.push_str(&table(&flags));
+        help
+    }
+}
+
+/// Lines of two columns, the descriptions aligned after the longest name
+pub fn table(rows: &[(String, &str)]) -> String {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    rows.iter()
+        .map(|(name, help)| format!("  {name:width$}  {help}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GREP: Command = Command {
+        name: "grep",
+        about: "Search a file",
+        args: &[
+            Arg {
+                name: "QUERY",
+                help: "The text to search",
+            },
+            Arg {
+                name: "FILE",
+                help: "The file to search in",
+            },
+        ],
+        flags: &[
+            Flag {
+                long: "ignore-case",
+                short: Some('i'),
+                value: None,
+                help: "Search ignoring the case",
+            },
+            Flag {
+                long: "threads",
+                short: None,
+                value: Some("N"),
+                help: "Number of threads",
+            },
+        ],
+    };
+
+    fn parse(args: &[&str]) -> Result<Args, ArgsError> {
+        GREP.parse(args.iter().map(|arg| String::from(*arg)))
+    }
+
+    #[test]
+    fn parses_flags_and_arguments() {
+        let args = parse(&["-i", "to", "--threads", "4", "poem.txt", "--threads=8"]).unwrap();
+        assert_eq!(args.args, ["to", "poem.txt"]);
+        assert!(args.has("ignore-case"));
+        assert_eq!(args.value("threads"), Some("8"));
+        assert_eq!(args.values("threads").collect::<Vec<_>>(), ["4", "8"]);
+        assert_eq!(args.parse_value("threads", 1, "a number"), Ok(8));
+
+        let args = parse(&["--", "-i", "-"]).unwrap();
+        assert_eq!(args.args, ["-i", "-"]);
+        assert!(!args.has("ignore-case"));
+        assert_eq!(args.parse_value("threads", 1, "a number"), Ok(1));
+    }
+
+    #[test]
+    fn rejects_what_the_command_does_not_describe() {
+        assert_eq!(parse(&["to", "poem.txt", "-h"]), Err(ArgsError::Help));
+        assert_eq!(parse(&["--help"]), Err(ArgsError::Help));
+        assert_eq!(
+            parse(&["--ignore", "to", "poem.txt"]),
+            Err(ArgsError::UnknownFlag(String::from("--ignore")))
+        );
+        assert_eq!(
+            parse(&["to", "poem.txt", "--threads"]),
+            Err(ArgsError::MissingValue("threads"))
+        );
+        assert_eq!(
+            parse(&["--ignore-case=yes", "to", "poem.txt"]),
+            Err(ArgsError::UnexpectedValue("ignore-case"))
+        );
+        assert_eq!(parse(&["to"]), Err(ArgsError::MissingArg("FILE")));
+        assert_eq!(
+            parse(&["to", "poem.txt", "more.txt"]),
+            Err(ArgsError::UnexpectedArg(String::from("more.txt")))
+        );
+        assert_eq!(
+            parse(&["--threads", "four", "to", "poem.txt"])
+                .unwrap()
+                .parse_value("threads", 1, "a number"),
+            Err(ArgsError::InvalidValue {
+                flag: "threads",
+                value: String::from("four"),
+                expected: "a number"
+            })
+        );
+    }
+
+    #[test]
+    fn help_is_generated_from_the_description() {
+        assert_eq!(
+            GREP.help("book"),
+            "Search a file
+
+Usage: book grep [OPTIONS] QUERY FILE
+
+Arguments:
+  QUERY  The text to search
+  FILE   The file to search in
+
+Options:
+  -i, --ignore-case  Search ignoring the case
+      --threads <N>  Number of threads
+  -h, --help         Print the help of the command
+"
+        );
+    }
+}
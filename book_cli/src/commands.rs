@@ -0,0 +1,217 @@
+//! The subcommands, each one with the description of its command line and the function running it
+
+use std::env;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+
+use book_errors::BookError;
+use c10_generics_traits_lifetimes::{Feed, NewsArticle, Tweet};
+use c12_minigrep::Config;
+use c18_object_oriented_programming::blog::Post;
+use c21_web_server::ThreadPool;
+use c21_web_server::feed;
+use c21_web_server::router::{Method, Router};
+use c21_web_server::search::Search;
+
+use crate::CliError;
+use crate::args::{Arg, Args, ArgsError, Command, Flag};
+
+pub const GREP: Command = Command {
+    name: "grep",
+    about: "Print the lines of a file containing the query, with minigrep",
+    args: &[
+        Arg {
+            name: "QUERY",
+            help: "The text to search",
+        },
+        Arg {
+            name: "FILE",
+            help: "The file to search in",
+        },
+    ],
+    flags: &[Flag {
+        long: "ignore-case",
+        short: Some('i'),
+        value: None,
+        help: "Ignore the case, also enabled by the IGNORE_CASE variable",
+    }],
+};
+
+/// Runs minigrep, the matching lines are printed by `c12_minigrep::run`
+pub fn grep(args: &Args) -> Result<(), CliError> {
+    let config = Config {
+        query: args.args[0].clone(),
+        file_path: args.args[1].clone(),
+        ignore_case: args.has("ignore-case") || env::var("IGNORE_CASE").is_ok(),
+    };
+    c12_minigrep::run(config).map_err(BookError::from)?;
+    Ok(())
+}
+
+pub const SERVE: Command = Command {
+    name: "serve",
+    about: "Run the web server, with the /search and /feed routes",
+    args: &[],
+    flags: &[
+        Flag {
+            long: "address",
+            short: Some('a'),
+            value: Some("ADDRESS"),
+            help: "The address to listen on, 127.0.0.1:7878 by default",
+        },
+        Flag {
+            long: "threads",
+            short: Some('t'),
+            value: Some("N"),
+            help: "The number of threads handling the requests, and running the searches, 4 by default",
+        },
+        Flag {
+            long: "root",
+            short: None,
+            value: Some("DIR"),
+            help: "The directory of the files searched by /search, utils by default",
+        },
+        Flag {
+            long: "requests",
+            short: None,
+            value: Some("N"),
+            help: "Stop after N requests, like the server of the chapter does after 10",
+        },
+    ],
+};
+
+/// Serves the search of the files of `--root` and an empty feed, until `--requests` are handled
+///
+/// The address is printed once the server is listening, with the port chosen by the system if it was `0`.
+pub fn serve(args: &Args) -> Result<(), CliError> {
+    let address = args.value("address").unwrap_or("127.0.0.1:7878");
+    let threads = args.parse_value("threads", 4, "a number of threads")?;
+    let requests = args.parse_value("requests", usize::MAX, "a number of requests")?;
+    let root = args.value("root").unwrap_or("utils");
+
+    // Built first, so a wrong number of threads is reported before anything else starts
+    let pool = ThreadPool::build(threads).map_err(BookError::from)?;
+    let search = Search::new(root, threads);
+    let mut router = Router::new();
+    router.stream_route(Method::Get, "/search", move |request| {
+        search.handle(request)
+    });
+    feed::routes(&mut router, Arc::new(Mutex::new(Feed::new())));
+    let router = Arc::new(router);
+
+    let listener = TcpListener::bind(address).map_err(BookError::from)?;
+    let address = listener.local_addr().map_err(BookError::from)?;
+    println!("Listening on http://{address}");
+
+    for stream in listener.incoming().take(requests) {
+        // A connection that failed doesn't stop the server, the next one may succeed
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                book_log::warn!("connection failed"; error = error);
+                continue;
+            }
+        };
+        let router = Arc::clone(&router);
+        pool.execute(move || {
+            router.handle_connection(stream);
+        });
+    }
+    Ok(())
+}
+
+pub const FEED: Command = Command {
+    name: "feed",
+    about: "Print the summaries of tweets and articles, with the media aggregator",
+    args: &[],
+    flags: &[
+        Flag {
+            long: "tweet",
+            short: Some('t'),
+            value: Some("USER=TEXT"),
+            help: "Add a tweet to the feed, can be repeated",
+        },
+        Flag {
+            long: "article",
+            short: Some('a'),
+            value: Some("AUTHOR=HEADLINE"),
+            help: "Add an article to the feed, can be repeated",
+        },
+    ],
+};
+
+/// Pushes the items in the order of the command line, and prints their summaries
+pub fn feed(args: &Args) -> Result<(), CliError> {
+    let mut feed = Feed::new();
+    for (flag, value) in &args.flags {
+        let value = value.as_deref().unwrap_or_default();
+        let (name, text) = value
+            .split_once('=')
+            .ok_or_else(|| ArgsError::InvalidValue {
+                flag,
+                value: String::from(value),
+                expected: "a name and a text separated by `=`",
+            })?;
+        match *flag {
+            "tweet" => feed.push(Tweet {
+                username: String::from(name),
+                content: String::from(text),
+                reply: false,
+                retweet: false,
+            }),
+            _ => feed.push(NewsArticle {
+                headline: String::from(text),
+                location: String::new(),
+                author: String::from(name),
+                content: String::new(),
+            }),
+        }
+    }
+
+    for summary in feed.summaries() {
+        println!("{summary}");
+    }
+    Ok(())
+}
+
+pub const BLOG: Command = Command {
+    name: "blog",
+    about: "Take a blog post through its workflow, and print its state and content",
+    args: &[Arg {
+        name: "TEXT",
+        help: "The text of the post",
+    }],
+    flags: &[
+        Flag {
+            long: "review",
+            short: Some('r'),
+            value: None,
+            help: "Request a review of the post",
+        },
+        Flag {
+            long: "approve",
+            short: Some('a'),
+            value: None,
+            help: "Approve the post, once it's been reviewed",
+        },
+    ],
+};
+
+/// Takes the steps of the workflow in the order of the command line
+///
+/// A step the post can't take from its state is an error, instead of being ignored like `Post::approve` does.
+pub fn blog(args: &Args) -> Result<(), CliError> {
+    let mut post = Post::new();
+    post.add_text(&args.args[0]);
+    for (flag, _) in &args.flags {
+        let step = match *flag {
+            "review" => post.try_request_review(),
+            _ => post.try_approve(),
+        };
+        step.map_err(BookError::from)?;
+    }
+
+    println!("state: {}", post.state());
+    println!("content: {}", post.content());
+    Ok(())
+}
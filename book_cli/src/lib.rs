@@ -0,0 +1,203 @@
+//! # Book CLI
+//!
+//! A single command line for the programs of the chapters, with a subcommand for each of them:
+//! - `book grep QUERY FILE`: minigrep, from chapter 12.
+//! - `book serve`: the web server of chapter 21, with the search and the feed routes.
+//! - `book feed`: the summaries of the media aggregator, from chapter 10.
+//! - `book blog TEXT`: the workflow of a blog post, from chapter 18.
+//!
+//! The subcommands parse their flags in the same way, with the `args` module, and every one of them has a `--help`.
+//! They also exit with the same codes: `SUCCESS`, `FAILURE` when a chapter returns an error, and `USAGE` for a wrong command line.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use book_errors::BookError;
+use book_log::{Level, StderrSink};
+
+// The description of the command lines, shared by the subcommands
+pub mod args;
+// The subcommands, running the chapters
+pub mod commands;
+
+use args::{ArgsError, Command};
+
+/// The name of the program in the usage and help
+pub const PROGRAM: &str = "book";
+
+/// The command ran, or only printed its help
+pub const SUCCESS: u8 = 0;
+/// The chapter running the command returned an error
+pub const FAILURE: u8 = 1;
+/// The command line doesn't match the description of the command, nothing ran
+pub const USAGE: u8 = 2;
+
+/// Error of a subcommand: either the command line or the chapter it runs
+#[derive(Debug)]
+pub enum CliError {
+    Usage(ArgsError),
+    Book(BookError),
+}
+
+impl CliError {
+    /// The code the program exits with, `USAGE` or `FAILURE`
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            CliError::Usage(_) => USAGE,
+            CliError::Book(_) => FAILURE,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CliError::Usage(error) => write!(f, "{error}"),
+            CliError::Book(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for CliError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CliError::Usage(error) => Some(error),
+            CliError::Book(error) => Some(error),
+        }
+    }
+}
+
+impl From<ArgsError> for CliError {
+    fn from(error: ArgsError) -> CliError {
+        CliError::Usage(error)
+    }
+}
+
+impl From<BookError> for CliError {
+    fn from(error: BookError) -> CliError {
+        CliError::Book(error)
+    }
+}
+
+/// A subcommand: the description of its command line, and the function running it
+pub struct Subcommand {
+    pub command: Command,
+    pub run: fn(&args::Args) -> Result<(), CliError>,
+}
+
+/// The subcommands, in the order of the help
+pub const SUBCOMMANDS: [Subcommand; 4] = [
+    Subcommand {
+        command: commands::GREP,
+        run: commands::grep,
+    },
+    Subcommand {
+        command: commands::SERVE,
+        run: commands::serve,
+    },
+    Subcommand {
+        command: commands::FEED,
+        run: commands::feed,
+    },
+    Subcommand {
+        command: commands::BLOG,
+        run: commands::blog,
+    },
+];
+
+/// The help of the program itself, listing the subcommands
+pub fn help() -> String {
+    let commands: Vec<(String, &str)> = SUBCOMMANDS
+        .iter()
+        .map(|subcommand| {
+            (
+                String::from(subcommand.command.name),
+                subcommand.command.about,
+            )
+        })
+        .collect();
+    let options = [
+        (
+            String::from("-v, --verbose"),
+            "Print the events of the chapters on the standard error",
+        ),
+        (String::from("-h, --help"), "Print this help"),
+    ];
+    format!(
+        "The programs of the chapters of the book, behind a single command line\n\n\
+         Usage: {PROGRAM} [OPTIONS] COMMAND [ARGS]\n\n\
+         Commands:\n{}\n\
+         Options:\n{}\n\
+         Run `{PROGRAM} COMMAND --help` for the arguments and options of a command.\n",
+        args::table(&commands),
+        args::table(&options)
+    )
+}
+
+/// Runs the command line, without the name of the program, and returns the code to exit with
+///
+/// The help goes to the standard output, the errors to the standard error, with the usage of the command after a mistake.
+pub fn run(args: impl IntoIterator<Item = String>) -> u8 {
+    let mut args = args.into_iter();
+    let mut verbose = false;
+    // The options of the program come before the name of the subcommand, the rest belongs to the subcommand
+    let name = loop {
+        match args.next().as_deref() {
+            Some("-h" | "--help") => {
+                print!("{}", help());
+                return SUCCESS;
+            }
+            Some("-v" | "--verbose") => verbose = true,
+            Some(flag) if flag.starts_with('-') => {
+                return usage_error(&ArgsError::UnknownFlag(String::from(flag)), None);
+            }
+            Some(name) => break String::from(name),
+            None => return usage_error(&ArgsError::MissingArg("COMMAND"), None),
+        }
+    };
+    let Some(subcommand) = SUBCOMMANDS
+        .iter()
+        .find(|subcommand| subcommand.command.name == name)
+    else {
+        return usage_error(&ArgsError::UnexpectedArg(name), None);
+    };
+
+    if verbose {
+        book_log::set_sink(Arc::new(StderrSink));
+        book_log::set_max_level(Level::Debug);
+    }
+
+    let command = &subcommand.command;
+    let result = command
+        .parse(args)
+        .map_err(CliError::from)
+        .and_then(|args| (subcommand.run)(&args));
+    match result {
+        Ok(()) => SUCCESS,
+        Err(CliError::Usage(ArgsError::Help)) => {
+            print!("{}", command.help(PROGRAM));
+            SUCCESS
+        }
+        Err(CliError::Usage(error)) => usage_error(&error, Some(command)),
+        Err(error) => {
+            eprintln!("error: {error}");
+            error.exit_code()
+        }
+    }
+}
+
+fn usage_error(error: &ArgsError, command: Option<&Command>) -> u8 {
+    let (usage, help) = match command {
+        Some(command) => (
+            command.usage(PROGRAM),
+            format!("{PROGRAM} {} --help", command.name),
+        ),
+        None => (
+            format!("Usage: {PROGRAM} [OPTIONS] COMMAND [ARGS]"),
+            format!("{PROGRAM} --help"),
+        ),
+    };
+    eprintln!("error: {error}\n\n{usage}\n\nFor more information, run `{help}`.");
+    USAGE
+}
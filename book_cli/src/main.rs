@@ -0,0 +1,7 @@
+use std::env;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    // The name of the program is skipped, the help always calls it `book`
+    ExitCode::from(book_cli::run(env::args().skip(1)))
+}
@@ -0,0 +1,159 @@
+//! The subcommands are run through the binary, to check what they print and the code they exit with
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Output, Stdio};
+
+use book_cli::{FAILURE, SUCCESS, USAGE};
+
+fn book(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_book"))
+        .args(args)
+        // The tests run in the directory of the crate, the files of the chapters are one level up
+        .current_dir("..")
+        .env_remove("IGNORE_CASE")
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> &str {
+    std::str::from_utf8(&output.stdout).unwrap()
+}
+
+fn stderr(output: &Output) -> &str {
+    std::str::from_utf8(&output.stderr).unwrap()
+}
+
+fn code(output: &Output) -> u8 {
+    output.status.code().unwrap() as u8
+}
+
+#[test]
+fn grep_prints_the_matching_lines() {
+    let output = book(&["grep", "to", "utils/poem.txt"]);
+    assert_eq!(code(&output), SUCCESS);
+    assert_eq!(
+        stdout(&output),
+        "Are you nobody, too?\nHow dreary to be somebody!\n"
+    );
+
+    let output = book(&["grep", "-i", "to", "utils/poem.txt"]);
+    assert_eq!(stdout(&output).lines().count(), 4);
+
+    let output = book(&["grep", "to", "utils/poem.md"]);
+    assert_eq!(code(&output), FAILURE);
+    assert!(stderr(&output).starts_with("error: I/O error: "));
+}
+
+#[test]
+fn feed_and_blog_run_their_chapters() {
+    let output = book(&[
+        "feed",
+        "-t",
+        "ferris=Hello!",
+        "--article",
+        "Rust Team=Rust 2024",
+    ]);
+    assert_eq!(code(&output), SUCCESS);
+    assert_eq!(
+        stdout(&output),
+        "ferris: Hello!\n(Read more from Rust Team...)\n"
+    );
+
+    let output = book(&[
+        "blog",
+        "--review",
+        "--approve",
+        "I ate a salad for lunch today",
+    ]);
+    assert_eq!(code(&output), SUCCESS);
+    assert_eq!(
+        stdout(&output),
+        "state: published\ncontent: I ate a salad for lunch today\n"
+    );
+
+    let output = book(&["blog", "--approve", "salad"]);
+    assert_eq!(code(&output), FAILURE);
+    assert_eq!(
+        stderr(&output),
+        "error: blog: can't approve a post in the draft state\n"
+    );
+}
+
+#[test]
+fn help_is_printed_for_every_command() {
+    let output = book(&["--help"]);
+    assert_eq!(code(&output), SUCCESS);
+    for command in ["grep", "serve", "feed", "blog"] {
+        assert!(stdout(&output).contains(&format!("\n  {command} ")));
+
+        let output = book(&[command, "--help"]);
+        assert_eq!(code(&output), SUCCESS);
+        assert!(stdout(&output).contains(&format!("\nUsage: book {command} [OPTIONS]")));
+        assert!(stdout(&output).contains("  -h, --help "));
+    }
+}
+
+#[test]
+fn mistakes_exit_with_the_usage() {
+    for args in [
+        &[][..],
+        &["--quiet"],
+        &["find", "to"],
+        &["grep", "to"],
+        &["grep", "--regex", "to", "utils/poem.txt"],
+        &["feed", "--tweet", "ferris"],
+        &["serve", "--threads", "many"],
+    ] {
+        let output = book(args);
+        assert_eq!(code(&output), USAGE, "{args:?}");
+        assert!(stdout(&output).is_empty());
+        assert!(stderr(&output).contains("\n\nUsage: book "), "{args:?}");
+    }
+
+    let output = book(&["grep", "to"]);
+    assert_eq!(
+        stderr(&output),
+        "error: missing the FILE argument\n\n\
+         Usage: book grep [OPTIONS] QUERY FILE\n\n\
+         For more information, run `book grep --help`.\n"
+    );
+}
+
+#[test]
+fn serve_answers_the_requests() {
+    let output = book(&["serve", "--threads", "0"]);
+    assert_eq!(code(&output), FAILURE);
+    assert_eq!(
+        stderr(&output),
+        "error: web server: a thread pool needs at least one thread\n"
+    );
+
+    // The port is chosen by the system, and printed by the server
+    let mut server = Command::new(env!("CARGO_BIN_EXE_book"))
+        .args(["serve", "--address", "127.0.0.1:0", "--requests", "1"])
+        .args(["--root", "utils", "--threads", "1"])
+        .current_dir("..")
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut output = BufReader::new(server.stdout.take().unwrap());
+    let mut line = String::new();
+    output.read_line(&mut line).unwrap();
+    let address = line.trim().strip_prefix("Listening on http://").unwrap();
+
+    let mut stream = TcpStream::connect(address).unwrap();
+    stream
+        .write_all(b"GET /search?q=Who&path=poem.txt HTTP/1.1\r\n\r\n")
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains(r#""text":"I'm nobody! Who are you?""#));
+
+    // The workers of the pool print what they do, the output stays open until the server exits
+    let mut rest = String::new();
+    output.read_to_string(&mut rest).unwrap();
+    assert!(rest.contains("Shutting down worker 0"));
+    assert_eq!(server.wait().unwrap().code(), Some(SUCCESS.into()));
+}
@@ -0,0 +1,172 @@
+//! # Book errors
+//!
+//! A single error type for the programs that use several chapters at once, like a command line running both minigrep and the web server.
+//! Every chapter keeps its own error type, and `BookError` has a `From` conversion for each of them, so `?` works across the chapters.
+//! The messages are prefixed with the part of the book they come from, so they read the same whatever the subsystem.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use c12_minigrep::ConfigError;
+use c18_object_oriented_programming::blog::WorkflowError;
+use c21_web_server::PoolCreationError;
+
+/// Any error of the chapters, with the original error as its source
+#[derive(Debug)]
+pub enum BookError {
+    /// Reading or writing a file or a connection
+    Io(io::Error),
+    /// The arguments of minigrep
+    Config(ConfigError),
+    /// Starting the thread pool of the web server
+    Pool(PoolCreationError),
+    /// A step of the blog workflow
+    Workflow(WorkflowError),
+    /// Any other error, e.g. the `Box<dyn Error>` returned by `c12_minigrep::run`
+    Other(Box<dyn Error>),
+}
+
+/// `Result` with `BookError` as the error, for the functions mixing the chapters
+pub type Result<T> = std::result::Result<T, BookError>;
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BookError::Io(error) => write!(f, "I/O error: {error}"),
+            BookError::Config(error) => write!(f, "minigrep: {error}"),
+            BookError::Pool(error) => write!(f, "web server: {error}"),
+            BookError::Workflow(error) => write!(f, "blog: {error}"),
+            BookError::Other(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for BookError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BookError::Io(error) => Some(error),
+            BookError::Config(error) => Some(error),
+            BookError::Pool(error) => Some(error),
+            BookError::Workflow(error) => Some(error),
+            BookError::Other(error) => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<io::Error> for BookError {
+    fn from(error: io::Error) -> BookError {
+        BookError::Io(error)
+    }
+}
+
+impl From<ConfigError> for BookError {
+    fn from(error: ConfigError) -> BookError {
+        BookError::Config(error)
+    }
+}
+
+impl From<PoolCreationError> for BookError {
+    fn from(error: PoolCreationError) -> BookError {
+        BookError::Pool(error)
+    }
+}
+
+impl From<WorkflowError> for BookError {
+    fn from(error: WorkflowError) -> BookError {
+        BookError::Workflow(error)
+    }
+}
+
+/// A boxed error of a known type is unboxed into its own variant, so it's displayed like the others
+impl From<Box<dyn Error>> for BookError {
+    fn from(error: Box<dyn Error>) -> BookError {
+        let error = match error.downcast::<io::Error>() {
+            Ok(error) => return BookError::Io(*error),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<ConfigError>() {
+            Ok(error) => return BookError::Config(*error),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<PoolCreationError>() {
+            Ok(error) => return BookError::Pool(*error),
+            Err(error) => error,
+        };
+        match error.downcast::<WorkflowError>() {
+            Ok(error) => BookError::Workflow(*error),
+            Err(error) => BookError::Other(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c12_minigrep::Config;
+    use c18_object_oriented_programming::blog::Post;
+    use c21_web_server::ThreadPool;
+
+    fn grep(args: &[&str]) -> Result<()> {
+        let config = Config::build(args.iter().map(|arg| String::from(*arg)))?;
+        c12_minigrep::run(config)?;
+        Ok(())
+    }
+
+    fn publish(text: &str) -> Result<Post> {
+        let mut post = Post::new();
+        post.add_text(text);
+        post.try_request_review()?;
+        post.try_approve()?;
+        Ok(post)
+    }
+
+    #[test]
+    fn question_mark_works_across_the_chapters() {
+        assert!(matches!(
+            grep(&["minigrep", "duct"]),
+            Err(BookError::Config(ConfigError::MissingFilePath))
+        ));
+        // The `Box<dyn Error>` of `run` is unboxed
+        assert!(matches!(
+            grep(&["minigrep", "duct", "no such file.txt"]),
+            Err(BookError::Io(error)) if error.kind() == io::ErrorKind::NotFound
+        ));
+        assert!(matches!(
+            ThreadPool::build(0).map_err(BookError::from),
+            Err(BookError::Pool(PoolCreationError::ZeroThreads))
+        ));
+        assert_eq!(publish("salad").unwrap().content(), "salad");
+    }
+
+    #[test]
+    fn display_names_the_subsystem() {
+        assert_eq!(
+            BookError::from(ConfigError::MissingQuery).to_string(),
+            "minigrep: Didn't get a query string"
+        );
+        assert_eq!(
+            BookError::from(PoolCreationError::ZeroThreads).to_string(),
+            "web server: a thread pool needs at least one thread"
+        );
+
+        let mut post = Post::new();
+        let error = BookError::from(post.try_approve().unwrap_err());
+        assert_eq!(
+            error.to_string(),
+            "blog: can't approve a post in the draft state"
+        );
+        assert_eq!(
+            error.source().unwrap().to_string(),
+            "can't approve a post in the draft state"
+        );
+    }
+
+    #[test]
+    fn unknown_boxed_errors_are_kept() {
+        let error: Box<dyn Error> = "not a number".parse::<i32>().unwrap_err().into();
+        let error = BookError::from(error);
+        assert!(matches!(error, BookError::Other(_)));
+        assert_eq!(error.to_string(), "invalid digit found in string");
+    }
+}
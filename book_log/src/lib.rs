@@ -0,0 +1,233 @@
+//! # Book log
+//!
+//! A small logging facade, so the chapters report what they do at runtime in the same way.
+//! The libraries emit events with the macros, e.g. `info!("request handled"; path = request.path, status = 200)`:
+//! each event has a level, a message, and structured fields, with a name and any value implementing `Debug`.
+//! The program chooses where the events go with `set_sink`: the standard error, memory for the tests, or any other `Sink`.
+//! Without a sink, the default, the events are dropped before being formatted, so the libraries stay silent.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+
+// The sinks provided by the crate
+pub mod sinks;
+pub use sinks::{Event, MemorySink, StderrSink};
+
+/// How important an event is, from the most to the least important
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let level = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        // `pad` instead of `write!`, so the width of `{:5}` is applied to the name
+        f.pad(level)
+    }
+}
+
+/// An event as emitted by the macros, borrowing the message and the values of the fields from the caller
+#[derive(Clone, Copy)]
+pub struct Record<'a> {
+    pub level: Level,
+    /// Module emitting the event, e.g. `c21_web_server::router`
+    pub target: &'a str,
+    pub message: fmt::Arguments<'a>,
+    pub fields: &'a [(&'static str, &'a dyn fmt::Debug)],
+}
+
+/// One line per event: `INFO  c21_web_server::router: request handled path="/" status=200`
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:5} {}: {}", self.level, self.target, self.message)?;
+        for (name, value) in self.fields {
+            write!(f, " {name}={value:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Destination of the events
+///
+/// It's shared by all the threads, so it needs `Send + Sync`, and has to use a `Mutex` or similar to keep any state.
+pub trait Sink: Send + Sync {
+    /// Whether the sink wants the events of the level and target, checked before formatting them
+    fn enabled(&self, _level: Level, _target: &str) -> bool {
+        true
+    }
+
+    fn record(&self, record: &Record);
+}
+
+/// Any closure taking a record is a sink, e.g. `set_sink(Arc::new(|record: &Record| println!("{record}")))`
+impl<F: Fn(&Record) + Send + Sync> Sink for F {
+    fn record(&self, record: &Record) {
+        self(record)
+    }
+}
+
+// The sink of the whole program, behind a `RwLock` as it's read for every event and only replaced now and then
+static SINK: RwLock<Option<Arc<dyn Sink>>> = RwLock::new(None);
+static MAX_LEVEL: AtomicUsize = AtomicUsize::new(Level::Info as usize);
+
+// A sink that panicked while the lock was held can't have left it in a broken state, so the poisoning is ignored
+fn current_sink() -> Option<Arc<dyn Sink>> {
+    SINK.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
+
+/// Send the events to the sink from now on, returning the previous one
+pub fn set_sink(sink: Arc<dyn Sink>) -> Option<Arc<dyn Sink>> {
+    SINK.write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .replace(sink)
+}
+
+/// Stop sending the events anywhere, returning the sink that was receiving them
+pub fn remove_sink() -> Option<Arc<dyn Sink>> {
+    SINK.write().unwrap_or_else(PoisonError::into_inner).take()
+}
+
+/// Only emit the events at least as important as the level, `Level::Info` if never set
+pub fn set_max_level(level: Level) {
+    MAX_LEVEL.store(level as usize, Ordering::Relaxed);
+}
+
+pub fn max_level() -> Level {
+    match MAX_LEVEL.load(Ordering::Relaxed) {
+        1 => Level::Error,
+        2 => Level::Warn,
+        3 => Level::Info,
+        4 => Level::Debug,
+        _ => Level::Trace,
+    }
+}
+
+/// Whether an event would reach a sink, used by the macros to skip formatting the ones that wouldn't
+pub fn enabled(level: Level, target: &str) -> bool {
+    level <= max_level() && current_sink().is_some_and(|sink| sink.enabled(level, target))
+}
+
+/// Send the record to the sink, if there's one, regardless of the levels
+/// The sink is called without holding the lock, so it can emit events or replace itself
+pub fn log(record: &Record) {
+    if let Some(sink) = current_sink() {
+        sink.record(record);
+    }
+}
+
+/// Emits an event at the given level, with the module calling it as the target
+///
+/// The message is a format string with its arguments, the fields come after a `;`:
+/// `event!(Level::Info, "job {} finished", id; worker = worker_id, elapsed = start.elapsed())`
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $message:literal $(, $arg:expr)* $(; $($name:ident = $value:expr),* $(,)?)?) => {{
+        let level = $level;
+        if $crate::enabled(level, module_path!()) {
+            $crate::log(&$crate::Record {
+                level,
+                target: module_path!(),
+                message: format_args!($message $(, $arg)*),
+                fields: &[$($((stringify!($name), &$value as &dyn ::std::fmt::Debug)),*)?],
+            });
+        }
+    }};
+}
+
+/// Emits an event at the `Error` level, like `event!`
+#[macro_export]
+macro_rules! error {
+    ($($event:tt)+) => {
+        $crate::event!($crate::Level::Error, $($event)+)
+    };
+}
+
+/// Emits an event at the `Warn` level, like `event!`
+#[macro_export]
+macro_rules! warn {
+    ($($event:tt)+) => {
+        $crate::event!($crate::Level::Warn, $($event)+)
+    };
+}
+
+/// Emits an event at the `Info` level, like `event!`
+#[macro_export]
+macro_rules! info {
+    ($($event:tt)+) => {
+        $crate::event!($crate::Level::Info, $($event)+)
+    };
+}
+
+/// Emits an event at the `Debug` level, like `event!`
+#[macro_export]
+macro_rules! debug {
+    ($($event:tt)+) => {
+        $crate::event!($crate::Level::Debug, $($event)+)
+    };
+}
+
+/// Emits an event at the `Trace` level, like `event!`
+#[macro_export]
+macro_rules! trace {
+    ($($event:tt)+) => {
+        $crate::event!($crate::Level::Trace, $($event)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_go_from_the_most_important() {
+        assert!(Level::Error < Level::Warn && Level::Debug < Level::Trace);
+        assert_eq!(format!("[{:5}]", Level::Warn), "[WARN ]");
+        assert_eq!(Level::Error.to_string(), "ERROR");
+    }
+
+    #[test]
+    fn records_are_displayed_on_one_line() {
+        let path = String::from("/sleep");
+        let record = Record {
+            level: Level::Info,
+            target: "c21_web_server::router",
+            message: format_args!("request handled in {} ms", 12),
+            fields: &[("path", &path), ("status", &200)],
+        };
+        assert_eq!(
+            record.to_string(),
+            r#"INFO  c21_web_server::router: request handled in 12 ms path="/sleep" status=200"#
+        );
+    }
+
+    #[test]
+    fn sinks_receive_the_records() {
+        let sink = MemorySink::new();
+        let record = Record {
+            level: Level::Debug,
+            target: "tests",
+            message: format_args!("job started"),
+            fields: &[("worker", &3)],
+        };
+        sink.record(&record);
+
+        let events = sink.take();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].message, "job started");
+        assert_eq!(events[0].field("worker"), Some("3"));
+        assert_eq!(events[0].to_string(), record.to_string());
+        assert!(sink.events().is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+//! Sinks for the common cases: printing the events, and keeping them to check them in the tests
+
+use std::fmt;
+use std::sync::{Mutex, PoisonError};
+
+use crate::{Level, Record, Sink};
+
+/// Prints every event on its own line to the standard error, so it doesn't mix with the output of the program
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrSink;
+
+impl Sink for StderrSink {
+    fn record(&self, record: &Record) {
+        eprintln!("{record}");
+    }
+}
+
+/// A record that owns its message and fields, with the values already formatted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    /// The names of the fields with their values formatted with `Debug`, in order
+    pub fields: Vec<(String, String)>,
+}
+
+impl Event {
+    /// The formatted value of the field, e.g. `"\"/\""` for a string and `"200"` for a number
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl From<&Record<'_>> for Event {
+    fn from(record: &Record) -> Event {
+        Event {
+            level: record.level,
+            target: String::from(record.target),
+            message: record.message.to_string(),
+            fields: record
+                .fields
+                .iter()
+                .map(|(name, value)| (String::from(*name), format!("{value:?}")))
+                .collect(),
+        }
+    }
+}
+
+/// The same line as the record it was made from
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:5} {}: {}", self.level, self.target, self.message)?;
+        for (name, value) in &self.fields {
+            write!(f, " {name}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the events in memory, in the order they were emitted
+#[derive(Debug, Default)]
+pub struct MemorySink {
+    events: Mutex<Vec<Event>>,
+}
+
+impl MemorySink {
+    pub fn new() -> MemorySink {
+        MemorySink::default()
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Return the events kept so far and start again from none
+    pub fn take(&self) -> Vec<Event> {
+        std::mem::take(&mut *self.events.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl Sink for MemorySink {
+    fn record(&self, record: &Record) {
+        self.events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Event::from(record));
+    }
+}
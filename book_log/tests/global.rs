@@ -0,0 +1,58 @@
+//! The sink and the maximum level are global, so they're checked in a single test to not be changed by another one in parallel
+
+use std::sync::{Arc, Mutex};
+
+use book_log::{Level, MemorySink, Record};
+
+fn handle(path: &str) {
+    book_log::info!("request handled"; path = path, status = 200);
+    book_log::debug!("{} bytes written", 6; path = path);
+}
+
+#[test]
+fn events_reach_the_sink_set_by_the_program() {
+    // Without a sink nothing is even formatted
+    assert!(!book_log::enabled(Level::Error, "tests"));
+    handle("/");
+
+    let sink = Arc::new(MemorySink::new());
+    assert!(book_log::set_sink(sink.clone()).is_none());
+    handle("/");
+    let events = sink.take();
+    assert_eq!(events.len(), 1, "debug is below the default level");
+    assert_eq!(events[0].level, Level::Info);
+    assert_eq!(events[0].target, "global");
+    assert_eq!(events[0].field("path"), Some(r#""/""#));
+    assert_eq!(
+        events[0].to_string(),
+        r#"INFO  global: request handled path="/" status=200"#
+    );
+
+    book_log::set_max_level(Level::Debug);
+    handle("/sleep");
+    let events = sink.take();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[1].message, "6 bytes written");
+
+    // A closure is a sink too, and setting it returns the previous one
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    let closure_lines = Arc::clone(&lines);
+    let previous = book_log::set_sink(Arc::new(move |record: &Record| {
+        closure_lines.lock().unwrap().push(record.to_string());
+    }));
+    assert!(previous.is_some());
+    book_log::warn!("slow request"; elapsed_ms = 5000);
+    assert_eq!(
+        *lines.lock().unwrap(),
+        ["WARN  global: slow request elapsed_ms=5000"]
+    );
+    assert!(sink.events().is_empty());
+
+    book_log::set_max_level(Level::Error);
+    book_log::warn!("not emitted");
+    assert_eq!(lines.lock().unwrap().len(), 1);
+
+    assert!(book_log::remove_sink().is_some());
+    book_log::error!("nowhere to go");
+    assert_eq!(lines.lock().unwrap().len(), 1);
+}
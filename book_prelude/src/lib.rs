@@ -0,0 +1,16 @@
+//! # Book prelude
+//!
+//! The most used items of the chapters, re-exported at the top level like `c14_cargo_crates::art` does for its modules.
+//! A program using several chapters can bring them all into scope with `use book_prelude::*;`,
+//! without knowing in which crate or module each one is defined.
+
+// Add `pub use` to re-export the items at top level
+pub use book_errors::BookError;
+pub use c10_generics_traits_lifetimes::Feed;
+pub use c10_generics_traits_lifetimes::Summary;
+pub use c12_minigrep::Config;
+pub use c18_object_oriented_programming::AveragedCollection;
+pub use c18_object_oriented_programming::blog::Post;
+pub use c18_object_oriented_programming::gui::Draw;
+pub use c18_object_oriented_programming::gui::Screen;
+pub use c21_web_server::ThreadPool;
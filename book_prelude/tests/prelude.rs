@@ -0,0 +1,66 @@
+use std::sync::mpsc;
+
+use book_prelude::*;
+
+struct Note(String);
+
+impl Summary for Note {
+    fn summarise_author(&self) -> String {
+        self.0.clone()
+    }
+}
+
+struct Text(String);
+
+impl Draw for Text {
+    fn draw(&self) {
+        println!("{}", self.0);
+    }
+}
+
+#[test]
+fn items_of_every_chapter_are_in_scope() {
+    let mut feed = Feed::new();
+    feed.push(Note(String::from("@rust")));
+    assert_eq!(
+        feed.get(0).map(|item| item.summarise()),
+        Some(String::from("(Read more from @rust...)"))
+    );
+
+    let config = Config::build(
+        ["minigrep", "duct", "poem.txt"]
+            .map(String::from)
+            .into_iter(),
+    );
+    assert_eq!(
+        config.map(|config| config.query).ok().as_deref(),
+        Some("duct")
+    );
+
+    let mut collection = AveragedCollection::new();
+    collection.add(2);
+    collection.add(4);
+    assert_eq!(collection.average(), 3.0);
+
+    let mut post = Post::new();
+    post.add_text("salad");
+    post.request_review();
+    post.approve();
+    assert_eq!(post.content(), "salad");
+
+    let screen = Screen {
+        components: vec![Box::new(Text(String::from("hello")))],
+    };
+    screen.run();
+
+    let pool = ThreadPool::new(1);
+    let (sender, receiver) = mpsc::channel();
+    pool.execute(move || sender.send(1).unwrap());
+    assert_eq!(receiver.recv().unwrap(), 1);
+
+    let error: BookError = ThreadPool::build(0).err().unwrap().into();
+    assert_eq!(
+        error.to_string(),
+        "web server: a thread pool needs at least one thread"
+    );
+}
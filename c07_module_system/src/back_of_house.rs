@@ -0,0 +1,339 @@
+use std::fmt;
+use std::thread::{self, JoinHandle};
+
+use crate::kitchen::{Kitchen, Ticket};
+use crate::menu::{self, MenuError, Season, TOASTS};
+// The tags are used in the lists of each dish without repeating the name of the enum
+use DietaryTag::*;
+
+pub struct Breakfast {
+    pub toast: String,
+    seasonal_fruit: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Appetizer {
+    Soup,
+    Salad,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Main {
+    Risotto,
+    GrilledFish,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dessert {
+    Tiramisu,
+    Sorbet,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DietaryTag {
+    Vegetarian,
+    Vegan,
+    GlutenFree,
+    DairyFree,
+}
+
+impl fmt::Display for DietaryTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tag = match self {
+            DietaryTag::Vegetarian => "vegetarian",
+            DietaryTag::Vegan => "vegan",
+            DietaryTag::GlutenFree => "gluten-free",
+            DietaryTag::DairyFree => "dairy-free",
+        };
+        write!(f, "{tag}")
+    }
+}
+
+// The dishes of every course have a price, a description and the diets they are suitable for
+// `Display` is required to print the name of the dish
+pub trait Dish: fmt::Display {
+    /// Price in cents.
+    fn price(&self) -> u32;
+    fn description(&self) -> &'static str;
+    fn tags(&self) -> &'static [DietaryTag];
+}
+
+impl Dish for Appetizer {
+    fn price(&self) -> u32 {
+        // Read from the house menu, where every appetizer is listed under the name it's displayed with
+        menu::house_price(&self.to_string()).expect("every appetizer is on the house menu")
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Appetizer::Soup => "vegetable soup of the day",
+            Appetizer::Salad => "green salad with seasonal vegetables",
+        }
+    }
+
+    fn tags(&self) -> &'static [DietaryTag] {
+        // Both appetizers are suitable for all the diets
+        &[Vegetarian, Vegan, GlutenFree, DairyFree]
+    }
+}
+
+impl Dish for Main {
+    fn price(&self) -> u32 {
+        menu::house_price(&self.to_string()).expect("every main course is on the house menu")
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Main::Risotto => "mushroom risotto with parmesan",
+            Main::GrilledFish => "grilled sea bass with potatoes",
+        }
+    }
+
+    fn tags(&self) -> &'static [DietaryTag] {
+        match self {
+            Main::Risotto => &[Vegetarian, GlutenFree],
+            Main::GrilledFish => &[GlutenFree, DairyFree],
+        }
+    }
+}
+
+impl Dish for Dessert {
+    fn price(&self) -> u32 {
+        menu::house_price(&self.to_string()).expect("every dessert is on the house menu")
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            Dessert::Tiramisu => "coffee and mascarpone tiramisu",
+            Dessert::Sorbet => "lemon sorbet",
+        }
+    }
+
+    fn tags(&self) -> &'static [DietaryTag] {
+        match self {
+            Dessert::Tiramisu => &[Vegetarian],
+            Dessert::Sorbet => &[Vegetarian, Vegan, GlutenFree, DairyFree],
+        }
+    }
+}
+
+impl fmt::Display for Appetizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Appetizer::Soup => "Soup",
+            Appetizer::Salad => "Salad",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for Main {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Main::Risotto => "Risotto",
+            Main::GrilledFish => "Grilled fish",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl fmt::Display for Dessert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Dessert::Tiramisu => "Tiramisu",
+            Dessert::Sorbet => "Sorbet",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// A meal of up to three courses: the main is required, the appetizer and the dessert are optional.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Course {
+    pub appetizer: Option<Appetizer>,
+    pub main: Main,
+    pub dessert: Option<Dessert>,
+}
+
+impl Course {
+    pub fn new(main: Main) -> Course {
+        Course {
+            appetizer: None,
+            main,
+            dessert: None,
+        }
+    }
+
+    pub fn with_appetizer(mut self, appetizer: Appetizer) -> Course {
+        self.appetizer = Some(appetizer);
+        self
+    }
+
+    pub fn with_dessert(mut self, dessert: Dessert) -> Course {
+        self.dessert = Some(dessert);
+        self
+    }
+
+    // The dishes in the order they are served, as trait objects since they have different types
+    fn dishes(&self) -> Vec<&dyn Dish> {
+        let mut dishes: Vec<&dyn Dish> = Vec::new();
+        if let Some(appetizer) = &self.appetizer {
+            dishes.push(appetizer);
+        }
+        dishes.push(&self.main);
+        if let Some(dessert) = &self.dessert {
+            dishes.push(dessert);
+        }
+        dishes
+    }
+
+    pub fn price(&self) -> u32 {
+        self.dishes().iter().map(|dish| dish.price()).sum()
+    }
+
+    /// The diets the whole course is suitable for, the tags shared by all its dishes.
+    pub fn tags(&self) -> Vec<DietaryTag> {
+        self.main
+            .tags()
+            .iter()
+            .copied()
+            .filter(|tag| self.dishes().iter().all(|dish| dish.tags().contains(tag)))
+            .collect()
+    }
+}
+
+// The kitchen ticket: a line for each dish, with the tags of the dish
+impl fmt::Display for Course {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = ["Appetizer", "Main", "Dessert"];
+        let dishes = [
+            self.appetizer.as_ref().map(|dish| dish as &dyn Dish),
+            Some(&self.main as &dyn Dish),
+            self.dessert.as_ref().map(|dish| dish as &dyn Dish),
+        ];
+        let mut first = true;
+        for (label, dish) in labels.iter().zip(dishes) {
+            let Some(dish) = dish else { continue };
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            let tags: Vec<String> = dish.tags().iter().map(|tag| tag.to_string()).collect();
+            write!(f, "{label}: {dish} - {}", dish.description())?;
+            if !tags.is_empty() {
+                write!(f, " [{}]", tags.join(", "))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Breakfast {
+    pub fn summer(toast: &str) -> Breakfast {
+        Breakfast {
+            toast: String::from(toast),
+            seasonal_fruit: String::from("peaches"),
+        }
+    }
+
+    // The builder is defined in this module, so it can set the private field
+    // Outside of the module a `Breakfast` can only be created with a fruit of the season
+    pub fn builder() -> BreakfastBuilder {
+        BreakfastBuilder::default()
+    }
+
+    // The fruit can be read but not changed, since it depends on the season
+    pub fn seasonal_fruit(&self) -> &str {
+        &self.seasonal_fruit
+    }
+}
+
+/// Chooses the toast and the fruit of a breakfast, which are validated once the season is known by `build`.
+#[derive(Debug, Default)]
+pub struct BreakfastBuilder {
+    toast: Option<String>,
+    fruit: Option<String>,
+}
+
+impl BreakfastBuilder {
+    /// Chooses one of `TOASTS`, white by default.
+    pub fn toast(mut self, toast: &str) -> BreakfastBuilder {
+        self.toast = Some(String::from(toast));
+        self
+    }
+
+    /// Chooses the fruit, by default the first one of the season.
+    pub fn fruit(mut self, fruit: &str) -> BreakfastBuilder {
+        self.fruit = Some(String::from(fruit));
+        self
+    }
+
+    pub fn build(self, season: Season) -> Result<Breakfast, MenuError> {
+        let toast = self.toast.unwrap_or_else(|| String::from(TOASTS[0]));
+        if !TOASTS.contains(&toast.as_str()) {
+            return Err(MenuError::UnknownToast(toast));
+        }
+        let fruits = season.fruits();
+        let seasonal_fruit = self.fruit.unwrap_or_else(|| String::from(fruits[0]));
+        if !fruits.contains(&seasonal_fruit.as_str()) {
+            return Err(MenuError::FruitOutOfSeason {
+                fruit: seasonal_fruit,
+                season,
+            });
+        }
+        Ok(Breakfast {
+            toast,
+            seasonal_fruit,
+        })
+    }
+}
+
+fn fix_incorrect_order() {
+    cook_order();
+    super::deliver_order();
+}
+
+fn cook_order() {}
+
+// Cooking a ticket prepares each of its items
+fn cook_ticket(ticket: &Ticket) {
+    for _ in &ticket.items {
+        cook_order();
+    }
+}
+
+/// Cooks the tickets of the kitchen one at a time, until it's closed and the queue is empty.
+///
+/// Returns the number of tickets cooked.
+pub fn work_in_kitchen(kitchen: &Kitchen) -> usize {
+    let mut cooked = 0;
+    while let Some(ticket) = kitchen.next_ticket() {
+        cook_ticket(&ticket);
+        kitchen.mark_ready(ticket);
+        cooked += 1;
+    }
+    cooked
+}
+
+/// Starts `cooks` threads working in the kitchen, each returning the number of tickets it cooked.
+pub fn hire_cooks(kitchen: &Kitchen, cooks: usize) -> Vec<JoinHandle<usize>> {
+    (0..cooks)
+        .map(|_| {
+            let kitchen = kitchen.clone();
+            thread::spawn(move || work_in_kitchen(&kitchen))
+        })
+        .collect()
+}
+
+/// Runs `cooks` jobs working in the kitchen on the `ThreadPool` of the web server, instead of spawning threads.
+///
+/// The jobs end once the kitchen is closed, dropping the pool waits for them.
+#[cfg(feature = "thread-pool")]
+pub fn hire_cooks_on(pool: &c21_web_server::ThreadPool, kitchen: &Kitchen, cooks: usize) {
+    for _ in 0..cooks {
+        let kitchen = kitchen.clone();
+        pool.execute(move || {
+            work_in_kitchen(&kitchen);
+        });
+    }
+}
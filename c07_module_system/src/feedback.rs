@@ -0,0 +1,143 @@
+//! Feedback of the guests
+//! Once an order is paid, the table can rate it once, from 1 to 5 stars, and leave a comment
+//! The rating counts towards every dish of the order, and the statistics of each dish are kept by an `AveragedCollection`
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use c18_object_oriented_programming::AveragedCollection;
+
+use crate::front_of_house::serving::{Order, OrderStatus};
+
+/// The lowest and highest number of stars of a rating.
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 5;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedbackError {
+    /// Only paid orders can be rated.
+    NotCompleted(OrderStatus),
+    /// The rating is not between `MIN_RATING` and `MAX_RATING`.
+    InvalidRating(u8),
+    /// The order was never sent to the kitchen, so it has no ticket telling it apart from the others.
+    NoTicket,
+    /// The order with this ticket has already been rated.
+    AlreadyRated(u32),
+}
+
+impl fmt::Display for FeedbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeedbackError::NotCompleted(status) => {
+                write!(f, "the order is {status:?}, it can be rated once paid")
+            }
+            FeedbackError::InvalidRating(rating) => write!(
+                f,
+                "{rating} is not a rating, it must be between {MIN_RATING} and {MAX_RATING}"
+            ),
+            FeedbackError::NoTicket => write!(f, "the order was never sent to the kitchen"),
+            FeedbackError::AlreadyRated(ticket) => {
+                write!(f, "the order of ticket {ticket} has already been rated")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FeedbackError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Feedback {
+    pub table: u32,
+    /// The kitchen ticket of the order.
+    pub ticket: u32,
+    /// The names of the dishes of the order.
+    pub dishes: Vec<String>,
+    pub rating: u8,
+    pub comment: String,
+}
+
+/// The statistics of the ratings of a dish.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DishStats {
+    pub count: usize,
+    pub average: f64,
+    pub min: u8,
+    pub max: u8,
+}
+
+// `AveragedCollection` doesn't implement `Debug`, so neither does the book
+#[derive(Default)]
+pub struct FeedbackBook {
+    feedback: Vec<Feedback>,
+    // The ratings of each dish, by name, with their running average
+    ratings: HashMap<String, AveragedCollection>,
+    // The tickets of the orders already rated
+    rated: HashSet<u32>,
+}
+
+impl FeedbackBook {
+    pub fn new() -> FeedbackBook {
+        FeedbackBook::default()
+    }
+
+    /// Rates a paid order, the rating counts once for each dish, whatever the quantity ordered.
+    ///
+    /// The orders are told apart by their kitchen ticket, so each of them can only be rated once.
+    pub fn rate(&mut self, order: &Order, rating: u8, comment: &str) -> Result<(), FeedbackError> {
+        if order.status() != OrderStatus::Paid {
+            return Err(FeedbackError::NotCompleted(order.status()));
+        }
+        if !(MIN_RATING..=MAX_RATING).contains(&rating) {
+            return Err(FeedbackError::InvalidRating(rating));
+        }
+        let ticket = order.ticket().ok_or(FeedbackError::NoTicket)?;
+        if !self.rated.insert(ticket) {
+            return Err(FeedbackError::AlreadyRated(ticket));
+        }
+
+        let dishes: Vec<String> = order.items().iter().map(|item| item.name.clone()).collect();
+        for dish in &dishes {
+            self.ratings
+                .entry(dish.clone())
+                .or_default()
+                .add(i32::from(rating));
+        }
+        self.feedback.push(Feedback {
+            table: order.table(),
+            ticket,
+            dishes,
+            rating,
+            comment: String::from(comment),
+        });
+        Ok(())
+    }
+
+    /// Returns all the feedback received, in the order it was left.
+    pub fn feedback(&self) -> &[Feedback] {
+        &self.feedback
+    }
+
+    /// Returns the statistics of a dish, or `None` if it has never been rated.
+    pub fn stats(&self, dish: &str) -> Option<DishStats> {
+        let ratings = self.ratings.get(dish)?;
+        // The ratings are between 1 and 5, so they always fit in a `u8`
+        Some(DishStats {
+            count: ratings.len(),
+            average: ratings.average(),
+            min: ratings.min()? as u8,
+            max: ratings.max()? as u8,
+        })
+    }
+
+    /// Returns the dishes rated, from the best average to the worst.
+    pub fn ranking(&self) -> Vec<(&str, f64)> {
+        let mut ranking: Vec<(&str, f64)> = self
+            .ratings
+            .iter()
+            .map(|(dish, ratings)| (dish.as_str(), ratings.average()))
+            .collect();
+        // Dishes with the same average are sorted by name, so the ranking doesn't depend on the order of the map
+        ranking.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+        ranking
+    }
+}
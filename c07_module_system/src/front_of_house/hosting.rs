@@ -1,2 +1,116 @@
-pub fn add_to_waitlist() {}
-pub fn seat_at_table() {}
+//! Hosting the guests
+//! Parties waiting for a table join the `Waitlist`, and are seated when a table big enough is free
+//! Parties with a reservation have priority over walk-ins
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Party {
+    pub name: String,
+    pub size: u32,
+    pub reservation: bool,
+}
+
+impl Party {
+    pub fn walk_in(name: &str, size: u32) -> Party {
+        Party {
+            name: String::from(name),
+            size,
+            reservation: false,
+        }
+    }
+
+    pub fn reservation(name: &str, size: u32) -> Party {
+        Party {
+            name: String::from(name),
+            size,
+            reservation: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Waitlist {
+    // Parties in the order they joined, the priority is applied when reading the queue
+    queue: VecDeque<Party>,
+    // Average time for a table to be free again
+    turnover: Duration,
+}
+
+impl Waitlist {
+    /// Creates an empty waitlist, where a table is expected to be free every `turnover`.
+    pub fn new(turnover: Duration) -> Waitlist {
+        Waitlist {
+            queue: VecDeque::new(),
+            turnover,
+        }
+    }
+
+    /// Adds a party at the end of the queue, returning its position in the line, from 0.
+    ///
+    /// A reservation goes ahead of the walk-ins, so its position can be lower than the length of the queue.
+    pub fn join(&mut self, party: Party) -> usize {
+        let reservation = party.reservation;
+        self.queue.push_back(party);
+        // The party is the last of its group: behind the other reservations, or behind everyone
+        if reservation {
+            self.queue.iter().filter(|party| party.reservation).count() - 1
+        } else {
+            self.queue.len() - 1
+        }
+    }
+
+    /// Removes the first party that fits at a table of `size` seats: the reservations first, then the walk-ins.
+    ///
+    /// Within each group the parties are seated in the order they joined.
+    pub fn next_table(&mut self, size: u32) -> Option<Party> {
+        let index = self
+            .queue
+            .iter()
+            .position(|party| party.reservation && party.size <= size)
+            .or_else(|| self.queue.iter().position(|party| party.size <= size))?;
+        self.queue.remove(index)
+    }
+
+    /// Removes a party that doesn't want to wait anymore.
+    pub fn leave(&mut self, name: &str) -> Option<Party> {
+        let index = self.queue.iter().position(|party| party.name == name)?;
+        self.queue.remove(index)
+    }
+
+    /// Returns the position of a party in the line, from 0, with the reservations ahead of the walk-ins.
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.in_line().position(|party| party.name == name)
+    }
+
+    /// Estimates how long a party has to wait: a table must be free for each party ahead, and then for this one.
+    pub fn estimated_wait(&self, name: &str) -> Option<Duration> {
+        let ahead = self.position(name)? as u32;
+        Some(self.turnover * (ahead + 1))
+    }
+
+    /// Iterates over the parties in the order they'll be seated, if the tables are big enough.
+    pub fn in_line(&self) -> impl Iterator<Item = &Party> {
+        let reservations = self.queue.iter().filter(|party| party.reservation);
+        let walk_ins = self.queue.iter().filter(|party| !party.reservation);
+        reservations.chain(walk_ins)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+// The first versions of these functions were empty, now they work on a waitlist
+pub fn add_to_waitlist(waitlist: &mut Waitlist, party: Party) -> usize {
+    waitlist.join(party)
+}
+
+pub fn seat_at_table(waitlist: &mut Waitlist, table_size: u32) -> Option<Party> {
+    waitlist.next_table(table_size)
+}
@@ -0,0 +1,255 @@
+//! Payments
+//! The `Bill` of an order lists its items, with the tax and the tip on top
+//! It can be paid with more methods at once, e.g. when the guests split it, and the `Receipt` is printed with `Display`
+
+use std::fmt;
+
+use super::serving::{Order, OrderError, OrderItem};
+use crate::menu::format_price;
+
+/// The sales tax applied to every bill, in percent.
+pub const TAX_PERCENT: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentMethod {
+    Cash,
+    Card,
+    Mobile,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Payment {
+    pub method: PaymentMethod,
+    /// Amount in cents.
+    pub amount: u32,
+}
+
+impl Payment {
+    pub fn cash(amount: u32) -> Payment {
+        Payment {
+            method: PaymentMethod::Cash,
+            amount,
+        }
+    }
+
+    pub fn card(amount: u32) -> Payment {
+        Payment {
+            method: PaymentMethod::Card,
+            amount,
+        }
+    }
+
+    pub fn mobile(amount: u32) -> Payment {
+        Payment {
+            method: PaymentMethod::Mobile,
+            amount,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentError {
+    /// The order can't be paid yet, or has already been paid.
+    Order(OrderError),
+    /// The payments don't cover the total of the bill.
+    Underpaid { total: u32, paid: u32 },
+    /// The total of the bill, or the sum of the payments, doesn't fit in a `u32` of cents.
+    Overflow,
+}
+
+impl fmt::Display for PaymentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaymentError::Order(error) => write!(f, "{error}"),
+            PaymentError::Underpaid { total, paid } => write!(
+                f,
+                "paid {} of {}",
+                format_price(*paid),
+                format_price(*total)
+            ),
+            PaymentError::Overflow => write!(f, "the amount is too large"),
+        }
+    }
+}
+
+impl std::error::Error for PaymentError {}
+
+impl From<OrderError> for PaymentError {
+    fn from(error: OrderError) -> Self {
+        PaymentError::Order(error)
+    }
+}
+
+// Every change of the lines or the tip is checked, so the amounts of the bill always fit in a `u32`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bill {
+    pub table: u32,
+    lines: Vec<OrderItem>,
+    tax_percent: u32,
+    tip: u32,
+}
+
+impl Bill {
+    pub fn new(table: u32, tax_percent: u32) -> Bill {
+        Bill {
+            table,
+            lines: Vec::new(),
+            tax_percent,
+            tip: 0,
+        }
+    }
+
+    /// The bill of an order, with the items at the price they were ordered and the `TAX_PERCENT`.
+    pub fn for_order(order: &Order) -> Result<Bill, PaymentError> {
+        let mut bill = Bill::new(order.table(), TAX_PERCENT);
+        for item in order.items() {
+            bill.add_line(item.clone())?;
+        }
+        Ok(bill)
+    }
+
+    /// Adds a line, unless the total of the bill would overflow.
+    pub fn add_line(&mut self, line: OrderItem) -> Result<(), PaymentError> {
+        self.lines.push(line);
+        if self.checked_total().is_none() {
+            self.lines.pop();
+            return Err(PaymentError::Overflow);
+        }
+        Ok(())
+    }
+
+    /// Adds a tip in cents, replacing the previous one, unless the total of the bill would overflow.
+    pub fn with_tip(mut self, tip: u32) -> Result<Bill, PaymentError> {
+        self.tip = tip;
+        self.checked_total().ok_or(PaymentError::Overflow)?;
+        Ok(self)
+    }
+
+    /// Adds a tip as a percentage of the subtotal, rounded to the nearest cent.
+    pub fn with_tip_percent(self, percent: u32) -> Result<Bill, PaymentError> {
+        let tip = percent_of(self.subtotal(), percent).ok_or(PaymentError::Overflow)?;
+        self.with_tip(tip)
+    }
+
+    pub fn lines(&self) -> &[OrderItem] {
+        &self.lines
+    }
+
+    pub fn subtotal(&self) -> u32 {
+        self.lines
+            .iter()
+            .map(|line| line.price * line.quantity)
+            .sum()
+    }
+
+    /// The tax on the subtotal, rounded to the nearest cent, the tip is not taxed.
+    pub fn tax(&self) -> u32 {
+        // The lines were checked when they were added, the tax fits
+        percent_of(self.subtotal(), self.tax_percent).unwrap_or(u32::MAX)
+    }
+
+    pub fn tip(&self) -> u32 {
+        self.tip
+    }
+
+    pub fn total(&self) -> u32 {
+        self.subtotal() + self.tax() + self.tip
+    }
+
+    // The total with every step checked, `None` if it overflows
+    fn checked_total(&self) -> Option<u32> {
+        let subtotal = self.lines.iter().try_fold(0u32, |subtotal, line| {
+            subtotal.checked_add(line.price.checked_mul(line.quantity)?)
+        })?;
+        subtotal
+            .checked_add(percent_of(subtotal, self.tax_percent)?)?
+            .checked_add(self.tip)
+    }
+
+    /// Splits the total in `people` shares, which differ at most by a cent and add up to the total.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `people` is 0.
+    pub fn split_evenly(&self, people: u32) -> Vec<u32> {
+        assert!(people > 0, "a bill can't be split between 0 people");
+        let total = self.total();
+        let share = total / people;
+        let remainder = total % people;
+        // The cents left over go to the first shares, one each
+        (0..people)
+            .map(|index| share + u32::from(index < remainder))
+            .collect()
+    }
+
+    /// Pays the bill, the amount exceeding the total is given back as change.
+    pub fn pay(self, payments: Vec<Payment>) -> Result<Receipt, PaymentError> {
+        let total = self.total();
+        let paid = payments
+            .iter()
+            .try_fold(0u32, |paid, payment| paid.checked_add(payment.amount))
+            .ok_or(PaymentError::Overflow)?;
+        if paid < total {
+            return Err(PaymentError::Underpaid { total, paid });
+        }
+        Ok(Receipt {
+            bill: self,
+            payments,
+            change: paid - total,
+        })
+    }
+}
+
+// Computed in `u64`, where it can't overflow, `None` if the result doesn't fit back in a `u32`
+fn percent_of(amount: u32, percent: u32) -> Option<u32> {
+    let result = (u64::from(amount) * u64::from(percent) + 50) / 100;
+    u32::try_from(result).ok()
+}
+
+// Width of the columns of bills and receipts
+const NAME_WIDTH: usize = 20;
+const PRICE_WIDTH: usize = 10;
+
+impl fmt::Display for Bill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Table {}", self.table)?;
+        for line in &self.lines {
+            let name = format!("{} x{}", line.name, line.quantity);
+            let amount = format_price(line.price * line.quantity);
+            writeln!(f, "{name:<NAME_WIDTH$}{amount:>PRICE_WIDTH$}")?;
+        }
+        let tax = format!("Tax ({}%)", self.tax_percent);
+        let rows = [
+            ("Subtotal", self.subtotal()),
+            (tax.as_str(), self.tax()),
+            ("Tip", self.tip),
+            ("Total", self.total()),
+        ];
+        for (name, amount) in rows {
+            let amount = format_price(amount);
+            writeln!(f, "{name:<NAME_WIDTH$}{amount:>PRICE_WIDTH$}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    pub bill: Bill,
+    pub payments: Vec<Payment>,
+    /// The amount paid over the total, in cents.
+    pub change: u32,
+}
+
+impl fmt::Display for Receipt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.bill)?;
+        for payment in &self.payments {
+            let method = format!("{:?}", payment.method);
+            let amount = format_price(payment.amount);
+            writeln!(f, "{method:<NAME_WIDTH$}{amount:>PRICE_WIDTH$}")?;
+        }
+        let change = format_price(self.change);
+        write!(f, "{:<NAME_WIDTH$}{change:>PRICE_WIDTH$}", "Change")
+    }
+}
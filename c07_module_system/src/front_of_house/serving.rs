@@ -0,0 +1,199 @@
+//! Serving the tables
+//! Each table places an `Order`, which goes through its lifecycle one status at a time:
+//! Placed -> Cooking -> Served -> Paid
+
+use std::fmt;
+
+use super::payments::{Bill, Payment, PaymentError, Receipt};
+use crate::kitchen::Kitchen;
+use crate::menu::{Menu, MenuError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderStatus {
+    Placed,
+    Cooking,
+    Served,
+    Paid,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+    /// The item couldn't be ordered from the menu.
+    Menu(MenuError),
+    /// The order can't go from its current status to the requested one.
+    InvalidTransition { from: OrderStatus, to: OrderStatus },
+    /// Items can only be added while the order is still `Placed`.
+    AlreadySent(OrderStatus),
+    /// The kitchen hasn't finished the ticket of the table yet.
+    NotReady(u32),
+    /// The total of the order in cents would no longer fit in a `u32`.
+    TotalOverflow,
+}
+
+impl fmt::Display for OrderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderError::Menu(error) => write!(f, "{error}"),
+            OrderError::InvalidTransition { from, to } => {
+                write!(f, "an order can't go from {from:?} to {to:?}")
+            }
+            OrderError::AlreadySent(status) => {
+                write!(f, "the order is {status:?}, no more items can be added")
+            }
+            OrderError::NotReady(table) => write!(f, "the order of table {table} is not ready"),
+            OrderError::TotalOverflow => write!(f, "the total of the order is too large"),
+        }
+    }
+}
+
+impl std::error::Error for OrderError {}
+
+// Allows to use `?` on the results of the menu in functions returning an `OrderError`
+impl From<MenuError> for OrderError {
+    fn from(error: MenuError) -> Self {
+        OrderError::Menu(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderItem {
+    pub name: String,
+    /// Price in cents of a single item, as it was when ordered.
+    pub price: u32,
+    pub quantity: u32,
+}
+
+// The fields are private so the status can only change through the transition methods
+#[derive(Debug, Clone)]
+pub struct Order {
+    table: u32,
+    items: Vec<OrderItem>,
+    status: OrderStatus,
+    // The ticket of the kitchen, once the order has been sent
+    ticket: Option<u32>,
+}
+
+impl Order {
+    pub fn new(table: u32) -> Order {
+        Order {
+            table,
+            items: Vec::new(),
+            status: OrderStatus::Placed,
+            ticket: None,
+        }
+    }
+
+    pub fn table(&self) -> u32 {
+        self.table
+    }
+
+    pub fn items(&self) -> &[OrderItem] {
+        &self.items
+    }
+
+    pub fn status(&self) -> OrderStatus {
+        self.status
+    }
+
+    /// The id of the kitchen ticket, if the order was sent with `take_order`.
+    pub fn ticket(&self) -> Option<u32> {
+        self.ticket
+    }
+
+    /// Adds an item from the menu at today's price, which includes the daily specials.
+    ///
+    /// The item is rejected if the total of the order would overflow, so `total` never does.
+    pub fn add_item(&mut self, menu: &Menu, name: &str, quantity: u32) -> Result<(), OrderError> {
+        if self.status != OrderStatus::Placed {
+            return Err(OrderError::AlreadySent(self.status));
+        }
+        let price = menu
+            .price_of(name)
+            .ok_or_else(|| MenuError::UnknownItem(String::from(name)))?;
+        price
+            .checked_mul(quantity)
+            .and_then(|cost| cost.checked_add(self.total()))
+            .ok_or(OrderError::TotalOverflow)?;
+        self.items.push(OrderItem {
+            name: String::from(name),
+            price,
+            quantity,
+        });
+        Ok(())
+    }
+
+    /// Total in cents of the items ordered.
+    pub fn total(&self) -> u32 {
+        self.items
+            .iter()
+            .map(|item| item.price * item.quantity)
+            .sum()
+    }
+
+    pub fn start_cooking(&mut self) -> Result<(), OrderError> {
+        self.transition(OrderStatus::Placed, OrderStatus::Cooking)
+    }
+
+    pub fn serve(&mut self) -> Result<(), OrderError> {
+        self.transition(OrderStatus::Cooking, OrderStatus::Served)
+    }
+
+    pub fn pay(&mut self) -> Result<(), OrderError> {
+        self.transition(OrderStatus::Served, OrderStatus::Paid)
+    }
+
+    // Each status can only be reached from the one before it
+    fn transition(&mut self, from: OrderStatus, to: OrderStatus) -> Result<(), OrderError> {
+        if self.status != from {
+            return Err(OrderError::InvalidTransition {
+                from: self.status,
+                to,
+            });
+        }
+        self.status = to;
+        Ok(())
+    }
+}
+
+/// Takes the order of a table, with one of each item, and sends it to the kitchen.
+///
+/// The order returned is already `Cooking`, and the kitchen has a ticket with its items.
+pub fn take_order(
+    table: u32,
+    menu: &Menu,
+    names: &[&str],
+    kitchen: &Kitchen,
+) -> Result<Order, OrderError> {
+    let mut order = Order::new(table);
+    for name in names {
+        order.add_item(menu, name, 1)?;
+    }
+    order.start_cooking()?;
+    order.ticket = Some(kitchen.enqueue(table, order.items.clone()));
+    Ok(order)
+}
+
+/// Brings the cooked order to the table.
+pub fn serve_order(order: &mut Order) -> Result<(), OrderError> {
+    order.serve()
+}
+
+/// Takes the payment of a served order, with a tip in cents, and returns the receipt.
+///
+/// The order is marked as paid only if the payments cover the total of its bill.
+pub fn take_payment(
+    order: &mut Order,
+    tip: u32,
+    payments: Vec<Payment>,
+) -> Result<Receipt, PaymentError> {
+    if order.status() != OrderStatus::Served {
+        return Err(OrderError::InvalidTransition {
+            from: order.status(),
+            to: OrderStatus::Paid,
+        }
+        .into());
+    }
+    let receipt = Bill::for_order(order)?.with_tip(tip)?.pay(payments)?;
+    order.pay()?;
+    Ok(receipt)
+}
@@ -0,0 +1,227 @@
+//! Tables of the restaurant
+//! The `FloorPlan` knows the capacity of each table, which ones are occupied, and the reservations for the day
+//! When a table is not available the parties wait in the `Waitlist` of hosting
+
+use std::fmt;
+
+use super::hosting::{Party, Waitlist};
+
+/// How long a reservation keeps a table, in minutes.
+pub const RESERVATION_MINUTES: u32 = 120;
+
+/// Time of the day, in hours and minutes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl Time {
+    pub fn new(hour: u32, minute: u32) -> Time {
+        Time { hour, minute }
+    }
+
+    fn minutes(self) -> u32 {
+        self.hour * 60 + self.minute
+    }
+
+    // Two reservations of the same table conflict if the second starts before the first is over
+    fn overlaps(self, other: Time) -> bool {
+        self.minutes().abs_diff(other.minutes()) < RESERVATION_MINUTES
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub number: u32,
+    pub capacity: u32,
+    // The name of the party sitting at the table
+    occupied_by: Option<String>,
+}
+
+impl Table {
+    pub fn is_free(&self) -> bool {
+        self.occupied_by.is_none()
+    }
+
+    pub fn occupied_by(&self) -> Option<&str> {
+        self.occupied_by.as_deref()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reservation {
+    pub name: String,
+    pub time: Time,
+    pub party_size: u32,
+    /// The table booked for the party.
+    pub table: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TableError {
+    /// No table is big enough for the party.
+    TooLarge(u32),
+    /// All the tables big enough are already reserved around that time.
+    Conflict(Time),
+    /// The party already has a reservation.
+    AlreadyReserved(String),
+    /// The reservation is not in the floor plan, or it has already been seated.
+    UnknownReservation(String),
+    UnknownTable(u32),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TableError::TooLarge(size) => write!(f, "no table can seat {size} people"),
+            TableError::Conflict(time) => write!(f, "all the tables are reserved around {time}"),
+            TableError::AlreadyReserved(name) => write!(f, "{name} already has a reservation"),
+            TableError::UnknownReservation(name) => write!(f, "there is no reservation for {name}"),
+            TableError::UnknownTable(number) => write!(f, "there is no table {number}"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+/// Where a party ended up when arriving at the restaurant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Seating {
+    /// Seated at the table with this number.
+    Seated(u32),
+    /// The table is still occupied, the party is in the waitlist at this position.
+    Waiting(usize),
+}
+
+#[derive(Debug, Default)]
+pub struct FloorPlan {
+    tables: Vec<Table>,
+    reservations: Vec<Reservation>,
+}
+
+impl FloorPlan {
+    /// Creates a floor plan with a free table for each capacity, numbered from 1.
+    pub fn new(capacities: &[u32]) -> FloorPlan {
+        let tables = capacities
+            .iter()
+            .zip(1..)
+            .map(|(&capacity, number)| Table {
+                number,
+                capacity,
+                occupied_by: None,
+            })
+            .collect();
+        FloorPlan {
+            tables,
+            reservations: Vec::new(),
+        }
+    }
+
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    pub fn table(&self, number: u32) -> Option<&Table> {
+        self.tables.iter().find(|table| table.number == number)
+    }
+
+    pub fn reservations(&self) -> &[Reservation] {
+        &self.reservations
+    }
+
+    /// Books the smallest table that fits the party and isn't reserved by someone else around that time.
+    pub fn reserve(
+        &mut self,
+        name: &str,
+        time: Time,
+        party_size: u32,
+    ) -> Result<Reservation, TableError> {
+        if self.reservations.iter().any(|booked| booked.name == name) {
+            return Err(TableError::AlreadyReserved(String::from(name)));
+        }
+        let mut fitting: Vec<&Table> = self
+            .tables
+            .iter()
+            .filter(|table| table.capacity >= party_size)
+            .collect();
+        if fitting.is_empty() {
+            return Err(TableError::TooLarge(party_size));
+        }
+        fitting.sort_by_key(|table| table.capacity);
+        let table = fitting
+            .into_iter()
+            .find(|table| {
+                !self
+                    .reservations
+                    .iter()
+                    .any(|booked| booked.table == table.number && booked.time.overlaps(time))
+            })
+            .ok_or(TableError::Conflict(time))?;
+
+        let reservation = Reservation {
+            name: String::from(name),
+            time,
+            party_size,
+            table: table.number,
+        };
+        self.reservations.push(reservation.clone());
+        Ok(reservation)
+    }
+
+    /// Seats a party with a reservation at its table.
+    ///
+    /// If the table is still occupied the party joins the waitlist, where reservations have priority,
+    /// and is seated by `free` when a table is available.
+    pub fn seat(
+        &mut self,
+        reservation: &Reservation,
+        waitlist: &mut Waitlist,
+    ) -> Result<Seating, TableError> {
+        let index = self
+            .reservations
+            .iter()
+            .position(|booked| booked == reservation)
+            .ok_or_else(|| TableError::UnknownReservation(reservation.name.clone()))?;
+        let reservation = self.reservations.remove(index);
+
+        let table = self
+            .tables
+            .iter_mut()
+            .find(|table| table.number == reservation.table)
+            .ok_or(TableError::UnknownTable(reservation.table))?;
+        if table.is_free() {
+            table.occupied_by = Some(reservation.name);
+            Ok(Seating::Seated(table.number))
+        } else {
+            let party = Party::reservation(&reservation.name, reservation.party_size);
+            Ok(Seating::Waiting(waitlist.join(party)))
+        }
+    }
+
+    /// Frees a table and seats the next party of the waitlist that fits, returning it.
+    pub fn free(
+        &mut self,
+        number: u32,
+        waitlist: &mut Waitlist,
+    ) -> Result<Option<Party>, TableError> {
+        let table = self
+            .tables
+            .iter_mut()
+            .find(|table| table.number == number)
+            .ok_or(TableError::UnknownTable(number))?;
+        table.occupied_by = None;
+
+        let party = waitlist.next_table(table.capacity);
+        if let Some(party) = &party {
+            table.occupied_by = Some(party.name.clone());
+        }
+        Ok(party)
+    }
+}
@@ -0,0 +1,126 @@
+//! Kitchen
+//! The link between the front and the back of the house: `serving::take_order` sends a `Ticket` for each order,
+//! the cooks of `back_of_house` take the tickets from the queue, cook them, and mark them as ready
+//! When a ticket is ready the pickup callback tells the servers to bring it to the table
+//! The queue is shared between threads, so the cooks can work in parallel
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+
+use crate::front_of_house::serving::OrderItem;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ticket {
+    pub id: u32,
+    pub table: u32,
+    pub items: Vec<OrderItem>,
+}
+
+// Shared, so it can be cloned out of its lock before being called
+type PickupCallback = Arc<dyn Fn(&Ticket) + Send + Sync>;
+
+#[derive(Default)]
+struct State {
+    queue: VecDeque<Ticket>,
+    ready: Vec<Ticket>,
+    next_id: u32,
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    // Wakes up the cooks waiting for a ticket
+    available: Condvar,
+    on_pickup: Mutex<Option<PickupCallback>>,
+}
+
+/// The queue of tickets, cloning it gives another handle to the same kitchen.
+#[derive(Clone, Default)]
+pub struct Kitchen {
+    shared: Arc<Shared>,
+}
+
+impl Kitchen {
+    pub fn new() -> Kitchen {
+        Kitchen::default()
+    }
+
+    /// Sets the callback called with each ticket when it's ready, replacing the previous one.
+    ///
+    /// It's called by the thread of the cook who marked the ticket as ready, once the ticket can be picked up.
+    pub fn on_pickup<F>(&self, callback: F)
+    where
+        F: Fn(&Ticket) + Send + Sync + 'static,
+    {
+        *self.shared.on_pickup.lock().unwrap() = Some(Arc::new(callback));
+    }
+
+    /// Adds a ticket for the items of a table at the end of the queue, returning its id.
+    pub fn enqueue(&self, table: u32, items: Vec<OrderItem>) -> u32 {
+        let mut state = self.shared.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        state.queue.push_back(Ticket { id, table, items });
+        self.shared.available.notify_one();
+        id
+    }
+
+    /// Takes the first ticket of the queue, waiting for one if the queue is empty.
+    ///
+    /// Returns `None` once the kitchen is closed and there are no tickets left, so the cooks can go home.
+    pub fn next_ticket(&self) -> Option<Ticket> {
+        let mut state = self.shared.state.lock().unwrap();
+        loop {
+            if let Some(ticket) = state.queue.pop_front() {
+                return Some(ticket);
+            }
+            if state.closed {
+                return None;
+            }
+            // `wait` releases the lock while sleeping, and takes it again when woken up
+            state = self.shared.available.wait(state).unwrap();
+        }
+    }
+
+    /// Takes the first ticket of the queue without waiting.
+    pub fn try_next_ticket(&self) -> Option<Ticket> {
+        self.shared.state.lock().unwrap().queue.pop_front()
+    }
+
+    /// Marks a ticket as ready and calls the pickup callback.
+    pub fn mark_ready(&self, ticket: Ticket) {
+        self.shared.state.lock().unwrap().ready.push(ticket.clone());
+        // The callback is called without holding any lock, so it can use the kitchen, even to replace the callback
+        let callback = self.shared.on_pickup.lock().unwrap().clone();
+        if let Some(callback) = callback {
+            callback(&ticket);
+        }
+    }
+
+    /// Removes a ready ticket by its id, when the server picks it up.
+    ///
+    /// The id tells apart the orders of the same table.
+    pub fn pick_up(&self, id: u32) -> Option<Ticket> {
+        let mut state = self.shared.state.lock().unwrap();
+        let index = state.ready.iter().position(|ticket| ticket.id == id)?;
+        Some(state.ready.remove(index))
+    }
+
+    /// Stops accepting work: the cooks finish the tickets in the queue and then stop waiting.
+    pub fn close(&self) {
+        self.shared.state.lock().unwrap().closed = true;
+        self.shared.available.notify_all();
+    }
+
+    /// The number of tickets waiting to be cooked.
+    pub fn pending(&self) -> usize {
+        self.shared.state.lock().unwrap().queue.len()
+    }
+
+    /// The ids of the tickets ready to be picked up.
+    pub fn ready(&self) -> Vec<u32> {
+        let state = self.shared.state.lock().unwrap();
+        state.ready.iter().map(|ticket| ticket.id).collect()
+    }
+}
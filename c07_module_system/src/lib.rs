@@ -20,43 +20,37 @@
 //     }
 // }
 
+// back_of_house has been moved to its own file as it grew, but it's still private
+// Only the items re-exported here are part of the public API, the rest stays an implementation detail of the restaurant
 #[allow(dead_code)]
-mod back_of_house {
-    pub struct Breakfast {
-        pub toast: String,
-        seasonal_fruit: String,
-    }
-
-    #[derive(Debug)]
-    pub enum Appetizer {
-        Soup,
-        Salad,
-    }
-
-    impl Breakfast {
-        pub fn summer(toast: &str) -> Breakfast {
-            Breakfast {
-                toast: String::from(toast),
-                seasonal_fruit: String::from("peaches"),
-            }
-        }
-    }
-
-    fn fix_incorrect_order() {
-        cook_order();
-        super::deliver_order();
-    }
-
-    fn cook_order() {}
-}
+mod back_of_house;
+#[cfg(feature = "thread-pool")]
+pub use back_of_house::hire_cooks_on;
+pub use back_of_house::{
+    Appetizer, Breakfast, BreakfastBuilder, Course, Dessert, DietaryTag, Dish, Main, hire_cooks,
+    work_in_kitchen,
+};
 
 // The front_of_house module is now imported here, with the hosting module being imported as well
 // In a way, the mod keyword loads the module as it is in the current scope, meaning that the content of front_of_house is now available in this module
 pub mod front_of_house;
 // pub use crate::front_of_house::hosting; // Absolute path
 pub use front_of_house::hosting; // Relative path
-                                 // If a file is part of the module tree, it can be loeaded just by using the mod keyword
-                                 // Other files in the project should refer to the already loaded module using a path to wher it was declared.
+// If a file is part of the module tree, it can be loeaded just by using the mod keyword
+// Other files in the project should refer to the already loaded module using a path to wher it was declared.
+
+// The menu is a public module in its own file, like front_of_house
+pub mod menu;
+// The kitchen connects the front and the back of the house, so it's at the root of the crate
+pub mod kitchen;
+// Feedback is a module of its own too, it uses the `AveragedCollection` of another crate of the workspace
+pub mod feedback;
+// The restaurant as a whole, and the prelude with the types used the most, so consumers don't need to know the module tree
+pub mod prelude;
+pub mod restaurant;
+pub mod staff;
+use menu::{Category, Menu};
+pub use restaurant::Restaurant;
 
 fn deliver_order() {}
 
@@ -67,9 +61,35 @@ pub fn eat_at_restaurant() {
     // Relative path, starting from the current module
     // front_of_house::hosting::seat_at_table(); // Doesn't work anymore, because fron_of_the_house and hosting have been moved to their own modules
 
-    hosting::add_to_waitlist(); // This wouldn't work without the `use front_of_house::hosting;` statement,
-    hosting::seat_at_table();
-    front_of_house::hosting::add_to_waitlist(); // This refers to the hosting module in the front_of_house module, so it works without use
+    let mut waitlist = hosting::Waitlist::new(std::time::Duration::from_secs(15 * 60));
+    // This wouldn't work without the `use front_of_house::hosting;` statement
+    hosting::add_to_waitlist(&mut waitlist, hosting::Party::walk_in("Smith", 2));
+    // This refers to the hosting module in the front_of_house module, so it works without use
+    front_of_house::hosting::add_to_waitlist(
+        &mut waitlist,
+        hosting::Party::reservation("Rossi", 4),
+    );
+    if let Some(wait) = waitlist.estimated_wait("Smith") {
+        println!(
+            "The Smith party will wait about {} minutes",
+            wait.as_secs() / 60
+        );
+    }
+    // The reservation is seated first, even if it joined later
+    if let Some(party) = hosting::seat_at_table(&mut waitlist, 4) {
+        println!("Seating the {} party of {}", party.name, party.size);
+    }
+
+    // Parties can also book a table in advance, and are seated at their table when they arrive
+    let mut floor = front_of_house::tables::FloorPlan::new(&[2, 4, 6]);
+    let time = front_of_house::tables::Time::new(20, 30);
+    let reservation = floor.reserve("Bianchi", time, 3).unwrap();
+    println!(
+        "Table {} reserved at {}",
+        reservation.table, reservation.time
+    );
+    let seating = floor.seat(&reservation, &mut waitlist).unwrap();
+    println!("The Bianchi party: {:?}", seating);
 
     // Order a breakfast in the summer with Rye toast
     let mut meal = back_of_house::Breakfast::summer("rye");
@@ -79,9 +99,115 @@ pub fn eat_at_restaurant() {
     println!("I'd like {} toast please", meal.toast);
 
     // meal.season_fruit = String::from("blueberries"); // This will throw an error because seasonal_fruit is private
+    // The builder can choose the fruit, but only among the ones of the season
+    let meal = Breakfast::builder()
+        .toast("sourdough")
+        .fruit("figs")
+        .build(menu::Season::Autumn)
+        .unwrap();
+    println!(
+        "I'd like {} toast with {} please",
+        meal.toast,
+        meal.seasonal_fruit()
+    );
 
     let order1 = crate::back_of_house::Appetizer::Salad; // Absolute path
     let order2 = back_of_house::Appetizer::Soup; // Relative path
 
     println!("Orders: {:?}, {:?}", order1, order2);
+
+    // A full course, printed for the kitchen with the diets of each dish
+    let course = back_of_house::Course::new(Main::Risotto)
+        .with_appetizer(order2)
+        .with_dessert(Dessert::Sorbet);
+    println!("{course}");
+    println!(
+        "Suitable for: {:?}, {}",
+        course.tags(),
+        menu::format_price(course.price())
+    );
+
+    // All the steps above can go through the `Restaurant` façade, without knowing which module does what
+    let mut restaurant = Restaurant::house();
+    restaurant.arrive(hosting::Party::walk_in("Verdi", 2));
+    let party = restaurant.seat(2).unwrap();
+    let cooks = restaurant.open_kitchen(1);
+    let mut order = restaurant.order(1, &["Salad", "Coffee"]).unwrap();
+    restaurant.close();
+    for cook in cooks {
+        cook.join().unwrap();
+    }
+    restaurant.serve(&mut order).unwrap();
+    let payments = vec![front_of_house::payments::Payment::cash(1000)];
+    let receipt = restaurant.pay(&mut order, 0, payments).unwrap();
+    println!(
+        "The {} party got {} of change",
+        party.name,
+        menu::format_price(receipt.change)
+    );
+
+    // The staff is scheduled in shifts, the shifts nobody can cover are reported as conflicts
+    use staff::{Day, Employee, Role, Schedule, Shift};
+    let employees = vec![
+        Employee::new("Anna", &[Role::Host, Role::Server], 40),
+        Employee::new("Marco", &[Role::Cook], 40),
+    ];
+    let shifts = vec![
+        Shift::new(Day::Friday, 18, 23, Role::Server, 1),
+        Shift::new(Day::Friday, 17, 23, Role::Cook, 2),
+    ];
+    let mut schedule = Schedule::new(employees, shifts).unwrap();
+    schedule.fill();
+    for conflict in schedule.conflicts() {
+        println!("Schedule conflict: {conflict}");
+    }
+
+    // Instead of hard-coded meals, the orders can come from the menu of the restaurant
+    let mut menu = Menu::house();
+    menu.add_special("Soup", 450).unwrap();
+    for item in menu.by_category(Category::Breakfast) {
+        println!(
+            "Breakfast: {} {}",
+            item.name,
+            menu::format_price(item.price)
+        );
+    }
+    for (item, price) in menu.specials() {
+        println!(
+            "Today's special: {} {}",
+            item.name,
+            menu::format_price(price)
+        );
+    }
+    if let Some(price) = menu.price_of("Soup") {
+        println!("I'd like the soup please, {}", menu::format_price(price));
+    }
+
+    // The order of a table goes through the kitchen before it can be served and paid
+    // Taking the order sends a ticket to the kitchen, where a cook prepares it and calls the server for the pickup
+    let kitchen = kitchen::Kitchen::new();
+    kitchen
+        .on_pickup(|ticket| println!("Ticket {} for table {} is ready", ticket.id, ticket.table));
+    let mut order =
+        front_of_house::serving::take_order(4, &menu, &["Soup", "Risotto"], &kitchen).unwrap();
+    let cooks = back_of_house::hire_cooks(&kitchen, 2);
+    kitchen.close();
+    for cook in cooks {
+        cook.join().unwrap();
+    }
+    kitchen.pick_up(order.ticket().unwrap()).unwrap();
+    front_of_house::serving::serve_order(&mut order).unwrap();
+    let payments = vec![front_of_house::payments::Payment::card(2500)];
+    let receipt = front_of_house::serving::take_payment(&mut order, 300, payments).unwrap();
+    println!("{receipt}");
+
+    // Once paid, the order can be rated, and the rating counts towards each dish ordered
+    let mut feedback = feedback::FeedbackBook::new();
+    feedback.rate(&order, 5, "The risotto was great").unwrap();
+    if let Some(stats) = feedback.stats("Risotto") {
+        println!(
+            "Risotto: {:.1} stars from {} ratings",
+            stats.average, stats.count
+        );
+    }
 }
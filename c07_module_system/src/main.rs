@@ -104,7 +104,7 @@ fn paths() {
     // Only the library crate can be shared with other crates, the binary crate is specific to the package.
     // The library crate can be made public, and the binary crate uses the library crate as a dependency.
 
-    // Another way to construct a relative path is to use the super keyword, which refers to the parent module. Such as `..` in a file system. Check lib.rs for the example.
+    // Another way to construct a relative path is to use the super keyword, which refers to the parent module. Such as `..` in a file system. Check back_of_house.rs for the example.
     // In this case super is used to refer to the deliver_order function which is a sibling of the module back_of_house.
 
     // No only functions can be made public, but structs and enums too.
@@ -161,7 +161,8 @@ fn use_keyword() {
         pub use c07_module_system::front_of_house::hosting;
 
         pub fn eat_at_restaurant() {
-            hosting::add_to_waitlist();
+            let mut waitlist = hosting::Waitlist::new(std::time::Duration::from_secs(600));
+            hosting::add_to_waitlist(&mut waitlist, hosting::Party::walk_in("Smith", 2));
         }
     }
     // The use word is used for bring external packages into scope too.
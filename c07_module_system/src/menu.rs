@@ -0,0 +1,187 @@
+//! Menu of the restaurant
+//! The items are grouped by category, and some of them can be offered as daily specials at a different price
+//! Prices are in cents, so they can be added up without rounding errors
+
+use std::collections::HashMap;
+use std::fmt;
+
+// Unlike the fields of a struct, the variants of a public enum are all public
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Breakfast,
+    Appetizer,
+    Main,
+    Dessert,
+    Drink,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    /// The fruits that can be served with a breakfast in this season, the first one is the default.
+    pub fn fruits(self) -> &'static [&'static str] {
+        match self {
+            Season::Spring => &["strawberries", "cherries"],
+            Season::Summer => &["peaches", "watermelon", "blueberries"],
+            Season::Autumn => &["apples", "pears", "figs"],
+            Season::Winter => &["oranges", "kiwis"],
+        }
+    }
+}
+
+/// The items of the house menu with their regular price, the prices of the dishes of `back_of_house` come from here.
+pub const HOUSE_ITEMS: [(&str, u32, Category); 9] = [
+    ("Summer breakfast", 850, Category::Breakfast),
+    ("Winter breakfast", 900, Category::Breakfast),
+    ("Soup", 550, Category::Appetizer),
+    ("Salad", 600, Category::Appetizer),
+    ("Risotto", 1400, Category::Main),
+    ("Grilled fish", 1800, Category::Main),
+    ("Tiramisu", 650, Category::Dessert),
+    ("Sorbet", 500, Category::Dessert),
+    ("Coffee", 250, Category::Drink),
+];
+
+/// The regular price of an item of the house menu, without the daily specials.
+pub fn house_price(name: &str) -> Option<u32> {
+    HOUSE_ITEMS
+        .iter()
+        .find(|(item, _, _)| *item == name)
+        .map(|&(_, price, _)| price)
+}
+
+/// The kinds of toast served with a breakfast.
+pub const TOASTS: [&str; 4] = ["white", "wheat", "rye", "sourdough"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MenuItem {
+    pub name: String,
+    /// Price in cents.
+    pub price: u32,
+    pub category: Category,
+}
+
+impl MenuItem {
+    pub fn new(name: &str, price: u32, category: Category) -> MenuItem {
+        MenuItem {
+            name: String::from(name),
+            price,
+            category,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MenuError {
+    /// There is no item with this name on the menu.
+    UnknownItem(String),
+    /// An item with this name is already on the menu.
+    DuplicateItem(String),
+    /// The toast is not one of `TOASTS`.
+    UnknownToast(String),
+    /// The fruit can't be served in this season.
+    FruitOutOfSeason { fruit: String, season: Season },
+}
+
+impl fmt::Display for MenuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MenuError::UnknownItem(name) => write!(f, "'{name}' is not on the menu"),
+            MenuError::DuplicateItem(name) => write!(f, "'{name}' is already on the menu"),
+            MenuError::UnknownToast(toast) => write!(f, "there is no {toast} toast"),
+            MenuError::FruitOutOfSeason { fruit, season } => {
+                write!(f, "{fruit} are not served in {season:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MenuError {}
+
+// The fields are private: items can only be added through `add_item`, which rejects duplicates
+#[derive(Debug, Default)]
+pub struct Menu {
+    items: Vec<MenuItem>,
+    // Special price of the day, by item name
+    specials: HashMap<String, u32>,
+}
+
+impl Menu {
+    pub fn new() -> Menu {
+        Menu::default()
+    }
+
+    /// The menu served by the restaurant, used by `eat_at_restaurant`.
+    pub fn house() -> Menu {
+        let mut menu = Menu::new();
+        for (name, price, category) in HOUSE_ITEMS {
+            // The names are all different, so adding them can't fail
+            menu.add_item(MenuItem::new(name, price, category)).unwrap();
+        }
+        menu
+    }
+
+    /// Adds an item to the menu, the names must be unique.
+    pub fn add_item(&mut self, item: MenuItem) -> Result<(), MenuError> {
+        if self.item(&item.name).is_some() {
+            return Err(MenuError::DuplicateItem(item.name));
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    pub fn item(&self, name: &str) -> Option<&MenuItem> {
+        self.items.iter().find(|item| item.name == name)
+    }
+
+    /// Returns the items of a category, in the order they were added.
+    pub fn by_category(&self, category: Category) -> Vec<&MenuItem> {
+        self.items
+            .iter()
+            .filter(|item| item.category == category)
+            .collect()
+    }
+
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Offers an item at a special price for the day.
+    pub fn add_special(&mut self, name: &str, price: u32) -> Result<(), MenuError> {
+        if self.item(name).is_none() {
+            return Err(MenuError::UnknownItem(String::from(name)));
+        }
+        self.specials.insert(String::from(name), price);
+        Ok(())
+    }
+
+    /// Returns the daily specials with their special price, in the order of the menu.
+    pub fn specials(&self) -> Vec<(&MenuItem, u32)> {
+        self.items
+            .iter()
+            .filter_map(|item| self.specials.get(&item.name).map(|&price| (item, price)))
+            .collect()
+    }
+
+    /// Removes the specials at the end of the day.
+    pub fn clear_specials(&mut self) {
+        self.specials.clear();
+    }
+
+    /// Returns the price of an item for today: the special price if it's a daily special, the regular one otherwise.
+    pub fn price_of(&self, name: &str) -> Option<u32> {
+        let item = self.item(name)?;
+        Some(self.specials.get(name).copied().unwrap_or(item.price))
+    }
+}
+
+/// Formats a price in cents, e.g. `$8.50` for 850.
+pub fn format_price(cents: u32) -> String {
+    format!("${}.{:02}", cents / 100, cents % 100)
+}
@@ -0,0 +1,11 @@
+//! Prelude
+//! Re-exports the types used the most, so they can all be brought into scope with:
+//! `use c07_module_system::prelude::*;`
+
+pub use crate::Restaurant;
+pub use crate::front_of_house::hosting::{self, Party, Waitlist, add_to_waitlist, seat_at_table};
+pub use crate::front_of_house::payments::{Bill, Payment, PaymentMethod, Receipt};
+pub use crate::front_of_house::serving::{Order, OrderError, OrderStatus};
+pub use crate::kitchen::{Kitchen, Ticket};
+pub use crate::menu::{Category, Menu, MenuError, MenuItem, Season};
+pub use crate::{Appetizer, Breakfast, Course, Dessert, Dish, Main};
@@ -0,0 +1,106 @@
+//! Restaurant
+//! A façade over the modules of the crate: it owns the menu, the waitlist and the kitchen,
+//! and goes through the steps of an order without the caller knowing which module each of them belongs to
+
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use crate::back_of_house;
+use crate::front_of_house::hosting::{self, Party, Waitlist};
+use crate::front_of_house::payments::{Payment, PaymentError, Receipt};
+use crate::front_of_house::serving::{self, Order, OrderError, OrderStatus};
+use crate::kitchen::Kitchen;
+use crate::menu::Menu;
+
+/// How long a table of the house restaurant takes to be free again.
+pub const HOUSE_TURNOVER: Duration = Duration::from_secs(15 * 60);
+
+pub struct Restaurant {
+    menu: Menu,
+    waitlist: Waitlist,
+    kitchen: Kitchen,
+}
+
+impl Restaurant {
+    pub fn new(menu: Menu, turnover: Duration) -> Restaurant {
+        Restaurant {
+            menu,
+            waitlist: Waitlist::new(turnover),
+            kitchen: Kitchen::new(),
+        }
+    }
+
+    /// The restaurant serving the house menu.
+    pub fn house() -> Restaurant {
+        Restaurant::new(Menu::house(), HOUSE_TURNOVER)
+    }
+
+    pub fn menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    /// Allows to change the menu, e.g. to add the daily specials.
+    pub fn menu_mut(&mut self) -> &mut Menu {
+        &mut self.menu
+    }
+
+    pub fn waitlist(&self) -> &Waitlist {
+        &self.waitlist
+    }
+
+    pub fn kitchen(&self) -> &Kitchen {
+        &self.kitchen
+    }
+
+    /// A party arrives and joins the waitlist, returning its position in the line.
+    pub fn arrive(&mut self, party: Party) -> usize {
+        hosting::add_to_waitlist(&mut self.waitlist, party)
+    }
+
+    /// A table of `size` seats is free, returns the party seated there.
+    pub fn seat(&mut self, size: u32) -> Option<Party> {
+        hosting::seat_at_table(&mut self.waitlist, size)
+    }
+
+    /// Starts the cooks, which work until the restaurant is closed.
+    pub fn open_kitchen(&self, cooks: usize) -> Vec<JoinHandle<usize>> {
+        back_of_house::hire_cooks(&self.kitchen, cooks)
+    }
+
+    /// Takes the order of a table from the menu and sends it to the kitchen.
+    pub fn order(&self, table: u32, names: &[&str]) -> Result<Order, OrderError> {
+        serving::take_order(table, &self.menu, names, &self.kitchen)
+    }
+
+    /// Picks up the order from the kitchen and serves it, if it's ready.
+    ///
+    /// The ticket is only taken from the kitchen once the order is known to be cooking.
+    pub fn serve(&self, order: &mut Order) -> Result<(), OrderError> {
+        if order.status() != OrderStatus::Cooking {
+            // `serve_order` rejects the order without touching the kitchen
+            return serving::serve_order(order);
+        }
+        let ready = order
+            .ticket()
+            .and_then(|ticket| self.kitchen.pick_up(ticket));
+        if ready.is_none() {
+            return Err(OrderError::NotReady(order.table()));
+        }
+        serving::serve_order(order)
+    }
+
+    /// Takes the payment of a served order, returning the receipt.
+    pub fn pay(
+        &self,
+        order: &mut Order,
+        tip: u32,
+        payments: Vec<Payment>,
+    ) -> Result<Receipt, PaymentError> {
+        serving::take_payment(order, tip, payments)
+    }
+
+    /// Closes the kitchen: the cooks finish the tickets in the queue and stop.
+    pub fn close(&self) {
+        self.kitchen.close();
+    }
+}
@@ -0,0 +1,293 @@
+//! Staff
+//! The employees work in shifts, each shift needs a number of people for a role
+//! The `Schedule` assigns the employees to the shifts, checking that they have the role,
+//! that they don't work two shifts at the same time, and that they don't go over their maximum hours
+//! The shifts without enough people are reported as conflicts
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Host,
+    Server,
+    Cook,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Day {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Employee {
+    pub name: String,
+    /// The roles the employee can cover.
+    pub roles: Vec<Role>,
+    /// The maximum number of hours in a week.
+    pub max_hours: u32,
+}
+
+impl Employee {
+    pub fn new(name: &str, roles: &[Role], max_hours: u32) -> Employee {
+        Employee {
+            name: String::from(name),
+            roles: roles.to_vec(),
+            max_hours,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shift {
+    pub day: Day,
+    /// Starting hour, from 0 to 23.
+    pub start: u32,
+    /// Ending hour, after `start` and at most 24.
+    pub end: u32,
+    pub role: Role,
+    /// The number of employees needed.
+    pub needed: usize,
+}
+
+impl Shift {
+    pub fn new(day: Day, start: u32, end: u32, role: Role, needed: usize) -> Shift {
+        Shift {
+            day,
+            start,
+            end,
+            role,
+            needed,
+        }
+    }
+
+    pub fn hours(&self) -> u32 {
+        self.end - self.start
+    }
+
+    fn overlaps(&self, other: &Shift) -> bool {
+        self.day == other.day && self.start < other.end && other.start < self.end
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaffError {
+    /// The shift at this index ends before it starts, or after midnight.
+    InvalidShift(usize),
+    DuplicateEmployee(String),
+    UnknownEmployee(String),
+    UnknownShift(usize),
+    /// The employee can't cover the role of the shift.
+    MissingRole {
+        name: String,
+        role: Role,
+    },
+    /// The shift would take the employee over the maximum hours.
+    TooManyHours {
+        name: String,
+        hours: u32,
+        max: u32,
+    },
+    /// The employee already works a shift at the same time.
+    Overlap {
+        name: String,
+        shift: usize,
+    },
+    /// The shift already has all the employees it needs.
+    ShiftFull(usize),
+}
+
+impl fmt::Display for StaffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StaffError::InvalidShift(shift) => write!(f, "shift {shift} has invalid hours"),
+            StaffError::DuplicateEmployee(name) => write!(f, "{name} is already an employee"),
+            StaffError::UnknownEmployee(name) => write!(f, "{name} is not an employee"),
+            StaffError::UnknownShift(shift) => write!(f, "there is no shift {shift}"),
+            StaffError::MissingRole { name, role } => write!(f, "{name} can't work as {role:?}"),
+            StaffError::TooManyHours { name, hours, max } => {
+                write!(f, "{name} would work {hours} hours, the maximum is {max}")
+            }
+            StaffError::Overlap { name, shift } => {
+                write!(f, "{name} already works shift {shift} at the same time")
+            }
+            StaffError::ShiftFull(shift) => write!(f, "shift {shift} is already covered"),
+        }
+    }
+}
+
+impl std::error::Error for StaffError {}
+
+/// A shift without enough employees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Conflict {
+    /// The index of the shift.
+    pub shift: usize,
+    pub role: Role,
+    /// How many employees are missing.
+    pub missing: usize,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "shift {} needs {} more {:?}",
+            self.shift, self.missing, self.role
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Schedule {
+    employees: Vec<Employee>,
+    shifts: Vec<Shift>,
+    // The indexes of the employees assigned to each shift
+    assignments: Vec<Vec<usize>>,
+}
+
+impl Schedule {
+    /// Creates an empty schedule, the shifts are identified by their index.
+    pub fn new(employees: Vec<Employee>, shifts: Vec<Shift>) -> Result<Schedule, StaffError> {
+        if let Some(index) = shifts
+            .iter()
+            .position(|shift| shift.start >= shift.end || shift.end > 24)
+        {
+            return Err(StaffError::InvalidShift(index));
+        }
+        for (index, employee) in employees.iter().enumerate() {
+            if employees[..index]
+                .iter()
+                .any(|other| other.name == employee.name)
+            {
+                return Err(StaffError::DuplicateEmployee(employee.name.clone()));
+            }
+        }
+        Ok(Schedule {
+            assignments: vec![Vec::new(); shifts.len()],
+            employees,
+            shifts,
+        })
+    }
+
+    pub fn shifts(&self) -> &[Shift] {
+        &self.shifts
+    }
+
+    /// Assigns an employee to a shift, if all the constraints are respected.
+    pub fn assign(&mut self, name: &str, shift: usize) -> Result<(), StaffError> {
+        let employee = self
+            .employees
+            .iter()
+            .position(|employee| employee.name == name)
+            .ok_or_else(|| StaffError::UnknownEmployee(String::from(name)))?;
+        self.check(employee, shift)?;
+        self.assignments[shift].push(employee);
+        Ok(())
+    }
+
+    // Checks the constraints of assigning an employee, by index, to a shift
+    fn check(&self, employee: usize, shift: usize) -> Result<(), StaffError> {
+        let candidate = &self.employees[employee];
+        let planned = self
+            .shifts
+            .get(shift)
+            .ok_or(StaffError::UnknownShift(shift))?;
+        if self.assignments[shift].len() >= planned.needed {
+            return Err(StaffError::ShiftFull(shift));
+        }
+        if !candidate.roles.contains(&planned.role) {
+            return Err(StaffError::MissingRole {
+                name: candidate.name.clone(),
+                role: planned.role,
+            });
+        }
+        if let Some(other) = self
+            .shifts_of_index(employee)
+            .find(|&other| self.shifts[other].overlaps(planned))
+        {
+            return Err(StaffError::Overlap {
+                name: candidate.name.clone(),
+                shift: other,
+            });
+        }
+        let hours = self.hours_of_index(employee) + planned.hours();
+        if hours > candidate.max_hours {
+            return Err(StaffError::TooManyHours {
+                name: candidate.name.clone(),
+                hours,
+                max: candidate.max_hours,
+            });
+        }
+        Ok(())
+    }
+
+    /// Fills the shifts in order, assigning each one to the employees with the fewest hours so far.
+    ///
+    /// It's a greedy assignment: it respects the constraints, but it may leave conflicts that a different
+    /// assignment would avoid. The shifts still missing employees are returned by `conflicts`.
+    pub fn fill(&mut self) {
+        for shift in 0..self.shifts.len() {
+            let mut candidates: Vec<usize> = (0..self.employees.len()).collect();
+            candidates.sort_by_key(|&employee| self.hours_of_index(employee));
+            for employee in candidates {
+                if self.check(employee, shift).is_ok() {
+                    self.assignments[shift].push(employee);
+                }
+            }
+        }
+    }
+
+    /// Returns the names of the employees assigned to a shift.
+    pub fn staff_of(&self, shift: usize) -> Vec<&str> {
+        self.assignments.get(shift).map_or(Vec::new(), |assigned| {
+            assigned
+                .iter()
+                .map(|&employee| self.employees[employee].name.as_str())
+                .collect()
+        })
+    }
+
+    /// Returns the hours an employee works in the schedule.
+    pub fn hours(&self, name: &str) -> Option<u32> {
+        let employee = self
+            .employees
+            .iter()
+            .position(|employee| employee.name == name)?;
+        Some(self.hours_of_index(employee))
+    }
+
+    /// Returns the shifts without enough employees, in the order of the shifts.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        self.shifts
+            .iter()
+            .zip(&self.assignments)
+            .enumerate()
+            .filter(|(_, (shift, assigned))| assigned.len() < shift.needed)
+            .map(|(index, (shift, assigned))| Conflict {
+                shift: index,
+                role: shift.role,
+                missing: shift.needed - assigned.len(),
+            })
+            .collect()
+    }
+
+    fn shifts_of_index(&self, employee: usize) -> impl Iterator<Item = usize> + '_ {
+        self.assignments
+            .iter()
+            .enumerate()
+            .filter(move |(_, assigned)| assigned.contains(&employee))
+            .map(|(shift, _)| shift)
+    }
+
+    fn hours_of_index(&self, employee: usize) -> u32 {
+        self.shifts_of_index(employee)
+            .map(|shift| self.shifts[shift].hours())
+            .sum()
+    }
+}
@@ -0,0 +1,45 @@
+use c07_module_system::Breakfast;
+use c07_module_system::menu::{MenuError, Season};
+
+#[test]
+fn builder_chooses_toast_and_fruit() {
+    let breakfast = Breakfast::builder()
+        .toast("rye")
+        .fruit("blueberries")
+        .build(Season::Summer)
+        .unwrap();
+
+    assert_eq!(breakfast.toast, "rye");
+    assert_eq!(breakfast.seasonal_fruit(), "blueberries");
+}
+
+#[test]
+fn builder_defaults_to_the_first_fruit_of_the_season() {
+    let breakfast = Breakfast::builder().build(Season::Winter).unwrap();
+
+    assert_eq!(breakfast.toast, "white");
+    assert_eq!(breakfast.seasonal_fruit(), "oranges");
+}
+
+#[test]
+fn fruits_must_be_in_season() {
+    let result = Breakfast::builder().fruit("peaches").build(Season::Winter);
+
+    assert_eq!(
+        result.err(),
+        Some(MenuError::FruitOutOfSeason {
+            fruit: String::from("peaches"),
+            season: Season::Winter,
+        })
+    );
+}
+
+#[test]
+fn toasts_must_be_known() {
+    let result = Breakfast::builder().toast("bagel").build(Season::Spring);
+
+    assert_eq!(
+        result.err(),
+        Some(MenuError::UnknownToast(String::from("bagel")))
+    );
+}
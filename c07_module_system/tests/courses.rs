@@ -0,0 +1,49 @@
+use c07_module_system::menu::Menu;
+use c07_module_system::{Appetizer, Course, Dessert, DietaryTag, Dish, Main};
+
+#[test]
+fn dish_prices_match_the_menu() {
+    let menu = Menu::house();
+    let dishes: [&dyn Dish; 6] = [
+        &Appetizer::Soup,
+        &Appetizer::Salad,
+        &Main::Risotto,
+        &Main::GrilledFish,
+        &Dessert::Tiramisu,
+        &Dessert::Sorbet,
+    ];
+
+    for dish in dishes {
+        assert_eq!(menu.price_of(&dish.to_string()), Some(dish.price()));
+    }
+}
+
+#[test]
+fn courses_add_up_their_dishes() {
+    let course = Course::new(Main::GrilledFish)
+        .with_appetizer(Appetizer::Salad)
+        .with_dessert(Dessert::Sorbet);
+
+    assert_eq!(course.price(), 600 + 1800 + 500);
+    assert_eq!(Course::new(Main::Risotto).price(), 1400);
+}
+
+#[test]
+fn courses_keep_the_tags_shared_by_all_dishes() {
+    let vegetarian = Course::new(Main::Risotto).with_dessert(Dessert::Tiramisu);
+    let fish = Course::new(Main::GrilledFish).with_appetizer(Appetizer::Soup);
+
+    assert_eq!(vegetarian.tags(), [DietaryTag::Vegetarian]);
+    assert_eq!(fish.tags(), [DietaryTag::GlutenFree, DietaryTag::DairyFree]);
+}
+
+#[test]
+fn courses_are_printed_for_the_kitchen() {
+    let course = Course::new(Main::Risotto).with_appetizer(Appetizer::Soup);
+
+    assert_eq!(
+        course.to_string(),
+        "Appetizer: Soup - vegetable soup of the day [vegetarian, vegan, gluten-free, dairy-free]
+Main: Risotto - mushroom risotto with parmesan [vegetarian, gluten-free]"
+    );
+}
@@ -0,0 +1,99 @@
+use c07_module_system::feedback::{FeedbackBook, FeedbackError};
+use c07_module_system::front_of_house::serving::{Order, OrderStatus, take_order};
+use c07_module_system::kitchen::Kitchen;
+use c07_module_system::menu::Menu;
+
+// The orders of a test share the kitchen, so each of them has its own ticket
+fn paid_order(kitchen: &Kitchen, table: u32, dishes: &[&str]) -> Order {
+    let mut order = take_order(table, &Menu::house(), dishes, kitchen).unwrap();
+    order.serve().unwrap();
+    order.pay().unwrap();
+    order
+}
+
+#[test]
+fn ratings_count_towards_each_dish() {
+    let mut book = FeedbackBook::new();
+    let kitchen = Kitchen::new();
+
+    book.rate(&paid_order(&kitchen, 1, &["Soup", "Risotto"]), 4, "Good")
+        .unwrap();
+    book.rate(&paid_order(&kitchen, 2, &["Risotto"]), 1, "Cold")
+        .unwrap();
+
+    let risotto = book.stats("Risotto").unwrap();
+    assert_eq!(risotto.count, 2);
+    assert_eq!(risotto.average, 2.5);
+    assert_eq!((risotto.min, risotto.max), (1, 4));
+    assert_eq!(book.stats("Soup").unwrap().count, 1);
+    assert_eq!(book.stats("Salad"), None);
+    assert_eq!(book.feedback()[1].comment, "Cold");
+}
+
+#[test]
+fn dishes_are_ranked_by_average() {
+    let mut book = FeedbackBook::new();
+    let kitchen = Kitchen::new();
+    book.rate(&paid_order(&kitchen, 1, &["Soup", "Risotto"]), 5, "")
+        .unwrap();
+    book.rate(&paid_order(&kitchen, 2, &["Risotto", "Coffee"]), 3, "")
+        .unwrap();
+
+    assert_eq!(
+        book.ranking(),
+        [("Soup", 5.0), ("Risotto", 4.0), ("Coffee", 3.0)]
+    );
+}
+
+#[test]
+fn only_paid_orders_can_be_rated() {
+    let mut book = FeedbackBook::new();
+    let order = take_order(1, &Menu::house(), &["Soup"], &Kitchen::new()).unwrap();
+
+    assert_eq!(
+        book.rate(&order, 5, "Fast!"),
+        Err(FeedbackError::NotCompleted(OrderStatus::Cooking))
+    );
+    assert!(book.feedback().is_empty());
+}
+
+#[test]
+fn ratings_are_between_one_and_five() {
+    let mut book = FeedbackBook::new();
+    let kitchen = Kitchen::new();
+    let order = paid_order(&kitchen, 1, &["Soup"]);
+
+    assert_eq!(
+        book.rate(&order, 0, ""),
+        Err(FeedbackError::InvalidRating(0))
+    );
+    assert_eq!(
+        book.rate(&order, 6, ""),
+        Err(FeedbackError::InvalidRating(6))
+    );
+    assert!(book.rate(&order, 5, "").is_ok());
+}
+
+#[test]
+fn orders_are_rated_once() {
+    let mut book = FeedbackBook::new();
+    let kitchen = Kitchen::new();
+    let order = paid_order(&kitchen, 1, &["Soup"]);
+    book.rate(&order, 5, "Great").unwrap();
+
+    assert_eq!(
+        book.rate(&order, 1, "Actually, no"),
+        Err(FeedbackError::AlreadyRated(1))
+    );
+    assert_eq!(book.stats("Soup").unwrap().count, 1);
+    // Another order of the same table is rated on its own
+    book.rate(&paid_order(&kitchen, 1, &["Soup"]), 3, "")
+        .unwrap();
+    assert_eq!(book.feedback().len(), 2);
+
+    let mut order = Order::new(2);
+    order.start_cooking().unwrap();
+    order.serve().unwrap();
+    order.pay().unwrap();
+    assert_eq!(book.rate(&order, 4, ""), Err(FeedbackError::NoTicket));
+}
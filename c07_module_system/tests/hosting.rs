@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use c07_module_system::hosting::{self, Party, Waitlist};
+
+const TURNOVER: Duration = Duration::from_secs(10 * 60);
+
+#[test]
+fn parties_are_seated_in_the_order_they_joined() {
+    let mut waitlist = Waitlist::new(TURNOVER);
+    waitlist.join(Party::walk_in("Smith", 2));
+    waitlist.join(Party::walk_in("Jones", 2));
+
+    assert_eq!(waitlist.next_table(2).unwrap().name, "Smith");
+    assert_eq!(waitlist.next_table(2).unwrap().name, "Jones");
+    assert_eq!(waitlist.next_table(2), None);
+}
+
+#[test]
+fn only_parties_fitting_the_table_are_seated() {
+    let mut waitlist = Waitlist::new(TURNOVER);
+    waitlist.join(Party::walk_in("Large", 6));
+    waitlist.join(Party::walk_in("Small", 2));
+
+    assert_eq!(waitlist.next_table(4).unwrap().name, "Small");
+    assert_eq!(waitlist.next_table(4), None);
+    assert_eq!(waitlist.len(), 1);
+}
+
+#[test]
+fn reservations_have_priority() {
+    let mut waitlist = Waitlist::new(TURNOVER);
+    assert_eq!(waitlist.join(Party::walk_in("Smith", 2)), 0);
+    assert_eq!(waitlist.join(Party::reservation("Rossi", 2)), 0);
+    assert_eq!(waitlist.position("Smith"), Some(1));
+
+    assert_eq!(
+        hosting::seat_at_table(&mut waitlist, 2).unwrap().name,
+        "Rossi"
+    );
+    assert_eq!(
+        hosting::seat_at_table(&mut waitlist, 2).unwrap().name,
+        "Smith"
+    );
+}
+
+#[test]
+fn parties_with_the_same_name_get_their_own_position() {
+    let mut waitlist = Waitlist::new(TURNOVER);
+    assert_eq!(waitlist.join(Party::walk_in("Smith", 2)), 0);
+    assert_eq!(waitlist.join(Party::walk_in("Smith", 4)), 1);
+    assert_eq!(waitlist.join(Party::reservation("Smith", 3)), 0);
+    assert_eq!(waitlist.join(Party::reservation("Smith", 5)), 1);
+    assert_eq!(waitlist.join(Party::walk_in("Smith", 1)), 4);
+}
+
+#[test]
+fn waits_grow_with_the_parties_ahead() {
+    let mut waitlist = Waitlist::new(TURNOVER);
+    hosting::add_to_waitlist(&mut waitlist, Party::walk_in("Smith", 2));
+    hosting::add_to_waitlist(&mut waitlist, Party::walk_in("Jones", 3));
+
+    assert_eq!(waitlist.estimated_wait("Smith"), Some(TURNOVER));
+    assert_eq!(waitlist.estimated_wait("Jones"), Some(TURNOVER * 2));
+    assert_eq!(waitlist.estimated_wait("Nobody"), None);
+
+    waitlist.leave("Smith").unwrap();
+    assert_eq!(waitlist.estimated_wait("Jones"), Some(TURNOVER));
+}
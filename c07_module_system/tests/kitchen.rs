@@ -0,0 +1,104 @@
+use std::sync::{Arc, Mutex};
+
+use c07_module_system::front_of_house::serving::take_order;
+use c07_module_system::kitchen::Kitchen;
+use c07_module_system::menu::Menu;
+use c07_module_system::{hire_cooks, work_in_kitchen};
+
+#[test]
+fn tickets_are_cooked_in_order() {
+    let menu = Menu::house();
+    let kitchen = Kitchen::new();
+    take_order(1, &menu, &["Soup"], &kitchen).unwrap();
+    take_order(2, &menu, &["Salad", "Risotto"], &kitchen).unwrap();
+
+    let first = kitchen.try_next_ticket().unwrap();
+    assert_eq!((first.id, first.table), (1, 1));
+    assert_eq!(first.items[0].name, "Soup");
+    assert_eq!(kitchen.pending(), 1);
+
+    kitchen.mark_ready(first);
+    assert_eq!(kitchen.ready(), [1]);
+    assert_eq!(kitchen.pick_up(1).unwrap().id, 1);
+    assert_eq!(kitchen.pick_up(1), None);
+}
+
+#[test]
+fn cooks_stop_once_the_kitchen_is_closed() {
+    let menu = Menu::house();
+    let kitchen = Kitchen::new();
+    for table in 1..=3 {
+        take_order(table, &menu, &["Coffee"], &kitchen).unwrap();
+    }
+    kitchen.close();
+
+    assert_eq!(work_in_kitchen(&kitchen), 3);
+    assert_eq!(kitchen.ready(), [1, 2, 3]);
+}
+
+#[test]
+fn servers_are_notified_of_every_pickup() {
+    let menu = Menu::house();
+    let kitchen = Kitchen::new();
+    let picked = Arc::new(Mutex::new(Vec::new()));
+    let notified = Arc::clone(&picked);
+    kitchen.on_pickup(move |ticket| notified.lock().unwrap().push(ticket.table));
+
+    // The cooks wait for the tickets sent while they are already working
+    let cooks = hire_cooks(&kitchen, 3);
+    for table in 1..=10 {
+        take_order(table, &menu, &["Soup"], &kitchen).unwrap();
+    }
+    kitchen.close();
+    let cooked: usize = cooks.into_iter().map(|cook| cook.join().unwrap()).sum();
+
+    assert_eq!(cooked, 10);
+    let mut picked = picked.lock().unwrap().clone();
+    picked.sort();
+    assert_eq!(picked, (1..=10).collect::<Vec<_>>());
+    assert_eq!(kitchen.pending(), 0);
+}
+
+#[test]
+fn callbacks_can_use_the_kitchen() {
+    let menu = Menu::house();
+    let kitchen = Kitchen::new();
+    let picked = Arc::new(Mutex::new(Vec::new()));
+    let notified = Arc::clone(&picked);
+    let handle = kitchen.clone();
+    kitchen.on_pickup(move |ticket| {
+        // The ticket is already ready when the callback is called
+        let picked_up = handle.pick_up(ticket.id).unwrap();
+        notified.lock().unwrap().push(picked_up.id);
+        handle.on_pickup(|_| {});
+    });
+
+    take_order(1, &menu, &["Soup"], &kitchen).unwrap();
+    take_order(2, &menu, &["Soup"], &kitchen).unwrap();
+    kitchen.mark_ready(kitchen.try_next_ticket().unwrap());
+    kitchen.mark_ready(kitchen.try_next_ticket().unwrap());
+
+    // The first call replaced the callback, the second ticket is left for the servers
+    assert_eq!(*picked.lock().unwrap(), [1]);
+    assert_eq!(kitchen.ready(), [2]);
+}
+
+#[cfg(feature = "thread-pool")]
+#[test]
+fn cooks_can_work_on_the_thread_pool() {
+    use c07_module_system::hire_cooks_on;
+    use c21_web_server::ThreadPool;
+
+    let menu = Menu::house();
+    let kitchen = Kitchen::new();
+    let pool = ThreadPool::new(2);
+    hire_cooks_on(&pool, &kitchen, 2);
+    for table in 1..=4 {
+        take_order(table, &menu, &["Salad"], &kitchen).unwrap();
+    }
+    kitchen.close();
+    // Dropping the pool waits for the cooks to finish
+    drop(pool);
+
+    assert_eq!(kitchen.ready().len(), 4);
+}
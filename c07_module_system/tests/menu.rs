@@ -0,0 +1,60 @@
+use c07_module_system::menu::{Category, Menu, MenuError, MenuItem, format_price};
+
+#[test]
+fn items_are_looked_up_by_category() {
+    let menu = Menu::house();
+
+    let names: Vec<&str> = menu
+        .by_category(Category::Appetizer)
+        .iter()
+        .map(|item| item.name.as_str())
+        .collect();
+
+    assert_eq!(names, ["Soup", "Salad"]);
+    assert_eq!(menu.item("Coffee").unwrap().category, Category::Drink);
+    assert!(menu.item("Pizza").is_none());
+}
+
+#[test]
+fn names_are_unique() {
+    let mut menu = Menu::new();
+    menu.add_item(MenuItem::new("Soup", 550, Category::Appetizer))
+        .unwrap();
+
+    let result = menu.add_item(MenuItem::new("Soup", 600, Category::Main));
+
+    assert_eq!(result, Err(MenuError::DuplicateItem(String::from("Soup"))));
+    assert_eq!(menu.items().len(), 1);
+}
+
+#[test]
+fn specials_change_the_price_for_the_day() {
+    let mut menu = Menu::house();
+
+    menu.add_special("Risotto", 1100).unwrap();
+
+    assert_eq!(menu.price_of("Risotto"), Some(1100));
+    assert_eq!(menu.price_of("Salad"), Some(600));
+    assert_eq!(menu.specials().len(), 1);
+    assert_eq!(menu.specials()[0].0.name, "Risotto");
+
+    menu.clear_specials();
+    assert_eq!(menu.price_of("Risotto"), Some(1400));
+    assert!(menu.specials().is_empty());
+}
+
+#[test]
+fn specials_must_be_on_the_menu() {
+    let mut menu = Menu::house();
+
+    assert_eq!(
+        menu.add_special("Pizza", 500),
+        Err(MenuError::UnknownItem(String::from("Pizza")))
+    );
+}
+
+#[test]
+fn prices_are_formatted_in_dollars() {
+    assert_eq!(format_price(850), "$8.50");
+    assert_eq!(format_price(5), "$0.05");
+}
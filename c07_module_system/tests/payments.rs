@@ -0,0 +1,125 @@
+use c07_module_system::front_of_house::payments::{Bill, Payment, PaymentError, TAX_PERCENT};
+use c07_module_system::front_of_house::serving::{
+    self, OrderError, OrderItem, OrderStatus, take_order,
+};
+use c07_module_system::kitchen::Kitchen;
+use c07_module_system::menu::Menu;
+
+fn line(name: &str, price: u32, quantity: u32) -> OrderItem {
+    OrderItem {
+        name: String::from(name),
+        price,
+        quantity,
+    }
+}
+
+fn bill() -> Bill {
+    let mut bill = Bill::new(7, 10);
+    bill.add_line(line("Soup", 550, 2)).unwrap();
+    bill.add_line(line("Coffee", 250, 1)).unwrap();
+    bill
+}
+
+#[test]
+fn totals_include_tax_and_tip() {
+    let bill = bill().with_tip(200).unwrap();
+
+    assert_eq!(bill.subtotal(), 1350);
+    assert_eq!(bill.tax(), 135);
+    assert_eq!(bill.total(), 1350 + 135 + 200);
+    assert_eq!(bill.with_tip_percent(15).unwrap().tip(), 203);
+}
+
+#[test]
+fn bills_are_split_to_the_cent() {
+    let bill = bill();
+
+    assert_eq!(bill.split_evenly(3), [495, 495, 495]);
+    assert_eq!(bill.split_evenly(4), [372, 371, 371, 371]);
+    assert_eq!(bill.split_evenly(4).iter().sum::<u32>(), bill.total());
+}
+
+#[test]
+fn payments_must_cover_the_total() {
+    let result = bill().pay(vec![Payment::card(1000), Payment::mobile(400)]);
+
+    assert_eq!(
+        result,
+        Err(PaymentError::Underpaid {
+            total: 1485,
+            paid: 1400,
+        })
+    );
+}
+
+#[test]
+fn amounts_cannot_overflow() {
+    let mut bill = bill();
+    assert_eq!(
+        bill.add_line(line("Caviar", u32::MAX, 2)),
+        Err(PaymentError::Overflow)
+    );
+    // The tax of the line would overflow, even if its price doesn't
+    assert_eq!(
+        bill.add_line(line("Caviar", u32::MAX - 2000, 1)),
+        Err(PaymentError::Overflow)
+    );
+    assert_eq!(bill.lines().len(), 2);
+    assert_eq!(bill.clone().with_tip(u32::MAX), Err(PaymentError::Overflow));
+    assert_eq!(
+        bill.clone().with_tip_percent(u32::MAX),
+        Err(PaymentError::Overflow)
+    );
+
+    assert_eq!(
+        bill.pay(vec![Payment::card(u32::MAX), Payment::cash(1)]),
+        Err(PaymentError::Overflow)
+    );
+}
+
+#[test]
+fn receipts_list_payments_and_change() {
+    let receipt = bill()
+        .with_tip(15)
+        .unwrap()
+        .pay(vec![Payment::card(1000), Payment::cash(600)])
+        .unwrap();
+
+    assert_eq!(receipt.change, 100);
+    assert_eq!(
+        receipt.to_string(),
+        "Table 7
+Soup x2                 $11.00
+Coffee x1                $2.50
+Subtotal                $13.50
+Tax (10%)                $1.35
+Tip                      $0.15
+Total                   $15.00
+Card                    $10.00
+Cash                     $6.00
+Change                   $1.00"
+    );
+}
+
+#[test]
+fn orders_are_paid_once_served() {
+    let menu = Menu::house();
+    let mut order = take_order(2, &menu, &["Risotto"], &Kitchen::new()).unwrap();
+
+    assert_eq!(
+        serving::take_payment(&mut order, 0, vec![Payment::cash(5000)]),
+        Err(PaymentError::Order(OrderError::InvalidTransition {
+            from: OrderStatus::Cooking,
+            to: OrderStatus::Paid,
+        }))
+    );
+
+    order.serve().unwrap();
+    let total = 1400 + 1400 * TAX_PERCENT / 100;
+    assert!(serving::take_payment(&mut order, 0, vec![Payment::cash(total - 1)]).is_err());
+    assert_eq!(order.status(), OrderStatus::Served);
+
+    let receipt = serving::take_payment(&mut order, 100, vec![Payment::card(total + 100)]).unwrap();
+    assert_eq!(receipt.change, 0);
+    assert_eq!(order.status(), OrderStatus::Paid);
+}
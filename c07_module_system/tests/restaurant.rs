@@ -0,0 +1,73 @@
+use c07_module_system::prelude::*;
+
+#[test]
+fn prelude_brings_the_common_types_into_scope() {
+    let menu = Menu::house();
+    let mut waitlist = Waitlist::new(std::time::Duration::from_secs(60));
+    add_to_waitlist(&mut waitlist, Party::walk_in("Smith", 2));
+
+    assert_eq!(menu.by_category(Category::Main).len(), 2);
+    assert_eq!(seat_at_table(&mut waitlist, 2).unwrap().name, "Smith");
+    assert_eq!(Order::new(1).status(), OrderStatus::Placed);
+    assert!(Breakfast::builder().build(Season::Summer).is_ok());
+}
+
+#[test]
+fn orders_go_from_the_door_to_the_receipt() {
+    let mut restaurant = Restaurant::house();
+    restaurant.menu_mut().add_special("Soup", 450).unwrap();
+    assert_eq!(restaurant.arrive(Party::walk_in("Smith", 2)), 0);
+    assert_eq!(restaurant.arrive(Party::reservation("Rossi", 4)), 0);
+
+    assert_eq!(restaurant.seat(4).unwrap().name, "Rossi");
+    assert_eq!(restaurant.waitlist().len(), 1);
+
+    let cooks = restaurant.open_kitchen(2);
+    let mut order = restaurant.order(5, &["Soup", "Risotto"]).unwrap();
+    restaurant.close();
+    for cook in cooks {
+        cook.join().unwrap();
+    }
+
+    restaurant.serve(&mut order).unwrap();
+    let receipt = restaurant
+        .pay(&mut order, 0, vec![Payment::card(2035)])
+        .unwrap();
+    assert_eq!(receipt.bill.subtotal(), 450 + 1400);
+    assert_eq!(receipt.change, 0);
+    assert_eq!(order.status(), OrderStatus::Paid);
+}
+
+#[test]
+fn orders_are_served_once_cooked() {
+    let restaurant = Restaurant::house();
+    let mut order = restaurant.order(3, &["Tiramisu"]).unwrap();
+
+    assert_eq!(restaurant.serve(&mut order), Err(OrderError::NotReady(3)));
+    assert_eq!(restaurant.kitchen().pending(), 1);
+}
+
+#[test]
+fn orders_of_the_same_table_are_served_by_ticket() {
+    let restaurant = Restaurant::house();
+    let kitchen = restaurant.kitchen();
+    let mut first = restaurant.order(3, &["Soup"]).unwrap();
+    let mut second = restaurant.order(3, &["Coffee"]).unwrap();
+    kitchen.mark_ready(kitchen.try_next_ticket().unwrap());
+
+    assert_eq!(restaurant.serve(&mut second), Err(OrderError::NotReady(3)));
+    assert_eq!(kitchen.ready(), [1]);
+    restaurant.serve(&mut first).unwrap();
+
+    // Serving the first order again fails before the ticket of the second one is picked up
+    kitchen.mark_ready(kitchen.try_next_ticket().unwrap());
+    assert_eq!(
+        restaurant.serve(&mut first),
+        Err(OrderError::InvalidTransition {
+            from: OrderStatus::Served,
+            to: OrderStatus::Served,
+        })
+    );
+    assert_eq!(kitchen.ready(), [2]);
+    restaurant.serve(&mut second).unwrap();
+}
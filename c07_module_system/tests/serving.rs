@@ -0,0 +1,88 @@
+use c07_module_system::front_of_house::payments::Payment;
+use c07_module_system::front_of_house::serving::{
+    self, Order, OrderError, OrderStatus, take_order,
+};
+use c07_module_system::kitchen::Kitchen;
+use c07_module_system::menu::{Menu, MenuError};
+
+#[test]
+fn orders_go_through_their_lifecycle() {
+    let menu = Menu::house();
+    let kitchen = Kitchen::new();
+    let mut order = take_order(3, &menu, &["Soup", "Coffee"], &kitchen).unwrap();
+    // Taking the order sends it to the kitchen
+    assert_eq!(order.status(), OrderStatus::Cooking);
+    assert_eq!(kitchen.pending(), 1);
+    assert_eq!(order.ticket(), Some(1));
+    serving::serve_order(&mut order).unwrap();
+    assert_eq!(order.status(), OrderStatus::Served);
+
+    let receipt = serving::take_payment(&mut order, 0, vec![Payment::cash(880)]).unwrap();
+    assert_eq!(receipt.bill.total(), 880);
+    assert_eq!(order.status(), OrderStatus::Paid);
+}
+
+#[test]
+fn statuses_cannot_be_skipped() {
+    let mut order = Order::new(1);
+
+    assert_eq!(
+        order.serve(),
+        Err(OrderError::InvalidTransition {
+            from: OrderStatus::Placed,
+            to: OrderStatus::Served,
+        })
+    );
+    assert_eq!(order.status(), OrderStatus::Placed);
+
+    order.start_cooking().unwrap();
+    assert!(order.start_cooking().is_err());
+    assert!(order.pay().is_err());
+}
+
+#[test]
+fn totals_use_the_price_of_the_day() {
+    let mut menu = Menu::house();
+    menu.add_special("Tiramisu", 500).unwrap();
+    let mut order = Order::new(2);
+
+    order.add_item(&menu, "Tiramisu", 2).unwrap();
+    order.add_item(&menu, "Coffee", 3).unwrap();
+
+    assert_eq!(order.total(), 2 * 500 + 3 * 250);
+    assert_eq!(order.items()[0].price, 500);
+}
+
+#[test]
+fn totals_cannot_overflow() {
+    let menu = Menu::house();
+    let mut order = Order::new(2);
+
+    assert_eq!(
+        order.add_item(&menu, "Coffee", u32::MAX),
+        Err(OrderError::TotalOverflow)
+    );
+    order.add_item(&menu, "Coffee", u32::MAX / 250).unwrap();
+    assert_eq!(
+        order.add_item(&menu, "Coffee", 1),
+        Err(OrderError::TotalOverflow)
+    );
+    assert_eq!(order.items().len(), 1);
+    assert_eq!(order.total(), u32::MAX / 250 * 250);
+}
+
+#[test]
+fn items_must_be_on_the_menu_and_ordered_before_cooking() {
+    let menu = Menu::house();
+
+    assert_eq!(
+        take_order(1, &menu, &["Pizza"], &Kitchen::new()).unwrap_err(),
+        OrderError::Menu(MenuError::UnknownItem(String::from("Pizza")))
+    );
+
+    let mut order = take_order(1, &menu, &["Soup"], &Kitchen::new()).unwrap();
+    assert_eq!(
+        order.add_item(&menu, "Salad", 1),
+        Err(OrderError::AlreadySent(OrderStatus::Cooking))
+    );
+}
@@ -0,0 +1,119 @@
+use c07_module_system::staff::{Conflict, Day, Employee, Role, Schedule, Shift, StaffError};
+
+fn employees() -> Vec<Employee> {
+    vec![
+        Employee::new("Anna", &[Role::Host, Role::Server], 20),
+        Employee::new("Luca", &[Role::Server], 10),
+        Employee::new("Marco", &[Role::Cook], 40),
+    ]
+}
+
+#[test]
+fn shifts_are_filled_within_the_constraints() {
+    let shifts = vec![
+        Shift::new(Day::Friday, 12, 16, Role::Host, 1),
+        Shift::new(Day::Friday, 18, 23, Role::Server, 2),
+        Shift::new(Day::Friday, 17, 23, Role::Cook, 1),
+    ];
+    let mut schedule = Schedule::new(employees(), shifts).unwrap();
+
+    schedule.fill();
+
+    assert_eq!(schedule.staff_of(0), ["Anna"]);
+    assert_eq!(schedule.staff_of(1), ["Luca", "Anna"]);
+    assert_eq!(schedule.staff_of(2), ["Marco"]);
+    assert_eq!(schedule.hours("Anna"), Some(9));
+    assert!(schedule.conflicts().is_empty());
+}
+
+#[test]
+fn uncovered_shifts_are_reported() {
+    let shifts = vec![
+        Shift::new(Day::Saturday, 17, 23, Role::Cook, 2),
+        Shift::new(Day::Saturday, 18, 22, Role::Host, 1),
+        Shift::new(Day::Saturday, 19, 23, Role::Server, 1),
+    ];
+    let mut schedule = Schedule::new(employees(), shifts).unwrap();
+
+    schedule.fill();
+
+    // Anna is the only host, so she can't also be the server at the same time, but Luca can
+    assert_eq!(
+        schedule.conflicts(),
+        [Conflict {
+            shift: 0,
+            role: Role::Cook,
+            missing: 1,
+        }]
+    );
+    assert_eq!(
+        schedule.conflicts()[0].to_string(),
+        "shift 0 needs 1 more Cook"
+    );
+}
+
+#[test]
+fn maximum_hours_are_respected() {
+    let shifts = vec![
+        Shift::new(Day::Monday, 10, 18, Role::Server, 1),
+        Shift::new(Day::Tuesday, 10, 18, Role::Server, 1),
+    ];
+    let mut schedule = Schedule::new(employees(), shifts).unwrap();
+    schedule.assign("Luca", 0).unwrap();
+
+    assert_eq!(
+        schedule.assign("Luca", 1),
+        Err(StaffError::TooManyHours {
+            name: String::from("Luca"),
+            hours: 16,
+            max: 10,
+        })
+    );
+}
+
+#[test]
+fn assignments_are_checked() {
+    let shifts = vec![
+        Shift::new(Day::Sunday, 12, 15, Role::Server, 1),
+        Shift::new(Day::Sunday, 14, 20, Role::Host, 1),
+    ];
+    let mut schedule = Schedule::new(employees(), shifts).unwrap();
+
+    assert_eq!(
+        schedule.assign("Marco", 0),
+        Err(StaffError::MissingRole {
+            name: String::from("Marco"),
+            role: Role::Server,
+        })
+    );
+    schedule.assign("Anna", 0).unwrap();
+    assert_eq!(schedule.assign("Luca", 0), Err(StaffError::ShiftFull(0)));
+    assert_eq!(
+        schedule.assign("Anna", 1),
+        Err(StaffError::Overlap {
+            name: String::from("Anna"),
+            shift: 0,
+        })
+    );
+    assert_eq!(
+        schedule.assign("Paolo", 1),
+        Err(StaffError::UnknownEmployee(String::from("Paolo")))
+    );
+    assert_eq!(schedule.assign("Anna", 7), Err(StaffError::UnknownShift(7)));
+}
+
+#[test]
+fn schedules_are_validated() {
+    let invalid = vec![Shift::new(Day::Monday, 20, 18, Role::Cook, 1)];
+    assert_eq!(
+        Schedule::new(employees(), invalid).unwrap_err(),
+        StaffError::InvalidShift(0)
+    );
+
+    let mut duplicated = employees();
+    duplicated.push(Employee::new("Anna", &[Role::Cook], 10));
+    assert_eq!(
+        Schedule::new(duplicated, Vec::new()).unwrap_err(),
+        StaffError::DuplicateEmployee(String::from("Anna"))
+    );
+}
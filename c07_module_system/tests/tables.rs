@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use c07_module_system::front_of_house::tables::{FloorPlan, Seating, TableError, Time};
+use c07_module_system::hosting::{Party, Waitlist};
+
+fn waitlist() -> Waitlist {
+    Waitlist::new(Duration::from_secs(15 * 60))
+}
+
+#[test]
+fn reservations_get_the_smallest_table_that_fits() {
+    let mut floor = FloorPlan::new(&[6, 2, 4]);
+
+    let reservation = floor.reserve("Rossi", Time::new(19, 0), 3).unwrap();
+
+    assert_eq!(reservation.table, 3);
+    assert_eq!(floor.reservations().len(), 1);
+}
+
+#[test]
+fn overlapping_reservations_use_another_table() {
+    let mut floor = FloorPlan::new(&[2, 4]);
+    floor.reserve("Rossi", Time::new(19, 0), 2).unwrap();
+
+    assert_eq!(
+        floor.reserve("Bianchi", Time::new(20, 0), 2).unwrap().table,
+        2
+    );
+    assert_eq!(
+        floor.reserve("Verdi", Time::new(20, 30), 2),
+        Err(TableError::Conflict(Time::new(20, 30)))
+    );
+    // Two hours after the first reservation the table is available again
+    assert_eq!(floor.reserve("Neri", Time::new(21, 0), 2).unwrap().table, 1);
+}
+
+#[test]
+fn reservations_are_validated() {
+    let mut floor = FloorPlan::new(&[2, 4]);
+    floor.reserve("Rossi", Time::new(19, 0), 2).unwrap();
+
+    assert_eq!(
+        floor.reserve("Bianchi", Time::new(19, 0), 8),
+        Err(TableError::TooLarge(8))
+    );
+    assert_eq!(
+        floor.reserve("Rossi", Time::new(13, 0), 2),
+        Err(TableError::AlreadyReserved(String::from("Rossi")))
+    );
+}
+
+#[test]
+fn parties_are_seated_at_their_table() {
+    let mut floor = FloorPlan::new(&[2, 4]);
+    let mut waitlist = waitlist();
+    let reservation = floor.reserve("Rossi", Time::new(19, 0), 4).unwrap();
+
+    assert_eq!(
+        floor.seat(&reservation, &mut waitlist),
+        Ok(Seating::Seated(2))
+    );
+    assert_eq!(floor.table(2).unwrap().occupied_by(), Some("Rossi"));
+    assert!(floor.reservations().is_empty());
+    assert_eq!(
+        floor.seat(&reservation, &mut waitlist),
+        Err(TableError::UnknownReservation(String::from("Rossi")))
+    );
+}
+
+#[test]
+fn occupied_tables_send_the_party_to_the_waitlist() {
+    let mut floor = FloorPlan::new(&[4]);
+    let mut waitlist = waitlist();
+    waitlist.join(Party::walk_in("Smith", 2));
+    let first = floor.reserve("Rossi", Time::new(18, 0), 4).unwrap();
+    let second = floor.reserve("Bianchi", Time::new(20, 0), 4).unwrap();
+    floor.seat(&first, &mut waitlist).unwrap();
+
+    // The Rossi party is late leaving, the Bianchi party waits ahead of the walk-ins
+    assert_eq!(floor.seat(&second, &mut waitlist), Ok(Seating::Waiting(0)));
+
+    let seated = floor.free(1, &mut waitlist).unwrap().unwrap();
+    assert_eq!(seated.name, "Bianchi");
+    assert_eq!(floor.table(1).unwrap().occupied_by(), Some("Bianchi"));
+    assert_eq!(waitlist.len(), 1);
+}
+
+#[test]
+fn freed_tables_stay_free_without_parties_waiting() {
+    let mut floor = FloorPlan::new(&[2]);
+    let mut waitlist = waitlist();
+    waitlist.join(Party::walk_in("Large", 6));
+
+    assert_eq!(floor.free(1, &mut waitlist), Ok(None));
+    assert!(floor.table(1).unwrap().is_free());
+    assert_eq!(
+        floor.free(9, &mut waitlist),
+        Err(TableError::UnknownTable(9))
+    );
+}
@@ -0,0 +1,78 @@
+//! Feed
+//! The media aggregator collects items of different types in a single `Feed`
+//! The only thing the feed knows about them is that they implement `Summary`, so it stores them as trait objects
+//! Subscribers are notified of each item as soon as it's pushed
+
+use crate::Summary;
+
+// `Send` is required so the feed can be moved to, or shared with, other threads and tasks
+type Subscriber = Box<dyn FnMut(&dyn Summary) + Send>;
+
+/// Ordered collection of items implementing [`Summary`], notifying its subscribers of every new item.
+pub struct Feed {
+    items: Vec<Box<dyn Summary + Send>>,
+    subscribers: Vec<Subscriber>,
+}
+
+impl Feed {
+    /// Creates an empty feed, without subscribers.
+    pub fn new() -> Self {
+        Feed {
+            items: Vec::new(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// Registers a callback called with each item pushed from now on.
+    ///
+    /// The items already in the feed are not replayed.
+    pub fn subscribe<F>(&mut self, subscriber: F)
+    where
+        F: FnMut(&dyn Summary) + Send + 'static,
+    {
+        self.subscribers.push(Box::new(subscriber));
+    }
+
+    /// Appends an item to the feed and notifies the subscribers, in the order they subscribed.
+    pub fn push<T>(&mut self, item: T)
+    where
+        T: Summary + Send + 'static,
+    {
+        for subscriber in &mut self.subscribers {
+            subscriber(&item);
+        }
+        self.items.push(Box::new(item));
+    }
+
+    /// Returns the item at `index`, in the order they were pushed.
+    pub fn get(&self, index: usize) -> Option<&dyn Summary> {
+        // `as` coerces the `&(dyn Summary + Send)` into a `&dyn Summary`, the `Send` bound is no use to the caller
+        self.items
+            .get(index)
+            .map(|item| item.as_ref() as &dyn Summary)
+    }
+
+    /// Iterates over the items, in the order they were pushed.
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Summary> {
+        self.items.iter().map(|item| item.as_ref() as &dyn Summary)
+    }
+
+    /// Returns the summary of every item, in the order they were pushed.
+    pub fn summaries(&self) -> Vec<String> {
+        self.iter().map(|item| item.summarise()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl Default for Feed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -4,6 +4,11 @@
 //! The purpose is creating a media aggregator library that displays a summary of data contained in the structs
 //! To do this, they need to implement a trait `Summary` that defines a `summarise` method on an instance
 
+// The `Feed` collecting the items of the aggregator is defined in the `feed` module
+pub mod feed;
+
+pub use feed::Feed;
+
 // This is the definition of the Summary public trait using the `trait` keyword
 pub trait Summary {
     // Method signature that need to be implemented.
@@ -20,6 +25,7 @@ pub trait Summary {
 
 // The following are the definitions for the structs `NewsArticle` and `Tweet`
 // They both implement the Summary trait
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NewsArticle {
     pub headline: String,
     pub location: String,
@@ -46,6 +52,7 @@ impl Summary for NewsArticle {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tweet {
     pub username: String,
     pub content: String,
@@ -0,0 +1,114 @@
+//! Micro-benchmarks that run in plain tests
+//! The `#[bench]` attribute and `cargo bench` with the built-in harness are only available in nightly Rust
+//! `time_it` measures a function on stable instead, so it can be called from a `#[test]` and reported with `cargo test -- --show-output`
+//! The first runs are not measured: they warm up the caches and the allocator, and would make the measure slower than it is
+//! The timings depend on the machine, so the tests should only check them loosely, e.g. comparing two functions
+
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+// Statistics of the measured runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub mean: Duration,
+    // 95% of the runs took at most this long
+    pub p95: Duration,
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} iterations: min {:?}, mean {:?}, p95 {:?}",
+            self.iterations, self.min, self.mean, self.p95
+        )
+    }
+}
+
+// Measures `f` over `iterations` runs, after a warm-up of a tenth of them
+pub fn time_it<F, R>(iterations: usize, f: F) -> Stats
+where
+    F: FnMut() -> R,
+{
+    time_it_with_warm_up(iterations / 10, iterations, f)
+}
+
+pub fn time_it_with_warm_up<F, R>(warm_up: usize, iterations: usize, mut f: F) -> Stats
+where
+    F: FnMut() -> R,
+{
+    assert!(iterations > 0, "time_it needs at least one iteration");
+    // `black_box` keeps the compiler from removing the calls whose result is not used
+    for _ in 0..warm_up {
+        black_box(f());
+    }
+    let mut timings: Vec<Duration> = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect();
+    timings.sort();
+    Stats {
+        iterations,
+        min: timings[0],
+        mean: timings.iter().sum::<Duration>() / iterations as u32,
+        p95: timings[percentile_index(iterations, 95)],
+    }
+}
+
+fn percentile_index(len: usize, percentile: usize) -> usize {
+    // The nearest-rank percentile: the smallest value with at least `percentile`% of the values below or equal to it
+    (len * percentile).div_ceil(100).max(1) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        assert_eq!(percentile_index(1, 95), 0);
+        assert_eq!(percentile_index(20, 95), 18);
+        assert_eq!(percentile_index(100, 95), 94);
+        assert_eq!(percentile_index(101, 95), 95);
+    }
+
+    #[test]
+    fn stats_are_ordered() {
+        let stats = time_it(200, || (0..1000u64).sum::<u64>());
+        assert_eq!(stats.iterations, 200);
+        assert!(stats.min <= stats.mean && stats.min <= stats.p95);
+        println!("sum of 1000 numbers, {stats}");
+    }
+
+    #[test]
+    fn warm_up_runs_are_not_measured() {
+        let mut calls = 0;
+        let stats = time_it_with_warm_up(5, 20, || calls += 1);
+        assert_eq!(calls, 25);
+        assert_eq!(stats.iterations, 20);
+
+        let mut calls = 0;
+        time_it(50, || calls += 1);
+        assert_eq!(calls, 55);
+    }
+
+    #[test]
+    fn slower_functions_take_longer() {
+        let fast = time_it(20, || black_box(1));
+        let slow = time_it(20, || std::thread::sleep(Duration::from_millis(1)));
+        assert!(slow.min >= Duration::from_millis(1));
+        assert!(fast.mean < slow.mean);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one iteration")]
+    fn no_iterations() {
+        time_it(0, || ());
+    }
+}
@@ -0,0 +1,181 @@
+//! Fixtures for the tests
+//! A fixture is the data a test needs before it can run, the "set up" step of a test
+//! The builders start from a default value, so a test only spells out what matters to it, e.g. `rectangle().wide(8).tall(7)`
+//! The module is public, not under `#[cfg(test)]`, so it can be used by the unit tests, the integration tests and the other crates
+//! of the workspace
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{Guess, GuessError, Rectangle};
+
+// Builder of a `Rectangle`, starting from a 1x1 square
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RectangleBuilder {
+    width: u32,
+    height: u32,
+}
+
+pub fn rectangle() -> RectangleBuilder {
+    RectangleBuilder {
+        width: 1,
+        height: 1,
+    }
+}
+
+impl RectangleBuilder {
+    pub fn wide(self, width: u32) -> RectangleBuilder {
+        RectangleBuilder { width, ..self }
+    }
+
+    pub fn tall(self, height: u32) -> RectangleBuilder {
+        RectangleBuilder { height, ..self }
+    }
+
+    pub fn square(self, side: u32) -> RectangleBuilder {
+        RectangleBuilder {
+            width: side,
+            height: side,
+        }
+    }
+
+    pub fn build(self) -> Rectangle {
+        Rectangle::new(self.width, self.height)
+    }
+}
+
+impl From<RectangleBuilder> for Rectangle {
+    fn from(builder: RectangleBuilder) -> Rectangle {
+        builder.build()
+    }
+}
+
+// Builder of a `Guess`, in the default range from 1 to 100
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessBuilder {
+    value: i32,
+    min: i32,
+    max: i32,
+}
+
+pub fn guess(value: i32) -> GuessBuilder {
+    GuessBuilder {
+        value,
+        min: 1,
+        max: 100,
+    }
+}
+
+impl GuessBuilder {
+    pub fn between(self, min: i32, max: i32) -> GuessBuilder {
+        GuessBuilder { min, max, ..self }
+    }
+
+    pub fn build(self) -> Guess {
+        // Panics like `Guess::new_in` if the value is out of the range, a fixture is expected to be valid
+        Guess::new_in(self.value, self.min, self.max)
+    }
+
+    pub fn try_build(self) -> Result<Guess, GuessError> {
+        // Same as `build`, for the tests of the invalid guesses
+        Guess::try_new_in(self.value, self.min, self.max)
+    }
+}
+
+// Tests run in parallel, in the same process or in different ones, so the name of each file has both the process id and a counter
+static NEXT_TEMP_FILE: AtomicUsize = AtomicUsize::new(0);
+
+// A file in the temporary directory of the system, removed when the fixture is dropped
+// So the file is cleaned up even when the test fails, as the panic drops the values of the test
+#[derive(Debug)]
+pub struct TempFile {
+    path: PathBuf,
+}
+
+pub fn temp_file(name: &str, contents: &str) -> TempFile {
+    TempFile::new(name, contents)
+}
+
+impl TempFile {
+    pub fn new(name: &str, contents: &str) -> TempFile {
+        // `name` is kept at the end, so the extension of the file is the one given
+        let id = NEXT_TEMP_FILE.fetch_add(1, Ordering::Relaxed);
+        let file_name = format!("fixture_{}_{id}_{name}", std::process::id());
+        let path = std::env::temp_dir().join(file_name);
+        // A fixture that can't be set up makes the test fail, like an `unwrap` in the test itself
+        fs::write(&path, contents).unwrap_or_else(|error| {
+            panic!("failed to create the fixture {}: {error}", path.display())
+        });
+        TempFile { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        fs::read_to_string(&self.path)
+    }
+}
+
+impl AsRef<Path> for TempFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        // The file may have been removed by the test itself, so the error is ignored
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_err_matches;
+
+    #[test]
+    fn builders_start_from_defaults() {
+        assert_eq!(rectangle().build(), Rectangle::new(1, 1));
+        assert_eq!(rectangle().wide(8).tall(7).build(), Rectangle::new(8, 7));
+        assert_eq!(rectangle().square(3).wide(4).build(), Rectangle::new(4, 3));
+        assert_eq!(Rectangle::from(rectangle().tall(5)), Rectangle::new(1, 5));
+
+        assert_eq!(guess(42).build(), Guess::new(42));
+        assert_eq!(guess(-5).between(-10, 10).build().range(), -10..=10);
+    }
+
+    #[test]
+    fn invalid_guesses_can_be_built_as_errors() {
+        assert_err_matches!(
+            guess(0).try_build(),
+            GuessError::OutOfRange { value: 0, .. }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "less than or equal to 10")]
+    fn invalid_guesses_panic_on_build() {
+        guess(42).between(1, 10).build();
+    }
+
+    #[test]
+    fn temp_files_are_removed_on_drop() {
+        let file = temp_file("notes.txt", "first line\nsecond line");
+        let path = file.path().to_path_buf();
+        assert_eq!(file.read().unwrap(), "first line\nsecond line");
+        assert_eq!(path.extension().unwrap(), "txt");
+
+        // Two fixtures with the same name don't share the file
+        let other = temp_file("notes.txt", "other");
+        assert_ne!(other.path(), path);
+
+        drop(file);
+        assert!(!path.exists());
+        assert!(other.path().exists());
+    }
+}
@@ -0,0 +1,323 @@
+//! Geometry of the rectangles used in the examples of the tests
+//! A rectangle only has a width and a height, it has no position
+//! So the intersection and the union are computed as if the rectangles had a corner in common
+//! The `Shape` trait generalises `can_hold` to circles and triangles: a shape can contain another one if the bounding box of the
+//! other one fits in it, placed where it fits best
+
+// Struct representing a rectangle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rectangle {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rectangle {
+    pub fn new(width: u32, height: u32) -> Rectangle {
+        Rectangle { width, height }
+    }
+
+    // The integer measures saturate at `u32::MAX` instead of overflowing
+    // `Shape::area` gives the exact area of any rectangle, as a `f64`
+    pub fn area(&self) -> u32 {
+        self.width.saturating_mul(self.height)
+    }
+
+    pub fn perimeter(&self) -> u32 {
+        self.width.saturating_add(self.height).saturating_mul(2)
+    }
+
+    pub fn scale(&self, factor: u32) -> Rectangle {
+        // Returns a rectangle with both sides multiplied by `factor`, saturating like the measures
+        Rectangle::new(
+            self.width.saturating_mul(factor),
+            self.height.saturating_mul(factor),
+        )
+    }
+
+    pub fn rotate(&self) -> Rectangle {
+        // Returns the rectangle rotated by 90 degrees, swapping width and height
+        Rectangle::new(self.height, self.width)
+    }
+
+    pub fn can_hold(&self, other: &Rectangle) -> bool {
+        // Check whether a rectangle can hold another one, both sides must be strictly larger
+        self.width > other.width && self.height > other.height
+    }
+
+    pub fn can_hold_rotated(&self, other: &Rectangle) -> bool {
+        // Same as `can_hold`, but the other rectangle can also be rotated to fit
+        self.can_hold(other) || self.can_hold(&other.rotate())
+    }
+
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        // The area covered by both rectangles, placed with a corner in common
+        // `None` if they don't overlap, when one of the sides is 0
+        let intersection =
+            Rectangle::new(self.width.min(other.width), self.height.min(other.height));
+        if intersection.width == 0 || intersection.height == 0 {
+            None
+        } else {
+            Some(intersection)
+        }
+    }
+
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        // The smallest rectangle containing both, placed with a corner in common
+        Rectangle::new(self.width.max(other.width), self.height.max(other.height))
+    }
+}
+
+// The smallest rectangle containing a shape, with sides that are not always integers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub width: f64,
+    pub height: f64,
+}
+
+pub trait Shape {
+    fn area(&self) -> f64;
+
+    fn bounding_box(&self) -> BoundingBox;
+
+    // Whether a box fits strictly inside the shape, without rotating it
+    // Strictly, like `Rectangle::can_hold`, so a shape can't contain a copy of itself
+    fn fits(&self, bounding_box: &BoundingBox) -> bool;
+}
+
+// Whether `outer` can contain `inner`, any shape in any other
+// The bounding box of `inner` is checked instead of its actual outline, so the answer errs on the side of `false`
+pub fn can_contain<A: Shape + ?Sized, B: Shape + ?Sized>(outer: &A, inner: &B) -> bool {
+    outer.fits(&inner.bounding_box())
+}
+
+impl Shape for Rectangle {
+    fn area(&self) -> f64 {
+        f64::from(self.width) * f64::from(self.height)
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            width: f64::from(self.width),
+            height: f64::from(self.height),
+        }
+    }
+
+    fn fits(&self, bounding_box: &BoundingBox) -> bool {
+        bounding_box.width < f64::from(self.width) && bounding_box.height < f64::from(self.height)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Circle {
+    pub radius: f64,
+}
+
+impl Circle {
+    pub fn new(radius: f64) -> Circle {
+        Circle { radius }
+    }
+}
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        core::f64::consts::PI * self.radius * self.radius
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        let diameter = 2.0 * self.radius;
+        BoundingBox {
+            width: diameter,
+            height: diameter,
+        }
+    }
+
+    fn fits(&self, bounding_box: &BoundingBox) -> bool {
+        // The box is placed at the centre, so it fits if its diagonal is shorter than the diameter
+        // The squares are compared, as `core` has no square root, so the radius is checked to be positive on its own
+        let diagonal_squared =
+            bounding_box.width * bounding_box.width + bounding_box.height * bounding_box.height;
+        self.radius > 0.0 && diagonal_squared < 4.0 * self.radius * self.radius
+    }
+}
+
+// An isosceles triangle, with the top vertex above the middle of the base
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Triangle {
+    pub base: f64,
+    pub height: f64,
+}
+
+impl Triangle {
+    pub fn new(base: f64, height: f64) -> Triangle {
+        Triangle { base, height }
+    }
+}
+
+impl Shape for Triangle {
+    fn area(&self) -> f64 {
+        self.base * self.height / 2.0
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        BoundingBox {
+            width: self.base,
+            height: self.height,
+        }
+    }
+
+    fn fits(&self, bounding_box: &BoundingBox) -> bool {
+        // The box stands on the middle of the base, and the triangle gets narrower linearly going up
+        // So the top corners of the box must be below the sides, where the triangle is `width` wide
+        if bounding_box.width >= self.base {
+            return false;
+        }
+        let height_at_width = self.height * (1.0 - bounding_box.width / self.base);
+        bounding_box.height < height_at_width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    #[cfg(feature = "std")]
+    use crate::property;
+
+    #[test]
+    fn area_and_perimeter() {
+        let rectangle = Rectangle::new(8, 7);
+        assert_eq!(rectangle.area(), 56);
+        assert_eq!(rectangle.perimeter(), 30);
+    }
+
+    #[test]
+    fn scale_multiplies_both_sides() {
+        let rectangle = Rectangle::new(3, 2).scale(4);
+        assert_eq!(rectangle, Rectangle::new(12, 8));
+        // The area grows with the square of the factor
+        assert_eq!(rectangle.area(), 6 * 16);
+    }
+
+    #[test]
+    fn measures_saturate_instead_of_overflowing() {
+        let huge = Rectangle::new(u32::MAX, 3);
+        assert_eq!(huge.area(), u32::MAX);
+        assert_eq!(huge.perimeter(), u32::MAX);
+        assert_eq!(huge.scale(2), Rectangle::new(u32::MAX, 6));
+        assert_approx_eq!(Shape::area(&huge), 3.0 * f64::from(u32::MAX));
+
+        // The area of this square doesn't fit in a `u32`, its intersection is still not empty
+        let square = Rectangle::new(1 << 16, 1 << 16);
+        assert_eq!(square.intersection(&square), Some(square));
+    }
+
+    #[test]
+    fn rotate_swaps_the_sides() {
+        let rectangle = Rectangle::new(8, 2);
+        assert_eq!(rectangle.rotate(), Rectangle::new(2, 8));
+        assert_eq!(rectangle.rotate().rotate(), rectangle);
+    }
+
+    #[test]
+    fn rotated_rectangles_can_fit() {
+        let tall = Rectangle::new(3, 10);
+        let wide = Rectangle::new(9, 2);
+        assert!(!tall.can_hold(&wide));
+        assert!(tall.can_hold_rotated(&wide));
+        // Rotating doesn't help if the other one is too big anyway
+        assert!(!tall.can_hold_rotated(&Rectangle::new(4, 11)));
+    }
+
+    #[test]
+    fn can_hold_needs_strictly_larger_sides() {
+        let rectangle = Rectangle::new(5, 5);
+        assert!(!rectangle.can_hold(&rectangle));
+        assert!(!rectangle.can_hold_rotated(&rectangle));
+    }
+
+    #[test]
+    fn intersection_is_the_common_area() {
+        let wide = Rectangle::new(8, 2);
+        let tall = Rectangle::new(3, 6);
+        assert_eq!(wide.intersection(&tall), Some(Rectangle::new(3, 2)));
+        assert_eq!(wide.intersection(&Rectangle::new(0, 4)), None);
+    }
+
+    #[test]
+    fn union_contains_both() {
+        let wide = Rectangle::new(8, 2);
+        let tall = Rectangle::new(3, 6);
+        let union = wide.union(&tall);
+        assert_eq!(union, Rectangle::new(8, 6));
+        assert!(union.area() >= wide.area().max(tall.area()));
+    }
+
+    #[test]
+    fn shapes_have_area_and_bounding_box() {
+        let circle = Circle::new(2.0);
+        assert_approx_eq!(circle.area(), 4.0 * std::f64::consts::PI);
+        assert_eq!(
+            circle.bounding_box(),
+            BoundingBox {
+                width: 4.0,
+                height: 4.0
+            }
+        );
+
+        let triangle = Triangle::new(6.0, 3.0);
+        assert_approx_eq!(triangle.area(), 9.0);
+        assert_eq!(
+            triangle.bounding_box(),
+            BoundingBox {
+                width: 6.0,
+                height: 3.0
+            }
+        );
+
+        // The method of the trait is the one of the shapes, the inherent one is used otherwise
+        assert_approx_eq!(Shape::area(&Rectangle::new(8, 7)), 56.0);
+    }
+
+    #[test]
+    fn can_contain_any_shape() {
+        let rectangle = Rectangle::new(10, 10);
+        let circle = Circle::new(5.0);
+        let triangle = Triangle::new(10.0, 10.0);
+
+        // A square holds a circle a little smaller than the inscribed one
+        assert!(can_contain(&rectangle, &Circle::new(4.9)));
+        assert!(!can_contain(&rectangle, &circle));
+        // A circle holds a square with a diagonal shorter than the diameter
+        assert!(can_contain(&circle, &Rectangle::new(7, 7)));
+        assert!(!can_contain(&circle, &Rectangle::new(8, 7)));
+        // A box 4 wide is under the sides of the triangle up to a height of 6
+        assert!(can_contain(&triangle, &Rectangle::new(4, 5)));
+        assert!(!can_contain(&triangle, &Rectangle::new(4, 6)));
+        assert!(!can_contain(&triangle, &triangle));
+    }
+
+    #[test]
+    fn can_contain_works_with_trait_objects() {
+        let shapes: Vec<Box<dyn Shape>> = vec![
+            Box::new(Rectangle::new(3, 3)),
+            Box::new(Circle::new(1.0)),
+            Box::new(Triangle::new(20.0, 20.0)),
+        ];
+        let inner = Rectangle::new(2, 2);
+        let holders: Vec<bool> = shapes
+            .iter()
+            .map(|shape| can_contain(shape.as_ref(), &inner))
+            .collect();
+        assert_eq!(holders, [true, false, true]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn can_contain_generalises_can_hold() {
+        // Between rectangles the generic check gives the same answer as `can_hold`
+        let pairs = (property::rectangles(20), property::rectangles(20));
+        property::for_all(500, pairs, |(outer, inner)| {
+            can_contain(outer, inner) == outer.can_hold(inner)
+        });
+    }
+}
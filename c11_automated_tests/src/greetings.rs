@@ -0,0 +1,154 @@
+//! Greetings in several languages
+//! Each language has its own word for hello, its own punctuation, and its own word for joining the last two names of a list
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    Italian,
+    Spanish,
+    French,
+    German,
+}
+
+impl Language {
+    pub fn from_code(code: &str) -> Language {
+        // Finds the language from its ISO 639-1 code, such as `it`, or from a tag such as `it-CH`
+        // Falls back to English for the languages without greetings
+        let primary = code.split(['-', '_']).next().unwrap_or_default();
+        match primary.to_lowercase().as_str() {
+            "it" => Language::Italian,
+            "es" => Language::Spanish,
+            "fr" => Language::French,
+            "de" => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    fn hello(self) -> &'static str {
+        match self {
+            Language::English => "Hello",
+            Language::Italian => "Ciao",
+            Language::Spanish => "Hola",
+            Language::French => "Bonjour",
+            Language::German => "Hallo",
+        }
+    }
+
+    fn and(self) -> &'static str {
+        match self {
+            Language::English => "and",
+            Language::Italian => "e",
+            Language::Spanish => "y",
+            Language::French => "et",
+            Language::German => "und",
+        }
+    }
+
+    fn exclaim(self, text: &str) -> String {
+        // Spanish opens the exclamation too, French puts a space before the exclamation mark
+        match self {
+            Language::Spanish => format!("¡{text}!"),
+            Language::French => format!("{text} !"),
+            _ => format!("{text}!"),
+        }
+    }
+}
+
+pub fn greet(name: &str, language: Language) -> String {
+    // Greets a person, the name is trimmed, and without a name the greeting is just the hello
+    greet_many(&[name], language)
+}
+
+pub fn greet_many(names: &[&str], language: Language) -> String {
+    // Greets a group of people, the names are separated by commas, except the last two joined by the word for "and"
+    // The empty names are skipped
+    let names: Vec<&str> = names
+        .iter()
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .collect();
+    let hello = language.hello();
+    let text = match names.as_slice() {
+        [] => hello.to_string(),
+        [name] => format!("{hello} {name}"),
+        [first @ .., last] => format!("{hello} {} {} {last}", first.join(", "), language.and()),
+    };
+    language.exclaim(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_contains_all;
+
+    #[test]
+    fn greets_in_each_language() {
+        assert_eq!(greet("Carol", Language::English), "Hello Carol!");
+        assert_eq!(greet("Carol", Language::Italian), "Ciao Carol!");
+        assert_eq!(greet("Carol", Language::Spanish), "¡Hola Carol!");
+        assert_eq!(greet("Carol", Language::French), "Bonjour Carol !");
+        assert_eq!(greet("Carol", Language::German), "Hallo Carol!");
+    }
+
+    #[test]
+    fn names_are_trimmed() {
+        assert_eq!(greet("  Carol\n", Language::English), "Hello Carol!");
+        assert_eq!(greet("   ", Language::Spanish), "¡Hola!");
+    }
+
+    #[test]
+    fn unknown_languages_fall_back_to_english() {
+        assert_eq!(Language::from_code("it"), Language::Italian);
+        assert_eq!(Language::from_code("FR-ca"), Language::French);
+        assert_eq!(Language::from_code("de_AT"), Language::German);
+        assert_eq!(Language::from_code("ja"), Language::English);
+        assert_eq!(Language::from_code(""), Language::English);
+    }
+
+    #[test]
+    fn groups_are_joined_with_the_local_and() {
+        let names = ["Ann", "Bob", "Carol"];
+        assert_eq!(
+            greet_many(&names, Language::English),
+            "Hello Ann, Bob and Carol!"
+        );
+        assert_eq!(
+            greet_many(&names, Language::Italian),
+            "Ciao Ann, Bob e Carol!"
+        );
+        assert_eq!(
+            greet_many(&names, Language::Spanish),
+            "¡Hola Ann, Bob y Carol!"
+        );
+        assert_eq!(
+            greet_many(&names, Language::French),
+            "Bonjour Ann, Bob et Carol !"
+        );
+        assert_eq!(
+            greet_many(&names[1..], Language::German),
+            "Hallo Bob und Carol!"
+        );
+    }
+
+    #[test]
+    fn groups_greet_everyone() {
+        for language in [Language::English, Language::Spanish, Language::German] {
+            assert_contains_all!(
+                greet_many(&["Ann", "Bob", "Carol"], language),
+                "Ann",
+                "Bob",
+                "Carol"
+            );
+        }
+    }
+
+    #[test]
+    fn empty_groups_get_a_plain_greeting() {
+        assert_eq!(greet_many(&[], Language::English), "Hello!");
+        assert_eq!(greet_many(&["", "Ann"], Language::Italian), "Ciao Ann!");
+    }
+}
@@ -21,59 +21,163 @@
 //! The convention is to create a module named `tests` in each file to ccontain the test functions, and annotate the module with `#[cfg(test)]
 //! This annotation tells Rust to run that code only when `cargo test` is called, and not `cargo build`
 //! They allow to test both public and private functions
+//!
+//! The crate is `no_std` without the `std` feature, enabled by default: only the modules using files, clocks, and random values need it
+//! The tests always have the standard library, as the test harness needs it
 
-// When crating a lib with cargo a test is automatically generated as a template: the `adder`:
-pub fn add(left: u64, right: u64) -> u64 {
-    // Add function that adds two numbers
-    left + right
-}
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use core::cmp::Ordering;
+use core::fmt;
+use core::ops::{Add, RangeInclusive};
+
+// When crating a lib with cargo a test is automatically generated as a template: the `adder`
+// It was `left + right` on `u64`, it is now in the `math` module, for any integer type and returning an error on overflow
+pub mod math;
+pub use math::{add, add_two};
 
 fn _internal_add(left: usize, right: usize) -> usize {
     left + right
 }
 
-// Struct representing a rectangle
-#[derive(Debug)]
-struct _Rectangle {
-    width: u32,
-    height: u32,
-}
+// The rectangle of the examples is now a public type, with its own tests, in the `geometry` module
+// With the other shapes, that can all be checked with `can_contain`
+pub mod geometry;
+pub use geometry::{Circle, Rectangle, Shape, Triangle, can_contain};
 
-impl _Rectangle {
-    fn _can_hold(&self, other: &_Rectangle) -> bool {
-        // Check whether a rectangle can hold another one
-        self.width > other.width && self.height > other.height
-    }
-}
+// Generators and runner for property-based tests, used by the tests of this crate
+#[cfg(feature = "std")]
+pub mod property;
 
-pub fn add_two(a: usize) -> usize {
-    // Function that adds two to any pointer-sized unsigned integer
-    a + 2
-}
+// Builders of the test data and temporary files, shared by the unit and the integration tests
+#[cfg(feature = "std")]
+pub mod fixtures;
+
+// Assertion macros with the messages of `assert_eq!`, exported at the root of the crate
+pub mod test_support;
+
+// Micro-benchmarks on stable Rust, to measure the functions of the chapters from plain tests
+#[cfg(feature = "std")]
+pub mod bench;
+
+// The greetings in other languages are in the `greetings` module
+pub mod greetings;
+pub use greetings::{Language, greet, greet_many};
 
-pub fn greeting(_name: &str) -> String {
+pub fn greeting(name: &str) -> String {
     // Function that generates a string greeting a person
+    // The first version returned the wrong string, to show how a failing test looks like:
+    // String::from("Hello")
     // Return the correct string
-    // format!("Hello {_name}!")
-    // Return the wrong string
-    String::from("Hello")
+    greet(name, Language::English)
 }
 
+// The value of a guess is always in its range, so a `Guess` can only be created through the constructors that check it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Guess {
-    _value: i32,
+    value: i32,
+    min: i32,
+    max: i32,
 }
 
+// The errors of the constructors and operations that don't panic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    OutOfRange { value: i64, min: i32, max: i32 },
+    // The minimum of the range is greater than the maximum
+    EmptyRange { min: i32, max: i32 },
+    // Two guesses can only be combined if they have the same range
+    RangeMismatch,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GuessError::OutOfRange { value, min, .. } if *value < i64::from(*min) => write!(
+                f,
+                "Guess value must be greater than or equal to {min}, got {value}."
+            ),
+            GuessError::OutOfRange { value, max, .. } => write!(
+                f,
+                "Guess value must be less than or equal to {max}, got {value}."
+            ),
+            GuessError::EmptyRange { min, max } => {
+                write!(f, "Guess range is empty, {min} is greater than {max}.")
+            }
+            GuessError::RangeMismatch => write!(f, "Guesses have different ranges."),
+        }
+    }
+}
+
+impl core::error::Error for GuessError {}
+
 impl Guess {
-    pub fn new(_value: i32) -> Guess {
+    pub fn new(value: i32) -> Guess {
         // The creation of `Guess` panics if the parameters is not between 1 and 100
         // The messages are different between the two conditions
-        if _value < 1 {
-            panic!("Guess value must be greater than or equal to 1, got {_value}.");
-        } else if _value > 100 {
-            panic!("Guess value must be less than or equal to 100, got {_value}.");
+        Guess::new_in(value, 1, 100)
+    }
+
+    pub fn new_in(value: i32, min: i32, max: i32) -> Guess {
+        // Same as `new`, with a range from `min` to `max` included
+        match Guess::try_new_in(value, min, max) {
+            Ok(guess) => guess,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    pub fn try_new_in(value: i32, min: i32, max: i32) -> Result<Guess, GuessError> {
+        // Same as `new_in`, but returns an error instead of panicking
+        if min > max {
+            return Err(GuessError::EmptyRange { min, max });
+        }
+        if value < min || value > max {
+            let value = i64::from(value);
+            return Err(GuessError::OutOfRange { value, min, max });
         }
         // If the value satisfies the condition the Guess is created
-        Guess { _value }
+        Ok(Guess { value, min, max })
+    }
+
+    pub fn value(&self) -> i32 {
+        // The field is private, so it can only be read with an accessor
+        self.value
+    }
+
+    pub fn range(&self) -> RangeInclusive<i32> {
+        self.min..=self.max
+    }
+}
+
+// Guesses can be compared only if they have the same range, otherwise `partial_cmp` returns `None`
+// This is why the trait is `PartialOrd` and not `Ord`
+impl PartialOrd for Guess {
+    fn partial_cmp(&self, other: &Guess) -> Option<Ordering> {
+        if self.range() != other.range() {
+            return None;
+        }
+        Some(self.value.cmp(&other.value))
+    }
+}
+
+// The sum of two guesses must be in their range too, so the result can fail
+impl Add for Guess {
+    type Output = Result<Guess, GuessError>;
+
+    fn add(self, other: Guess) -> Result<Guess, GuessError> {
+        if self.range() != other.range() {
+            return Err(GuessError::RangeMismatch);
+        }
+        // The sum is computed in `i64`, so it can't overflow even when the range is all the `i32`
+        let value = i64::from(self.value) + i64::from(other.value);
+        let (min, max) = (self.min, self.max);
+        match i32::try_from(value) {
+            Ok(value) => Guess::try_new_in(value, min, max),
+            Err(_) => Err(GuessError::OutOfRange { value, min, max }),
+        }
     }
 }
 
@@ -88,7 +192,7 @@ mod tests {
     fn it_works() {
         // Test of the add function
         let result = add(2, 2);
-        assert_eq!(result, 4);
+        assert_eq!(result, Ok(4));
     }
 
     // #[test]
@@ -97,34 +201,25 @@ mod tests {
     //     panic!("Make this test fail");
     // }
 
+    // The fixtures and the property-based tests need the `std` feature
+    #[cfg(feature = "std")]
     #[test]
     fn larger_can_hold_smaller() {
-        let larger = _Rectangle {
-            width: 8,
-            height: 7,
-        };
-        let smaller = _Rectangle {
-            width: 5,
-            height: 1,
-        };
+        // The fixtures build the test data spelling out only the sides
+        let larger = fixtures::rectangle().wide(8).tall(7).build();
+        let smaller = fixtures::rectangle().wide(5).build();
         // The `assert!` macro is used  when a condition evaluates to `true`
         // If it is nothing happens and the test is passed, otherwise it panics
-        assert!(larger._can_hold(&smaller));
+        assert!(larger.can_hold(&smaller));
     }
 
     #[test]
     fn smaller_cannot_hold_larger() {
-        let larger = _Rectangle {
-            width: 8,
-            height: 7,
-        };
-        let smaller = _Rectangle {
-            width: 5,
-            height: 1,
-        };
+        let larger = Rectangle::new(8, 7);
+        let smaller = Rectangle::new(5, 1);
 
         // The assert can be be used on negated resultss
-        assert!(!smaller._can_hold(&larger));
+        assert!(!smaller.can_hold(&larger));
     }
 
     #[test]
@@ -135,7 +230,7 @@ mod tests {
         // The opposite is `assert_ne!` (`!=`)
         // In Rust the arguments are called `left` and `right`
         // Usually `left` is the result while `right` is the testing value
-        assert_eq!(result, 4);
+        assert_eq!(result, Ok(4));
     }
 
     #[test]
@@ -143,11 +238,17 @@ mod tests {
         let res = greeting("Carol");
         // The `assert!` macro allows to add a custom error message as second parameter
         assert!(
-            !res.contains("Carol"),
+            res.contains("Carol"),
             "Greeting did not contain name, value was `{res}`"
         );
     }
 
+    #[test]
+    fn greeting_contains_name_and_hello() {
+        // The assertions of the `test_support` module already report the value in the message
+        assert_contains_all!(greeting("Carol"), "Hello", "Carol");
+    }
+
     #[test]
     #[should_panic]
     fn greater_than_100() {
@@ -163,6 +264,68 @@ mod tests {
         Guess::new(0);
     }
 
+    #[test]
+    #[should_panic(expected = "less than or equal to 10, got 11")]
+    fn greater_than_custom_max() {
+        // `new_in` panics with the same messages as `new`, using the custom range
+        Guess::new_in(11, -10, 10);
+    }
+
+    #[test]
+    fn guess_in_custom_range() {
+        let guess = Guess::new_in(-5, -10, 10);
+        assert_eq!(guess.value(), -5);
+        assert_eq!(guess.range(), -10..=10);
+        assert_err_matches!(
+            Guess::try_new_in(0, 5, 1),
+            GuessError::EmptyRange { min: 5, max: 1 }
+        );
+    }
+
+    #[test]
+    fn guesses_compare_within_the_same_range() {
+        assert!(Guess::new(10) < Guess::new(20));
+        assert!(Guess::new(30) >= Guess::new(30));
+        // With different ranges the guesses are not comparable, so every comparison is false
+        let other = Guess::new_in(10, 0, 10);
+        assert_eq!(Guess::new(10).partial_cmp(&other), None);
+        assert_eq!(
+            (Guess::new(10) < other, Guess::new(10) >= other),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn guesses_add_within_their_range() {
+        assert_eq!(Guess::new(40) + Guess::new(2), Ok(Guess::new(42)));
+        assert_eq!(
+            Guess::new(60) + Guess::new(50),
+            Err(GuessError::OutOfRange {
+                value: 110,
+                min: 1,
+                max: 100
+            })
+        );
+        assert_err_matches!(
+            Guess::new(1) + Guess::new_in(1, 1, 10),
+            GuessError::RangeMismatch
+        );
+        let max = Guess::new_in(i32::MAX, 0, i32::MAX);
+        // The sum is reported even if it doesn't fit in an `i32`
+        assert_err_matches!(max + max, GuessError::OutOfRange { value, .. } if value > i64::from(i32::MAX));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn add_is_commutative() {
+        // A property-based test checks the property on many random values, instead of a single example
+        // The values cover the whole `u64`, so both sums overflow together
+        let numbers = property::ints(0..=u64::MAX);
+        property::for_all(500, (numbers, property::ints(0..=u64::MAX)), |&(a, b)| {
+            add(a, b).ok() == add(b, a).ok()
+        });
+    }
+
     #[test]
     fn it_works_2() -> Result<(), String> {
         // A test can also have the `Result<(), String>` return type
@@ -170,7 +333,7 @@ mod tests {
         // In this way the question mark operator can be used in the body of tests
         // This is convenient for tests that should fail if any operation returns `Err`
         // This kind of tests doesn't allow `#[should_panic]`
-        let result = add(2, 2);
+        let result = add(2, 2).map_err(|error| error.to_string())?;
 
         if result == 4 {
             Ok(())
@@ -0,0 +1,174 @@
+//! Arithmetic of the `adder` template, for any integer type
+//! `left + right` panics on overflow in debug builds and wraps around in release builds, so the functions make the choice explicit:
+//! - `add` and `add_two` return an error on overflow
+//! - `saturating_add` stops at the minimum or the maximum of the type
+//! - `wrapping_add` wraps around, like the release builds
+//!
+//! The traits are implemented by a macro for every primitive integer type, the same way the standard library does
+
+use core::fmt::{self, Debug, Display};
+
+// The addition that reports the overflow instead of panicking or wrapping
+pub trait CheckedAdd: Sized + Copy {
+    fn checked_add(self, other: Self) -> Option<Self>;
+}
+
+// The integer types, with the other kinds of addition and the constant used by `add_two`
+pub trait Integer: CheckedAdd + Debug + Display {
+    const TWO: Self;
+
+    fn saturating_add(self, other: Self) -> Self;
+    fn wrapping_add(self, other: Self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedAdd for $t {
+                fn checked_add(self, other: $t) -> Option<$t> {
+                    <$t>::checked_add(self, other)
+                }
+            }
+
+            impl Integer for $t {
+                const TWO: $t = 2;
+
+                fn saturating_add(self, other: $t) -> $t {
+                    <$t>::saturating_add(self, other)
+                }
+
+                fn wrapping_add(self, other: $t) -> $t {
+                    <$t>::wrapping_add(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+// The operands of an addition that doesn't fit in their type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow<T> {
+    pub left: T,
+    pub right: T,
+}
+
+impl<T: Display> fmt::Display for Overflow<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {} overflows", self.left, self.right)
+    }
+}
+
+impl<T: Debug + Display> core::error::Error for Overflow<T> {}
+
+pub fn add<T: CheckedAdd>(left: T, right: T) -> Result<T, Overflow<T>> {
+    left.checked_add(right).ok_or(Overflow { left, right })
+}
+
+pub fn add_two<T: Integer>(a: T) -> Result<T, Overflow<T>> {
+    add(a, T::TWO)
+}
+
+pub fn saturating_add<T: Integer>(left: T, right: T) -> T {
+    left.saturating_add(right)
+}
+
+pub fn wrapping_add<T: Integer>(left: T, right: T) -> T {
+    left.wrapping_add(right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_reports_the_overflow() {
+        assert_eq!(add(2u8, 2), Ok(4));
+        assert_eq!(add(u8::MAX, 0), Ok(u8::MAX));
+        assert_eq!(
+            add(u8::MAX, 1),
+            Err(Overflow {
+                left: u8::MAX,
+                right: 1
+            })
+        );
+        assert_eq!(
+            add(i64::MIN, -1).unwrap_err().to_string(),
+            format!("{} + -1 overflows", i64::MIN)
+        );
+    }
+
+    #[test]
+    fn all_the_sums_of_8_bits_integers() {
+        // With 8 bits there are only 65536 pairs, so every one of them is checked against the sum in 16 bits
+        for left in u8::MIN..=u8::MAX {
+            for right in u8::MIN..=u8::MAX {
+                let sum = u16::from(left) + u16::from(right);
+                assert_eq!(add(left, right).ok(), u8::try_from(sum).ok());
+                assert_eq!(
+                    saturating_add(left, right),
+                    u8::try_from(sum).unwrap_or(u8::MAX)
+                );
+                assert_eq!(wrapping_add(left, right), (sum % 256) as u8);
+            }
+        }
+        for left in i8::MIN..=i8::MAX {
+            for right in i8::MIN..=i8::MAX {
+                let sum = i16::from(left) + i16::from(right);
+                assert_eq!(add(left, right).ok(), i8::try_from(sum).ok());
+                assert_eq!(saturating_add(left, right), sum.clamp(-128, 127) as i8);
+                assert_eq!(wrapping_add(left, right), sum as u8 as i8);
+            }
+        }
+    }
+
+    // The edges of every type: the sums around the minimum and the maximum
+    macro_rules! edge_cases {
+        ($($name:ident: $t:ty),*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert_eq!(add_two(<$t>::MAX - 2), Ok(<$t>::MAX));
+                    assert!(add_two(<$t>::MAX - 1).is_err());
+                    assert!(add(<$t>::MAX, <$t>::MAX).is_err());
+                    assert_eq!(add(<$t>::MIN, 0), Ok(<$t>::MIN));
+                    assert_eq!(add(<$t>::MIN, <$t>::MAX), Ok(<$t>::MIN + <$t>::MAX));
+                    assert_eq!(saturating_add(<$t>::MAX, 1), <$t>::MAX);
+                    assert_eq!(wrapping_add(<$t>::MAX, 1), <$t>::MIN);
+                    assert_eq!(wrapping_add(<$t>::MAX, <$t>::MAX), <$t>::MAX - 1 + <$t>::MIN);
+                }
+            )*
+        };
+    }
+
+    // Only the signed types can overflow below the minimum
+    macro_rules! signed_edge_cases {
+        ($($name:ident: $t:ty),*) => {
+            $(
+                #[test]
+                fn $name() {
+                    assert!(add(<$t>::MIN, -1).is_err());
+                    assert!(add(<$t>::MIN, <$t>::MIN).is_err());
+                    assert_eq!(add(<$t>::MIN, <$t>::MAX), Ok(-1));
+                    assert_eq!(add_two(-2 as $t), Ok(0));
+                    assert_eq!(saturating_add(<$t>::MIN, <$t>::MIN), <$t>::MIN);
+                    assert_eq!(wrapping_add(<$t>::MIN, -1), <$t>::MAX);
+                    assert_eq!(wrapping_add(<$t>::MIN, <$t>::MIN), 0);
+                }
+            )*
+        };
+    }
+
+    edge_cases!(
+        edges_i8: i8, edges_i16: i16, edges_i32: i32, edges_i64: i64, edges_i128: i128, edges_isize: isize,
+        edges_u8: u8, edges_u16: u16, edges_u32: u32, edges_u64: u64, edges_u128: u128, edges_usize: usize
+    );
+
+    signed_edge_cases!(
+        signed_edges_i8: i8, signed_edges_i16: i16, signed_edges_i32: i32, signed_edges_i64: i64,
+        signed_edges_i128: i128, signed_edges_isize: isize
+    );
+}
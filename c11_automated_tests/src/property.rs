@@ -0,0 +1,327 @@
+//! Property-based testing
+//! Instead of checking a function with a few chosen examples, a property is checked on many random values
+//! E.g. "`add(a, b)` is equal to `add(b, a)`" for any `a` and `b`
+//! The values come from a `Generator`, which also knows how to make a value simpler, so a failure can be shrunk
+//! When a property fails, the report includes the seed of the failing case, to reproduce it with `for_all_seeded(seed, 1, ...)`
+
+use std::fmt::{self, Debug};
+use std::ops::RangeInclusive;
+
+use rand::distr::uniform::SampleUniform;
+use rand::distr::{Alphanumeric, SampleString};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::{Guess, Rectangle};
+
+// Upper limit of the shrinking steps, so a generator that always finds a simpler value can't loop forever
+const MAX_SHRINK_STEPS: usize = 1000;
+
+pub trait Generator {
+    type Value: Debug + Clone;
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Value;
+
+    // Returns simpler values than `value`, the simplest first
+    // The default implementation doesn't shrink at all
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        Vec::new()
+    }
+}
+
+// Integers in a range, shrinking towards 0, or towards the bound of the range closest to 0
+pub struct Ints<T> {
+    range: RangeInclusive<T>,
+}
+
+pub fn ints<T>(range: RangeInclusive<T>) -> Ints<T> {
+    Ints { range }
+}
+
+// The integer types that can be converted to `i128` without loss, so they all shrink with the same arithmetic
+impl<T> Generator for Ints<T>
+where
+    T: SampleUniform + PartialOrd + Copy + Debug + Into<i128> + TryFrom<i128>,
+{
+    type Value = T;
+
+    fn generate(&self, rng: &mut StdRng) -> T {
+        rng.random_range(self.range.clone())
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        let value: i128 = (*value).into();
+        let target = 0.clamp((*self.range.start()).into(), (*self.range.end()).into());
+        if value == target {
+            return Vec::new();
+        }
+        // Jump straight to the target, then halfway there, then a single step
+        let halfway = value - (value - target) / 2;
+        let step = value - (value - target).signum();
+        let mut candidates = vec![target];
+        for candidate in [halfway, step] {
+            if candidate != value && !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+            .into_iter()
+            .filter_map(|candidate| T::try_from(candidate).ok())
+            .collect()
+    }
+}
+
+// Alphanumeric strings up to a length, shrinking by cutting them
+pub struct Strings {
+    max_len: usize,
+}
+
+pub fn strings(max_len: usize) -> Strings {
+    Strings { max_len }
+}
+
+impl Generator for Strings {
+    type Value = String;
+
+    fn generate(&self, rng: &mut StdRng) -> String {
+        let len = rng.random_range(0..=self.max_len);
+        Alphanumeric.sample_string(rng, len)
+    }
+
+    fn shrink(&self, value: &String) -> Vec<String> {
+        // The strings are ASCII, so they can be cut at any byte
+        let len = value.len();
+        let mut candidates = Vec::new();
+        for cut in [0, len / 2, len.saturating_sub(1)] {
+            let candidate = value[..cut].to_string();
+            if cut < len && !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+// Rectangles with sides up to a length, shrinking one side at a time
+pub struct Rectangles {
+    sides: Ints<u32>,
+}
+
+pub fn rectangles(max_side: u32) -> Rectangles {
+    Rectangles {
+        sides: ints(0..=max_side),
+    }
+}
+
+impl Generator for Rectangles {
+    type Value = Rectangle;
+
+    fn generate(&self, rng: &mut StdRng) -> Rectangle {
+        Rectangle::new(self.sides.generate(rng), self.sides.generate(rng))
+    }
+
+    fn shrink(&self, value: &Rectangle) -> Vec<Rectangle> {
+        let widths = self.sides.shrink(&value.width).into_iter();
+        let heights = self.sides.shrink(&value.height).into_iter();
+        widths
+            .map(|width| Rectangle::new(width, value.height))
+            .chain(heights.map(|height| Rectangle::new(value.width, height)))
+            .collect()
+    }
+}
+
+// Guesses in a range, always valid
+pub struct Guesses {
+    min: i32,
+    max: i32,
+    values: Ints<i32>,
+}
+
+pub fn guesses(min: i32, max: i32) -> Guesses {
+    Guesses {
+        min,
+        max,
+        values: ints(min..=max),
+    }
+}
+
+impl Generator for Guesses {
+    type Value = Guess;
+
+    fn generate(&self, rng: &mut StdRng) -> Guess {
+        Guess::new_in(self.values.generate(rng), self.min, self.max)
+    }
+
+    fn shrink(&self, value: &Guess) -> Vec<Guess> {
+        self.values
+            .shrink(&value.value())
+            .into_iter()
+            .map(|value| Guess::new_in(value, self.min, self.max))
+            .collect()
+    }
+}
+
+// A pair of generators generates pairs, shrinking one element at a time
+impl<A: Generator, B: Generator> Generator for (A, B) {
+    type Value = (A::Value, B::Value);
+
+    fn generate(&self, rng: &mut StdRng) -> Self::Value {
+        (self.0.generate(rng), self.1.generate(rng))
+    }
+
+    fn shrink(&self, (a, b): &Self::Value) -> Vec<Self::Value> {
+        let firsts = self.0.shrink(a).into_iter().map(|a| (a, b.clone()));
+        let seconds = self.1.shrink(b).into_iter().map(|b| (a.clone(), b));
+        firsts.chain(seconds).collect()
+    }
+}
+
+// The report of a property that doesn't hold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure<V> {
+    // The seed of the failing case
+    pub seed: u64,
+    // The number of cases that passed before
+    pub passed: usize,
+    pub original: V,
+    // The simplest value found that still fails
+    pub shrunk: V,
+}
+
+impl<V: Debug> fmt::Display for Failure<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "property failed after {} cases with seed {}: {:?}, shrunk to {:?}",
+            self.passed, self.seed, self.original, self.shrunk
+        )
+    }
+}
+
+pub fn check<G, P>(
+    seed: u64,
+    cases: usize,
+    generator: &G,
+    property: P,
+) -> Result<(), Failure<G::Value>>
+where
+    G: Generator,
+    P: Fn(&G::Value) -> bool,
+{
+    // Checks the property on `cases` values, the case `n` uses the seed `seed + n`
+    // So a failing case can be run again on its own, starting from its seed
+    for case in 0..cases {
+        let case_seed = seed.wrapping_add(case as u64);
+        let value = generator.generate(&mut StdRng::seed_from_u64(case_seed));
+        if !property(&value) {
+            let shrunk = shrink(generator, &property, value.clone());
+            return Err(Failure {
+                seed: case_seed,
+                passed: case,
+                original: value,
+                shrunk,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn shrink<G, P>(generator: &G, property: &P, mut value: G::Value) -> G::Value
+where
+    G: Generator,
+    P: Fn(&G::Value) -> bool,
+{
+    // Keeps replacing the value with the first simpler one that still fails, until none does
+    for _ in 0..MAX_SHRINK_STEPS {
+        let simpler = generator
+            .shrink(&value)
+            .into_iter()
+            .find(|candidate| !property(candidate));
+        match simpler {
+            Some(simpler) => value = simpler,
+            None => break,
+        }
+    }
+    value
+}
+
+pub fn for_all<G, P>(cases: usize, generator: G, property: P)
+where
+    G: Generator,
+    P: Fn(&G::Value) -> bool,
+{
+    // Checks the property with a random seed, or the one in the `PROPERTY_SEED` environment variable, and panics if it fails
+    let seed = std::env::var("PROPERTY_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(rand::random);
+    for_all_seeded(seed, cases, generator, property);
+}
+
+pub fn for_all_seeded<G, P>(seed: u64, cases: usize, generator: G, property: P)
+where
+    G: Generator,
+    P: Fn(&G::Value) -> bool,
+{
+    if let Err(failure) = check(seed, cases, &generator, property) {
+        panic!("{failure}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_values_are_in_range() {
+        for_all(200, ints(-5..=5), |value: &i32| (-5..=5).contains(value));
+        for_all(200, strings(8), |value| {
+            value.len() <= 8 && value.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+        for_all(200, guesses(10, 20), |guess| {
+            (10..=20).contains(&guess.value())
+        });
+    }
+
+    #[test]
+    fn same_seed_same_values() {
+        let generator = (ints(0..=u64::MAX), strings(20));
+        let first = generator.generate(&mut StdRng::seed_from_u64(42));
+        let second = generator.generate(&mut StdRng::seed_from_u64(42));
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn failures_are_shrunk_to_the_boundary() {
+        let failure = check(7, 100, &ints(0..=1000), |value: &u32| *value < 50).unwrap_err();
+        assert_eq!(failure.shrunk, 50);
+        assert!(failure.original >= 50);
+        // The failing seed reproduces the same value on its own
+        let again = check(failure.seed, 1, &ints(0..=1000), |value: &u32| *value < 50);
+        assert_eq!(again.unwrap_err().original, failure.original);
+    }
+
+    #[test]
+    fn ints_shrink_towards_the_range() {
+        assert_eq!(ints(-10..=10).shrink(&-8), [0, -4, -7]);
+        assert_eq!(ints(5..=10).shrink(&9), [5, 7, 8]);
+        assert_eq!(ints(5..=10).shrink(&5), []);
+    }
+
+    #[test]
+    fn composite_values_shrink_each_part() {
+        let failure =
+            check(1, 100, &rectangles(100), |rectangle| rectangle.area() < 10).unwrap_err();
+        assert!(failure.shrunk.area() >= 10);
+        assert!(failure.shrunk.width <= 10 && failure.shrunk.height <= 10);
+
+        let failure = check(3, 100, &strings(30), |value| !value.contains('a')).unwrap_err();
+        assert!(failure.shrunk.ends_with('a'));
+    }
+
+    #[test]
+    #[should_panic(expected = "property failed")]
+    fn for_all_panics_with_the_report() {
+        for_all_seeded(0, 100, guesses(1, 100), |guess| guess.value() < 90);
+    }
+}
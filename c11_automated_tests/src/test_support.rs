@@ -0,0 +1,156 @@
+//! Assertions that are missing from the standard library
+//! They are macros, like `assert!` and `assert_eq!`, so the panic is reported at the line of the test and the values can be of any type
+//! The panic messages follow the ones of `assert_eq!`, with both values on their own line
+//! `#[macro_export]` puts the macros at the root of the crate, so the other chapters use them as `c11_automated_tests::assert_approx_eq!`
+//! They are also re-exported here, to be imported with the rest of the module
+
+/// Asserts that two floats are equal, allowing for a difference up to `eps` (`1e-9` if not given).
+/// `NaN` is never equal to anything, so it always fails.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, 1e-9)
+    };
+    ($left:expr, $right:expr, $eps:expr $(,)?) => {
+        match (&$left, &$right, &$eps) {
+            (left, right, eps) => {
+                let diff = (*left - *right).abs();
+                // A `NaN` is not greater than anything, so it's checked on its own
+                if diff.is_nan() || diff > *eps {
+                    ::core::panic!(
+                        "assertion `left ≈ right` failed (eps: {:?})\n  left: {:?}\n right: {:?}\n  diff: {:?}",
+                        eps, left, right, diff
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that a value contains all the needles, calling its `contains` method.
+/// So it works with strings, `assert_contains_all!(text, "a", "b")`, and with slices, `assert_contains_all!(vec, &1, &2)`.
+/// The panic lists every missing needle, not only the first one.
+#[macro_export]
+macro_rules! assert_contains_all {
+    ($haystack:expr, $($needle:expr),+ $(,)?) => {
+        match &$haystack {
+            haystack => {
+                let mut missing: $crate::test_support::__private::Vec<
+                    $crate::test_support::__private::String,
+                > = $crate::test_support::__private::Vec::new();
+                $(
+                    if !haystack.contains($needle) {
+                        missing.push($crate::test_support::__private::format!("{:?}", $needle));
+                    }
+                )+
+                if !missing.is_empty() {
+                    ::core::panic!(
+                        "assertion `haystack contains all the needles` failed\n haystack: {:?}\n  missing: {}",
+                        haystack,
+                        missing.join(", ")
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that a `Result` is an `Err` matching a pattern, with an optional guard like in `matches!`.
+/// E.g. `assert_err_matches!(Guess::try_new_in(0, 5, 1), GuessError::EmptyRange { .. })`.
+#[macro_export]
+macro_rules! assert_err_matches {
+    ($result:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {
+        match $result {
+            Err($pattern) $(if $guard)? => {}
+            other => ::core::panic!(
+                "assertion `result matches Err({})` failed\n result: {:?}",
+                ::core::stringify!($pattern $(if $guard)?),
+                other
+            ),
+        }
+    };
+}
+
+pub use crate::{assert_approx_eq, assert_contains_all, assert_err_matches};
+
+// The items used by the macros, through `$crate` so the expansion doesn't depend on what's in scope where they are used
+// `alloc` instead of `std`, so they also work in the `no_std` chapters
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::format;
+    pub use alloc::string::String;
+    pub use alloc::vec::Vec;
+}
+
+#[cfg(test)]
+mod tests {
+    #[derive(Debug, PartialEq)]
+    enum Error {
+        Code(u32),
+        Message(String),
+    }
+
+    #[test]
+    fn approx_eq_allows_rounding_errors() {
+        assert_approx_eq!(0.1f64 + 0.2, 0.3);
+        assert_approx_eq!(1.0f32 / 3.0, 0.333, 1e-3);
+    }
+
+    #[test]
+    #[should_panic(expected = "left: 1.0\n right: 1.5\n  diff: 0.5")]
+    fn approx_eq_reports_both_values() {
+        assert_approx_eq!(1.0f64, 1.5, 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "left ≈ right")]
+    fn approx_eq_fails_with_nan() {
+        assert_approx_eq!(f64::NAN, f64::NAN, f64::INFINITY);
+    }
+
+    #[test]
+    fn contains_all_works_with_strings_and_slices() {
+        assert_contains_all!("Hello Ann, Bob and Carol!", "Ann", "Carol", 'H');
+        assert_contains_all!(vec![1, 2, 3], &3, &1);
+    }
+
+    #[test]
+    #[should_panic(expected = "missing: \"Dan\", \"Eve\"")]
+    fn contains_all_reports_every_missing_needle() {
+        assert_contains_all!(String::from("Hello Ann!"), "Ann", "Dan", "Eve");
+    }
+
+    #[test]
+    fn err_matches_with_patterns_and_guards() {
+        let result: Result<(), Error> = Err(Error::Code(404));
+        assert_err_matches!(result, Error::Code(_));
+        assert_err_matches!(Err::<(), _>(Error::Code(500)), Error::Code(code) if code >= 500);
+        let result: Result<(), Error> = Err(Error::Message(String::from("not found")));
+        assert_err_matches!(result, Error::Message(message) if message.ends_with("found"));
+    }
+
+    #[test]
+    fn contains_all_does_not_depend_on_the_names_in_scope() {
+        // Local types named like the ones of the prelude are not the ones used by the macro
+        #[allow(dead_code)]
+        struct Vec;
+        #[allow(dead_code)]
+        struct String;
+
+        assert_contains_all!("I'm nobody! Who are you?", "nobody", "you");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "result matches Err(Error::Message(_))` failed\n result: Err(Code(404))"
+    )]
+    fn err_matches_reports_other_errors() {
+        assert_err_matches!(Err::<(), _>(Error::Code(404)), Error::Message(_));
+    }
+
+    #[test]
+    #[should_panic(expected = "result: Ok(1)")]
+    fn err_matches_fails_with_ok() {
+        assert_err_matches!(Ok::<_, Error>(1), Error::Code(_));
+    }
+}
@@ -7,6 +7,9 @@
 //! It is possible to run run all the tests in a particular file with `cargo test --test file`
 //! The tests directory only works for library crates.
 use c11_automated_tests::add_two;
+// The fixtures write files, so they only exist with the `std` feature
+#[cfg(feature = "std")]
+use c11_automated_tests::{assert_contains_all, fixtures, greeting};
 
 mod common;
 
@@ -16,5 +19,17 @@ fn it_adds_two() {
     common::setup();
     // Test of the public function `add_two` in lib.rs
     let result = add_two(2);
-    assert_eq!(result, 4);
+    assert_eq!(result, Ok(4));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn fixtures_are_shared_with_integration_tests() {
+    // The `fixtures` module is public, so the integration tests use the same builders as the unit tests
+    let rectangle = fixtures::rectangle().wide(8).tall(7).build();
+    assert_eq!(rectangle.area(), 56);
+    assert_eq!(fixtures::guess(42).build().value(), 42);
+
+    let file = fixtures::temp_file("greeting.txt", &greeting("Carol"));
+    assert_contains_all!(file.read().unwrap(), "Hello", "Carol");
 }
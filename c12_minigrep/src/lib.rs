@@ -1,9 +1,33 @@
 //! Module containing all the elements necessary for `minigrep` to work, with their tests
 // Error is a trait representing the basic expectations for error values
 use std::error::Error;
+use std::fmt;
 // The `fs` module of `std` is used to handle files
 use std::{env, fs};
 
+/// Error returned when the arguments don't make a `Config`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// Returned by `build_old` when there are less than two arguments
+    NotEnoughParameters,
+    MissingQuery,
+    MissingFilePath,
+}
+
+impl fmt::Display for ConfigError {
+    /// The messages are the ones the chapter returns as `&'static str`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ConfigError::NotEnoughParameters => "not enough parameters",
+            ConfigError::MissingQuery => "Didn't get a query string",
+            ConfigError::MissingFilePath => "Didn't get a file path",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl Error for ConfigError {}
+
 /// Struct used for collecting the `query` and `file_path` configs
 pub struct Config {
     pub query: String,
@@ -20,12 +44,12 @@ impl Config {
     ///
     /// # Returns
     ///
-    /// * `Result<Config, &'static str>`: a Result with the config or a string as error
-    pub fn build_old(args: &[String]) -> Result<Config, &'static str> {
+    /// * `Result<Config, ConfigError>`: a Result with the config or the reason it couldn't be built
+    pub fn build_old(args: &[String]) -> Result<Config, ConfigError> {
         // This method can be improved using iterators, as follows
         // Error handling return Result with an error if the parameters are not enough
         if args.len() < 3 {
-            return Err("not enough parameters");
+            return Err(ConfigError::NotEnoughParameters);
         }
         // We want to clone the values so Config will own them, without managing lifetimes
         // However the clone function is inefficient
@@ -48,19 +72,19 @@ impl Config {
     ///
     /// # Returns
     ///
-    /// * `Result<Config, &'static str>`: a Result with the config or a string as error
-    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, &'static str> {
+    /// * `Result<Config, ConfigError>`: a Result with the config or the reason it couldn't be built
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, ConfigError> {
         args.next(); // Name of the program
 
         let query = match args.next() {
             // The value is extracted from the iterator using a `match`
             Some(arg) => arg,
-            None => return Err("Didn't get a query string"),
+            None => return Err(ConfigError::MissingQuery),
         };
 
         let file_path = match args.next() {
             Some(arg) => arg,
-            None => return Err("Didn't get a file path"),
+            None => return Err(ConfigError::MissingFilePath),
         };
 
         let ignore_case = env::var("IGNORE_CASE").is_ok();
@@ -83,8 +107,13 @@ impl Config {
 ///
 /// * `Result<Config, &'static str>`: unit type in the Ok case, a type that implements the `Error` trait in the Err case
 pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
+    book_log::debug!("reading the file"; path = config.file_path);
     // Instead of `expect` `?` is used so it will return the error instead of panicking
-    let contents = fs::read_to_string(config.file_path)?;
+    // let contents = fs::read_to_string(config.file_path)?;
+    // The error is logged with the path before being returned, so the path is only borrowed
+    let contents = fs::read_to_string(&config.file_path).inspect_err(|error| {
+        book_log::warn!("can't read the file"; path = config.file_path, error = error);
+    })?;
 
     // Add lines to res. Pay attention not to put semicolon inside of `if` and `else`
     let res = if config.ignore_case {
@@ -92,6 +121,14 @@ pub fn run(config: Config) -> Result<(), Box<dyn Error>> {
     } else {
         search(&config.query, &contents)
     };
+    book_log::info!(
+        "file searched";
+        path = config.file_path,
+        query = config.query,
+        ignore_case = config.ignore_case,
+        lines = contents.lines().count(),
+        matches = res.len(),
+    );
 
     // Print each line of the result
     for line in res {
@@ -194,4 +231,30 @@ mod tests {
             search_case_insensitive(query, contents)
         );
     }
+
+    #[test]
+    fn missing_arguments() {
+        let args = |args: &[&str]| {
+            args.iter()
+                .map(|arg| String::from(*arg))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            Config::build(args(&["minigrep"]).into_iter()).err(),
+            Some(ConfigError::MissingQuery)
+        );
+        assert_eq!(
+            Config::build(args(&["minigrep", "duct"]).into_iter()).err(),
+            Some(ConfigError::MissingFilePath)
+        );
+        assert_eq!(
+            Config::build_old(&args(&["minigrep", "duct"])).err(),
+            Some(ConfigError::NotEnoughParameters)
+        );
+        assert_eq!(
+            ConfigError::MissingQuery.to_string(),
+            "Didn't get a query string"
+        );
+    }
 }
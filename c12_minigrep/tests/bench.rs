@@ -0,0 +1,47 @@
+//! Benchmarks of the searches, run with `cargo test --test bench -- --include-ignored --show-output`
+//! The comparisons of the timings are ignored by default as their result depends on the load of the machine
+
+use c11_automated_tests::bench::time_it;
+use c12_minigrep::{search, search_case_insensitive};
+
+/// A text of `lines` lines, one every ten containing the query.
+fn contents(lines: usize) -> String {
+    (0..lines)
+        .map(|n| {
+            if n % 10 == 0 {
+                format!("line {n}: safe, fast, productive.\n")
+            } else {
+                format!("line {n}: pick three.\n")
+            }
+        })
+        .collect()
+}
+
+#[test]
+#[ignore = "depends on the load of the machine"]
+fn search_scales_with_the_contents() {
+    let short = contents(100);
+    let long = contents(10_000);
+
+    let short_stats = time_it(100, || search("duct", &short));
+    let long_stats = time_it(100, || search("duct", &long));
+    println!("search in 100 lines, {short_stats}");
+    println!("search in 10000 lines, {long_stats}");
+
+    assert_eq!(search("duct", &long).len(), 1000);
+    assert!(short_stats.min < long_stats.min);
+}
+
+#[test]
+#[ignore = "depends on the load of the machine"]
+fn case_insensitive_search_lowercases_every_line() {
+    let contents = contents(10_000);
+
+    let sensitive = time_it(50, || search("DUCT", &contents));
+    let insensitive = time_it(50, || search_case_insensitive("DUCT", &contents));
+    println!("case sensitive search, {sensitive}");
+    println!("case insensitive search, {insensitive}");
+
+    // The insensitive search allocates a lowercase copy of each line, so it's never faster
+    assert!(sensitive.min < insensitive.min);
+}
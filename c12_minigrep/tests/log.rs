@@ -0,0 +1,37 @@
+//! The events of the searches, in a single test as the sink of `book_log` is global
+
+use std::sync::Arc;
+
+use book_log::{Level, MemorySink};
+use c12_minigrep::Config;
+
+fn config(query: &str, file_path: &str) -> Config {
+    Config {
+        query: String::from(query),
+        file_path: String::from(file_path),
+        ignore_case: false,
+    }
+}
+
+#[test]
+fn searches_are_logged() {
+    let sink = Arc::new(MemorySink::new());
+    book_log::set_sink(sink.clone());
+
+    // The tests run from the directory of the package, the poem is in the one of the workspace
+    c12_minigrep::run(config("body", "../utils/poem.txt")).unwrap();
+    let events = sink.take();
+    assert_eq!(events.len(), 1, "reading the file is only a debug event");
+    assert_eq!(events[0].message, "file searched");
+    assert_eq!(events[0].field("query"), Some(r#""body""#));
+    assert_eq!(events[0].field("lines"), Some("9"));
+    assert_eq!(events[0].field("matches"), Some("3"));
+
+    book_log::set_max_level(Level::Debug);
+    assert!(c12_minigrep::run(config("body", "no such file.txt")).is_err());
+    let events = sink.take();
+    let messages: Vec<_> = events.iter().map(|event| event.message.as_str()).collect();
+    assert_eq!(messages, ["reading the file", "can't read the file"]);
+    assert_eq!(events[1].level, Level::Warn);
+    assert_eq!(events[1].field("path"), Some(r#""no such file.txt""#));
+}
@@ -0,0 +1,142 @@
+//! # Blend
+//!
+//! Blends of any number of colors, and gradients between two of them to render color ramps.
+
+#[cfg(not(feature = "std"))]
+use crate::art::float::F64Ext;
+use crate::art::kinds::Color;
+use crate::art::rgb::Rgb;
+
+/// Averages the RGB values of colors, each one counting as much as its weight.
+///
+/// Negative weights are treated as `0.0`. If there are no colors, or all the weights are `0.0`, the result is black.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Color, PrimaryColor, Rgb, blend_all};
+///
+/// let blend = blend_all([
+///     (Color::from(PrimaryColor::Red), 3.0),
+///     (Color::from(Rgb(0, 0, 0)), 1.0),
+/// ]);
+///
+/// assert_eq!(Rgb(191, 0, 0), blend);
+/// ```
+pub fn blend_all(colors: impl IntoIterator<Item = (Color, f64)>) -> Rgb {
+    let mut sums = [0.0; 3];
+    let mut total = 0.0;
+
+    for (color, weight) in colors {
+        let weight = weight.max(0.0);
+        let rgb = color.to_rgb();
+        sums[0] += rgb.0 as f64 * weight;
+        sums[1] += rgb.1 as f64 * weight;
+        sums[2] += rgb.2 as f64 * weight;
+        total += weight;
+    }
+
+    if total == 0.0 {
+        return Rgb(0, 0, 0);
+    }
+    let channel = |sum: f64| (sum / total).round().clamp(0.0, 255.0) as u8;
+    Rgb(channel(sums[0]), channel(sums[1]), channel(sums[2]))
+}
+
+/// An iterator over the colors of a gradient, created with [`gradient`].
+#[derive(Debug, Clone)]
+pub struct Gradient {
+    from: Rgb,
+    to: Rgb,
+    steps: usize,
+    next: usize,
+}
+
+impl Iterator for Gradient {
+    type Item = Rgb;
+
+    fn next(&mut self) -> Option<Rgb> {
+        if self.next >= self.steps {
+            return None;
+        }
+        let ratio = if self.steps == 1 {
+            0.0
+        } else {
+            self.next as f64 / (self.steps - 1) as f64
+        };
+        self.next += 1;
+
+        Some(blend_all([
+            (Color::Rgb(self.from), 1.0 - ratio),
+            (Color::Rgb(self.to), ratio),
+        ]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.steps - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for Gradient {}
+
+/// Creates an iterator over `steps` colors going evenly from one color to another.
+///
+/// The first color is `from` and the last one is `to`, unless there is a single step, which is just `from`.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{PrimaryColor, Rgb, gradient};
+///
+/// let ramp: Vec<Rgb> = gradient(PrimaryColor::Red, Rgb(0, 0, 0), 3).collect();
+///
+/// assert_eq!(vec![Rgb(255, 0, 0), Rgb(128, 0, 0), Rgb(0, 0, 0)], ramp);
+/// ```
+pub fn gradient(from: impl Into<Color>, to: impl Into<Color>, steps: usize) -> Gradient {
+    Gradient {
+        from: from.into().to_rgb(),
+        to: to.into().to_rgb(),
+        steps,
+        next: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blend_of_nothing_is_black() {
+        assert_eq!(Rgb(0, 0, 0), blend_all([]));
+        assert_eq!(Rgb(0, 0, 0), blend_all([(Color::Rgb(Rgb(9, 9, 9)), -1.0)]));
+    }
+
+    #[test]
+    fn weights_are_relative() {
+        let colors = |scale: f64| {
+            blend_all([
+                (Color::Rgb(Rgb(200, 0, 40)), 1.0 * scale),
+                (Color::Rgb(Rgb(0, 100, 40)), 4.0 * scale),
+            ])
+        };
+
+        assert_eq!(Rgb(40, 80, 40), colors(1.0));
+        assert_eq!(colors(1.0), colors(0.25));
+    }
+
+    #[test]
+    fn gradient_has_the_number_of_steps() {
+        let ramp = gradient(Rgb(0, 0, 0), Rgb(255, 255, 255), 5);
+
+        assert_eq!(5, ramp.len());
+        let colors: Vec<Rgb> = ramp.collect();
+        assert_eq!(Rgb(0, 0, 0), colors[0]);
+        assert_eq!(Rgb(255, 255, 255), colors[4]);
+        assert!(colors.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        assert_eq!(0, gradient(Rgb(0, 0, 0), Rgb(1, 1, 1), 0).count());
+        assert_eq!(
+            vec![Rgb(7, 7, 7)],
+            gradient(Rgb(7, 7, 7), Rgb(1, 1, 1), 1).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! # CSS
+//!
+//! The 140 named colors of the web, as defined by CSS, with lookups by name and by value.
+//!
+//! Some colors have two names, such as `aqua` and `cyan`: both constants exist, and the reverse lookup
+//! gives the first name in alphabetical order.
+
+use crate::art::rgb::Rgb;
+
+pub const ALICE_BLUE: Rgb = Rgb(240, 248, 255);
+pub const ANTIQUE_WHITE: Rgb = Rgb(250, 235, 215);
+pub const AQUA: Rgb = Rgb(0, 255, 255);
+pub const AQUAMARINE: Rgb = Rgb(127, 255, 212);
+pub const AZURE: Rgb = Rgb(240, 255, 255);
+pub const BEIGE: Rgb = Rgb(245, 245, 220);
+pub const BISQUE: Rgb = Rgb(255, 228, 196);
+pub const BLACK: Rgb = Rgb(0, 0, 0);
+pub const BLANCHED_ALMOND: Rgb = Rgb(255, 235, 205);
+pub const BLUE: Rgb = Rgb(0, 0, 255);
+pub const BLUE_VIOLET: Rgb = Rgb(138, 43, 226);
+pub const BROWN: Rgb = Rgb(165, 42, 42);
+pub const BURLY_WOOD: Rgb = Rgb(222, 184, 135);
+pub const CADET_BLUE: Rgb = Rgb(95, 158, 160);
+pub const CHARTREUSE: Rgb = Rgb(127, 255, 0);
+pub const CHOCOLATE: Rgb = Rgb(210, 105, 30);
+pub const CORAL: Rgb = Rgb(255, 127, 80);
+pub const CORNFLOWER_BLUE: Rgb = Rgb(100, 149, 237);
+pub const CORNSILK: Rgb = Rgb(255, 248, 220);
+pub const CRIMSON: Rgb = Rgb(220, 20, 60);
+pub const CYAN: Rgb = Rgb(0, 255, 255);
+pub const DARK_BLUE: Rgb = Rgb(0, 0, 139);
+pub const DARK_CYAN: Rgb = Rgb(0, 139, 139);
+pub const DARK_GOLDEN_ROD: Rgb = Rgb(184, 134, 11);
+pub const DARK_GRAY: Rgb = Rgb(169, 169, 169);
+pub const DARK_GREEN: Rgb = Rgb(0, 100, 0);
+pub const DARK_KHAKI: Rgb = Rgb(189, 183, 107);
+pub const DARK_MAGENTA: Rgb = Rgb(139, 0, 139);
+pub const DARK_OLIVE_GREEN: Rgb = Rgb(85, 107, 47);
+pub const DARK_ORANGE: Rgb = Rgb(255, 140, 0);
+pub const DARK_ORCHID: Rgb = Rgb(153, 50, 204);
+pub const DARK_RED: Rgb = Rgb(139, 0, 0);
+pub const DARK_SALMON: Rgb = Rgb(233, 150, 122);
+pub const DARK_SEA_GREEN: Rgb = Rgb(143, 188, 143);
+pub const DARK_SLATE_BLUE: Rgb = Rgb(72, 61, 139);
+pub const DARK_SLATE_GRAY: Rgb = Rgb(47, 79, 79);
+pub const DARK_TURQUOISE: Rgb = Rgb(0, 206, 209);
+pub const DARK_VIOLET: Rgb = Rgb(148, 0, 211);
+pub const DEEP_PINK: Rgb = Rgb(255, 20, 147);
+pub const DEEP_SKY_BLUE: Rgb = Rgb(0, 191, 255);
+pub const DIM_GRAY: Rgb = Rgb(105, 105, 105);
+pub const DODGER_BLUE: Rgb = Rgb(30, 144, 255);
+pub const FIRE_BRICK: Rgb = Rgb(178, 34, 34);
+pub const FLORAL_WHITE: Rgb = Rgb(255, 250, 240);
+pub const FOREST_GREEN: Rgb = Rgb(34, 139, 34);
+pub const FUCHSIA: Rgb = Rgb(255, 0, 255);
+pub const GAINSBORO: Rgb = Rgb(220, 220, 220);
+pub const GHOST_WHITE: Rgb = Rgb(248, 248, 255);
+pub const GOLD: Rgb = Rgb(255, 215, 0);
+pub const GOLDEN_ROD: Rgb = Rgb(218, 165, 32);
+pub const GRAY: Rgb = Rgb(128, 128, 128);
+pub const GREEN: Rgb = Rgb(0, 128, 0);
+pub const GREEN_YELLOW: Rgb = Rgb(173, 255, 47);
+pub const HONEY_DEW: Rgb = Rgb(240, 255, 240);
+pub const HOT_PINK: Rgb = Rgb(255, 105, 180);
+pub const INDIAN_RED: Rgb = Rgb(205, 92, 92);
+pub const INDIGO: Rgb = Rgb(75, 0, 130);
+pub const IVORY: Rgb = Rgb(255, 255, 240);
+pub const KHAKI: Rgb = Rgb(240, 230, 140);
+pub const LAVENDER: Rgb = Rgb(230, 230, 250);
+pub const LAVENDER_BLUSH: Rgb = Rgb(255, 240, 245);
+pub const LAWN_GREEN: Rgb = Rgb(124, 252, 0);
+pub const LEMON_CHIFFON: Rgb = Rgb(255, 250, 205);
+pub const LIGHT_BLUE: Rgb = Rgb(173, 216, 230);
+pub const LIGHT_CORAL: Rgb = Rgb(240, 128, 128);
+pub const LIGHT_CYAN: Rgb = Rgb(224, 255, 255);
+pub const LIGHT_GOLDEN_ROD_YELLOW: Rgb = Rgb(250, 250, 210);
+pub const LIGHT_GRAY: Rgb = Rgb(211, 211, 211);
+pub const LIGHT_GREEN: Rgb = Rgb(144, 238, 144);
+pub const LIGHT_PINK: Rgb = Rgb(255, 182, 193);
+pub const LIGHT_SALMON: Rgb = Rgb(255, 160, 122);
+pub const LIGHT_SEA_GREEN: Rgb = Rgb(32, 178, 170);
+pub const LIGHT_SKY_BLUE: Rgb = Rgb(135, 206, 250);
+pub const LIGHT_SLATE_GRAY: Rgb = Rgb(119, 136, 153);
+pub const LIGHT_STEEL_BLUE: Rgb = Rgb(176, 196, 222);
+pub const LIGHT_YELLOW: Rgb = Rgb(255, 255, 224);
+pub const LIME: Rgb = Rgb(0, 255, 0);
+pub const LIME_GREEN: Rgb = Rgb(50, 205, 50);
+pub const LINEN: Rgb = Rgb(250, 240, 230);
+pub const MAGENTA: Rgb = Rgb(255, 0, 255);
+pub const MAROON: Rgb = Rgb(128, 0, 0);
+pub const MEDIUM_AQUA_MARINE: Rgb = Rgb(102, 205, 170);
+pub const MEDIUM_BLUE: Rgb = Rgb(0, 0, 205);
+pub const MEDIUM_ORCHID: Rgb = Rgb(186, 85, 211);
+pub const MEDIUM_PURPLE: Rgb = Rgb(147, 112, 219);
+pub const MEDIUM_SEA_GREEN: Rgb = Rgb(60, 179, 113);
+pub const MEDIUM_SLATE_BLUE: Rgb = Rgb(123, 104, 238);
+pub const MEDIUM_SPRING_GREEN: Rgb = Rgb(0, 250, 154);
+pub const MEDIUM_TURQUOISE: Rgb = Rgb(72, 209, 204);
+pub const MEDIUM_VIOLET_RED: Rgb = Rgb(199, 21, 133);
+pub const MIDNIGHT_BLUE: Rgb = Rgb(25, 25, 112);
+pub const MINT_CREAM: Rgb = Rgb(245, 255, 250);
+pub const MISTY_ROSE: Rgb = Rgb(255, 228, 225);
+pub const MOCCASIN: Rgb = Rgb(255, 228, 181);
+pub const NAVAJO_WHITE: Rgb = Rgb(255, 222, 173);
+pub const NAVY: Rgb = Rgb(0, 0, 128);
+pub const OLD_LACE: Rgb = Rgb(253, 245, 230);
+pub const OLIVE: Rgb = Rgb(128, 128, 0);
+pub const OLIVE_DRAB: Rgb = Rgb(107, 142, 35);
+pub const ORANGE: Rgb = Rgb(255, 165, 0);
+pub const ORANGE_RED: Rgb = Rgb(255, 69, 0);
+pub const ORCHID: Rgb = Rgb(218, 112, 214);
+pub const PALE_GOLDEN_ROD: Rgb = Rgb(238, 232, 170);
+pub const PALE_GREEN: Rgb = Rgb(152, 251, 152);
+pub const PALE_TURQUOISE: Rgb = Rgb(175, 238, 238);
+pub const PALE_VIOLET_RED: Rgb = Rgb(219, 112, 147);
+pub const PAPAYA_WHIP: Rgb = Rgb(255, 239, 213);
+pub const PEACH_PUFF: Rgb = Rgb(255, 218, 185);
+pub const PERU: Rgb = Rgb(205, 133, 63);
+pub const PINK: Rgb = Rgb(255, 192, 203);
+pub const PLUM: Rgb = Rgb(221, 160, 221);
+pub const POWDER_BLUE: Rgb = Rgb(176, 224, 230);
+pub const PURPLE: Rgb = Rgb(128, 0, 128);
+pub const RED: Rgb = Rgb(255, 0, 0);
+pub const ROSY_BROWN: Rgb = Rgb(188, 143, 143);
+pub const ROYAL_BLUE: Rgb = Rgb(65, 105, 225);
+pub const SADDLE_BROWN: Rgb = Rgb(139, 69, 19);
+pub const SALMON: Rgb = Rgb(250, 128, 114);
+pub const SANDY_BROWN: Rgb = Rgb(244, 164, 96);
+pub const SEA_GREEN: Rgb = Rgb(46, 139, 87);
+pub const SEA_SHELL: Rgb = Rgb(255, 245, 238);
+pub const SIENNA: Rgb = Rgb(160, 82, 45);
+pub const SILVER: Rgb = Rgb(192, 192, 192);
+pub const SKY_BLUE: Rgb = Rgb(135, 206, 235);
+pub const SLATE_BLUE: Rgb = Rgb(106, 90, 205);
+pub const SLATE_GRAY: Rgb = Rgb(112, 128, 144);
+pub const SNOW: Rgb = Rgb(255, 250, 250);
+pub const SPRING_GREEN: Rgb = Rgb(0, 255, 127);
+pub const STEEL_BLUE: Rgb = Rgb(70, 130, 180);
+pub const TAN: Rgb = Rgb(210, 180, 140);
+pub const TEAL: Rgb = Rgb(0, 128, 128);
+pub const THISTLE: Rgb = Rgb(216, 191, 216);
+pub const TOMATO: Rgb = Rgb(255, 99, 71);
+pub const TURQUOISE: Rgb = Rgb(64, 224, 208);
+pub const VIOLET: Rgb = Rgb(238, 130, 238);
+pub const WHEAT: Rgb = Rgb(245, 222, 179);
+pub const WHITE: Rgb = Rgb(255, 255, 255);
+pub const WHITE_SMOKE: Rgb = Rgb(245, 245, 245);
+pub const YELLOW: Rgb = Rgb(255, 255, 0);
+pub const YELLOW_GREEN: Rgb = Rgb(154, 205, 50);
+
+/// Every named color with its lowercase name, in alphabetical order.
+pub const COLORS: [(&str, Rgb); 140] = [
+    ("aliceblue", ALICE_BLUE),
+    ("antiquewhite", ANTIQUE_WHITE),
+    ("aqua", AQUA),
+    ("aquamarine", AQUAMARINE),
+    ("azure", AZURE),
+    ("beige", BEIGE),
+    ("bisque", BISQUE),
+    ("black", BLACK),
+    ("blanchedalmond", BLANCHED_ALMOND),
+    ("blue", BLUE),
+    ("blueviolet", BLUE_VIOLET),
+    ("brown", BROWN),
+    ("burlywood", BURLY_WOOD),
+    ("cadetblue", CADET_BLUE),
+    ("chartreuse", CHARTREUSE),
+    ("chocolate", CHOCOLATE),
+    ("coral", CORAL),
+    ("cornflowerblue", CORNFLOWER_BLUE),
+    ("cornsilk", CORNSILK),
+    ("crimson", CRIMSON),
+    ("cyan", CYAN),
+    ("darkblue", DARK_BLUE),
+    ("darkcyan", DARK_CYAN),
+    ("darkgoldenrod", DARK_GOLDEN_ROD),
+    ("darkgray", DARK_GRAY),
+    ("darkgreen", DARK_GREEN),
+    ("darkkhaki", DARK_KHAKI),
+    ("darkmagenta", DARK_MAGENTA),
+    ("darkolivegreen", DARK_OLIVE_GREEN),
+    ("darkorange", DARK_ORANGE),
+    ("darkorchid", DARK_ORCHID),
+    ("darkred", DARK_RED),
+    ("darksalmon", DARK_SALMON),
+    ("darkseagreen", DARK_SEA_GREEN),
+    ("darkslateblue", DARK_SLATE_BLUE),
+    ("darkslategray", DARK_SLATE_GRAY),
+    ("darkturquoise", DARK_TURQUOISE),
+    ("darkviolet", DARK_VIOLET),
+    ("deeppink", DEEP_PINK),
+    ("deepskyblue", DEEP_SKY_BLUE),
+    ("dimgray", DIM_GRAY),
+    ("dodgerblue", DODGER_BLUE),
+    ("firebrick", FIRE_BRICK),
+    ("floralwhite", FLORAL_WHITE),
+    ("forestgreen", FOREST_GREEN),
+    ("fuchsia", FUCHSIA),
+    ("gainsboro", GAINSBORO),
+    ("ghostwhite", GHOST_WHITE),
+    ("gold", GOLD),
+    ("goldenrod", GOLDEN_ROD),
+    ("gray", GRAY),
+    ("green", GREEN),
+    ("greenyellow", GREEN_YELLOW),
+    ("honeydew", HONEY_DEW),
+    ("hotpink", HOT_PINK),
+    ("indianred", INDIAN_RED),
+    ("indigo", INDIGO),
+    ("ivory", IVORY),
+    ("khaki", KHAKI),
+    ("lavender", LAVENDER),
+    ("lavenderblush", LAVENDER_BLUSH),
+    ("lawngreen", LAWN_GREEN),
+    ("lemonchiffon", LEMON_CHIFFON),
+    ("lightblue", LIGHT_BLUE),
+    ("lightcoral", LIGHT_CORAL),
+    ("lightcyan", LIGHT_CYAN),
+    ("lightgoldenrodyellow", LIGHT_GOLDEN_ROD_YELLOW),
+    ("lightgray", LIGHT_GRAY),
+    ("lightgreen", LIGHT_GREEN),
+    ("lightpink", LIGHT_PINK),
+    ("lightsalmon", LIGHT_SALMON),
+    ("lightseagreen", LIGHT_SEA_GREEN),
+    ("lightskyblue", LIGHT_SKY_BLUE),
+    ("lightslategray", LIGHT_SLATE_GRAY),
+    ("lightsteelblue", LIGHT_STEEL_BLUE),
+    ("lightyellow", LIGHT_YELLOW),
+    ("lime", LIME),
+    ("limegreen", LIME_GREEN),
+    ("linen", LINEN),
+    ("magenta", MAGENTA),
+    ("maroon", MAROON),
+    ("mediumaquamarine", MEDIUM_AQUA_MARINE),
+    ("mediumblue", MEDIUM_BLUE),
+    ("mediumorchid", MEDIUM_ORCHID),
+    ("mediumpurple", MEDIUM_PURPLE),
+    ("mediumseagreen", MEDIUM_SEA_GREEN),
+    ("mediumslateblue", MEDIUM_SLATE_BLUE),
+    ("mediumspringgreen", MEDIUM_SPRING_GREEN),
+    ("mediumturquoise", MEDIUM_TURQUOISE),
+    ("mediumvioletred", MEDIUM_VIOLET_RED),
+    ("midnightblue", MIDNIGHT_BLUE),
+    ("mintcream", MINT_CREAM),
+    ("mistyrose", MISTY_ROSE),
+    ("moccasin", MOCCASIN),
+    ("navajowhite", NAVAJO_WHITE),
+    ("navy", NAVY),
+    ("oldlace", OLD_LACE),
+    ("olive", OLIVE),
+    ("olivedrab", OLIVE_DRAB),
+    ("orange", ORANGE),
+    ("orangered", ORANGE_RED),
+    ("orchid", ORCHID),
+    ("palegoldenrod", PALE_GOLDEN_ROD),
+    ("palegreen", PALE_GREEN),
+    ("paleturquoise", PALE_TURQUOISE),
+    ("palevioletred", PALE_VIOLET_RED),
+    ("papayawhip", PAPAYA_WHIP),
+    ("peachpuff", PEACH_PUFF),
+    ("peru", PERU),
+    ("pink", PINK),
+    ("plum", PLUM),
+    ("powderblue", POWDER_BLUE),
+    ("purple", PURPLE),
+    ("red", RED),
+    ("rosybrown", ROSY_BROWN),
+    ("royalblue", ROYAL_BLUE),
+    ("saddlebrown", SADDLE_BROWN),
+    ("salmon", SALMON),
+    ("sandybrown", SANDY_BROWN),
+    ("seagreen", SEA_GREEN),
+    ("seashell", SEA_SHELL),
+    ("sienna", SIENNA),
+    ("silver", SILVER),
+    ("skyblue", SKY_BLUE),
+    ("slateblue", SLATE_BLUE),
+    ("slategray", SLATE_GRAY),
+    ("snow", SNOW),
+    ("springgreen", SPRING_GREEN),
+    ("steelblue", STEEL_BLUE),
+    ("tan", TAN),
+    ("teal", TEAL),
+    ("thistle", THISTLE),
+    ("tomato", TOMATO),
+    ("turquoise", TURQUOISE),
+    ("violet", VIOLET),
+    ("wheat", WHEAT),
+    ("white", WHITE),
+    ("whitesmoke", WHITE_SMOKE),
+    ("yellow", YELLOW),
+    ("yellowgreen", YELLOW_GREEN),
+];
+
+/// Finds a color by its CSS name, ignoring case.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Rgb, css};
+///
+/// assert_eq!(Some(css::CORNFLOWER_BLUE), css::from_name("CornflowerBlue"));
+/// assert_eq!(Some(Rgb(0, 128, 128)), css::from_name("teal"));
+/// assert_eq!(None, css::from_name("Blue-Green"));
+/// ```
+pub fn from_name(name: &str) -> Option<Rgb> {
+    COLORS
+        .iter()
+        .find(|(css_name, _)| css_name.eq_ignore_ascii_case(name.trim()))
+        .map(|&(_, rgb)| rgb)
+}
+
+/// Finds the CSS name of a color, if it has exactly that value.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Rgb, css};
+///
+/// assert_eq!(Some("tomato"), css::name_of(Rgb(255, 99, 71)));
+/// assert_eq!(Some("aqua"), css::name_of(css::CYAN));
+/// assert_eq!(None, css::name_of(Rgb(1, 2, 3)));
+/// ```
+pub fn name_of(rgb: Rgb) -> Option<&'static str> {
+    COLORS
+        .iter()
+        .find(|&&(_, css_rgb)| css_rgb == rgb)
+        .map(|&(name, _)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_sorted_and_lowercase() {
+        assert!(COLORS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        assert!(
+            COLORS
+                .iter()
+                .all(|(name, _)| name.chars().all(|c| c.is_ascii_lowercase()))
+        );
+    }
+
+    #[test]
+    fn every_name_round_trips() {
+        for (name, rgb) in COLORS {
+            assert_eq!(Some(rgb), from_name(name));
+            assert_eq!(Some(rgb), name_of(rgb).and_then(from_name));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! # Distance
+//!
+//! How different two colors look, to compare them and to find the named color closest to any value.
+
+#[cfg(not(feature = "std"))]
+use crate::art::float::F64Ext;
+use crate::art::kinds::Color;
+use crate::art::rgb::Rgb;
+
+/// The ways of measuring the distance between two colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Metric {
+    /// The straight line distance between the colors in the RGB cube.
+    #[default]
+    Euclidean,
+    /// The "redmean" approximation of how different the colors look to the human eye.
+    ///
+    /// It weights the RGB components according to how red the colors are, since the eye is more sensitive to green
+    /// and its sensitivity to red and blue changes with the amount of red. It's cheap and much closer to perception
+    /// than `Euclidean`, without having to convert to another color space.
+    Perceptual,
+}
+
+impl Metric {
+    /// Measures the distance between two colors according to this metric.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Metric, Rgb};
+    ///
+    /// let black = Rgb(0, 0, 0);
+    /// let white = Rgb(255, 255, 255);
+    ///
+    /// assert_eq!(0.0, Metric::Perceptual.distance(black, black));
+    /// assert!(Metric::Perceptual.distance(black, white) > Metric::Euclidean.distance(black, white));
+    /// ```
+    pub fn distance(&self, a: impl Into<Color>, b: impl Into<Color>) -> f64 {
+        let (a, b) = (a.into().to_rgb(), b.into().to_rgb());
+        let dr = a.0 as f64 - b.0 as f64;
+        let dg = a.1 as f64 - b.1 as f64;
+        let db = a.2 as f64 - b.2 as f64;
+
+        match self {
+            Metric::Euclidean => (dr * dr + dg * dg + db * db).sqrt(),
+            Metric::Perceptual => {
+                let red_mean = (a.0 as f64 + b.0 as f64) / 2.0;
+                ((2.0 + red_mean / 256.0) * dr * dr
+                    + 4.0 * dg * dg
+                    + (2.0 + (255.0 - red_mean) / 256.0) * db * db)
+                    .sqrt()
+            }
+        }
+    }
+
+    /// Finds the named color closest to a color according to this metric.
+    ///
+    /// When two named colors are equally close the first one in `Color::NAMED` wins.
+    pub fn nearest_named(&self, color: impl Into<Color>) -> Color {
+        let rgb = color.into().to_rgb();
+        Color::NAMED
+            .into_iter()
+            .min_by(|a, b| self.distance(rgb, *a).total_cmp(&self.distance(rgb, *b)))
+            .expect("there is at least one named color")
+    }
+}
+
+/// Measures the Euclidean distance between two colors in the RGB cube.
+///
+/// It goes from `0.0` for the same color to about `441.67` between black and white.
+/// Use `Metric::Perceptual` for a distance closer to how different the colors look.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{PrimaryColor, Rgb, distance};
+///
+/// assert_eq!(0.0, distance(PrimaryColor::Red, Rgb(255, 0, 0)));
+/// assert_eq!(255.0, distance(Rgb(0, 0, 0), Rgb(0, 0, 255)));
+/// ```
+pub fn distance(a: impl Into<Color>, b: impl Into<Color>) -> f64 {
+    Metric::Euclidean.distance(a, b)
+}
+
+/// Snaps an RGB value to the closest named color, using the Euclidean distance.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Color, PrimaryColor, Rgb, nearest_named};
+///
+/// assert_eq!(Color::Primary(PrimaryColor::Red), nearest_named(Rgb(230, 20, 10)));
+/// ```
+pub fn nearest_named(rgb: Rgb) -> Color {
+    Metric::Euclidean.nearest_named(rgb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art::{SecondaryColor, TertiaryColor};
+
+    #[test]
+    fn distance_is_symmetric() {
+        let (a, b) = (Rgb(12, 200, 99), Rgb(240, 3, 56));
+
+        for metric in [Metric::Euclidean, Metric::Perceptual] {
+            assert_eq!(metric.distance(a, b), metric.distance(b, a));
+            assert_eq!(0.0, metric.distance(a, a));
+        }
+    }
+
+    #[test]
+    fn named_colors_are_their_own_nearest() {
+        for color in Color::NAMED {
+            assert_eq!(color, nearest_named(color.to_rgb()));
+            assert_eq!(color, Metric::Perceptual.nearest_named(color));
+        }
+    }
+
+    #[test]
+    fn snaps_to_the_closest_named_color() {
+        assert_eq!(
+            Color::Secondary(SecondaryColor::Green),
+            nearest_named(Rgb(10, 120, 20))
+        );
+        assert_eq!(
+            Color::Tertiary(TertiaryColor::RedOrange),
+            Metric::Perceptual.nearest_named(Color::from(TertiaryColor::RedOrange).to_rgb())
+        );
+    }
+}
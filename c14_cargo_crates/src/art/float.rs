@@ -0,0 +1,130 @@
+//! # Float
+//!
+//! The methods of `f64` used by the library that `core` doesn't have, as they come from the math library of the platform.
+//! Without the `std` feature the modules import `F64Ext` and call them with the same names as the inherent methods.
+//! With `std` the inherent methods take precedence, so the trait is only used by the tests comparing it with the standard library.
+//! The other `no_std` crates of the workspace import it from here too, such as `c18_object_oriented_programming`.
+
+/// Replacements for the inherent methods of `f64` that are only available with `std`.
+pub trait F64Ext {
+    /// Rounds to the nearest integer, half-way cases away from `0.0`, like `f64::round`.
+    fn round(self) -> f64;
+
+    /// The non-negative square root, `NaN` for negative numbers, like `f64::sqrt`.
+    fn sqrt(self) -> f64;
+
+    /// The least non-negative remainder of the division by `rhs`, like `f64::rem_euclid`.
+    fn rem_euclid(self, rhs: f64) -> f64;
+}
+
+/// From 2^52 on every `f64` is an integer, so there's nothing to round.
+const INTEGER_ONLY: f64 = 4_503_599_627_370_496.0;
+
+impl F64Ext for f64 {
+    fn round(self) -> f64 {
+        if self.is_nan() || self.abs() >= INTEGER_ONLY {
+            return self;
+        }
+        // The cast truncates towards zero, and the difference with the truncated value is exact in this range
+        let truncated = self as i64 as f64;
+        let fraction = self - truncated;
+        if fraction >= 0.5 {
+            truncated + 1.0
+        } else if fraction <= -0.5 {
+            truncated - 1.0
+        } else {
+            truncated
+        }
+    }
+
+    fn sqrt(self) -> f64 {
+        if self < 0.0 {
+            return f64::NAN;
+        }
+        if self == 0.0 || self.is_nan() || self.is_infinite() {
+            return self;
+        }
+        // Halving the exponent gives a first guess within a factor of 2
+        let mut root = f64::from_bits((self.to_bits() >> 1) + (1023 << 51));
+        // After the first step of Newton's method the guess is never below the root,
+        // and then it decreases at every step until it stops changing
+        root = (root + self / root) / 2.0;
+        loop {
+            let next = (root + self / root) / 2.0;
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    fn rem_euclid(self, rhs: f64) -> f64 {
+        let remainder = self % rhs;
+        if remainder < 0.0 {
+            remainder + rhs.abs()
+        } else {
+            remainder
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [f64; 12] = [
+        0.0,
+        -0.0,
+        0.5,
+        -0.5,
+        1.5,
+        -2.5,
+        0.49999999999999994,
+        127.5,
+        254.99,
+        1e-310,
+        1e300,
+        -7.25,
+    ];
+
+    #[test]
+    fn round_like_std() {
+        for value in VALUES {
+            assert_eq!(F64Ext::round(value), value.round(), "round({value})");
+        }
+        assert!(F64Ext::round(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn sqrt_like_std() {
+        for value in VALUES.into_iter().chain([195_075.0, 3.0, 0.3]) {
+            let expected = value.sqrt();
+            if expected.is_nan() {
+                assert!(F64Ext::sqrt(value).is_nan(), "sqrt({value})");
+            } else {
+                // Newton's method can be one unit in the last place off the correctly rounded root
+                assert!(
+                    (F64Ext::sqrt(value) - expected).abs() <= expected * f64::EPSILON,
+                    "sqrt({value})"
+                );
+            }
+        }
+        assert_eq!(F64Ext::sqrt(f64::INFINITY), f64::INFINITY);
+    }
+
+    #[test]
+    fn rem_euclid_like_std() {
+        for value in VALUES.into_iter().chain([-30.0, 360.0, 725.0, -725.0]) {
+            assert_eq!(
+                F64Ext::rem_euclid(value, 360.0),
+                value.rem_euclid(360.0),
+                "{value} rem 360"
+            );
+            assert_eq!(
+                F64Ext::rem_euclid(value, 2.0),
+                value.rem_euclid(2.0),
+                "{value} rem 2"
+            );
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! # Model
+//!
+//! Color models define how colors combine: paint, light, and ink all mix differently.
+//! Each model implements the `ColorModel` trait, so new ones can be added without changing the color types.
+
+use crate::art::blend::blend_all;
+#[cfg(not(feature = "std"))]
+use crate::art::float::F64Ext;
+use crate::art::kinds::{Color, MixedColor};
+use crate::art::rgb::Rgb;
+
+/// A way of mixing colors.
+pub trait ColorModel {
+    /// Combines two colors in equal amounts according to this model.
+    fn mix(&self, a: Color, b: Color) -> Color;
+}
+
+/// The RYB color model of paint, where the primary colors are red, yellow, and blue.
+///
+/// Mixing two colors made of primary colors gives the named color with the same amounts if there is one,
+/// such as orange for red and yellow. RGB values have no amounts of primary colors, so they are averaged instead.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Color, ColorModel, PrimaryColor, RybModel, SecondaryColor};
+///
+/// let color = RybModel.mix(PrimaryColor::Blue.into(), PrimaryColor::Yellow.into());
+///
+/// assert_eq!(Color::Secondary(SecondaryColor::Green), color);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RybModel;
+
+impl ColorModel for RybModel {
+    fn mix(&self, a: Color, b: Color) -> Color {
+        match (MixedColor::try_from(a), MixedColor::try_from(b)) {
+            (Ok(a), Ok(b)) => {
+                let mixed = a.mix(&b, 0.5);
+                mixed.named().unwrap_or(Color::Mixed(mixed))
+            }
+            _ => Color::Rgb(blend_all([(a, 1.0), (b, 1.0)])),
+        }
+    }
+}
+
+/// The additive RGB color model of light, where colors add up towards white.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Color, ColorModel, Rgb, RgbModel};
+///
+/// let color = RgbModel.mix(Rgb(255, 0, 0).into(), Rgb(0, 255, 0).into());
+///
+/// assert_eq!(Color::Rgb(Rgb(255, 255, 0)), color);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct RgbModel;
+
+impl ColorModel for RgbModel {
+    fn mix(&self, a: Color, b: Color) -> Color {
+        let (a, b) = (a.to_rgb(), b.to_rgb());
+        Color::Rgb(Rgb(
+            a.0.saturating_add(b.0),
+            a.1.saturating_add(b.1),
+            a.2.saturating_add(b.2),
+        ))
+    }
+}
+
+/// A color with its cyan, magenta, yellow, and key (black) inks, according to the CMYK color model.
+///
+/// Each ink goes from `0.0` (none) to `1.0` (full coverage).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cmyk {
+    pub cyan: f64,
+    pub magenta: f64,
+    pub yellow: f64,
+    pub key: f64,
+}
+
+impl From<Rgb> for Cmyk {
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Cmyk, Rgb};
+    ///
+    /// let cmyk = Cmyk::from(Rgb(255, 0, 0));
+    ///
+    /// assert_eq!(Cmyk { cyan: 0.0, magenta: 1.0, yellow: 1.0, key: 0.0 }, cmyk);
+    /// ```
+    fn from(rgb: Rgb) -> Cmyk {
+        let (r, g, b) = (
+            rgb.0 as f64 / 255.0,
+            rgb.1 as f64 / 255.0,
+            rgb.2 as f64 / 255.0,
+        );
+        let key = 1.0 - r.max(g).max(b);
+        if key == 1.0 {
+            return Cmyk {
+                cyan: 0.0,
+                magenta: 0.0,
+                yellow: 0.0,
+                key,
+            };
+        }
+
+        Cmyk {
+            cyan: (1.0 - r - key) / (1.0 - key),
+            magenta: (1.0 - g - key) / (1.0 - key),
+            yellow: (1.0 - b - key) / (1.0 - key),
+            key,
+        }
+    }
+}
+
+impl From<Cmyk> for Rgb {
+    fn from(cmyk: Cmyk) -> Rgb {
+        let channel = |ink: f64| {
+            let light = (1.0 - ink.clamp(0.0, 1.0)) * (1.0 - cmyk.key.clamp(0.0, 1.0));
+            (light * 255.0).round() as u8
+        };
+        Rgb(
+            channel(cmyk.cyan),
+            channel(cmyk.magenta),
+            channel(cmyk.yellow),
+        )
+    }
+}
+
+/// The subtractive CMYK color model of printing inks, where colors overlap towards black.
+///
+/// # Examples
+/// ```
+/// use c14_cargo_crates::art::{Color, ColorModel, CmykModel, Rgb};
+///
+/// let cyan = Rgb(0, 255, 255);
+/// let yellow = Rgb(255, 255, 0);
+///
+/// assert_eq!(Color::Rgb(Rgb(0, 255, 0)), CmykModel.mix(cyan.into(), yellow.into()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct CmykModel;
+
+impl ColorModel for CmykModel {
+    fn mix(&self, a: Color, b: Color) -> Color {
+        let (a, b) = (Cmyk::from(a.to_rgb()), Cmyk::from(b.to_rgb()));
+        // Each ink lets through a fraction of the light, so overlapping inks let through the product of the fractions
+        let overlap = |a: f64, b: f64| 1.0 - (1.0 - a) * (1.0 - b);
+        Color::Rgb(Rgb::from(Cmyk {
+            cyan: overlap(a.cyan, b.cyan),
+            magenta: overlap(a.magenta, b.magenta),
+            yellow: overlap(a.yellow, b.yellow),
+            key: overlap(a.key, b.key),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art::{PrimaryColor, SecondaryColor};
+
+    /// A model that always gives the first color, to check that new models plug in.
+    struct First;
+
+    impl ColorModel for First {
+        fn mix(&self, a: Color, _: Color) -> Color {
+            a
+        }
+    }
+
+    #[test]
+    fn models_can_be_used_interchangeably() {
+        let models: Vec<Box<dyn ColorModel>> = vec![
+            Box::new(RybModel),
+            Box::new(RgbModel),
+            Box::new(CmykModel),
+            Box::new(First),
+        ];
+        let (red, blue) = (
+            Color::from(PrimaryColor::Red),
+            Color::from(PrimaryColor::Blue),
+        );
+        let mixes: Vec<Color> = models.iter().map(|model| model.mix(red, blue)).collect();
+
+        assert_eq!(
+            vec![
+                Color::Secondary(SecondaryColor::Purple),
+                Color::Rgb(Rgb(255, 0, 255)),
+                Color::Rgb(Rgb(0, 0, 0)),
+                red,
+            ],
+            mixes
+        );
+    }
+
+    #[test]
+    fn ryb_averages_rgb_values() {
+        let color = RybModel.mix(Rgb(200, 0, 0).into(), PrimaryColor::Blue.into());
+
+        assert_eq!(Color::Rgb(Rgb(100, 0, 128)), color);
+    }
+
+    #[test]
+    fn cmyk_round_trip() {
+        for rgb in [
+            Rgb(0, 0, 0),
+            Rgb(255, 255, 255),
+            Rgb(255, 165, 0),
+            Rgb(12, 34, 56),
+        ] {
+            assert_eq!(rgb, Rgb::from(Cmyk::from(rgb)));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! # Palette
+//!
+//! Sets of colors that go well together, generated from a base color with a color scheme.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::{slice, vec};
+
+use crate::art::kinds::Color;
+use crate::art::rgb::{Hsl, Rgb};
+
+/// The ways of choosing the colors of a palette from the base color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scheme {
+    /// The base color with two darker and two lighter shades of it.
+    Monochrome,
+    /// The base color with the two colors evenly spaced around the color wheel (120° apart).
+    Triadic,
+    /// The base color with the two colors adjacent to its complement (150° and 210° away).
+    SplitComplementary,
+}
+
+/// An ordered list of colors, starting from the base color it was generated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: Vec<Rgb>,
+}
+
+impl Palette {
+    /// Generates the palette of a base color according to a scheme.
+    ///
+    /// The first color is always the base color. The other ones follow the color wheel for
+    /// `Triadic` and `SplitComplementary`, and go from the darkest to the lightest for `Monochrome`.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Palette, PrimaryColor, Scheme};
+    ///
+    /// let palette = Palette::generate(PrimaryColor::Red, Scheme::Triadic);
+    ///
+    /// assert_eq!(vec!["#FF0000", "#00FF00", "#0000FF"], palette.to_hex());
+    /// ```
+    pub fn generate(base: impl Into<Color>, scheme: Scheme) -> Palette {
+        let base = base.into().to_rgb();
+        let hsl = base.to_hsl();
+        let rotate = |degrees: f64| {
+            Rgb::from(Hsl {
+                hue: hsl.hue + degrees,
+                ..hsl
+            })
+        };
+        let shade = |delta: f64| {
+            Rgb::from(Hsl {
+                lightness: (hsl.lightness + delta).clamp(0.0, 1.0),
+                ..hsl
+            })
+        };
+
+        let others = match scheme {
+            Scheme::Monochrome => vec![shade(-0.3), shade(-0.15), shade(0.15), shade(0.3)],
+            Scheme::Triadic => vec![rotate(120.0), rotate(240.0)],
+            Scheme::SplitComplementary => vec![rotate(150.0), rotate(210.0)],
+        };
+
+        let mut colors = vec![base];
+        colors.extend(others);
+        Palette { colors }
+    }
+
+    /// Returns the base color the palette was generated from.
+    pub fn base(&self) -> Rgb {
+        self.colors[0]
+    }
+
+    /// Returns the colors of the palette, in order.
+    pub fn colors(&self) -> &[Rgb] {
+        &self.colors
+    }
+
+    /// Returns an iterator over the colors of the palette, in order.
+    pub fn iter(&self) -> slice::Iter<'_, Rgb> {
+        self.colors.iter()
+    }
+
+    /// Creates a palette from colors that are already chosen, the first being the base color.
+    ///
+    /// Returns `None` if there are no colors, since a palette always has a base color.
+    #[cfg(feature = "serde")]
+    pub(crate) fn from_colors(colors: Vec<Rgb>) -> Option<Palette> {
+        if colors.is_empty() {
+            None
+        } else {
+            Some(Palette { colors })
+        }
+    }
+
+    /// Renders every color of the palette as a hexadecimal string, such as `#FF0000`.
+    pub fn to_hex(&self) -> Vec<String> {
+        self.iter().map(Rgb::to_hex).collect()
+    }
+}
+
+impl IntoIterator for Palette {
+    type Item = Rgb;
+    type IntoIter = vec::IntoIter<Rgb>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.colors.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Palette {
+    type Item = &'a Rgb;
+    type IntoIter = slice::Iter<'a, Rgb>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art::PrimaryColor;
+
+    #[test]
+    fn split_complementary_of_blue() {
+        let palette = Palette::generate(PrimaryColor::Blue, Scheme::SplitComplementary);
+
+        assert_eq!(vec!["#0000FF", "#FF8000", "#80FF00"], palette.to_hex(),);
+    }
+
+    #[test]
+    fn monochrome_goes_from_dark_to_light() {
+        let palette = Palette::generate(PrimaryColor::Blue, Scheme::Monochrome);
+        let base = palette.base().to_hsl();
+        let lightness: Vec<f64> = palette
+            .iter()
+            .skip(1)
+            .map(|c| c.to_hsl().lightness)
+            .collect();
+
+        assert_eq!(5, palette.colors().len());
+        assert!(lightness.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(lightness[1] < base.lightness && base.lightness < lightness[2]);
+        for color in &palette {
+            assert!((color.to_hsl().hue - base.hue).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn palette_can_be_consumed() {
+        let palette = Palette::generate(Rgb(10, 20, 30), Scheme::Triadic);
+        let colors: Vec<Rgb> = palette.into_iter().collect();
+
+        assert_eq!(Rgb(10, 20, 30), colors[0]);
+        assert_eq!(3, colors.len());
+    }
+}
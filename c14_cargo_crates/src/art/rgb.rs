@@ -0,0 +1,261 @@
+//! # Rgb
+//!
+//! Real color values, to make the RYB color model interoperate with screens and files.
+
+use alloc::format;
+use alloc::string::String;
+
+#[cfg(not(feature = "std"))]
+use crate::art::float::F64Ext;
+use crate::art::kinds::{MixedColor, PrimaryColor, SecondaryColor};
+
+/// A color with its red, green, and blue components, according to the RGB color model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+/// A color with its hue, saturation, and lightness, according to the HSL color model.
+///
+/// * `hue` - The angle on the color wheel, in degrees between `0.0` and `360.0`.
+/// * `saturation` - The intensity of the color, between `0.0` (gray) and `1.0`.
+/// * `lightness` - The brightness of the color, between `0.0` (black) and `1.0` (white).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+/// The corners of the RYB cube in RGB, used to convert mixes of primary colors.
+///
+/// The index of each corner is made of the bits of red, yellow, and blue, in this order.
+const RYB_CORNERS: [(f64, f64, f64); 8] = [
+    (255.0, 255.0, 255.0), // no color: white
+    (0.0, 0.0, 255.0),     // blue
+    (255.0, 255.0, 0.0),   // yellow
+    (0.0, 128.0, 0.0),     // green
+    (255.0, 0.0, 0.0),     // red
+    (128.0, 0.0, 128.0),   // purple
+    (255.0, 165.0, 0.0),   // orange
+    (0.0, 0.0, 0.0),       // all the colors: black
+];
+
+impl Rgb {
+    /// Converts the color to the HSL color model.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Hsl, Rgb};
+    ///
+    /// let hsl = Rgb(255, 0, 0).to_hsl();
+    ///
+    /// assert_eq!(Hsl { hue: 0.0, saturation: 1.0, lightness: 0.5 }, hsl);
+    /// ```
+    pub fn to_hsl(&self) -> Hsl {
+        let r = self.0 as f64 / 255.0;
+        let g = self.1 as f64 / 255.0;
+        let b = self.2 as f64 / 255.0;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+
+        if delta == 0.0 {
+            return Hsl {
+                hue: 0.0,
+                saturation: 0.0,
+                lightness,
+            };
+        }
+
+        let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+        let hue = if max == r {
+            60.0 * ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / delta + 2.0)
+        } else {
+            60.0 * ((r - g) / delta + 4.0)
+        };
+
+        Hsl {
+            hue,
+            saturation,
+            lightness,
+        }
+    }
+
+    /// Creates a color from its HSL representation.
+    ///
+    /// The hue wraps around the color wheel, while saturation and lightness are clamped between `0.0` and `1.0`.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Hsl, Rgb};
+    ///
+    /// let color = Rgb::from_hsl(Hsl { hue: 240.0, saturation: 1.0, lightness: 0.5 });
+    ///
+    /// assert_eq!(Rgb(0, 0, 255), color);
+    /// ```
+    pub fn from_hsl(hsl: Hsl) -> Rgb {
+        let hue = hsl.hue.rem_euclid(360.0);
+        let saturation = hsl.saturation.clamp(0.0, 1.0);
+        let lightness = hsl.lightness.clamp(0.0, 1.0);
+
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - ((hue / 60.0).rem_euclid(2.0) - 1.0).abs());
+        let m = lightness - chroma / 2.0;
+        let (r, g, b) = match hue as u32 / 60 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+
+        Rgb(to_channel(r + m), to_channel(g + m), to_channel(b + m))
+    }
+}
+
+impl Rgb {
+    /// Renders the color as a hexadecimal string, such as `#FF0000`.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::Rgb;
+    ///
+    /// assert_eq!("#FFA500", Rgb(255, 165, 0).to_hex());
+    /// ```
+    pub fn to_hex(&self) -> String {
+        format!("#{:02X}{:02X}{:02X}", self.0, self.1, self.2)
+    }
+}
+
+/// Converts a component between `0.0` and `1.0` to a `u8` channel.
+fn to_channel(value: f64) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+impl From<Hsl> for Rgb {
+    fn from(hsl: Hsl) -> Rgb {
+        Rgb::from_hsl(hsl)
+    }
+}
+
+impl From<Rgb> for Hsl {
+    fn from(rgb: Rgb) -> Hsl {
+        rgb.to_hsl()
+    }
+}
+
+impl From<MixedColor> for Rgb {
+    /// Converts a mix of primary colors by interpolating the corners of the RYB cube.
+    ///
+    /// The amounts are scaled so the most present color is at its full intensity, so equal mixes land on the corners.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{PrimaryColor, Rgb, mix_ratio};
+    ///
+    /// let color = Rgb::from(mix_ratio(&PrimaryColor::Red, &PrimaryColor::Yellow, 0.5));
+    ///
+    /// assert_eq!(Rgb(255, 165, 0), color);
+    /// ```
+    fn from(color: MixedColor) -> Rgb {
+        let amounts = [
+            color.amount(PrimaryColor::Red),
+            color.amount(PrimaryColor::Yellow),
+            color.amount(PrimaryColor::Blue),
+        ];
+        let max = amounts.iter().cloned().fold(0.0, f64::max);
+        let [r, y, b] = amounts.map(|amount| amount / max);
+
+        let mut rgb = (0.0, 0.0, 0.0);
+        for (index, corner) in RYB_CORNERS.iter().enumerate() {
+            let weight = |bit: usize, amount: f64| {
+                if index & bit != 0 {
+                    amount
+                } else {
+                    1.0 - amount
+                }
+            };
+            let weight = weight(4, r) * weight(2, y) * weight(1, b);
+            rgb.0 += corner.0 * weight;
+            rgb.1 += corner.1 * weight;
+            rgb.2 += corner.2 * weight;
+        }
+
+        Rgb(
+            to_channel(rgb.0 / 255.0),
+            to_channel(rgb.1 / 255.0),
+            to_channel(rgb.2 / 255.0),
+        )
+    }
+}
+
+impl From<PrimaryColor> for Rgb {
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{PrimaryColor, Rgb};
+    ///
+    /// assert_eq!(Rgb(255, 0, 0), Rgb::from(PrimaryColor::Red));
+    /// assert_eq!(Rgb(255, 255, 0), Rgb::from(PrimaryColor::Yellow));
+    /// assert_eq!(Rgb(0, 0, 255), Rgb::from(PrimaryColor::Blue));
+    /// ```
+    fn from(color: PrimaryColor) -> Rgb {
+        Rgb::from(MixedColor::from(color))
+    }
+}
+
+impl From<SecondaryColor> for Rgb {
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Rgb, SecondaryColor};
+    ///
+    /// assert_eq!(Rgb(255, 165, 0), Rgb::from(SecondaryColor::Orange));
+    /// assert_eq!(Rgb(0, 128, 0), Rgb::from(SecondaryColor::Green));
+    /// assert_eq!(Rgb(128, 0, 128), Rgb::from(SecondaryColor::Purple));
+    /// ```
+    fn from(color: SecondaryColor) -> Rgb {
+        Rgb::from(MixedColor::from(color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsl_round_trip() {
+        for rgb in [
+            Rgb(0, 0, 0),
+            Rgb(255, 255, 255),
+            Rgb(128, 128, 128),
+            Rgb(255, 165, 0),
+            Rgb(12, 200, 99),
+            Rgb(40, 10, 250),
+            Rgb(250, 10, 140),
+        ] {
+            assert_eq!(rgb, Rgb::from(Hsl::from(rgb)));
+        }
+    }
+
+    #[test]
+    fn hsl_wraps_and_clamps() {
+        let hsl = Hsl {
+            hue: 480.0,
+            saturation: 2.0,
+            lightness: 0.5,
+        };
+
+        assert_eq!(Rgb(0, 255, 0), Rgb::from_hsl(hsl));
+    }
+
+    #[test]
+    fn partial_mix_is_between_the_primaries() {
+        let Rgb(r, g, b) = Rgb::from(MixedColor::new(0.7, 0.0, 0.3));
+
+        assert!(r > b && b > 0, "got {r}, {g}, {b}");
+        assert_eq!(0, g);
+    }
+}
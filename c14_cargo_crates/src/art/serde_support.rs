@@ -0,0 +1,174 @@
+//! `Serialize`/`Deserialize` for the art types, enabled by the `serde` feature.
+//!
+//! Colors are stored the way a person would write them rather than as numeric variant tags:
+//! named colors by their name, RGB values as hex strings, and partial mixes as the amounts of each primary color.
+//! So a palette is just a list of hex strings, such as `["#FF0000", "#00FF00", "#0000FF"]`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::art::kinds::{Color, MixedColor, PrimaryColor, SecondaryColor, TertiaryColor};
+use crate::art::palette::Palette;
+use crate::art::rgb::Rgb;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+/// Implements `Serialize` with `Display` and `Deserialize` with `FromStr`, for types written as a single string.
+macro_rules! as_string {
+    ($($ty:ty),*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    String::deserialize(deserializer)?
+                        .parse()
+                        .map_err(D::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+as_string!(PrimaryColor, SecondaryColor, TertiaryColor, Rgb);
+
+#[derive(Serialize, Deserialize)]
+struct StoredMix {
+    red: f64,
+    yellow: f64,
+    blue: f64,
+}
+
+impl Serialize for MixedColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        StoredMix {
+            red: self.amount(PrimaryColor::Red),
+            yellow: self.amount(PrimaryColor::Yellow),
+            blue: self.amount(PrimaryColor::Blue),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MixedColor {
+    /// The amounts are normalised like in `MixedColor::new`, so `{"red": 3, "yellow": 0, "blue": 1}` is valid.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let stored = StoredMix::deserialize(deserializer)?;
+        Ok(MixedColor::new(stored.red, stored.yellow, stored.blue))
+    }
+}
+
+/// A color as written in a file: either a name or hex string, or the amounts of a partial mix.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredColor {
+    Text(String),
+    Mixed(MixedColor),
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Color::Mixed(color) => color.serialize(serializer),
+            _ => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match StoredColor::deserialize(deserializer)? {
+            StoredColor::Text(text) => text.parse().map_err(D::Error::custom),
+            StoredColor::Mixed(color) => Ok(Color::Mixed(color)),
+        }
+    }
+}
+
+impl Serialize for Palette {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Palette {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let colors = Vec::<Rgb>::deserialize(deserializer)?;
+        Palette::from_colors(colors)
+            .ok_or_else(|| D::Error::custom("a palette needs at least the base color"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::art::{Hsl, Scheme};
+
+    #[test]
+    fn named_colors_are_stored_by_name() {
+        assert_eq!(
+            r#""Red""#,
+            serde_json::to_string(&PrimaryColor::Red).unwrap()
+        );
+        assert_eq!(
+            r#""Blue-Green""#,
+            serde_json::to_string(&Color::Tertiary(TertiaryColor::BlueGreen)).unwrap()
+        );
+        assert_eq!(
+            SecondaryColor::Purple,
+            serde_json::from_str(r#""purple""#).unwrap()
+        );
+        assert!(serde_json::from_str::<PrimaryColor>(r#""Teal""#).is_err());
+    }
+
+    #[test]
+    fn rgb_is_stored_as_hex() {
+        assert_eq!(
+            r##""#FFA500""##,
+            serde_json::to_string(&Rgb(255, 165, 0)).unwrap()
+        );
+        assert_eq!(Rgb(255, 0, 0), serde_json::from_str(r##""#F00""##).unwrap());
+        assert_eq!(
+            Color::Rgb(Rgb(1, 2, 3)),
+            serde_json::from_str(r##""#010203""##).unwrap()
+        );
+    }
+
+    #[test]
+    fn mixes_are_stored_as_amounts() {
+        let color = Color::Mixed(MixedColor::new(3.0, 0.0, 1.0));
+        let json = serde_json::to_string(&color).unwrap();
+
+        assert_eq!(r#"{"red":0.75,"yellow":0.0,"blue":0.25}"#, json);
+        assert_eq!(color, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn palette_round_trip() {
+        let palette = Palette::generate(PrimaryColor::Red, Scheme::Triadic);
+        let json = serde_json::to_string(&palette).unwrap();
+
+        assert_eq!(r##"["#FF0000","#00FF00","#0000FF"]"##, json);
+        assert_eq!(palette, serde_json::from_str(&json).unwrap());
+        assert!(serde_json::from_str::<Palette>("[]").is_err());
+    }
+
+    #[test]
+    fn scheme_and_hsl_use_field_names() {
+        assert_eq!(
+            r#""SplitComplementary""#,
+            serde_json::to_string(&Scheme::SplitComplementary).unwrap()
+        );
+        let hsl = Hsl {
+            hue: 120.0,
+            saturation: 1.0,
+            lightness: 0.5,
+        };
+        assert_eq!(
+            r#"{"hue":120.0,"saturation":1.0,"lightness":0.5}"#,
+            serde_json::to_string(&hsl).unwrap()
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! # Text
+//!
+//! Text representation of the colors, so they can round-trip through configuration files and command lines.
+//!
+//! Named colors are displayed with their name, such as `Red`, and RGB values as hexadecimal strings, such as `#FF0000`.
+//! Parsing accepts the names in any case, and hexadecimal codes in the long (`#FF0000`) or short (`#F00`) form.
+//! RGB values can also be parsed from the CSS color names, such as `tomato`.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+use crate::art::css;
+use crate::art::kinds::{Color, MixedColor, PrimaryColor, SecondaryColor, TertiaryColor};
+use crate::art::rgb::Rgb;
+
+/// Error returned when a string doesn't represent a color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string is empty or only contains whitespace.
+    Empty,
+    /// The string is not a known color name.
+    UnknownName {
+        name: String,
+        expected: &'static str,
+    },
+    /// The string starts with `#` but is not a valid hexadecimal color.
+    InvalidHex(String),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseColorError::Empty => write!(f, "empty color"),
+            ParseColorError::UnknownName { name, expected } => {
+                write!(f, "unknown color `{name}`, expected {expected}")
+            }
+            ParseColorError::InvalidHex(hex) => write!(
+                f,
+                "invalid hex color `{hex}`, expected `#` followed by 3 or 6 hexadecimal digits"
+            ),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+/// Trims the string, failing if nothing is left.
+fn trimmed(s: &str) -> Result<&str, ParseColorError> {
+    match s.trim() {
+        "" => Err(ParseColorError::Empty),
+        s => Ok(s),
+    }
+}
+
+impl fmt::Display for PrimaryColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PrimaryColor::Red => "Red",
+            PrimaryColor::Yellow => "Yellow",
+            PrimaryColor::Blue => "Blue",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for PrimaryColor {
+    type Err = ParseColorError;
+
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::PrimaryColor;
+    ///
+    /// assert_eq!(Ok(PrimaryColor::Blue), "blue".parse());
+    /// assert!("Green".parse::<PrimaryColor>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<PrimaryColor, ParseColorError> {
+        let s = trimmed(s)?;
+        match s.to_lowercase().as_str() {
+            "red" => Ok(PrimaryColor::Red),
+            "yellow" => Ok(PrimaryColor::Yellow),
+            "blue" => Ok(PrimaryColor::Blue),
+            _ => Err(ParseColorError::UnknownName {
+                name: s.to_string(),
+                expected: "one of Red, Yellow, Blue",
+            }),
+        }
+    }
+}
+
+impl fmt::Display for SecondaryColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            SecondaryColor::Orange => "Orange",
+            SecondaryColor::Green => "Green",
+            SecondaryColor::Purple => "Purple",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for SecondaryColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<SecondaryColor, ParseColorError> {
+        let s = trimmed(s)?;
+        match s.to_lowercase().as_str() {
+            "orange" => Ok(SecondaryColor::Orange),
+            "green" => Ok(SecondaryColor::Green),
+            "purple" => Ok(SecondaryColor::Purple),
+            _ => Err(ParseColorError::UnknownName {
+                name: s.to_string(),
+                expected: "one of Orange, Green, Purple",
+            }),
+        }
+    }
+}
+
+impl fmt::Display for TertiaryColor {
+    /// Displays the name of the two colors it's made of, such as `Red-Orange`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            TertiaryColor::RedOrange => "Red-Orange",
+            TertiaryColor::YellowOrange => "Yellow-Orange",
+            TertiaryColor::YellowGreen => "Yellow-Green",
+            TertiaryColor::BlueGreen => "Blue-Green",
+            TertiaryColor::BluePurple => "Blue-Purple",
+            TertiaryColor::RedPurple => "Red-Purple",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for TertiaryColor {
+    type Err = ParseColorError;
+
+    /// Parses the name ignoring case and separators, so `Red-Orange`, `red orange`, and `RedOrange` are the same.
+    fn from_str(s: &str) -> Result<TertiaryColor, ParseColorError> {
+        let s = trimmed(s)?;
+        let name: String = s
+            .chars()
+            .filter(|c| !matches!(c, '-' | ' ' | '_'))
+            .collect();
+        match name.to_lowercase().as_str() {
+            "redorange" => Ok(TertiaryColor::RedOrange),
+            "yelloworange" => Ok(TertiaryColor::YellowOrange),
+            "yellowgreen" => Ok(TertiaryColor::YellowGreen),
+            "bluegreen" => Ok(TertiaryColor::BlueGreen),
+            "bluepurple" => Ok(TertiaryColor::BluePurple),
+            "redpurple" => Ok(TertiaryColor::RedPurple),
+            _ => Err(ParseColorError::UnknownName {
+                name: s.to_string(),
+                expected: "one of Red-Orange, Yellow-Orange, Yellow-Green, Blue-Green, Blue-Purple, Red-Purple",
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Rgb {
+    /// Displays the color as a hexadecimal string, such as `#FF0000`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = ParseColorError;
+
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::Rgb;
+    ///
+    /// assert_eq!(Ok(Rgb(255, 165, 0)), "#FFA500".parse());
+    /// assert_eq!(Ok(Rgb(255, 0, 0)), "#f00".parse());
+    /// assert_eq!(Ok(Rgb(255, 99, 71)), "Tomato".parse());
+    /// assert_eq!(
+    ///     "invalid hex color `#FFA50`, expected `#` followed by 3 or 6 hexadecimal digits",
+    ///     "#FFA50".parse::<Rgb>().unwrap_err().to_string()
+    /// );
+    /// ```
+    fn from_str(s: &str) -> Result<Rgb, ParseColorError> {
+        let s = trimmed(s)?;
+        let invalid = || ParseColorError::InvalidHex(s.to_string());
+        let Some(digits) = s.strip_prefix('#') else {
+            // Only hexadecimal digits are most likely a hex code missing the `#`
+            if s.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            return css::from_name(s).ok_or_else(|| ParseColorError::UnknownName {
+                name: s.to_string(),
+                expected: "a CSS color name, or a hex code like #FF0000",
+            });
+        };
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |hex: &str| u8::from_str_radix(hex, 16).map_err(|_| invalid());
+
+        match digits.len() {
+            // Each digit of the short form is repeated, so `#F00` is `#FF0000`
+            3 => Ok(Rgb(
+                channel(&digits[0..1].repeat(2))?,
+                channel(&digits[1..2].repeat(2))?,
+                channel(&digits[2..3].repeat(2))?,
+            )),
+            6 => Ok(Rgb(
+                channel(&digits[0..2])?,
+                channel(&digits[2..4])?,
+                channel(&digits[4..6])?,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl fmt::Display for MixedColor {
+    /// Displays the amounts of the primary colors, such as `70% Red, 30% Blue`, skipping the missing ones.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let parts: Vec<String> = [PrimaryColor::Red, PrimaryColor::Yellow, PrimaryColor::Blue]
+            .into_iter()
+            .filter(|&color| self.amount(color) > 0.0)
+            .map(|color| format!("{:.0}% {color}", self.amount(color) * 100.0))
+            .collect();
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Primary(color) => write!(f, "{color}"),
+            Color::Secondary(color) => write!(f, "{color}"),
+            Color::Tertiary(color) => write!(f, "{color}"),
+            Color::Mixed(color) => write!(f, "{color}"),
+            Color::Rgb(color) => write!(f, "{color}"),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parses a primary, secondary, or tertiary color name, or a hexadecimal RGB value.
+    ///
+    /// CSS color names are parsed as RGB values, unless they are also the name of a color of the RYB model.
+    ///
+    /// # Examples
+    /// ```
+    /// use c14_cargo_crates::art::{Color, PrimaryColor, Rgb, SecondaryColor};
+    ///
+    /// assert_eq!(Ok(Color::Primary(PrimaryColor::Red)), "Red".parse());
+    /// assert_eq!(Ok(Color::Secondary(SecondaryColor::Purple)), "PURPLE".parse());
+    /// assert_eq!(Ok(Color::Rgb(Rgb(18, 52, 86))), "#123456".parse());
+    /// assert_eq!(Ok(Color::Rgb(Rgb(0, 128, 128))), "teal".parse());
+    /// ```
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let s = trimmed(s)?;
+        if s.starts_with('#') {
+            return s.parse().map(Color::Rgb);
+        }
+        s.parse()
+            .map(Color::Primary)
+            .or_else(|_| s.parse().map(Color::Secondary))
+            .or_else(|_| s.parse().map(Color::Tertiary))
+            .or_else(|_| css::from_name(s).map(Color::Rgb).ok_or(()))
+            .map_err(|_| ParseColorError::UnknownName {
+                name: s.to_string(),
+                expected: "a color name, or a hex code like #FF0000",
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_round_trip() {
+        for color in [PrimaryColor::Red, PrimaryColor::Yellow, PrimaryColor::Blue] {
+            assert_eq!(Ok(color), color.to_string().parse());
+        }
+        for color in [
+            SecondaryColor::Orange,
+            SecondaryColor::Green,
+            SecondaryColor::Purple,
+        ] {
+            assert_eq!(Ok(color), color.to_string().parse());
+            assert_eq!(Ok(Color::Secondary(color)), color.to_string().parse());
+        }
+        for color in [TertiaryColor::RedOrange, TertiaryColor::BluePurple] {
+            assert_eq!(Ok(color), color.to_string().parse());
+            assert_eq!(Ok(Color::Tertiary(color)), color.to_string().parse());
+        }
+        assert_eq!(Ok(TertiaryColor::YellowGreen), "yellow green".parse());
+    }
+
+    #[test]
+    fn rgb_round_trip() {
+        for rgb in [Rgb(0, 0, 0), Rgb(255, 255, 255), Rgb(18, 52, 86)] {
+            assert_eq!(Ok(rgb), rgb.to_string().parse());
+            assert_eq!(Ok(Color::Rgb(rgb)), Color::Rgb(rgb).to_string().parse());
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Err(ParseColorError::Empty), "  ".parse::<Color>());
+        assert_eq!(
+            "unknown color `Redd`, expected one of Red, Yellow, Blue",
+            "Redd".parse::<PrimaryColor>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            "unknown color `vermilion`, expected a color name, or a hex code like #FF0000",
+            "vermilion".parse::<Color>().unwrap_err().to_string()
+        );
+        assert_eq!(
+            Err(ParseColorError::InvalidHex(String::from("#GG0000"))),
+            "#GG0000".parse::<Rgb>()
+        );
+        assert_eq!(
+            Err(ParseColorError::InvalidHex(String::from("FF0000"))),
+            "FF0000".parse::<Rgb>()
+        );
+        assert_eq!(
+            "unknown color `vermilion`, expected a CSS color name, or a hex code like #FF0000",
+            "vermilion".parse::<Rgb>().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn mixed_color_display() {
+        assert_eq!(
+            "70% Red, 30% Blue",
+            MixedColor::new(0.7, 0.0, 0.3).to_string()
+        );
+    }
+}
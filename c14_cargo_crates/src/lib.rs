@@ -1,6 +1,12 @@
 //! # c14_cargo_crates
 //!
 //! `c14_cargo_crates` is a package that exaplain how crates and docs work
+//!
+//! Without the `std` feature, enabled by default, the library is `no_std` and only needs `alloc`.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 /// Adds one to the number given.
 ///
@@ -34,13 +40,35 @@ pub mod art {
     //! A library for modeling artistic concepts.
 
     // Add `pub use` to re-export the items at top level
+    pub use self::blend::Gradient;
+    pub use self::blend::blend_all;
+    pub use self::blend::gradient;
+    pub use self::distance::Metric;
+    pub use self::distance::distance;
+    pub use self::distance::nearest_named;
+    pub use self::kinds::Color;
+    pub use self::kinds::MixedColor;
     pub use self::kinds::PrimaryColor;
     pub use self::kinds::SecondaryColor;
+    pub use self::kinds::TertiaryColor;
+    pub use self::model::Cmyk;
+    pub use self::model::CmykModel;
+    pub use self::model::ColorModel;
+    pub use self::model::RgbModel;
+    pub use self::model::RybModel;
+    pub use self::palette::Palette;
+    pub use self::palette::Scheme;
+    pub use self::rgb::Hsl;
+    pub use self::rgb::Rgb;
+    pub use self::text::ParseColorError;
     pub use self::utils::mix;
+    pub use self::utils::mix_ratio;
 
     pub mod kinds {
+        use crate::art::rgb::Rgb;
+
         /// The primary colors according to the RYB color model.
-        #[derive(Debug)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum PrimaryColor {
             Red,
             Yellow,
@@ -48,29 +76,312 @@ pub mod art {
         }
 
         /// The secondary colors according to the RYB color model.
-        #[derive(Debug)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
         pub enum SecondaryColor {
             Orange,
             Green,
             Purple,
         }
+
+        /// The tertiary colors according to the RYB color model.
+        ///
+        /// Each of them is made of a primary color and the secondary color next to it on the color wheel, in equal amounts.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum TertiaryColor {
+            RedOrange,
+            YellowOrange,
+            YellowGreen,
+            BlueGreen,
+            BluePurple,
+            RedPurple,
+        }
+
+        /// A color made of arbitrary amounts of the primary colors of the RYB color model.
+        ///
+        /// The amounts are fractions of the whole color, so they always add up to `1.0`.
+        /// It represents partial mixes, such as 70% red and 30% blue, that don't have a name.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct MixedColor {
+            red: f64,
+            yellow: f64,
+            blue: f64,
+        }
+
+        impl MixedColor {
+            /// Creates a color from the amounts of each primary color, normalised so they add up to `1.0`.
+            ///
+            /// Negative amounts are treated as `0.0`, and if all the amounts are `0.0` the color is an equal mix of the three.
+            ///
+            /// # Examples
+            /// ```
+            /// use c14_cargo_crates::art::{MixedColor, PrimaryColor};
+            ///
+            /// let color = MixedColor::new(3.0, 0.0, 1.0);
+            ///
+            /// assert_eq!(0.75, color.amount(PrimaryColor::Red));
+            /// assert_eq!(0.25, color.amount(PrimaryColor::Blue));
+            /// ```
+            pub fn new(red: f64, yellow: f64, blue: f64) -> MixedColor {
+                let (red, yellow, blue) = (red.max(0.0), yellow.max(0.0), blue.max(0.0));
+                let total = red + yellow + blue;
+                if total == 0.0 {
+                    return MixedColor::new(1.0, 1.0, 1.0);
+                }
+                MixedColor {
+                    red: red / total,
+                    yellow: yellow / total,
+                    blue: blue / total,
+                }
+            }
+
+            /// Returns the fraction of the color made of the given primary color.
+            pub fn amount(&self, color: PrimaryColor) -> f64 {
+                match color {
+                    PrimaryColor::Red => self.red,
+                    PrimaryColor::Yellow => self.yellow,
+                    PrimaryColor::Blue => self.blue,
+                }
+            }
+
+            /// Returns the named color with the same amounts of primary colors, if there is one.
+            ///
+            /// # Examples
+            /// ```
+            /// use c14_cargo_crates::art::{Color, MixedColor, TertiaryColor};
+            ///
+            /// assert_eq!(
+            ///     Some(Color::Tertiary(TertiaryColor::RedOrange)),
+            ///     MixedColor::new(3.0, 1.0, 0.0).named()
+            /// );
+            /// assert_eq!(None, MixedColor::new(0.7, 0.0, 0.3).named());
+            /// ```
+            pub fn named(&self) -> Option<Color> {
+                let is_close = |other: MixedColor| {
+                    (self.red - other.red).abs() < 1e-9
+                        && (self.yellow - other.yellow).abs() < 1e-9
+                        && (self.blue - other.blue).abs() < 1e-9
+                };
+
+                Color::NAMED
+                    .into_iter()
+                    .find(|&color| MixedColor::try_from(color).is_ok_and(is_close))
+            }
+
+            /// Mixes this color with another one, `ratio` being the fraction of this color in the result.
+            ///
+            /// The `ratio` is clamped between `0.0` and `1.0`.
+            pub fn mix(&self, other: &MixedColor, ratio: f64) -> MixedColor {
+                let ratio = ratio.clamp(0.0, 1.0);
+                MixedColor::new(
+                    self.red * ratio + other.red * (1.0 - ratio),
+                    self.yellow * ratio + other.yellow * (1.0 - ratio),
+                    self.blue * ratio + other.blue * (1.0 - ratio),
+                )
+            }
+        }
+
+        impl From<PrimaryColor> for MixedColor {
+            /// A primary color is made entirely of itself.
+            fn from(color: PrimaryColor) -> MixedColor {
+                match color {
+                    PrimaryColor::Red => MixedColor::new(1.0, 0.0, 0.0),
+                    PrimaryColor::Yellow => MixedColor::new(0.0, 1.0, 0.0),
+                    PrimaryColor::Blue => MixedColor::new(0.0, 0.0, 1.0),
+                }
+            }
+        }
+
+        impl From<SecondaryColor> for MixedColor {
+            /// A secondary color is made of equal amounts of two primary colors.
+            fn from(color: SecondaryColor) -> MixedColor {
+                match color {
+                    SecondaryColor::Orange => MixedColor::new(1.0, 1.0, 0.0),
+                    SecondaryColor::Green => MixedColor::new(0.0, 1.0, 1.0),
+                    SecondaryColor::Purple => MixedColor::new(1.0, 0.0, 1.0),
+                }
+            }
+        }
+
+        impl From<TertiaryColor> for MixedColor {
+            /// A tertiary color is made of equal amounts of a primary and a secondary color,
+            /// so three parts of the primary color and one of the other.
+            fn from(color: TertiaryColor) -> MixedColor {
+                match color {
+                    TertiaryColor::RedOrange => MixedColor::new(3.0, 1.0, 0.0),
+                    TertiaryColor::YellowOrange => MixedColor::new(1.0, 3.0, 0.0),
+                    TertiaryColor::YellowGreen => MixedColor::new(0.0, 3.0, 1.0),
+                    TertiaryColor::BlueGreen => MixedColor::new(0.0, 1.0, 3.0),
+                    TertiaryColor::BluePurple => MixedColor::new(1.0, 0.0, 3.0),
+                    TertiaryColor::RedPurple => MixedColor::new(3.0, 0.0, 1.0),
+                }
+            }
+        }
+
+        impl TryFrom<Color> for MixedColor {
+            type Error = Rgb;
+
+            /// Gets the amounts of primary colors of a color, failing for RGB values that are not made of them.
+            fn try_from(color: Color) -> Result<MixedColor, Rgb> {
+                match color {
+                    Color::Primary(color) => Ok(MixedColor::from(color)),
+                    Color::Secondary(color) => Ok(MixedColor::from(color)),
+                    Color::Tertiary(color) => Ok(MixedColor::from(color)),
+                    Color::Mixed(color) => Ok(color),
+                    Color::Rgb(color) => Err(color),
+                }
+            }
+        }
+
+        /// Any of the colors of the library, so functions can accept them interchangeably.
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub enum Color {
+            Primary(PrimaryColor),
+            Secondary(SecondaryColor),
+            Tertiary(TertiaryColor),
+            Mixed(MixedColor),
+            Rgb(Rgb),
+        }
+
+        impl Color {
+            /// The colors with a name: the primary, secondary, and tertiary colors.
+            pub const NAMED: [Color; 12] = [
+                Color::Primary(PrimaryColor::Red),
+                Color::Primary(PrimaryColor::Yellow),
+                Color::Primary(PrimaryColor::Blue),
+                Color::Secondary(SecondaryColor::Orange),
+                Color::Secondary(SecondaryColor::Green),
+                Color::Secondary(SecondaryColor::Purple),
+                Color::Tertiary(TertiaryColor::RedOrange),
+                Color::Tertiary(TertiaryColor::YellowOrange),
+                Color::Tertiary(TertiaryColor::YellowGreen),
+                Color::Tertiary(TertiaryColor::BlueGreen),
+                Color::Tertiary(TertiaryColor::BluePurple),
+                Color::Tertiary(TertiaryColor::RedPurple),
+            ];
+
+            /// Converts the color to its RGB value.
+            ///
+            /// # Examples
+            /// ```
+            /// use c14_cargo_crates::art::{Color, PrimaryColor, Rgb};
+            ///
+            /// assert_eq!(Rgb(255, 0, 0), Color::from(PrimaryColor::Red).to_rgb());
+            /// ```
+            pub fn to_rgb(&self) -> Rgb {
+                match *self {
+                    Color::Primary(color) => Rgb::from(color),
+                    Color::Secondary(color) => Rgb::from(color),
+                    Color::Tertiary(color) => Rgb::from(MixedColor::from(color)),
+                    Color::Mixed(color) => Rgb::from(color),
+                    Color::Rgb(color) => color,
+                }
+            }
+        }
+
+        impl From<PrimaryColor> for Color {
+            fn from(color: PrimaryColor) -> Color {
+                Color::Primary(color)
+            }
+        }
+
+        impl From<SecondaryColor> for Color {
+            fn from(color: SecondaryColor) -> Color {
+                Color::Secondary(color)
+            }
+        }
+
+        impl From<TertiaryColor> for Color {
+            fn from(color: TertiaryColor) -> Color {
+                Color::Tertiary(color)
+            }
+        }
+
+        impl From<MixedColor> for Color {
+            fn from(color: MixedColor) -> Color {
+                Color::Mixed(color)
+            }
+        }
+
+        impl From<Rgb> for Color {
+            fn from(color: Rgb) -> Color {
+                Color::Rgb(color)
+            }
+        }
+
+        impl From<Color> for Rgb {
+            fn from(color: Color) -> Rgb {
+                color.to_rgb()
+            }
+        }
     }
 
+    pub mod blend;
+    pub mod css;
+    pub mod distance;
+    // Public so the other `no_std` crates of the workspace don't need their own copy
+    pub mod float;
+    pub mod model;
+    pub mod palette;
+    pub mod rgb;
+    #[cfg(feature = "serde")]
+    mod serde_support;
+    pub mod text;
+
     pub mod utils {
         use crate::art::kinds::*;
+        use crate::art::model::{ColorModel, RybModel};
 
-        /// Combines two primary colors in equal amounts to create a secondary color.
-        /// By default it returns Orange
-        pub fn mix(c1: &PrimaryColor, c2: &PrimaryColor) -> SecondaryColor {
-            match (c1, c2) {
-                (PrimaryColor::Red, PrimaryColor::Yellow)
-                | (PrimaryColor::Yellow, PrimaryColor::Red) => SecondaryColor::Orange,
-                (PrimaryColor::Red, PrimaryColor::Blue)
-                | (PrimaryColor::Blue, PrimaryColor::Red) => SecondaryColor::Purple,
-                (PrimaryColor::Blue, PrimaryColor::Yellow)
-                | (PrimaryColor::Yellow, PrimaryColor::Blue) => SecondaryColor::Green,
-                _ => SecondaryColor::Orange,
-            }
+        /// Combines two colors in equal amounts.
+        ///
+        /// Two different primary colors create a secondary color, and a primary color with a secondary color next to it
+        /// creates a tertiary color. Mixing a color with itself gives the same color.
+        /// Combinations without a name, such as red and green, are returned as `Color::Mixed` with their actual amounts.
+        ///
+        /// This is the same as `RybModel.mix`, use a `ColorModel` to mix colors as light or ink instead of paint.
+        ///
+        /// # Examples
+        /// ```
+        /// use c14_cargo_crates::art::{Color, PrimaryColor, SecondaryColor, TertiaryColor, mix};
+        ///
+        /// assert_eq!(
+        ///     Color::Secondary(SecondaryColor::Orange),
+        ///     mix(&PrimaryColor::Red, &PrimaryColor::Yellow)
+        /// );
+        /// assert_eq!(
+        ///     Color::Tertiary(TertiaryColor::BlueGreen),
+        ///     mix(&PrimaryColor::Blue, &SecondaryColor::Green)
+        /// );
+        /// assert_eq!(
+        ///     Color::Primary(PrimaryColor::Blue),
+        ///     mix(&PrimaryColor::Blue, &PrimaryColor::Blue)
+        /// );
+        /// ```
+        pub fn mix<A, B>(c1: &A, c2: &B) -> Color
+        where
+            A: Into<MixedColor> + Copy,
+            B: Into<MixedColor> + Copy,
+        {
+            RybModel.mix(Color::Mixed((*c1).into()), Color::Mixed((*c2).into()))
+        }
+
+        /// Combines two primary colors in the given proportion, `ratio` being the fraction of `c1` in the result.
+        ///
+        /// Differently from `mix`, the result is not collapsed to a secondary color, so partial mixes are representable.
+        /// The `ratio` is clamped between `0.0` and `1.0`.
+        ///
+        /// # Examples
+        /// ```
+        /// use c14_cargo_crates::art::{MixedColor, PrimaryColor, SecondaryColor, mix_ratio};
+        ///
+        /// let color = mix_ratio(&PrimaryColor::Red, &PrimaryColor::Blue, 0.7);
+        /// assert!((color.amount(PrimaryColor::Red) - 0.7).abs() < 1e-9);
+        /// assert!((color.amount(PrimaryColor::Blue) - 0.3).abs() < 1e-9);
+        ///
+        /// let color = mix_ratio(&PrimaryColor::Blue, &PrimaryColor::Yellow, 0.5);
+        /// assert_eq!(MixedColor::from(SecondaryColor::Green), color);
+        /// ```
+        pub fn mix_ratio(c1: &PrimaryColor, c2: &PrimaryColor, ratio: f64) -> MixedColor {
+            MixedColor::from(*c1).mix(&MixedColor::from(*c2), ratio)
         }
     }
 }
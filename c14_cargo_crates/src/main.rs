@@ -24,10 +24,12 @@ fn main() {
     let red = PrimaryColor::Red;
     let yellow = PrimaryColor::Yellow;
     let res = mix(&red, &yellow);
-    println!("{:?} + {:?} = {:?}", red, yellow, res);
+    println!("{red} + {yellow} = {res}");
 
     // Use of a dependency in the same package
     let n = 1;
-    let res = c11_automated_tests::add_two(n);
-    println!("{n} + 2 = {res}")
+    match c11_automated_tests::add_two(n) {
+        Ok(res) => println!("{n} + 2 = {res}"),
+        Err(error) => println!("{error}"),
+    }
 }
@@ -0,0 +1,266 @@
+//! # Arena
+//!
+//! The same tree as the `tree` module, with all the nodes stored in a single `Vec` and referred to by their index
+//! A parent link is a plain index instead of a `Weak`, so there are no reference counts and no `RefCell`
+//! The tree owns every node, so changing it needs a mutable reference to the tree, and the borrow checker verifies it at compile time
+//! A removed node leaves an empty slot that is never reused, so an old `NodeId` can't point to another node
+
+use std::collections::VecDeque;
+
+/// Handle to a node of an `Arena`, only meaningful for the arena that returned it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(usize);
+
+#[derive(Debug)]
+struct Slot<T> {
+    value: T,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+}
+
+/// Tree stored in a `Vec`, always with a root
+#[derive(Debug)]
+pub struct Arena<T> {
+    slots: Vec<Option<Slot<T>>>,
+}
+
+impl<T> Arena<T> {
+    /// Constructor of a tree with a single node
+    pub fn new(root: T) -> Arena<T> {
+        Arena {
+            slots: vec![Some(Slot {
+                value: root,
+                parent: None,
+                children: vec![],
+            })],
+        }
+    }
+
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    fn slot(&self, id: NodeId) -> Option<&Slot<T>> {
+        self.slots.get(id.0).and_then(Option::as_ref)
+    }
+
+    /// Value of the node, `None` if it was removed
+    pub fn value(&self, id: NodeId) -> Option<&T> {
+        self.slot(id).map(|slot| &slot.value)
+    }
+
+    pub fn value_mut(&mut self, id: NodeId) -> Option<&mut T> {
+        self.slots
+            .get_mut(id.0)
+            .and_then(Option::as_mut)
+            .map(|slot| &mut slot.value)
+    }
+
+    /// Parent of the node, `None` for the root or a removed node
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.slot(id).and_then(|slot| slot.parent)
+    }
+
+    /// Children of the node, empty for a removed node
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        self.slot(id).map_or(&[], |slot| &slot.children)
+    }
+
+    pub fn is_leaf(&self, id: NodeId) -> bool {
+        self.children(id).is_empty()
+    }
+
+    /// Add a node with the value as the last child of the parent, and return it
+    /// Panics if the parent was removed
+    pub fn add_child(&mut self, parent: NodeId, value: T) -> NodeId {
+        let child = NodeId(self.slots.len());
+        self.slots
+            .get_mut(parent.0)
+            .and_then(Option::as_mut)
+            .expect("the parent must be a node of the arena")
+            .children
+            .push(child);
+        self.slots.push(Some(Slot {
+            value,
+            parent: Some(parent),
+            children: vec![],
+        }));
+        child
+    }
+
+    /// Remove the child from the parent, with all its descendants
+    /// Unlike in the `Rc` tree the removed nodes are dropped right away, returns `false` if it wasn't a child of the parent
+    pub fn remove_child(&mut self, parent: NodeId, child: NodeId) -> bool {
+        let Some(children) = self
+            .slots
+            .get_mut(parent.0)
+            .and_then(Option::as_mut)
+            .map(|slot| &mut slot.children)
+        else {
+            return false;
+        };
+        let Some(index) = children.iter().position(|&id| id == child) else {
+            return false;
+        };
+        children.remove(index);
+        let mut removed = vec![child];
+        while let Some(id) = removed.pop() {
+            if let Some(slot) = self.slots[id.0].take() {
+                removed.extend(slot.children);
+            }
+        }
+        true
+    }
+
+    /// Iterator over the nodes in pre-order: each node comes before its children, the children in order
+    pub fn depth_first(&self) -> DepthFirst<'_, T> {
+        DepthFirst {
+            arena: self,
+            stack: vec![self.root()],
+        }
+    }
+
+    /// Iterator over the nodes level by level, from the root
+    pub fn breadth_first(&self) -> BreadthFirst<'_, T> {
+        BreadthFirst {
+            arena: self,
+            queue: VecDeque::from([self.root()]),
+        }
+    }
+
+    /// Nodes from the given one up to the root, both included, empty for a removed node
+    pub fn path_to_root(&self, id: NodeId) -> Vec<NodeId> {
+        let mut path: Vec<NodeId> = self.slot(id).map(|_| id).into_iter().collect();
+        while let Some(parent) = path.last().and_then(|&id| self.parent(id)) {
+            path.push(parent);
+        }
+        path
+    }
+
+    /// Number of nodes, the root included
+    pub fn len(&self) -> usize {
+        self.slots.iter().flatten().count()
+    }
+
+    /// Always `false`, a tree has at least its root
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// Depth-first iterator over the ids of the nodes
+pub struct DepthFirst<'a, T> {
+    arena: &'a Arena<T>,
+    stack: Vec<NodeId>,
+}
+
+impl<T> Iterator for DepthFirst<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.stack.pop()?;
+        // Pushed in reverse, so the first child is the next one popped
+        self.stack
+            .extend(self.arena.children(id).iter().rev().copied());
+        Some(id)
+    }
+}
+
+/// Breadth-first iterator over the ids of the nodes
+pub struct BreadthFirst<'a, T> {
+    arena: &'a Arena<T>,
+    queue: VecDeque<NodeId>,
+}
+
+impl<T> Iterator for BreadthFirst<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let id = self.queue.pop_front()?;
+        self.queue.extend(self.arena.children(id).iter().copied());
+        Some(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The same tree of the tests of the `tree` module:
+    ///
+    /// ```text
+    ///       1
+    ///     /   \
+    ///    2     3
+    ///   / \     \
+    ///  4   5     6
+    /// ```
+    fn sample() -> (Arena<i32>, Vec<NodeId>) {
+        let mut arena = Arena::new(1);
+        let root = arena.root();
+        let two = arena.add_child(root, 2);
+        let three = arena.add_child(root, 3);
+        let four = arena.add_child(two, 4);
+        let five = arena.add_child(two, 5);
+        let six = arena.add_child(three, 6);
+        (arena, vec![root, two, three, four, five, six])
+    }
+
+    fn values(arena: &Arena<i32>, ids: impl IntoIterator<Item = NodeId>) -> Vec<i32> {
+        ids.into_iter()
+            .map(|id| *arena.value(id).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn iterators_visit_every_node_once() {
+        let (arena, _) = sample();
+        assert_eq!(values(&arena, arena.depth_first()), [1, 2, 4, 5, 3, 6]);
+        assert_eq!(values(&arena, arena.breadth_first()), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(arena.len(), 6);
+    }
+
+    #[test]
+    fn paths_go_up_to_the_root() {
+        let (arena, ids) = sample();
+        assert_eq!(values(&arena, arena.path_to_root(ids[4])), [5, 2, 1]);
+        assert_eq!(arena.path_to_root(arena.root()), [arena.root()]);
+        assert_eq!(arena.parent(ids[5]), Some(ids[2]));
+    }
+
+    #[test]
+    fn removed_children_take_their_descendants() {
+        let (mut arena, ids) = sample();
+        let (root, two) = (ids[0], ids[1]);
+
+        assert!(arena.remove_child(root, two));
+        assert!(!arena.remove_child(root, two));
+        assert_eq!(values(&arena, arena.depth_first()), [1, 3, 6]);
+        assert_eq!(arena.len(), 3);
+        // The ids of the removed nodes stay invalid, even after adding new nodes
+        let seven = arena.add_child(root, 7);
+        assert_ne!(seven, two);
+        assert!(arena.value(ids[3]).is_none() && arena.parent(ids[3]).is_none());
+        assert!(arena.path_to_root(ids[4]).is_empty());
+        assert!(!arena.remove_child(two, ids[3]));
+    }
+
+    #[test]
+    fn values_can_be_changed_in_place() {
+        let (mut arena, ids) = sample();
+        for id in arena.depth_first().collect::<Vec<_>>() {
+            if arena.is_leaf(id) {
+                *arena.value_mut(id).unwrap() *= 10;
+            }
+        }
+        assert_eq!(values(&arena, ids), [1, 2, 3, 40, 50, 60]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the parent must be a node of the arena")]
+    fn removed_nodes_cant_get_children() {
+        let (mut arena, ids) = sample();
+        arena.remove_child(ids[0], ids[2]);
+        arena.add_child(ids[5], 7);
+    }
+}
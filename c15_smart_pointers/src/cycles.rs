@@ -0,0 +1,293 @@
+//! # Reference cycles
+//!
+//! Diagnostic for the structures built with `Rc`, like the cons lists and the tree, to find the nodes kept alive by a cycle
+//! The detector keeps a `Weak` to every node it tracks, so it never keeps a node alive itself
+//! A report walks the strong links from the roots still in use: a tracked node that is alive but can't be reached from them is only owned by other unreachable nodes, so it's a suspected cycle
+//! Once every root is dropped, a structure without cycles reports no node alive
+
+use std::collections::HashSet;
+use std::fmt;
+use std::rc::{Rc, Weak};
+
+use crate::list::RcList;
+use crate::tree::Node;
+
+/// Type whose values own other values of the same type through `Rc`
+pub trait Links: Sized {
+    /// The nodes owned by this one, the `Weak` links are not included as they can't create a cycle
+    fn links(&self) -> Vec<Rc<Self>>;
+}
+
+impl<T> Links for RcList<T> {
+    fn links(&self) -> Vec<Rc<RcList<T>>> {
+        self.tail().into_iter().map(Rc::clone).collect()
+    }
+}
+
+impl<T> Links for Node<T> {
+    fn links(&self) -> Vec<Rc<Node<T>>> {
+        self.children()
+    }
+}
+
+/// Counts of a tracked node, identified by the order it was tracked in
+/// The counts don't include the `Weak` kept by the detector
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeCounts {
+    pub id: usize,
+    pub strong: usize,
+    pub weak: usize,
+    /// Whether the node can be reached from the roots following the strong links
+    pub reachable: bool,
+}
+
+impl NodeCounts {
+    /// A node still alive but unreachable is owned only by nodes out of reach too
+    pub fn is_suspected_cycle(&self) -> bool {
+        self.strong > 0 && !self.reachable
+    }
+}
+
+/// Counts of all the tracked nodes, in the order they were tracked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub nodes: Vec<NodeCounts>,
+}
+
+impl Report {
+    pub fn suspected_cycles(&self) -> Vec<NodeCounts> {
+        self.nodes
+            .iter()
+            .copied()
+            .filter(NodeCounts::is_suspected_cycle)
+            .collect()
+    }
+
+    /// `true` if every node still alive can be reached from the roots
+    pub fn is_leak_free(&self) -> bool {
+        self.suspected_cycles().is_empty()
+    }
+}
+
+impl fmt::Display for Report {
+    /// One line per node, with the suspected cycles marked
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for node in &self.nodes {
+            write!(
+                f,
+                "node {}: strong = {}, weak = {}",
+                node.id, node.strong, node.weak
+            )?;
+            if node.is_suspected_cycle() {
+                write!(f, " (suspected cycle)")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracker of the nodes of a structure, to report their counts later
+pub struct CycleDetector<N> {
+    tracked: Vec<Weak<N>>,
+}
+
+impl<N> Default for CycleDetector<N> {
+    fn default() -> CycleDetector<N> {
+        CycleDetector { tracked: vec![] }
+    }
+}
+
+impl<N: Links> CycleDetector<N> {
+    pub fn new() -> CycleDetector<N> {
+        CycleDetector::default()
+    }
+
+    /// Track a single node, and return its id in the reports
+    pub fn track(&mut self, node: &Rc<N>) -> usize {
+        self.tracked.push(Rc::downgrade(node));
+        self.tracked.len() - 1
+    }
+
+    /// Track the root and every node reachable from it not tracked yet, the root first
+    pub fn track_all(&mut self, root: &Rc<N>) {
+        let mut seen: HashSet<*const N> = self.tracked.iter().map(Weak::as_ptr).collect();
+        for node in walk(&[root]) {
+            if seen.insert(Rc::as_ptr(&node)) {
+                self.track(&node);
+            }
+        }
+    }
+
+    /// Counts of the tracked nodes, with the roots the structure is still used from
+    pub fn report(&self, roots: &[&Rc<N>]) -> Report {
+        // The nodes of the walk are dropped before counting, so they don't change the counts
+        let reachable: HashSet<*const N> = walk(roots).iter().map(Rc::as_ptr).collect();
+        let nodes = self
+            .tracked
+            .iter()
+            .enumerate()
+            .map(|(id, node)| {
+                let strong = node.strong_count();
+                NodeCounts {
+                    id,
+                    strong,
+                    // Without strong references `weak_count` is 0, otherwise it counts the one of the detector too
+                    weak: node.weak_count().saturating_sub(1),
+                    reachable: strong > 0 && reachable.contains(&node.as_ptr()),
+                }
+            })
+            .collect();
+        Report { nodes }
+    }
+}
+
+/// Every node reachable from the roots once, in depth-first order
+/// The visited nodes are remembered, so the walk ends on a cycle too
+fn walk<N: Links>(roots: &[&Rc<N>]) -> Vec<Rc<N>> {
+    let mut visited = HashSet::new();
+    let mut nodes = vec![];
+    let mut stack: Vec<Rc<N>> = roots.iter().rev().map(|root| Rc::clone(root)).collect();
+    while let Some(node) = stack.pop() {
+        if !visited.insert(Rc::as_ptr(&node)) {
+            continue;
+        }
+        stack.extend(node.links().into_iter().rev());
+        nodes.push(node);
+    }
+    nodes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Tree;
+    use std::cell::RefCell;
+
+    /// Node that can link to any other, cycles included
+    struct Vertex {
+        edges: RefCell<Vec<Rc<Vertex>>>,
+    }
+
+    impl Vertex {
+        fn new() -> Rc<Vertex> {
+            Rc::new(Vertex {
+                edges: RefCell::new(vec![]),
+            })
+        }
+
+        fn link(&self, other: &Rc<Vertex>) {
+            self.edges.borrow_mut().push(Rc::clone(other));
+        }
+    }
+
+    impl Links for Vertex {
+        fn links(&self) -> Vec<Rc<Vertex>> {
+            self.edges.borrow().clone()
+        }
+    }
+
+    #[test]
+    fn shared_lists_are_leak_free() {
+        let mut detector = CycleDetector::new();
+        let a = Rc::new([5, 10].into_iter().collect::<RcList<i32>>());
+        let b = a.push_front(3);
+        let c = a.push_front(4);
+        detector.track_all(&b);
+        detector.track_all(&c);
+
+        // `b`, 5, 10, `Nil`, then only `c` as the rest is shared
+        let report = detector.report(&[&b, &c]);
+        assert_eq!(report.nodes.len(), 5);
+        assert_eq!(
+            report
+                .nodes
+                .iter()
+                .map(|node| node.strong)
+                .collect::<Vec<_>>(),
+            [1, 3, 1, 1, 1]
+        );
+        assert!(report.is_leak_free());
+
+        drop((a, b, c));
+        let report = detector.report(&[]);
+        assert!(report.nodes.iter().all(|node| node.strong == 0));
+        assert!(report.is_leak_free());
+    }
+
+    #[test]
+    fn trees_are_leak_free() {
+        let mut detector = CycleDetector::new();
+        let tree = Tree::new(5);
+        let leaf = tree.add_child(tree.root(), 3);
+        detector.track_all(tree.root());
+
+        let report = detector.report(&[tree.root()]);
+        // The parent links of the children are weak
+        assert_eq!(
+            report.nodes,
+            [
+                NodeCounts {
+                    id: 0,
+                    strong: 1,
+                    weak: 1,
+                    reachable: true
+                },
+                NodeCounts {
+                    id: 1,
+                    strong: 2,
+                    weak: 0,
+                    reachable: true
+                },
+            ]
+        );
+
+        drop(tree);
+        let report = detector.report(&[&leaf]);
+        assert_eq!((report.nodes[0].strong, report.nodes[1].strong), (0, 1));
+        assert!(report.is_leak_free());
+    }
+
+    #[test]
+    fn cycles_are_reported_once_unreachable() {
+        let mut detector = CycleDetector::new();
+        let a = Vertex::new();
+        let b = Vertex::new();
+        let c = Vertex::new();
+        a.link(&b);
+        b.link(&c);
+        c.link(&b);
+        detector.track_all(&a);
+        assert_eq!(detector.tracked.len(), 3);
+
+        // While `a` is used the cycle is reachable, and the walk stops on it
+        assert!(detector.report(&[&a]).is_leak_free());
+
+        drop((a, b, c));
+        let report = detector.report(&[]);
+        let ids: Vec<usize> = report
+            .suspected_cycles()
+            .iter()
+            .map(|node| node.id)
+            .collect();
+        assert_eq!(ids, [1, 2]);
+        assert_eq!(
+            report.to_string(),
+            "node 0: strong = 0, weak = 0\n\
+             node 1: strong = 1, weak = 0 (suspected cycle)\n\
+             node 2: strong = 1, weak = 0 (suspected cycle)\n"
+        );
+
+        // Breaking the cycle by hand frees both nodes
+        let b = detector.tracked[1].upgrade().unwrap();
+        b.edges.borrow_mut().clear();
+        drop(b);
+        assert!(
+            detector
+                .report(&[])
+                .nodes
+                .iter()
+                .all(|node| node.strong == 0)
+        );
+    }
+}
@@ -2,44 +2,636 @@
 //!
 //! Library containing the Messenger trait and the LimitTracker
 
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Add, Deref, DerefMut};
+use std::time::{Duration, Instant};
+
+/// Severity of the quota usage, from the lowest to the highest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum QuotaLevel {
+    /// Usage under 75% of the quota
+    Ok,
+    /// Usage over 75% of the quota
+    Warning,
+    /// Usage over 90% of the quota
+    Urgent,
+    /// Usage at or over the quota
+    Exceeded,
+}
+
+impl QuotaLevel {
+    /// Get the level corresponding to the ratio between the value and the maximum
+    pub fn from_ratio(ratio: f64) -> QuotaLevel {
+        if ratio >= 1.0 {
+            QuotaLevel::Exceeded
+        } else if ratio >= 0.9 {
+            QuotaLevel::Urgent
+        } else if ratio >= 0.75 {
+            QuotaLevel::Warning
+        } else {
+            QuotaLevel::Ok
+        }
+    }
+
+    /// Default message describing the level, used when sending it to a `Messenger`
+    pub fn message(&self) -> &'static str {
+        match self {
+            QuotaLevel::Ok => "Quota usage is ok",
+            QuotaLevel::Warning => "Warning: quota over 75%",
+            QuotaLevel::Urgent => "Urgent warning: quota over 90%",
+            QuotaLevel::Exceeded => "Error: quota exceeded!",
+        }
+    }
+}
+
+impl fmt::Display for QuotaLevel {
+    /// Display the default message of the level
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+/// Error returned by a `Messenger` that couldn't deliver a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SendError(pub String);
+
+impl fmt::Display for SendError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "failed to send message: {}", self.0)
+    }
+}
+
+impl Error for SendError {}
+
 /// Trait defining the send method for sending messages regarding the quota
+/// The level is passed along the message, so the receiver can branch on the severity without parsing the text
 pub trait Messenger {
-    fn send(&self, msg: &str);
+    fn send(&self, level: QuotaLevel, msg: &str);
+
+    /// Send a message reporting whether it was delivered
+    /// By default `send` is called and the message is considered delivered, sinks that can fail should override it
+    fn try_send(&self, level: QuotaLevel, msg: &str) -> Result<(), SendError> {
+        self.send(level, msg);
+        Ok(())
+    }
+}
+
+/// Messenger sending every message to several messengers, such as console, file, and webhook
+/// Each messenger is isolated from the others: a failing one doesn't prevent the delivery to the rest
+#[derive(Default)]
+pub struct MultiMessenger<'a> {
+    messengers: Vec<&'a dyn Messenger>,
+}
+
+impl<'a> MultiMessenger<'a> {
+    /// Constructor
+    pub fn new(messengers: Vec<&'a dyn Messenger>) -> MultiMessenger<'a> {
+        MultiMessenger { messengers }
+    }
+
+    /// Add a messenger to the ones receiving the messages
+    pub fn push(&mut self, messenger: &'a dyn Messenger) {
+        self.messengers.push(messenger);
+    }
+
+    /// Send the message to every messenger, returning the outcome for each of them in order
+    pub fn send_all(&self, level: QuotaLevel, msg: &str) -> Vec<Result<(), SendError>> {
+        self.messengers
+            .iter()
+            .map(|messenger| messenger.try_send(level, msg))
+            .collect()
+    }
+}
+
+impl Messenger for MultiMessenger<'_> {
+    /// Send the message to every messenger, ignoring the failures
+    fn send(&self, level: QuotaLevel, msg: &str) {
+        let _ = self.send_all(level, msg);
+    }
+
+    /// Send the message to every messenger, failing with the combined errors if any of them failed
+    fn try_send(&self, level: QuotaLevel, msg: &str) -> Result<(), SendError> {
+        let errors: Vec<String> = self
+            .send_all(level, msg)
+            .into_iter()
+            .filter_map(|res| res.err())
+            .map(|SendError(err)| err)
+            .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SendError(errors.join("; ")))
+        }
+    }
+}
+
+/// Trait defining an asynchronous send method, for transports that are slow to deliver the messages (e.g. network)
+/// Implementors can use `async fn send`, the caller awaits the delivery without blocking the runtime
+pub trait AsyncMessenger {
+    fn send(&self, level: QuotaLevel, msg: &str) -> impl Future<Output = ()>;
+}
+
+/// Quantity measured by a `LimitTracker`, such as bytes, requests, or money
+/// It provides the ratio against the maximum, and the formatting with its unit through `Display`
+pub trait Quota: Copy + Default + PartialOrd + Add<Output = Self> + fmt::Display {
+    /// Get the ratio between the value and the maximum
+    fn ratio(&self, max: &Self) -> f64;
+
+    /// Describe the usage against the maximum, such as "1.2 GiB of 2 GiB used"
+    fn usage(&self, max: &Self) -> String {
+        format!("{self} of {max} used")
+    }
+}
+
+/// Plain number without unit
+impl Quota for usize {
+    fn ratio(&self, max: &Self) -> f64 {
+        *self as f64 / *max as f64
+    }
+}
+
+pub mod units {
+    //! Quantities with a unit that can be used as `Quota` of a `LimitTracker`
+    use super::Quota;
+    use std::fmt;
+    use std::ops::Add;
+
+    /// Amount of memory or storage, displayed with binary units (KiB, MiB, ...)
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Bytes(pub u64);
+
+    impl Bytes {
+        pub const KIB: Bytes = Bytes(1 << 10);
+        pub const MIB: Bytes = Bytes(1 << 20);
+        pub const GIB: Bytes = Bytes(1 << 30);
+    }
+
+    impl Add for Bytes {
+        type Output = Bytes;
+
+        fn add(self, other: Bytes) -> Bytes {
+            Bytes(self.0 + other.0)
+        }
+    }
+
+    impl Quota for Bytes {
+        fn ratio(&self, max: &Self) -> f64 {
+            self.0 as f64 / max.0 as f64
+        }
+    }
+
+    impl fmt::Display for Bytes {
+        /// Display the value with the largest unit keeping it over 1, and one decimal if needed
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            const UNITS: [&str; 5] = ["KiB", "MiB", "GiB", "TiB", "PiB"];
+            if self.0 < 1024 {
+                return write!(f, "{} B", self.0);
+            }
+            let mut value = self.0 as f64 / 1024.0;
+            let mut unit = 0;
+            while value >= 1024.0 && unit < UNITS.len() - 1 {
+                value /= 1024.0;
+                unit += 1;
+            }
+            let value = format!("{value:.1}");
+            let value = value.strip_suffix(".0").unwrap_or(&value);
+            write!(f, "{value} {}", UNITS[unit])
+        }
+    }
+
+    /// Number of requests, such as API calls
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Requests(pub u64);
+
+    impl Add for Requests {
+        type Output = Requests;
+
+        fn add(self, other: Requests) -> Requests {
+            Requests(self.0 + other.0)
+        }
+    }
+
+    impl Quota for Requests {
+        fn ratio(&self, max: &Self) -> f64 {
+            self.0 as f64 / max.0 as f64
+        }
+    }
+
+    impl fmt::Display for Requests {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                1 => write!(f, "1 request"),
+                n => write!(f, "{n} requests"),
+            }
+        }
+    }
+
+    /// Amount of money in cents, such as a budget
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Money(pub u64);
+
+    impl Add for Money {
+        type Output = Money;
+
+        fn add(self, other: Money) -> Money {
+            Money(self.0 + other.0)
+        }
+    }
+
+    impl Quota for Money {
+        fn ratio(&self, max: &Self) -> f64 {
+            self.0 as f64 / max.0 as f64
+        }
+    }
+
+    impl fmt::Display for Money {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "${}.{:02}", self.0 / 100, self.0 % 100)
+        }
+    }
+}
+
+pub mod list;
+pub use list::{List, RcList};
+
+pub mod tree;
+pub use tree::{Node, Tree, path_to_root};
+
+pub mod arena;
+pub use arena::{Arena, NodeId};
+
+pub mod cycles;
+pub use cycles::{CycleDetector, Links};
+
+pub mod observers;
+pub use observers::{Observer, Observers};
+
+pub mod tracked;
+pub use tracked::{Recorder, TrackedRc};
+
+/// Smart pointer of the chapter, holding a single value and dereferencing to it like a `Box<T>`
+/// A hook can be given to run when the pointer is dropped, receiving the value it owned
+pub struct MyBox<T> {
+    // Always `Some`, until `drop` or `into_inner` take the value out, as the fields of a type implementing `Drop` can't be moved
+    value: Option<T>,
+    on_drop: Option<Box<dyn FnOnce(T)>>,
 }
 
+impl<T> MyBox<T> {
+    /// Constructor of a pointer without a drop hook
+    pub fn new(x: T) -> MyBox<T> {
+        MyBox {
+            value: Some(x),
+            on_drop: None,
+        }
+    }
+
+    /// Constructor of a pointer that calls the hook with its value when dropped
+    pub fn with_drop_hook(x: T, hook: impl FnOnce(T) + 'static) -> MyBox<T> {
+        let mut my_box = MyBox::new(x);
+        my_box.set_drop_hook(hook);
+        my_box
+    }
+
+    /// Replace the drop hook, the previous one is never called
+    pub fn set_drop_hook(&mut self, hook: impl FnOnce(T) + 'static) {
+        self.on_drop = Some(Box::new(hook));
+    }
+
+    /// Take the value out of the pointer, without calling the drop hook
+    pub fn into_inner(mut self) -> T {
+        self.on_drop = None;
+        self.value
+            .take()
+            .expect("the value is only taken when the pointer is consumed")
+    }
+}
+
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+            .as_ref()
+            .expect("the value is only taken when the pointer is consumed")
+    }
+}
+
+impl<T> DerefMut for MyBox<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+            .as_mut()
+            .expect("the value is only taken when the pointer is consumed")
+    }
+}
+
+impl<T> Drop for MyBox<T> {
+    /// Hand the value to the hook, if any, otherwise it's dropped as usual
+    fn drop(&mut self) {
+        if let (Some(value), Some(hook)) = (self.value.take(), self.on_drop.take()) {
+            hook(value);
+        }
+    }
+}
+
+impl<T> AsRef<T> for MyBox<T> {
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T> From<T> for MyBox<T> {
+    fn from(x: T) -> MyBox<T> {
+        MyBox::new(x)
+    }
+}
+
+/// The hook can't be printed, only whether there is one
+impl<T: fmt::Debug> fmt::Debug for MyBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MyBox")
+            .field("value", &**self)
+            .field("has_drop_hook", &self.on_drop.is_some())
+            .finish()
+    }
+}
+
+/// Value set on a `LimitTracker`, with the moment it was set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Usage<Q = usize> {
+    pub value: Q,
+    pub at: Instant,
+}
+
+/// Token bucket limiting how many messages can be sent in a period of time
+/// The bucket holds up to `capacity` tokens, one token is refilled every `refill` and each message takes one
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill: Duration,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Constructor, the bucket starts full
+    fn new(capacity: u32, refill: Duration, now: Instant) -> TokenBucket {
+        TokenBucket {
+            capacity: capacity as f64,
+            tokens: capacity as f64,
+            refill,
+            last_refill: now,
+        }
+    }
+
+    /// Take a token if there is one available at the moment `now`
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refilled = if self.refill.is_zero() {
+            self.capacity
+        } else {
+            elapsed.as_secs_f64() / self.refill.as_secs_f64()
+        };
+        self.tokens = (self.tokens + refilled).min(self.capacity);
+        self.last_refill = self.last_refill.max(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Messenger used by a `LimitTracker`, either borrowed or owned by the tracker
+enum MessengerRef<'a, T: ?Sized> {
+    Borrowed(&'a T),
+    Owned(Box<T>),
+}
+
+impl<T: ?Sized> Deref for MessengerRef<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self {
+            MessengerRef::Borrowed(messenger) => messenger,
+            MessengerRef::Owned(messenger) => messenger,
+        }
+    }
+}
+
+/// Default margin below a threshold that the usage needs to reach before the level is considered lowered
+pub const DEFAULT_HYSTERESIS: f64 = 0.05;
+
 /// Struct for tracking the quota of the messages
-pub struct LimitTracker<'a, T: Messenger> {
-    messenger: &'a T,
-    value: usize,
-    max: usize,
+/// The last notified level is kept, so values in the same band don't send the same message again
+/// The messenger can be a `Messenger`, used by `set_value`, or an `AsyncMessenger`, used by `set_value_async`
+/// Every value set is recorded in the history, used to generate usage reports
+/// The measured quantity `Q` is a plain number by default, but can be any `Quota` such as `units::Bytes`
+/// The messenger is borrowed when created with `new`, or owned when created with `with_messenger` or `from_box`,
+/// in which case the tracker has no borrow to outlive and can be stored in long-lived structs as `LimitTracker<'static, dyn Messenger>`
+pub struct LimitTracker<'a, T: ?Sized, Q: Quota = usize> {
+    messenger: MessengerRef<'a, T>,
+    value: Q,
+    max: Q,
+    notified: QuotaLevel,
+    hysteresis: f64,
+    history: Vec<Usage<Q>>,
+    rate_limit: Option<TokenBucket>,
 }
-impl<'a, T> LimitTracker<'a, T>
-where
-    T: Messenger,
-{
+impl<'a, T: ?Sized, Q: Quota> LimitTracker<'a, T, Q> {
     /// Constructor
-    pub fn new(messenger: &'a T, max: usize) -> LimitTracker<'a, T> {
+    pub fn new(messenger: &'a T, max: Q) -> LimitTracker<'a, T, Q> {
+        LimitTracker::from_ref(MessengerRef::Borrowed(messenger), max)
+    }
+
+    /// Constructor taking ownership of a boxed messenger, such as a `Box<dyn Messenger>`
+    pub fn from_box(messenger: Box<T>, max: Q) -> LimitTracker<'a, T, Q> {
+        LimitTracker::from_ref(MessengerRef::Owned(messenger), max)
+    }
+
+    /// Get the messenger used by the tracker
+    pub fn messenger(&self) -> &T {
+        &self.messenger
+    }
+
+    /// Constructor shared by the borrowed and owned versions
+    fn from_ref(messenger: MessengerRef<'a, T>, max: Q) -> LimitTracker<'a, T, Q> {
         LimitTracker {
             messenger,
-            value: 0,
+            value: Q::default(),
             max,
+            notified: QuotaLevel::Ok,
+            hysteresis: DEFAULT_HYSTERESIS,
+            history: Vec::new(),
+            rate_limit: None,
         }
     }
 
-    /// Set value of the tracker and send message if quota over 75%
-    /// This method doesn't return anything, so can't be used to make assertions
-    pub fn set_value(&mut self, value: usize) {
+    /// Limit the messages sent to a burst of `burst` messages, and then one every `interval`
+    /// For example `set_rate_limit(1, Duration::from_secs(60))` sends at most one message per minute
+    /// A message dropped by the limit isn't considered notified, so it's sent by a later value once allowed
+    pub fn set_rate_limit(&mut self, burst: u32, interval: Duration) {
+        self.rate_limit = Some(TokenBucket::new(burst, interval, Instant::now()));
+    }
+
+    /// Remove the limit on the messages sent
+    pub fn remove_rate_limit(&mut self) {
+        self.rate_limit = None;
+    }
+
+    /// Get every value set on the tracker, in order, with the moment it was set
+    pub fn history(&self) -> &[Usage<Q>] {
+        &self.history
+    }
+
+    /// Get the highest value set on the tracker, `None` if no value was set
+    pub fn peak(&self) -> Option<Q> {
+        self.history
+            .iter()
+            .map(|usage| usage.value)
+            .reduce(|peak, value| if value > peak { value } else { peak })
+    }
+
+    /// Get how long the value stayed at or over the threshold
+    /// Each value counts until the next one was set, and the last one until now
+    pub fn time_over(&self, threshold: Q) -> Duration {
+        self.time_over_until(threshold, Instant::now())
+    }
+
+    /// Get how long the value stayed at or over the threshold, counting the last value until `end`
+    fn time_over_until(&self, threshold: Q, end: Instant) -> Duration {
+        let ends = self.history.iter().skip(1).map(|usage| usage.at);
+        self.history
+            .iter()
+            .zip(ends.chain(std::iter::once(end)))
+            .filter(|(usage, _)| usage.value >= threshold)
+            .map(|(usage, until)| until.saturating_duration_since(usage.at))
+            .sum()
+    }
+
+    /// Get the current value of the tracker
+    pub fn value(&self) -> Q {
+        self.value
+    }
+
+    /// Get the maximum of the tracker
+    pub fn max(&self) -> Q {
+        self.max
+    }
+
+    /// Set the value back to zero and forget the notified level, for example at the start of a billing period
+    /// The zero value is recorded in the history, but no message is sent
+    pub fn reset(&mut self) {
+        self.value = Q::default();
+        self.notified = QuotaLevel::Ok;
+        self.history.push(Usage {
+            value: self.value,
+            at: Instant::now(),
+        });
+    }
+
+    /// Set the margin, as a ratio of the maximum, below a threshold that lowers the notified level
+    /// With a margin of `0.05` a warning sent at 75% is sent again only after the usage went under 70%
+    pub fn set_hysteresis(&mut self, margin: f64) {
+        self.hysteresis = margin.max(0.0);
+    }
+
+    /// Forget the last notified level, so the next value over 75% sends a message again
+    pub fn reset_notifications(&mut self) {
+        self.notified = QuotaLevel::Ok;
+    }
+
+    /// Set the value and get the level to notify, if it differs from the last notified one
+    fn update(&mut self, value: Q) -> Option<QuotaLevel> {
+        let now = Instant::now();
         self.value = value;
+        self.history.push(Usage { value, at: now });
+        self.evaluate(now)
+    }
 
-        let percenteage_of_max = self.value as f64 / self.max as f64;
+    /// Get the level to notify for the current value and maximum, if it differs from the last notified one
+    fn evaluate(&mut self, now: Instant) -> Option<QuotaLevel> {
+        let percenteage_of_max = self.value.ratio(&self.max);
+
+        let mut level = QuotaLevel::from_ratio(percenteage_of_max);
+        if level < self.notified {
+            // The level is lowered only once the usage goes under the threshold by more than the margin
+            level = QuotaLevel::from_ratio(percenteage_of_max + self.hysteresis).min(self.notified);
+        }
+        if level == self.notified {
+            return None;
+        }
 
-        if percenteage_of_max >= 1.0 {
-            self.messenger.send("Error: quota exceeded!");
-        } else if percenteage_of_max >= 0.9 {
-            self.messenger.send("Urgent warning: quota over 90%");
-        } else if percenteage_of_max >= 0.75 {
-            self.messenger.send("Warning: quota over 75%");
+        if level == QuotaLevel::Ok {
+            self.notified = level;
+            return None;
+        }
+        if let Some(bucket) = &mut self.rate_limit
+            && !bucket.try_take(now)
+        {
+            return None;
         }
+        self.notified = level;
+        Some(level)
+    }
+
+    /// Build the message sent for the level, including the current usage
+    fn message(&self, level: QuotaLevel) -> String {
+        format!("{level} ({})", self.value.usage(&self.max))
+    }
+}
+
+impl<'a, T, Q: Quota> LimitTracker<'a, T, Q> {
+    /// Constructor taking ownership of the messenger
+    pub fn with_messenger(messenger: T, max: Q) -> LimitTracker<'a, T, Q> {
+        LimitTracker::from_box(Box::new(messenger), max)
+    }
+}
+
+impl<T, Q> LimitTracker<'_, T, Q>
+where
+    T: Messenger + ?Sized,
+    Q: Quota,
+{
+    /// Set value of the tracker and send message if quota over 75%
+    /// The message is sent only when the level differs from the last notified one
+    /// The level that fired the message is returned, so it can be used to make assertions without a mock messenger
+    pub fn set_value(&mut self, value: Q) -> Option<QuotaLevel> {
+        let level = self.update(value)?;
+        self.messenger.send(level, &self.message(level));
+        Some(level)
+    }
+
+    /// Increase the value of the tracker by `delta`, sending a message like `set_value`
+    pub fn add(&mut self, delta: Q) -> Option<QuotaLevel> {
+        self.set_value(self.value + delta)
+    }
+
+    /// Change the maximum of the tracker, and send a message if the current value crosses a threshold against it
+    pub fn set_max(&mut self, max: Q) -> Option<QuotaLevel> {
+        self.max = max;
+        let level = self.evaluate(Instant::now())?;
+        self.messenger.send(level, &self.message(level));
+        Some(level)
+    }
+}
+
+impl<T, Q> LimitTracker<'_, T, Q>
+where
+    T: AsyncMessenger + ?Sized,
+    Q: Quota,
+{
+    /// Asynchronous version of `set_value`, that awaits the delivery of the message
+    /// While the message is being delivered the runtime can make progress on other futures
+    pub async fn set_value_async(&mut self, value: Q) -> Option<QuotaLevel> {
+        let level = self.update(value)?;
+        self.messenger.send(level, &self.message(level)).await;
+        Some(level)
     }
 }
 
@@ -47,11 +639,12 @@ where
 mod tests {
     use super::*;
     use std::cell::RefCell;
+    use std::rc::Rc;
 
     /// Mock object used to keep track of the sent messages in order to make assertion for the `set_value` method of the `LimitTracker`
     struct MockMessenger {
         // sent_messages: Vec<String>, // Change for internal mutability
-        sent_messages: RefCell<Vec<String>>,
+        sent_messages: RefCell<Vec<(QuotaLevel, String)>>,
     }
 
     ///Constructor that initialises a vector for keeping track of the sent messages
@@ -66,24 +659,26 @@ mod tests {
 
     /// Implementation of the Messenger trait
     impl Messenger for MockMessenger {
-        fn send(&self, msg: &str) {
+        fn send(&self, level: QuotaLevel, msg: &str) {
             // self.sent_messages.push(String::from(msg)); // Not working
             // The code above doesn't work because the send method takes a n immutable reference to self
             // To fix it `&mut self` could be used in both `trait` and `impl`, but the trait shouldn't be modified just for testing.
             // In this case interior mutability can help: the sent messages can be stored in a `RefCell<T>`, so the send method will be able to modify `sent_messages`
             // The methods becomes as follows:
-            self.sent_messages.borrow_mut().push(String::from(msg));
+            self.sent_messages
+                .borrow_mut()
+                .push((level, String::from(msg)));
             // In this case self is still immutable, mathcing to the trait, but the `borrow_mut` method on `self.sent_messages` allows to get a mutable reference of the `RefCell<Vec<String>>` value.
 
             // With `RefCell<T>` the `borrow` method returns a `Ref<T>`, while `borrow_mut()` `RefMut<T>`, and both implement `Deref` so they can be used as regular references
             // `RefCell<T>` keeps track of how many `Ref<T>` and `RefMut<T>` are active, and every `borrow` increasees the count of immutable borrows, it dereases when the reference goes out of scope.
             // `RefCell<T>`, lets use many immutable borrows, or one mutable at any point in time. If this rule is violated, `RefCell<T>` will panic at runtime:
             // Example:
-            let mut one_borrow = self.sent_messages.borrow_mut();
-            let mut two_borrow = self.sent_messages.borrow_mut();
+            // let mut one_borrow = self.sent_messages.borrow_mut();
+            // let mut two_borrow = self.sent_messages.borrow_mut();
 
-            one_borrow.push(String::from(msg));
-            two_borrow.push(String::from(msg));
+            // one_borrow.push((level, String::from(msg)));
+            // two_borrow.push((level, String::from(msg)));
 
             // Having two `borrow_mut` makes the program panic with the error: `already borrowed: BorrowMutError`
             // Choosing to catch borrowing errors at runtime means potentially finding mistakes in the code later in the development, and incur in a small runtime performance penality because of keeping track of the borrows
@@ -100,4 +695,492 @@ mod tests {
         // assert_eq!(mock_messenger.sent_messages.len(), 1); // Change for internal mutability
         assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
     }
+
+    #[test]
+    fn it_sends_the_level_with_the_message() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        limit_tracker.set_value(10);
+        limit_tracker.set_value(80);
+        limit_tracker.set_value(95);
+        limit_tracker.set_value(100);
+
+        assert_eq!(
+            *mock_messenger.sent_messages.borrow(),
+            vec![
+                (
+                    QuotaLevel::Warning,
+                    String::from("Warning: quota over 75% (80 of 100 used)")
+                ),
+                (
+                    QuotaLevel::Urgent,
+                    String::from("Urgent warning: quota over 90% (95 of 100 used)")
+                ),
+                (
+                    QuotaLevel::Exceeded,
+                    String::from("Error: quota exceeded! (100 of 100 used)")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn level_from_ratio() {
+        assert_eq!(QuotaLevel::from_ratio(0.0), QuotaLevel::Ok);
+        assert_eq!(QuotaLevel::from_ratio(0.75), QuotaLevel::Warning);
+        assert_eq!(QuotaLevel::from_ratio(0.9), QuotaLevel::Urgent);
+        assert_eq!(QuotaLevel::from_ratio(1.5), QuotaLevel::Exceeded);
+        assert!(QuotaLevel::Urgent > QuotaLevel::Warning);
+        assert_eq!(QuotaLevel::Exceeded.to_string(), "Error: quota exceeded!");
+    }
+
+    #[test]
+    fn it_returns_the_fired_level() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        assert_eq!(limit_tracker.set_value(50), None);
+        assert_eq!(limit_tracker.set_value(75), Some(QuotaLevel::Warning));
+        assert_eq!(limit_tracker.set_value(90), Some(QuotaLevel::Urgent));
+        assert_eq!(limit_tracker.set_value(101), Some(QuotaLevel::Exceeded));
+    }
+
+    #[test]
+    fn it_does_not_repeat_messages_in_the_same_band() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        assert_eq!(limit_tracker.set_value(80), Some(QuotaLevel::Warning));
+        assert_eq!(limit_tracker.set_value(85), None);
+        assert_eq!(limit_tracker.set_value(76), None);
+        assert_eq!(limit_tracker.set_value(91), Some(QuotaLevel::Urgent));
+        assert_eq!(limit_tracker.set_value(99), None);
+
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 2);
+    }
+
+    #[test]
+    fn it_notifies_again_after_dropping_below_the_margin() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        assert_eq!(limit_tracker.set_value(80), Some(QuotaLevel::Warning));
+        // Still within 5% of the 75% threshold, so the warning is kept
+        assert_eq!(limit_tracker.set_value(72), None);
+        assert_eq!(limit_tracker.set_value(78), None);
+        // Under 70%, so the next crossing sends the warning again
+        assert_eq!(limit_tracker.set_value(60), None);
+        assert_eq!(limit_tracker.set_value(78), Some(QuotaLevel::Warning));
+
+        // Lowering to another band notifies the new level
+        assert_eq!(limit_tracker.set_value(100), Some(QuotaLevel::Exceeded));
+        assert_eq!(limit_tracker.set_value(80), Some(QuotaLevel::Warning));
+    }
+
+    #[test]
+    fn it_notifies_again_after_reset() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        limit_tracker.set_hysteresis(0.0);
+
+        assert_eq!(limit_tracker.set_value(95), Some(QuotaLevel::Urgent));
+        assert_eq!(limit_tracker.set_value(95), None);
+        limit_tracker.reset_notifications();
+        assert_eq!(limit_tracker.set_value(95), Some(QuotaLevel::Urgent));
+        // Without margin the level is lowered as soon as the threshold is crossed
+        assert_eq!(limit_tracker.set_value(89), Some(QuotaLevel::Warning));
+    }
+
+    /// Messenger that always fails, used to check that the other messengers still receive the messages
+    struct FailingMessenger;
+
+    impl Messenger for FailingMessenger {
+        fn send(&self, _level: QuotaLevel, _msg: &str) {}
+
+        fn try_send(&self, _level: QuotaLevel, _msg: &str) -> Result<(), SendError> {
+            Err(SendError(String::from("webhook unreachable")))
+        }
+    }
+
+    #[test]
+    fn it_fans_out_to_every_messenger() {
+        let console = MockMessenger::new();
+        let file = MockMessenger::new();
+        let multi_messenger = MultiMessenger::new(vec![&console, &file]);
+        let mut limit_tracker = LimitTracker::new(&multi_messenger, 100);
+
+        limit_tracker.set_value(80);
+
+        assert_eq!(console.sent_messages.borrow().len(), 1);
+        assert_eq!(
+            *console.sent_messages.borrow(),
+            *file.sent_messages.borrow()
+        );
+    }
+
+    #[test]
+    fn it_isolates_failing_messengers() {
+        let console = MockMessenger::new();
+        let webhook = FailingMessenger;
+        let file = MockMessenger::new();
+        let mut multi_messenger = MultiMessenger::default();
+        multi_messenger.push(&console);
+        multi_messenger.push(&webhook);
+        multi_messenger.push(&file);
+
+        let results = multi_messenger.send_all(QuotaLevel::Urgent, "Urgent");
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err(SendError(String::from("webhook unreachable"))),
+                Ok(())
+            ]
+        );
+        assert_eq!(
+            multi_messenger.try_send(QuotaLevel::Urgent, "Urgent"),
+            Err(SendError(String::from("webhook unreachable")))
+        );
+        assert_eq!(console.sent_messages.borrow().len(), 2);
+        assert_eq!(file.sent_messages.borrow().len(), 2);
+    }
+
+    #[test]
+    fn it_tracks_with_a_trait_object() {
+        let mock_messenger = MockMessenger::new();
+        let messenger: &dyn Messenger = &mock_messenger;
+        let mut limit_tracker = LimitTracker::new(messenger, 10);
+
+        assert_eq!(limit_tracker.set_value(10), Some(QuotaLevel::Exceeded));
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+    }
+
+    /// Asynchronous mock that takes some time to deliver each message, like a webhook
+    struct SlowMessenger {
+        sent_messages: RefCell<Vec<QuotaLevel>>,
+    }
+
+    impl AsyncMessenger for SlowMessenger {
+        async fn send(&self, level: QuotaLevel, _msg: &str) {
+            trpl::sleep(Duration::from_millis(50)).await;
+            self.sent_messages.borrow_mut().push(level);
+        }
+    }
+
+    #[test]
+    fn it_awaits_async_delivery() {
+        let slow_messenger = SlowMessenger {
+            sent_messages: RefCell::new(vec![]),
+        };
+        let mut limit_tracker = LimitTracker::new(&slow_messenger, 100);
+
+        trpl::run(async {
+            assert_eq!(limit_tracker.set_value_async(10).await, None);
+            assert_eq!(
+                limit_tracker.set_value_async(92).await,
+                Some(QuotaLevel::Urgent)
+            );
+        });
+
+        assert_eq!(
+            *slow_messenger.sent_messages.borrow(),
+            vec![QuotaLevel::Urgent]
+        );
+    }
+
+    #[test]
+    fn it_does_not_block_other_futures() {
+        let slow_messenger = SlowMessenger {
+            sent_messages: RefCell::new(vec![]),
+        };
+        let mut limit_tracker = LimitTracker::new(&slow_messenger, 100);
+        let ticks = RefCell::new(0);
+
+        trpl::run(async {
+            let notify = limit_tracker.set_value_async(100);
+            let count = async {
+                for _ in 0..3 {
+                    *ticks.borrow_mut() += 1;
+                    trpl::sleep(Duration::from_millis(1)).await;
+                }
+            };
+            // The counter completes while the slow messenger is still delivering
+            let (level, _) = trpl::join(notify, count).await;
+            assert_eq!(level, Some(QuotaLevel::Exceeded));
+        });
+
+        assert_eq!(*ticks.borrow(), 3);
+        assert_eq!(slow_messenger.sent_messages.borrow().len(), 1);
+    }
+
+    #[test]
+    fn it_records_the_history() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        assert_eq!(limit_tracker.peak(), None);
+
+        for value in [10, 95, 40] {
+            limit_tracker.set_value(value);
+        }
+
+        let values: Vec<usize> = limit_tracker.history().iter().map(|u| u.value).collect();
+        assert_eq!(values, vec![10, 95, 40]);
+        assert!(
+            limit_tracker
+                .history()
+                .windows(2)
+                .all(|pair| pair[0].at <= pair[1].at)
+        );
+        assert_eq!(limit_tracker.peak(), Some(95));
+    }
+
+    #[test]
+    fn it_reports_the_time_over_a_threshold() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        // The history is filled directly to have deterministic timestamps
+        limit_tracker.history = vec![
+            Usage {
+                value: 10,
+                at: at(0),
+            },
+            Usage {
+                value: 80,
+                at: at(10),
+            },
+            Usage {
+                value: 95,
+                at: at(15),
+            },
+            Usage {
+                value: 20,
+                at: at(30),
+            },
+            Usage {
+                value: 90,
+                at: at(40),
+            },
+        ];
+
+        assert_eq!(
+            limit_tracker.time_over_until(75, at(45)),
+            Duration::from_secs(25)
+        );
+        assert_eq!(
+            limit_tracker.time_over_until(90, at(45)),
+            Duration::from_secs(20)
+        );
+        assert_eq!(limit_tracker.time_over_until(100, at(45)), Duration::ZERO);
+        assert_eq!(
+            limit_tracker.time_over_until(0, at(45)),
+            Duration::from_secs(45)
+        );
+        assert!(limit_tracker.time_over(90) >= Duration::from_secs(15));
+    }
+
+    #[test]
+    fn token_bucket_refills_over_time() {
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut bucket = TokenBucket::new(2, Duration::from_secs(60), start);
+
+        assert!(bucket.try_take(at(0)));
+        assert!(bucket.try_take(at(1)));
+        assert!(!bucket.try_take(at(30)));
+        assert!(bucket.try_take(at(61)));
+        assert!(!bucket.try_take(at(62)));
+        // The bucket never holds more than its capacity
+        assert!(bucket.try_take(at(1000)));
+        assert!(bucket.try_take(at(1000)));
+        assert!(!bucket.try_take(at(1000)));
+    }
+
+    #[test]
+    fn it_limits_the_rate_of_messages() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        limit_tracker.set_rate_limit(1, Duration::from_secs(60));
+
+        assert_eq!(limit_tracker.set_value(80), Some(QuotaLevel::Warning));
+        assert_eq!(limit_tracker.set_value(95), None);
+        assert_eq!(limit_tracker.set_value(100), None);
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+
+        // The dropped level is sent once the limit is removed
+        limit_tracker.remove_rate_limit();
+        assert_eq!(limit_tracker.set_value(100), Some(QuotaLevel::Exceeded));
+    }
+
+    #[test]
+    fn it_formats_the_usage_with_units() {
+        use units::{Bytes, Money, Requests};
+
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, Bytes(2 * Bytes::GIB.0));
+
+        assert_eq!(limit_tracker.set_value(Bytes(Bytes::GIB.0 * 12 / 10)), None);
+        assert_eq!(
+            limit_tracker.set_value(Bytes(Bytes::GIB.0 * 19 / 10)),
+            Some(QuotaLevel::Urgent)
+        );
+        assert_eq!(
+            mock_messenger.sent_messages.borrow()[0].1,
+            "Urgent warning: quota over 90% (1.9 GiB of 2 GiB used)"
+        );
+        assert_eq!(limit_tracker.peak(), Some(Bytes(Bytes::GIB.0 * 19 / 10)));
+
+        assert_eq!(Bytes(512).to_string(), "512 B");
+        assert_eq!(Bytes(1536).to_string(), "1.5 KiB");
+        assert_eq!(Bytes::MIB.to_string(), "1 MiB");
+        assert_eq!(
+            Requests(75).usage(&Requests(100)),
+            "75 requests of 100 requests used"
+        );
+        assert_eq!(Money(1234).usage(&Money(5000)), "$12.34 of $50.00 used");
+    }
+
+    #[test]
+    fn it_adds_to_the_current_value() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        assert_eq!(limit_tracker.add(50), None);
+        assert_eq!(limit_tracker.add(30), Some(QuotaLevel::Warning));
+        assert_eq!(limit_tracker.add(20), Some(QuotaLevel::Exceeded));
+        assert_eq!(limit_tracker.value(), 100);
+    }
+
+    #[test]
+    fn it_reevaluates_when_the_max_changes() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+        limit_tracker.set_hysteresis(0.0);
+
+        assert_eq!(limit_tracker.set_value(80), Some(QuotaLevel::Warning));
+        // Lowering the maximum moves the same value to a higher level
+        assert_eq!(limit_tracker.set_max(85), Some(QuotaLevel::Urgent));
+        // Raising it moves the value back under the thresholds
+        assert_eq!(limit_tracker.set_max(200), None);
+        assert_eq!(limit_tracker.max(), 200);
+        assert_eq!(limit_tracker.set_max(80), Some(QuotaLevel::Exceeded));
+        assert_eq!(
+            mock_messenger.sent_messages.borrow().last().unwrap().1,
+            "Error: quota exceeded! (80 of 80 used)"
+        );
+        // Changing the maximum doesn't record a new value
+        assert_eq!(limit_tracker.history().len(), 1);
+    }
+
+    #[test]
+    fn it_resets_the_tracker() {
+        let mock_messenger = MockMessenger::new();
+        let mut limit_tracker = LimitTracker::new(&mock_messenger, 100);
+
+        assert_eq!(limit_tracker.set_value(95), Some(QuotaLevel::Urgent));
+        limit_tracker.reset();
+        assert_eq!(limit_tracker.value(), 0);
+        assert_eq!(limit_tracker.history().last().unwrap().value, 0);
+        assert_eq!(mock_messenger.sent_messages.borrow().len(), 1);
+
+        assert_eq!(limit_tracker.add(95), Some(QuotaLevel::Urgent));
+    }
+
+    #[test]
+    fn it_owns_the_messenger() {
+        let mut limit_tracker = LimitTracker::with_messenger(MockMessenger::new(), 100);
+
+        assert_eq!(limit_tracker.set_value(80), Some(QuotaLevel::Warning));
+        assert_eq!(limit_tracker.messenger().sent_messages.borrow().len(), 1);
+    }
+
+    /// Messenger writing in a log shared with the test, so it can be inspected once boxed as a trait object
+    struct SharedLogMessenger(Rc<RefCell<Vec<QuotaLevel>>>);
+
+    impl Messenger for SharedLogMessenger {
+        fn send(&self, level: QuotaLevel, _msg: &str) {
+            self.0.borrow_mut().push(level);
+        }
+    }
+
+    /// Long-lived struct storing the tracker without a borrowed messenger
+    struct Service {
+        quota: LimitTracker<'static, dyn Messenger>,
+    }
+
+    #[test]
+    fn it_owns_a_boxed_trait_object() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let mut service = Service {
+            quota: LimitTracker::from_box(Box::new(SharedLogMessenger(Rc::clone(&log))), 10),
+        };
+
+        service.quota.set_value(9);
+        service.quota.set_value(10);
+
+        assert_eq!(
+            *log.borrow(),
+            vec![QuotaLevel::Urgent, QuotaLevel::Exceeded]
+        );
+    }
+
+    fn greet(name: &str) -> String {
+        format!("Hello {name}")
+    }
+
+    fn shout(name: &mut str) {
+        name.make_ascii_uppercase();
+    }
+
+    #[test]
+    fn my_box_derefs_like_a_box() {
+        let x = 5;
+        let y = MyBox::new(x);
+        assert_eq!(5, *y);
+        assert_eq!(*y.as_ref(), 5);
+        assert_eq!(MyBox::from(7).into_inner(), 7);
+    }
+
+    #[test]
+    fn my_box_coerces_references() {
+        let mut m = MyBox::new(String::from("Rust"));
+        // From `&MyBox<String>` to `&String` and then to `&str`
+        assert_eq!(greet(&m), "Hello Rust");
+        // Without coercion
+        assert_eq!(greet(&(*m)[..]), "Hello Rust");
+        // From `&mut MyBox<String>` to `&mut String` and then to `&mut str`
+        shout(&mut m);
+        // From `&mut MyBox<String>` to `&str`, a mutable reference coerces to an immutable one
+        let name: &mut MyBox<String> = &mut m;
+        assert_eq!(greet(name), "Hello RUST");
+        // Methods are found through `Deref` and `DerefMut` too
+        m.push('!');
+        assert_eq!(m.len(), 5);
+        assert_eq!(m.into_inner(), "RUST!");
+    }
+
+    #[test]
+    fn my_box_calls_the_drop_hook() {
+        let dropped = Rc::new(RefCell::new(vec![]));
+        let log = Rc::clone(&dropped);
+        let mut a = MyBox::with_drop_hook(1, move |value| log.borrow_mut().push(value));
+        *a += 10;
+        drop(a);
+        assert_eq!(*dropped.borrow(), [11]);
+
+        // The hook is not called when the value is taken out
+        let log = Rc::clone(&dropped);
+        let b = MyBox::with_drop_hook(2, move |value| log.borrow_mut().push(value));
+        assert_eq!(b.into_inner(), 2);
+
+        // Only the last hook set is called
+        let mut c = MyBox::new(3);
+        let (first, last) = (Rc::clone(&dropped), Rc::clone(&dropped));
+        c.set_drop_hook(move |value| first.borrow_mut().push(value * 100));
+        c.set_drop_hook(move |value| last.borrow_mut().push(value));
+        drop(c);
+        assert_eq!(*dropped.borrow(), [11, 3]);
+    }
 }
@@ -0,0 +1,301 @@
+//! # Cons lists
+//!
+//! The cons lists of the chapter, generic over the type of the items
+//! `List` owns its tail in a `Box`, so every list has a single owner and pushing an item takes the whole list
+//! `RcList` shares its tail in an `Rc`, so it's persistent: pushing an item creates a new list that shares the old one, which stays valid
+//! Both are dropped with a loop instead of recursively, so a long list doesn't overflow the stack
+
+use std::fmt;
+use std::rc::Rc;
+
+/// Cons list with a single owner, the tail is boxed to give the recursive type a known size
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum List<T> {
+    Cons(T, Box<List<T>>),
+    #[default]
+    Nil,
+}
+
+impl<T> List<T> {
+    /// Constructor of the empty list
+    pub fn new() -> List<T> {
+        List::Nil
+    }
+
+    /// Return the list with the value in front, the list is moved into the new one
+    pub fn push_front(self, value: T) -> List<T> {
+        List::Cons(value, Box::new(self))
+    }
+
+    /// First item, `None` for `Nil`
+    pub fn head(&self) -> Option<&T> {
+        match self {
+            List::Cons(value, _) => Some(value),
+            List::Nil => None,
+        }
+    }
+
+    /// List without the first item, `None` for `Nil`
+    pub fn tail(&self) -> Option<&List<T>> {
+        match self {
+            List::Cons(_, tail) => Some(tail),
+            List::Nil => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, List::Nil)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: Some(self).filter(|list| !list.is_empty()),
+            tail: List::tail,
+            head: List::head,
+        }
+    }
+}
+
+impl<T> Drop for List<T> {
+    /// Unlink the tails one by one, otherwise dropping a box drops the next one inside its own `drop`, recursing once per item
+    /// The fields of a type implementing `Drop` can't be moved out, so each tail is swapped with `Nil` instead
+    fn drop(&mut self) {
+        let mut next = match self {
+            List::Cons(_, tail) => std::mem::replace(&mut **tail, List::Nil),
+            List::Nil => return,
+        };
+        while let List::Cons(_, tail) = &mut next {
+            let after = std::mem::replace(&mut **tail, List::Nil);
+            // The previous item is dropped here, with `Nil` as its tail
+            next = after;
+        }
+    }
+}
+
+/// Cons list with shared tails, the lists built from the same list share its items instead of copying them
+#[derive(Debug, PartialEq, Eq)]
+pub enum RcList<T> {
+    Cons(T, Rc<RcList<T>>),
+    Nil,
+}
+
+impl<T> RcList<T> {
+    /// Constructor of the empty list, already in an `Rc` so it can be shared
+    pub fn new() -> Rc<RcList<T>> {
+        Rc::new(RcList::Nil)
+    }
+
+    /// Return a new list with the value in front of this one, that is shared and not moved
+    pub fn push_front(self: &Rc<Self>, value: T) -> Rc<RcList<T>> {
+        Rc::new(RcList::Cons(value, Rc::clone(self)))
+    }
+
+    /// First item, `None` for `Nil`
+    pub fn head(&self) -> Option<&T> {
+        match self {
+            RcList::Cons(value, _) => Some(value),
+            RcList::Nil => None,
+        }
+    }
+
+    /// List without the first item, as an `Rc` so it can be shared by a new list too
+    pub fn tail(&self) -> Option<&Rc<RcList<T>>> {
+        match self {
+            RcList::Cons(_, tail) => Some(tail),
+            RcList::Nil => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, RcList::Nil)
+    }
+
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T, RcList<T>> {
+        Iter {
+            next: Some(self).filter(|list| !list.is_empty()),
+            tail: |list| list.tail().map(|tail| &**tail),
+            head: RcList::head,
+        }
+    }
+}
+
+impl<T> Drop for RcList<T> {
+    /// Unlink the tails that are not shared, a shared tail is left to the other lists that own it
+    /// The tails are swapped with the same `Nil`, so the loop allocates only once
+    fn drop(&mut self) {
+        let RcList::Cons(_, tail) = self else {
+            return;
+        };
+        let nil = Rc::new(RcList::Nil);
+        let mut next = std::mem::replace(tail, Rc::clone(&nil));
+        // `try_unwrap` fails when the tail is shared, then only its count is decremented
+        while let Ok(mut list) = Rc::try_unwrap(next) {
+            match &mut list {
+                RcList::Cons(_, tail) => next = std::mem::replace(tail, Rc::clone(&nil)),
+                RcList::Nil => break,
+            }
+        }
+    }
+}
+
+/// Iterator over the items of a list, from the head
+/// It walks both kinds of list with the functions returning their head and their tail
+pub struct Iter<'a, T, L = List<T>> {
+    next: Option<&'a L>,
+    tail: fn(&'a L) -> Option<&'a L>,
+    head: fn(&'a L) -> Option<&'a T>,
+}
+
+impl<'a, T, L> Iterator for Iter<'a, T, L> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let list = self.next?;
+        self.next = (self.tail)(list);
+        (self.head)(list)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a List<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a RcList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, RcList<T>>;
+
+    fn into_iter(self) -> Iter<'a, T, RcList<T>> {
+        self.iter()
+    }
+}
+
+/// The items keep the order of the iterator, the first one becomes the head
+impl<T> FromIterator<T> for List<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+        let items: Vec<T> = iter.into_iter().collect();
+        items
+            .into_iter()
+            .rev()
+            .fold(List::Nil, |list, value| list.push_front(value))
+    }
+}
+
+/// Collected into a list that is not shared yet, it can be put in an `Rc` to push items on it
+impl<T> FromIterator<T> for RcList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> RcList<T> {
+        let items: Vec<T> = iter.into_iter().collect();
+        items.into_iter().rev().fold(RcList::Nil, |list, value| {
+            RcList::Cons(value, Rc::new(list))
+        })
+    }
+}
+
+/// Display as nested pairs, like in the chapter: `(1, (2, (3, Nil)))`
+fn write_nested<'a, T: fmt::Display + 'a>(
+    f: &mut fmt::Formatter<'_>,
+    items: impl Iterator<Item = &'a T>,
+) -> fmt::Result {
+    let mut depth = 0;
+    for item in items {
+        write!(f, "({item}, ")?;
+        depth += 1;
+    }
+    write!(f, "Nil{}", ")".repeat(depth))
+}
+
+impl<T: fmt::Display> fmt::Display for List<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_nested(f, self.iter())
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for RcList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_nested(f, self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn box_list_from_the_chapter() {
+        use List::{Cons, Nil};
+
+        let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
+        assert_eq!(list.head(), Some(&1));
+        assert_eq!(list.tail().and_then(List::head), Some(&2));
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.to_string(), "(1, (2, (3, Nil)))");
+        assert_eq!(list, [1, 2, 3].into_iter().collect());
+        assert_eq!(List::new().push_front(3).push_front(2).push_front(1), list);
+    }
+
+    #[test]
+    fn empty_lists() {
+        let list: List<i32> = List::new();
+        assert!(list.is_empty());
+        assert_eq!((list.head(), list.tail(), list.len()), (None, None, 0));
+        assert_eq!(list.to_string(), "Nil");
+
+        let shared: RcList<i32> = std::iter::empty().collect();
+        assert!(shared.is_empty() && shared.tail().is_none());
+    }
+
+    #[test]
+    fn rc_lists_share_their_tail() {
+        let a = Rc::new([5, 10].into_iter().collect::<RcList<i32>>());
+        let b = a.push_front(3);
+        let c = a.push_front(4);
+
+        // `a` is still usable, and owned by `b` and `c` too
+        assert_eq!(Rc::strong_count(&a), 3);
+        assert_eq!(a.to_string(), "(5, (10, Nil))");
+        assert_eq!(b.iter().copied().collect::<Vec<_>>(), [3, 5, 10]);
+        assert_eq!(c.iter().copied().collect::<Vec<_>>(), [4, 5, 10]);
+        assert!(Rc::ptr_eq(b.tail().unwrap(), c.tail().unwrap()));
+
+        drop(b);
+        assert_eq!(Rc::strong_count(&a), 2);
+        drop(c);
+        assert_eq!(Rc::strong_count(&a), 1);
+        assert_eq!(a.len(), 2);
+    }
+
+    #[test]
+    fn items_are_generic() {
+        let words: List<String> = ["a", "b"].into_iter().map(String::from).collect();
+        let lengths: Vec<usize> = words.iter().map(String::len).collect();
+        assert_eq!(lengths, [1, 1]);
+        for word in &words {
+            assert!(!word.is_empty());
+        }
+    }
+
+    #[test]
+    fn long_lists_drop_without_overflowing() {
+        let list: List<u32> = (0..1_000_000).collect();
+        assert_eq!(list.len(), 1_000_000);
+        drop(list);
+
+        let shared = Rc::new((0..1_000_000).collect::<RcList<u32>>());
+        let longer = shared.push_front(7);
+        drop(shared);
+        // The items are still owned by `longer`
+        assert_eq!(longer.len(), 1_000_001);
+    }
+}
@@ -73,17 +73,18 @@ fn box_t() {
         // Because `Box<T>` is a pointer, Rust always knows how much space a `Box<T>` uses, because a pointer size doesn't change on the amount of data it points to.
         // Modifying the Const variant using a `Box<List>` instead of List will point to the next List value on the heap, instead of another List value directly.
         // Conceptually there is still a List, butr this implementation is more like placing items next to each other instead of one insed another.
-        #[derive(Debug)]
-        #[allow(dead_code)]
-        enum List {
-            Cons(i32, Box<List>),
-            Nil,
-        }
-
-        use List::{Cons, Nil};
+        // The library has the same `List`, generic over the type of the items, with methods to use it as a real list
+        use c15_smart_pointers::List::{self, Cons, Nil};
 
         let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
-        println!("{:?}", list)
+        println!("{:?}", list);
+        println!(
+            "{list} has {} items, the first is {:?}",
+            list.len(),
+            list.head()
+        );
+        let list: List<i32> = list.iter().map(|item| item * 10).collect();
+        println!("{list}")
         // The Cons variant needs the size of an i32 plus the space to store the box’s pointer data.
         // The Nil variant stores no values, so it needs less space than the Cons variant.
         // Any List will take up to the size of an i32, plus the size of a box's pointer data, breaking the inifite recursive chain.
@@ -115,34 +116,31 @@ fn defer_trait() {
     }
     // The difference is that y is an instance of `Box<T>` pointing to a copy of the value of 5, rather than a reference to the value of x
     // it is possible to define a smart pointer similar to `Box`
-    struct MyBox<T>(T);
-
-    impl<T> MyBox<T> {
-        fn new(x: T) -> MyBox<T> {
-            MyBox(x)
-        }
-    }
-    // MyBox is a struct with a generic parameter `T`, while the MyBox type is a tuple struct with one element of type T.
+    // The library defines it as a struct with a generic parameter `T`, see `c15_smart_pointers::MyBox`
+    // In the chapter MyBox is a tuple struct with one element of type T: `struct MyBox<T>(T);` with a `new` function returning `MyBox(x)`
+    // The library keeps the value in an `Option<T>` field instead, `value`, so `drop` and `into_inner` can take it out
+    use c15_smart_pointers::MyBox;
 
     let x = 5;
     let y = MyBox::new(x);
 
     assert_eq!(5, x);
     // assert_eq!(5, *y);
-    // MyBox<T> can't be dereference because that ability is not implemented, the `Deref` trait is needed.
-
-    use std::ops::Deref;
-
-    impl<T> Deref for MyBox<T> {
-        type Target = T; // Associated type for the `Deref` trait to use
-
-        // Associated  types are a slightly different  way of declaring a generic parameter
-
-        /// Return a reference to the value to be accessed with the * operator
-        fn deref(&self) -> &Self::Target {
-            &self.0 // Access the first value in a tuple struct
-        }
-    }
+    // MyBox<T> can't be dereference without implementing the `Deref` trait, which the library does as follows:
+    //
+    // impl<T> Deref for MyBox<T> {
+    //     type Target = T; // Associated type for the `Deref` trait to use
+    //
+    //     // Associated  types are a slightly different  way of declaring a generic parameter
+    //
+    //     /// Return a reference to the value to be accessed with the * operator
+    //     fn deref(&self) -> &Self::Target {
+    //         // The value is always there until the pointer is consumed, the chapter's tuple struct returns `&self.0`
+    //         self.value
+    //             .as_ref()
+    //             .expect("the value is only taken when the pointer is consumed")
+    //     }
+    // }
 
     assert_eq!(5, *y); // Now it works
 
@@ -248,15 +246,9 @@ fn rc_t() {
         // In this case the number of references increases, letting `a` and `b` to share ownership of the `Rc<List>`, allowing to create and share ownership with `c` too.
         // The reference count increases each time `Rc::clone` is called, and the data won't be cleaned up until the data within `Rc<List>` has no references to it
 
-        #[derive(Debug)]
-        #[allow(dead_code)]
-        enum List {
-            Cons(i32, Rc<List>),
-            Nil,
-        }
-
+        // The library has this `List` as `RcList`, that is persistent: new lists are created sharing the existing ones
+        use c15_smart_pointers::RcList::{self, Cons, Nil};
         use std::rc::Rc;
-        use List::{Cons, Nil};
 
         let a = Rc::new(Cons(5, Rc::new(Cons(10, Rc::new(Nil))))); // List shared between `b`, and `c`
         let b = Cons(3, Rc::clone(&a)); // The `Rc<List>` in `a` is cloned, allowing multiple ownership
@@ -277,6 +269,34 @@ fn rc_t() {
             println!("Count after c goes out of scope = {}", Rc::strong_count(&a));
         }
         // In this example the reference count can be seen as it increases and decreases base on the `clone` and `drop` operations
+        // The library `TrackedRc` records the same timeline, telling which owner changed the count
+        {
+            use c15_smart_pointers::{Recorder, TrackedRc};
+
+            let recorder = Recorder::new();
+            {
+                let a = TrackedRc::with_recorder(
+                    Cons(5, Rc::new(Cons(10, Rc::new(Nil)))),
+                    "a",
+                    &recorder,
+                );
+                let _b = TrackedRc::clone_as(&a, "b");
+                {
+                    let _c = TrackedRc::clone_as(&a, "c");
+                }
+            }
+            for event in recorder.events() {
+                println!("{event}");
+            }
+        }
+        // `RcList::push_front` clones the `Rc` in the same way:
+        let shared = RcList::new().push_front(10).push_front(5);
+        let d = shared.push_front(3);
+        let e = shared.push_front(4);
+        println!(
+            "{d} and {e} share {shared}, count = {}",
+            Rc::strong_count(&shared)
+        );
         // `Rc<T>` allows to share data between multiple parts of the program reading only
         // If `Rc<T>` allowed modifying it would violate the borrowing rules: multiple mutable borrows to the same place can cause data races and inconsistencies.
     }
@@ -384,6 +404,24 @@ fn memory_leaks() {
     // The reference count is 2 for both `a` and `b`, at the end Rust rops `b`, which decreases the reference count to 1, but it won't be dropped, becaue the reference count should be 0.
     // The same happens to `a`, with reference count at 1 after it's dropped, so both the memory allocated for `a` and `b` will remain uncollected forever.
     // println!("a next item = {:?}", a.tail()); // This overflows the stack
+    // The library `CycleDetector` shows it: once `a` and `b` are dropped, both are still alive and can't be reached
+    {
+        use c15_smart_pointers::{CycleDetector, Links};
+
+        impl Links for List {
+            fn links(&self) -> Vec<Rc<List>> {
+                self.tail()
+                    .map(|link| vec![Rc::clone(&link.borrow())])
+                    .unwrap_or_default()
+            }
+        }
+
+        let mut detector = CycleDetector::new();
+        detector.track_all(&a);
+        drop(a);
+        drop(b);
+        print!("{}", detector.report(&[]));
+    }
     // If a complex program allocated lots of memory the program might overwhelm the system
     // A solution to avoid reference cycles is reorganising the data structures so certain references express ownerwhip and some don't. Only the owners affect if  avalue can be dropped.
     // So `Rc::clone` increases the `strong_count` of an `Rc<T>`, and it will be cleaned only when it reaches 0.
@@ -466,4 +504,16 @@ fn memory_leaks() {
         );
         // All the logic managing the counts and dropping is built into `Rc<T>` and `Weak<T>` and how they implement the `Drop` trait
     }
+    // The library wraps the same nodes in a `Tree`, that links the parent when a child is added
+    {
+        use c15_smart_pointers::{Tree, path_to_root};
+
+        let tree = Tree::new(5);
+        let leaf = tree.add_child(tree.root(), 3);
+        let path: Vec<i32> = path_to_root(&leaf)
+            .iter()
+            .map(|node| *node.value())
+            .collect();
+        println!("path from leaf to root = {path:?}");
+    }
 }
@@ -0,0 +1,175 @@
+//! # Observers
+//!
+//! A registry of observers that doesn't own them, with `Weak<T>` like the parent links of the tree
+//! Whoever subscribes keeps the `Rc` and decides how long the observer lives, the registry never keeps it alive
+//! An observer dropped by its owner is removed the next time the registry notifies or prunes its observers
+
+use std::rc::{Rc, Weak};
+
+/// Receiver of the values sent to an `Observers<T>`
+pub trait Observer<T: ?Sized> {
+    fn notify(&self, value: &T);
+}
+
+/// Closures can observe too, once in an `Rc`
+impl<T: ?Sized, F: Fn(&T)> Observer<T> for F {
+    fn notify(&self, value: &T) {
+        self(value)
+    }
+}
+
+/// Registry of weak references to observers, notified in the order they subscribed
+pub struct Observers<T: ?Sized> {
+    observers: Vec<Weak<dyn Observer<T>>>,
+}
+
+impl<T: ?Sized> Default for Observers<T> {
+    fn default() -> Observers<T> {
+        Observers { observers: vec![] }
+    }
+}
+
+impl<T: ?Sized> Observers<T> {
+    pub fn new() -> Observers<T> {
+        Observers::default()
+    }
+
+    /// Register the observer without taking ownership, it's notified as long as the `Rc` has an owner
+    pub fn subscribe<O: Observer<T> + 'static>(&mut self, observer: &Rc<O>) {
+        let observer: Weak<O> = Rc::downgrade(observer);
+        self.observers.push(observer);
+    }
+
+    /// Remove the observer, returns `false` if it wasn't subscribed
+    pub fn unsubscribe<O: Observer<T> + 'static>(&mut self, observer: &Rc<O>) -> bool {
+        let len = self.observers.len();
+        let target = Rc::as_ptr(observer) as *const ();
+        self.observers
+            .retain(|weak| weak.as_ptr() as *const () != target);
+        self.observers.len() < len
+    }
+
+    /// Send the value to every observer still alive, removing the dropped ones, and return how many were notified
+    pub fn notify(&mut self, value: &T) -> usize {
+        // Upgrading keeps each observer alive while it's notified, even if it drops its last other owner meanwhile
+        self.observers.retain(|weak| match weak.upgrade() {
+            Some(observer) => {
+                observer.notify(value);
+                true
+            }
+            None => false,
+        });
+        self.observers.len()
+    }
+
+    /// Remove the observers that were dropped, without notifying the others
+    pub fn prune(&mut self) {
+        self.observers.retain(|weak| weak.strong_count() > 0);
+    }
+
+    /// Number of observers still alive
+    pub fn len(&self) -> usize {
+        self.observers
+            .iter()
+            .filter(|weak| weak.strong_count() > 0)
+            .count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// Observer keeping the last value it received
+    #[derive(Default)]
+    struct Display {
+        shown: Cell<i32>,
+    }
+
+    impl Observer<i32> for Display {
+        fn notify(&self, value: &i32) {
+            self.shown.set(*value);
+        }
+    }
+
+    #[test]
+    fn live_observers_are_notified_in_order() {
+        let log = Rc::new(RefCell::new(vec![]));
+        let first = {
+            let log = Rc::clone(&log);
+            Rc::new(move |value: &i32| log.borrow_mut().push(("first", *value)))
+        };
+        let second = {
+            let log = Rc::clone(&log);
+            Rc::new(move |value: &i32| log.borrow_mut().push(("second", *value)))
+        };
+        let display = Rc::new(Display::default());
+
+        let mut observers = Observers::new();
+        observers.subscribe(&first);
+        observers.subscribe(&second);
+        observers.subscribe(&display);
+
+        assert_eq!(observers.notify(&7), 3);
+        assert_eq!(*log.borrow(), [("first", 7), ("second", 7)]);
+        assert_eq!(display.shown.get(), 7);
+    }
+
+    #[test]
+    fn dropped_observers_are_pruned() {
+        let display = Rc::new(Display::default());
+        let temporary = Rc::new(Display::default());
+        let mut observers = Observers::new();
+        observers.subscribe(&display);
+        observers.subscribe(&temporary);
+
+        // The registry holds weak references only
+        assert_eq!(
+            (Rc::strong_count(&temporary), Rc::weak_count(&temporary)),
+            (1, 1)
+        );
+        drop(temporary);
+        assert_eq!(observers.len(), 1);
+        assert_eq!(observers.observers.len(), 2);
+
+        assert_eq!(observers.notify(&1), 1);
+        assert_eq!(observers.observers.len(), 1);
+
+        drop(display);
+        observers.prune();
+        assert!(observers.is_empty() && observers.observers.is_empty());
+        assert_eq!(observers.notify(&2), 0);
+    }
+
+    #[test]
+    fn observers_can_unsubscribe() {
+        let display = Rc::new(Display::default());
+        let other = Rc::new(Display::default());
+        let mut observers = Observers::new();
+        observers.subscribe(&display);
+
+        assert!(!observers.unsubscribe(&other));
+        assert!(observers.unsubscribe(&display));
+        observers.notify(&3);
+        assert_eq!(display.shown.get(), 0);
+    }
+
+    #[test]
+    fn unsized_values_can_be_observed() {
+        let seen = Rc::new(RefCell::new(String::new()));
+        let observer = {
+            let seen = Rc::clone(&seen);
+            Rc::new(move |text: &str| seen.borrow_mut().push_str(text))
+        };
+        let mut observers: Observers<str> = Observers::new();
+        observers.subscribe(&observer);
+        observers.notify("hello ");
+        observers.notify("world");
+        assert_eq!(*seen.borrow(), "hello world");
+    }
+}
@@ -0,0 +1,252 @@
+//! # Tracked Rc
+//!
+//! A wrapper around `Rc<T>` that records every clone and drop, to check how the reference count changes over time
+//! Each handle has a label, so the events tell which owner was created or dropped, e.g. `b` and `c` in the chapter
+//! The events go to a `Recorder`, either one given to the first handle or the global one of the current thread
+//! The clones share the recorder of the handle they were cloned from
+
+use std::cell::RefCell;
+use std::fmt;
+use std::ops::Deref;
+use std::rc::Rc;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    Created,
+    Cloned,
+    Dropped,
+}
+
+/// Change of the reference count of a value, by the handle with the label
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Event {
+    pub kind: EventKind,
+    pub label: String,
+    /// Strong count right after the event
+    pub strong: usize,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            EventKind::Created => "created",
+            EventKind::Cloned => "cloned",
+            EventKind::Dropped => "dropped",
+        };
+        write!(f, "{} {kind}, strong = {}", self.label, self.strong)
+    }
+}
+
+/// Timeline of the events of all the handles using it, in the order they happened
+#[derive(Debug, Default)]
+pub struct Recorder {
+    events: RefCell<Vec<Event>>,
+}
+
+thread_local! {
+    /// Recorder of the handles created without one, one per thread so the tests running in parallel don't mix their events
+    static GLOBAL: Rc<Recorder> = Rc::new(Recorder::default());
+}
+
+impl Recorder {
+    pub fn new() -> Rc<Recorder> {
+        Rc::new(Recorder::default())
+    }
+
+    /// The recorder of the current thread, used by `TrackedRc::new`
+    pub fn global() -> Rc<Recorder> {
+        GLOBAL.with(Rc::clone)
+    }
+
+    fn record(&self, kind: EventKind, label: &str, strong: usize) {
+        self.events.borrow_mut().push(Event {
+            kind,
+            label: String::from(label),
+            strong,
+        });
+    }
+
+    pub fn events(&self) -> Vec<Event> {
+        self.events.borrow().clone()
+    }
+
+    /// Return the events recorded so far and start a new timeline
+    pub fn take(&self) -> Vec<Event> {
+        self.events.take()
+    }
+
+    /// The events as lines of text, handy to compare with the expected timeline
+    pub fn timeline(&self) -> Vec<String> {
+        self.events.borrow().iter().map(Event::to_string).collect()
+    }
+}
+
+/// Handle to a shared value like `Rc<T>`, with a label and a recorder for its events
+pub struct TrackedRc<T> {
+    inner: Rc<T>,
+    label: String,
+    recorder: Rc<Recorder>,
+}
+
+impl<T> TrackedRc<T> {
+    /// Constructor recording into the global recorder of the thread
+    pub fn new(value: T, label: &str) -> TrackedRc<T> {
+        TrackedRc::with_recorder(value, label, &Recorder::global())
+    }
+
+    /// Constructor recording into the given recorder
+    pub fn with_recorder(value: T, label: &str, recorder: &Rc<Recorder>) -> TrackedRc<T> {
+        let tracked = TrackedRc {
+            inner: Rc::new(value),
+            label: String::from(label),
+            recorder: Rc::clone(recorder),
+        };
+        tracked.record(EventKind::Created, 1);
+        tracked
+    }
+
+    /// Clone the handle with a label of its own, like `Rc::clone`
+    pub fn clone_as(this: &TrackedRc<T>, label: &str) -> TrackedRc<T> {
+        let clone = TrackedRc {
+            inner: Rc::clone(&this.inner),
+            label: String::from(label),
+            recorder: Rc::clone(&this.recorder),
+        };
+        clone.record(EventKind::Cloned, Rc::strong_count(&clone.inner));
+        clone
+    }
+
+    pub fn label(this: &TrackedRc<T>) -> &str {
+        &this.label
+    }
+
+    pub fn strong_count(this: &TrackedRc<T>) -> usize {
+        Rc::strong_count(&this.inner)
+    }
+
+    pub fn ptr_eq(this: &TrackedRc<T>, other: &TrackedRc<T>) -> bool {
+        Rc::ptr_eq(&this.inner, &other.inner)
+    }
+
+    fn record(&self, kind: EventKind, strong: usize) {
+        self.recorder.record(kind, &self.label, strong);
+    }
+}
+
+/// A plain clone keeps the label, `TrackedRc::clone_as` gives it a new one
+impl<T> Clone for TrackedRc<T> {
+    fn clone(&self) -> TrackedRc<T> {
+        TrackedRc::clone_as(self, &self.label)
+    }
+}
+
+impl<T> Drop for TrackedRc<T> {
+    /// Recorded before the `Rc` is dropped, with the count it will have after
+    fn drop(&mut self) {
+        self.record(EventKind::Dropped, Rc::strong_count(&self.inner) - 1);
+    }
+}
+
+impl<T> Deref for TrackedRc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TrackedRc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TrackedRc")
+            .field("label", &self.label)
+            .field("value", &*self.inner)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RcList;
+
+    #[test]
+    fn timeline_of_the_chapter() {
+        let recorder = Recorder::new();
+        {
+            let a = TrackedRc::with_recorder(
+                [5, 10].into_iter().collect::<RcList<i32>>(),
+                "a",
+                &recorder,
+            );
+            let _b = TrackedRc::clone_as(&a, "b");
+            {
+                let _c = TrackedRc::clone_as(&a, "c");
+            }
+            assert_eq!(TrackedRc::strong_count(&a), 2);
+        }
+        // The locals are dropped in reverse order of declaration
+        assert_eq!(
+            recorder.timeline(),
+            [
+                "a created, strong = 1",
+                "b cloned, strong = 2",
+                "c cloned, strong = 3",
+                "c dropped, strong = 2",
+                "b dropped, strong = 1",
+                "a dropped, strong = 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn clones_keep_the_label_and_recorder() {
+        let recorder = Recorder::new();
+        let a = TrackedRc::with_recorder(String::from("shared"), "a", &recorder);
+        let also_a = a.clone();
+        assert_eq!(TrackedRc::label(&also_a), "a");
+        assert!(TrackedRc::ptr_eq(&a, &also_a));
+        assert_eq!(also_a.len(), 6);
+        drop(a);
+
+        assert_eq!(
+            recorder.take(),
+            [
+                Event {
+                    kind: EventKind::Created,
+                    label: String::from("a"),
+                    strong: 1,
+                },
+                Event {
+                    kind: EventKind::Cloned,
+                    label: String::from("a"),
+                    strong: 2,
+                },
+                Event {
+                    kind: EventKind::Dropped,
+                    label: String::from("a"),
+                    strong: 1,
+                },
+            ]
+        );
+        drop(also_a);
+        assert_eq!(recorder.timeline(), ["a dropped, strong = 0"]);
+    }
+
+    #[test]
+    fn the_global_recorder_is_per_thread() {
+        Recorder::global().take();
+        let value = TrackedRc::new(1, "value");
+        std::thread::spawn(|| {
+            let other = TrackedRc::new(2, "other");
+            drop(other);
+            assert_eq!(Recorder::global().events().len(), 2);
+        })
+        .join()
+        .unwrap();
+        drop(value);
+        assert_eq!(
+            Recorder::global().timeline(),
+            ["value created, strong = 1", "value dropped, strong = 0"]
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! # Tree
+//!
+//! The tree of the chapter, where the nodes own their children with `Rc` and point to their parent with `Weak`
+//! The children are in a `RefCell` so they can be added and removed through shared references
+//! A parent link never keeps a node alive, so a node that outlived its tree simply has no parent any more
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::{Rc, Weak};
+
+/// Node holding a value, its children and a weak link to its parent
+#[derive(Debug)]
+pub struct Node<T> {
+    value: T,
+    children: RefCell<Vec<Rc<Node<T>>>>,
+    parent: RefCell<Weak<Node<T>>>,
+}
+
+impl<T> Node<T> {
+    fn new(value: T) -> Rc<Node<T>> {
+        Rc::new(Node {
+            value,
+            children: RefCell::new(vec![]),
+            parent: RefCell::new(Weak::new()),
+        })
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Parent of the node, `None` for the root, a removed node, or when the parent was dropped
+    pub fn parent(&self) -> Option<Rc<Node<T>>> {
+        self.parent.borrow().upgrade()
+    }
+
+    /// Children of the node, cloned so the tree can be changed while they're used
+    pub fn children(&self) -> Vec<Rc<Node<T>>> {
+        self.children.borrow().clone()
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.children.borrow().is_empty()
+    }
+}
+
+/// Tree owning its root, and through it all the nodes
+#[derive(Debug)]
+pub struct Tree<T> {
+    root: Rc<Node<T>>,
+}
+
+impl<T> Tree<T> {
+    /// Constructor of a tree with a single node
+    pub fn new(root: T) -> Tree<T> {
+        Tree {
+            root: Node::new(root),
+        }
+    }
+
+    pub fn root(&self) -> &Rc<Node<T>> {
+        &self.root
+    }
+
+    /// Add a node with the value as the last child of the parent, and return it
+    pub fn add_child(&self, parent: &Rc<Node<T>>, value: T) -> Rc<Node<T>> {
+        let child = Node::new(value);
+        *child.parent.borrow_mut() = Rc::downgrade(parent);
+        parent.children.borrow_mut().push(Rc::clone(&child));
+        child
+    }
+
+    /// Detach the child from the parent, with all its descendants
+    /// The child is dropped with the last `Rc` pointing to it, returns `false` if it wasn't a child of the parent
+    pub fn remove_child(&self, parent: &Rc<Node<T>>, child: &Rc<Node<T>>) -> bool {
+        let mut children = parent.children.borrow_mut();
+        let Some(index) = children.iter().position(|node| Rc::ptr_eq(node, child)) else {
+            return false;
+        };
+        children.remove(index);
+        *child.parent.borrow_mut() = Weak::new();
+        true
+    }
+
+    /// Iterator over the nodes in pre-order: each node comes before its children, the children in order
+    pub fn depth_first(&self) -> DepthFirst<T> {
+        DepthFirst {
+            stack: vec![Rc::clone(&self.root)],
+        }
+    }
+
+    /// Iterator over the nodes level by level, from the root
+    pub fn breadth_first(&self) -> BreadthFirst<T> {
+        BreadthFirst {
+            queue: VecDeque::from([Rc::clone(&self.root)]),
+        }
+    }
+
+    /// Number of nodes, the root included
+    pub fn len(&self) -> usize {
+        self.depth_first().count()
+    }
+
+    /// Always `false`, a tree has at least its root
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// Nodes from the given one up to the root, both included
+/// Every parent link is upgraded, and the path stops at the first parent that was dropped
+pub fn path_to_root<T>(node: &Rc<Node<T>>) -> Vec<Rc<Node<T>>> {
+    let mut path = vec![Rc::clone(node)];
+    while let Some(parent) = path.last().and_then(|node| node.parent()) {
+        path.push(parent);
+    }
+    path
+}
+
+/// Depth-first iterator, the nodes are `Rc` clones so they can be kept after the iteration
+pub struct DepthFirst<T> {
+    stack: Vec<Rc<Node<T>>>,
+}
+
+impl<T> Iterator for DepthFirst<T> {
+    type Item = Rc<Node<T>>;
+
+    fn next(&mut self) -> Option<Rc<Node<T>>> {
+        let node = self.stack.pop()?;
+        // Pushed in reverse, so the first child is the next one popped
+        self.stack
+            .extend(node.children.borrow().iter().rev().map(Rc::clone));
+        Some(node)
+    }
+}
+
+/// Breadth-first iterator, the nodes are `Rc` clones so they can be kept after the iteration
+pub struct BreadthFirst<T> {
+    queue: VecDeque<Rc<Node<T>>>,
+}
+
+impl<T> Iterator for BreadthFirst<T> {
+    type Item = Rc<Node<T>>;
+
+    fn next(&mut self) -> Option<Rc<Node<T>>> {
+        let node = self.queue.pop_front()?;
+        self.queue
+            .extend(node.children.borrow().iter().map(Rc::clone));
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tree with values showing the position of the nodes:
+    ///
+    /// ```text
+    ///       1
+    ///     /   \
+    ///    2     3
+    ///   / \     \
+    ///  4   5     6
+    /// ```
+    fn sample() -> (Tree<i32>, Vec<Rc<Node<i32>>>) {
+        let tree = Tree::new(1);
+        let root = Rc::clone(tree.root());
+        let two = tree.add_child(&root, 2);
+        let three = tree.add_child(&root, 3);
+        let four = tree.add_child(&two, 4);
+        let five = tree.add_child(&two, 5);
+        let six = tree.add_child(&three, 6);
+        (tree, vec![root, two, three, four, five, six])
+    }
+
+    fn values(nodes: impl IntoIterator<Item = Rc<Node<i32>>>) -> Vec<i32> {
+        nodes.into_iter().map(|node| *node.value()).collect()
+    }
+
+    #[test]
+    fn branch_and_leaf_from_the_chapter() {
+        let tree = Tree::new(5);
+        let branch = tree.root();
+        let leaf = tree.add_child(branch, 3);
+
+        assert_eq!(leaf.parent().map(|parent| *parent.value()), Some(5));
+        assert!(leaf.is_leaf() && !branch.is_leaf());
+        // The parent link is weak, only `branch.children` and `leaf` own the leaf
+        assert_eq!((Rc::strong_count(&leaf), Rc::weak_count(&leaf)), (2, 0));
+        assert_eq!((Rc::strong_count(branch), Rc::weak_count(branch)), (1, 1));
+    }
+
+    #[test]
+    fn iterators_visit_every_node_once() {
+        let (tree, _) = sample();
+        assert_eq!(values(tree.depth_first()), [1, 2, 4, 5, 3, 6]);
+        assert_eq!(values(tree.breadth_first()), [1, 2, 3, 4, 5, 6]);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(values(Tree::new(0).depth_first()), [0]);
+    }
+
+    #[test]
+    fn paths_go_up_to_the_root() {
+        let (tree, nodes) = sample();
+        assert_eq!(values(path_to_root(&nodes[4])), [5, 2, 1]);
+        assert_eq!(values(path_to_root(&nodes[5])), [6, 3, 1]);
+        assert_eq!(values(path_to_root(tree.root())), [1]);
+    }
+
+    #[test]
+    fn removed_children_take_their_descendants() {
+        let (tree, nodes) = sample();
+        let (root, two) = (&nodes[0], &nodes[1]);
+
+        assert!(tree.remove_child(root, two));
+        assert!(!tree.remove_child(root, two));
+        assert_eq!(values(tree.depth_first()), [1, 3, 6]);
+        assert!(two.parent().is_none());
+        // The removed subtree is still whole
+        assert_eq!(values(path_to_root(&nodes[3])), [4, 2]);
+        assert_eq!(values(root.children()), [3]);
+    }
+
+    #[test]
+    fn dropped_parents_end_the_path() {
+        let (tree, mut nodes) = sample();
+        let five = nodes.remove(4);
+        // The tree and every other handle are dropped, only `five` is left
+        drop(nodes);
+        drop(tree);
+        assert!(five.parent().is_none());
+        assert_eq!(values(path_to_root(&five)), [5]);
+    }
+
+    #[test]
+    fn nodes_can_be_added_while_iterating() {
+        let (tree, _) = sample();
+        for node in tree.breadth_first().collect::<Vec<_>>() {
+            if node.is_leaf() {
+                tree.add_child(&node, node.value() * 10);
+            }
+        }
+        assert_eq!(values(tree.breadth_first()), [1, 2, 3, 4, 5, 6, 40, 50, 60]);
+    }
+}
@@ -0,0 +1,93 @@
+//! Benchmarks of the `Rc` tree against the arena, run with `cargo test --test bench -- --include-ignored --show-output`
+//! They only print the timings, the comparison of the inserts is ignored by default as its result depends on the load of the machine
+
+use std::rc::Rc;
+
+use c11_automated_tests::bench::time_it;
+use c15_smart_pointers::{Arena, NodeId, Tree};
+
+/// Number of nodes of the trees, each node with up to 4 children
+const NODES: usize = 10_000;
+const CHILDREN: usize = 4;
+
+/// Each node is added to the first node with less than 4 children, so the tree is filled level by level
+fn rc_tree() -> Tree<usize> {
+    let tree = Tree::new(0);
+    let mut parents = vec![Rc::clone(tree.root())];
+    for value in 1..NODES {
+        let parent = &parents[(value - 1) / CHILDREN];
+        let child = tree.add_child(parent, value);
+        parents.push(child);
+    }
+    tree
+}
+
+fn arena_tree() -> Arena<usize> {
+    let mut arena = Arena::new(0);
+    let mut parents = vec![arena.root()];
+    for value in 1..NODES {
+        let parent = parents[(value - 1) / CHILDREN];
+        let child = arena.add_child(parent, value);
+        parents.push(child);
+    }
+    arena
+}
+
+fn id_value(arena: &Arena<usize>, id: NodeId) -> usize {
+    *arena.value(id).unwrap()
+}
+
+#[test]
+fn both_trees_have_the_same_shape() {
+    let tree = rc_tree();
+    let arena = arena_tree();
+    let rc_values: Vec<usize> = tree.depth_first().map(|node| *node.value()).collect();
+    let arena_values: Vec<usize> = arena.depth_first().map(|id| id_value(&arena, id)).collect();
+    assert_eq!(rc_values, arena_values);
+    assert_eq!((tree.len(), arena.len()), (NODES, NODES));
+}
+
+#[test]
+#[ignore = "depends on the load of the machine"]
+fn arena_inserts_faster() {
+    let rc_stats = time_it(20, rc_tree);
+    let arena_stats = time_it(20, arena_tree);
+    println!("insert {NODES} nodes in the Rc tree, {rc_stats}");
+    println!("insert {NODES} nodes in the arena, {arena_stats}");
+
+    // The `Rc` tree allocates every node on its own, the arena grows a single `Vec`
+    assert!(arena_stats.min < rc_stats.min);
+}
+
+#[test]
+fn traversals_visit_the_same_nodes() {
+    let tree = rc_tree();
+    let arena = arena_tree();
+
+    let rc_stats = time_it(20, || {
+        tree.depth_first().map(|node| *node.value()).sum::<usize>()
+    });
+    let arena_stats = time_it(20, || {
+        arena
+            .depth_first()
+            .map(|id| id_value(&arena, id))
+            .sum::<usize>()
+    });
+    println!("depth first over the Rc tree, {rc_stats}");
+    println!("depth first over the arena, {arena_stats}");
+
+    // The `Rc` iterator clones every node and borrows every `RefCell`, the arena copies indices
+    // That is only a few percent in a debug build, too close to the noise to be asserted
+    let sum = NODES * (NODES - 1) / 2;
+    assert_eq!(
+        tree.depth_first().map(|node| *node.value()).sum::<usize>(),
+        sum
+    );
+    assert_eq!(
+        arena
+            .depth_first()
+            .map(|id| id_value(&arena, id))
+            .sum::<usize>(),
+        sum
+    );
+}
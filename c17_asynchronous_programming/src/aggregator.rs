@@ -0,0 +1,85 @@
+//! Stream source for the media aggregator of chapter 10.
+//!
+//! The items of a stream, like the messages of `get_messages`, are converted into items implementing `Summary`
+//! and pushed into a `Feed` one at a time, as soon as they arrive, so the subscribers of the feed see them live
+//! instead of once the whole stream is over.
+
+use std::pin::pin;
+
+use c10_generics_traits_lifetimes::{Feed, Summary};
+use trpl::{Stream, StreamExt};
+
+/// A text message received from a stream, as an item of the aggregator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StreamMessage {
+    /// The name of the stream the message comes from, used as the author.
+    pub source: String,
+    pub content: String,
+}
+
+impl StreamMessage {
+    pub fn new(source: impl Into<String>, content: impl Into<String>) -> Self {
+        StreamMessage {
+            source: source.into(),
+            content: content.into(),
+        }
+    }
+}
+
+impl Summary for StreamMessage {
+    fn summarise(&self) -> String {
+        format!("[{}] {}", self.source, self.content)
+    }
+
+    fn summarise_author(&self) -> String {
+        self.source.clone()
+    }
+}
+
+/// Pushes every item of the stream into the feed as it arrives, converted by `to_item`.
+///
+/// The subscribers of the feed are notified by `Feed::push` while the stream is still running.
+///
+/// # Arguments
+///
+/// * `stream: Stream` - The source of the items, consumed until it ends.
+/// * `feed: &mut Feed` - The feed receiving the items.
+/// * `to_item: FnMut(S::Item) -> T` - Converts each item of the stream into an item of the aggregator.
+///
+/// # Returns
+///
+/// * `usize`: The number of items pushed into the feed
+pub async fn feed_from_stream<S, T, F>(stream: S, feed: &mut Feed, mut to_item: F) -> usize
+where
+    S: Stream,
+    T: Summary + Send + 'static,
+    F: FnMut(S::Item) -> T,
+{
+    // The stream may hold futures, pinning it here allows to call `next` on any stream
+    let mut stream = pin!(stream);
+    let mut pushed = 0;
+    while let Some(item) = stream.next().await {
+        feed.push(to_item(item));
+        pushed += 1;
+    }
+    pushed
+}
+
+/// Pushes every message of a stream of text into the feed as a [`StreamMessage`] from `source`.
+///
+/// # Arguments
+///
+/// * `stream: Stream<Item = String>` - The messages, e.g. from `get_messages`.
+/// * `source: &str` - The name of the stream, the author of the messages.
+/// * `feed: &mut Feed` - The feed receiving the messages.
+///
+/// # Returns
+///
+/// * `usize`: The number of messages pushed into the feed
+pub async fn feed_messages<S>(stream: S, source: &str, feed: &mut Feed) -> usize
+where
+    S: Stream<Item = String>,
+{
+    feed_from_stream(stream, feed, |content| StreamMessage::new(source, content)).await
+}
@@ -0,0 +1,156 @@
+//! A broadcast channel, where every receiver gets every message.
+//!
+//! With `trpl::channel` each message goes to a single receiver. Here each receiver has its own position
+//! in a shared buffer of the last `capacity` messages, so a slow receiver can fall behind: the
+//! [`LagPolicy`] decides whether it silently skips the messages it missed, or is told about them.
+
+use std::{error::Error, fmt};
+
+use tokio::sync::broadcast::{self, error::RecvError};
+use trpl::{Stream, StreamExt};
+
+/// What a receiver gets when it falls behind by more than the capacity of the channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LagPolicy {
+    /// The oldest messages are dropped, and the receiver goes on from the oldest one still in the buffer.
+    DropOldest,
+    /// The receiver gets a [`Lagged`] error with the number of messages it missed, then goes on like with `DropOldest`.
+    Error,
+}
+
+/// The error received by a receiver that missed some messages, with [`LagPolicy::Error`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lagged(pub u64);
+
+impl fmt::Display for Lagged {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the receiver lagged behind and missed {} messages",
+            self.0
+        )
+    }
+}
+
+impl Error for Lagged {}
+
+/// Creates a broadcast channel holding up to `capacity` messages not yet received by every receiver.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+pub fn broadcast<T: Clone>(
+    capacity: usize,
+    policy: LagPolicy,
+) -> (BroadcastSender<T>, BroadcastReceiver<T>) {
+    assert!(capacity > 0, "the channel must hold at least one message");
+    let (tx, rx) = broadcast::channel(capacity);
+    (
+        BroadcastSender { tx, policy },
+        BroadcastReceiver { rx, policy },
+    )
+}
+
+/// The sending side of a broadcast channel, it can be cloned to send from more tasks.
+#[derive(Debug, Clone)]
+pub struct BroadcastSender<T> {
+    tx: broadcast::Sender<T>,
+    policy: LagPolicy,
+}
+
+impl<T: Clone> BroadcastSender<T> {
+    /// Sends a message to all the current receivers, returning how many they are.
+    ///
+    /// Sending never waits: when the buffer is full the oldest message is overwritten.
+    /// If there are no receivers the message is given back.
+    pub fn send(&self, message: T) -> Result<usize, T> {
+        self.tx.send(message).map_err(|error| error.0)
+    }
+
+    /// Creates a new receiver, getting the messages sent from now on.
+    pub fn subscribe(&self) -> BroadcastReceiver<T> {
+        BroadcastReceiver {
+            rx: self.tx.subscribe(),
+            policy: self.policy,
+        }
+    }
+
+    /// Returns the number of receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.tx.receiver_count()
+    }
+}
+
+/// The receiving side of a broadcast channel.
+#[derive(Debug)]
+pub struct BroadcastReceiver<T> {
+    rx: broadcast::Receiver<T>,
+    policy: LagPolicy,
+}
+
+impl<T: Clone> BroadcastReceiver<T> {
+    /// Waits for the next message, returns `None` once all the senders are dropped and the messages received.
+    pub async fn recv(&mut self) -> Option<Result<T, Lagged>> {
+        loop {
+            match self.rx.recv().await {
+                Ok(message) => return Some(Ok(message)),
+                Err(RecvError::Closed) => return None,
+                Err(RecvError::Lagged(missed)) => match self.policy {
+                    LagPolicy::DropOldest => continue,
+                    LagPolicy::Error => return Some(Err(Lagged(missed))),
+                },
+            }
+        }
+    }
+
+    /// Turns the receiver into a stream of the messages, like `ReceiverStream` for `trpl::channel`.
+    pub fn into_stream(self) -> impl Stream<Item = Result<T, Lagged>> {
+        futures::stream::unfold(self, |mut receiver| async move {
+            let message = receiver.recv().await?;
+            Some((message, receiver))
+        })
+    }
+}
+
+/// Feeds the items of a stream to `receivers` consumers, each of them getting every item.
+///
+/// The stream is consumed by a spawned task, so it must be called while the runtime is running.
+/// The receivers are created before the task starts, so none of them misses the first items,
+/// and their streams end once the source ends.
+///
+/// # Arguments
+///
+/// * `source: Stream` - The stream to share, such as `get_messages()`.
+/// * `receivers: usize` - The number of consumers.
+/// * `capacity: usize` - The number of items kept for the slowest consumer.
+/// * `policy: LagPolicy` - What a consumer falling behind gets.
+///
+/// # Returns
+///
+/// * `Vec<BroadcastReceiver<S::Item>>`: One receiver for each consumer.
+pub fn spawn_broadcast<S>(
+    source: S,
+    receivers: usize,
+    capacity: usize,
+    policy: LagPolicy,
+) -> Vec<BroadcastReceiver<S::Item>>
+where
+    S: Stream + Send + 'static,
+    S::Item: Clone + Send + 'static,
+{
+    let (tx, rx) = broadcast(capacity, policy);
+    let mut all = vec![rx];
+    all.extend((1..receivers).map(|_| tx.subscribe()));
+    all.truncate(receivers);
+
+    trpl::spawn_task(async move {
+        let mut source = Box::pin(source);
+        while let Some(item) = source.next().await {
+            // Without receivers left there is nobody to send to
+            if tx.send(item).is_err() {
+                break;
+            }
+        }
+    });
+    all
+}
@@ -0,0 +1,94 @@
+//! Cooperative cancellation of async tasks.
+//!
+//! A task can't be stopped from the outside in the middle of its work, but it can check a shared token
+//! at its await points: the clones of a [`CancellationToken`] share the same state, so one of them
+//! can be given to the task, and another one kept to cancel it.
+
+use std::{
+    future::{self, Future},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Poll, Waker},
+};
+
+use trpl::Either;
+
+#[derive(Debug, Default)]
+struct State {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// A token signalling that some work should stop.
+///
+/// Once cancelled, a token stays cancelled. Cloning the token doesn't create a new one,
+/// all the clones are cancelled together.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<State>,
+}
+
+impl CancellationToken {
+    /// Creates a new token, not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and all its clones, waking up the tasks awaiting [`CancellationToken::cancelled`].
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        let wakers = std::mem::take(&mut *self.state.wakers.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// Returns `true` if the token, or any of its clones, has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns a future completing once the token is cancelled, right away if it already is.
+    pub fn cancelled(&self) -> impl Future<Output = ()> + '_ {
+        future::poll_fn(|cx| {
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            let mut wakers = self.state.wakers.lock().unwrap();
+            // The token could have been cancelled while waiting for the lock, after the wakers were taken
+            if self.is_cancelled() {
+                return Poll::Ready(());
+            }
+            if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+    }
+}
+
+/// Runs a future until it completes or the token is cancelled, whichever comes first.
+///
+/// # Arguments
+///
+/// * `future: Future` - The future to run, dropped without being polled again if the token is cancelled.
+/// * `token: &CancellationToken` - The token stopping the future.
+///
+/// # Returns
+///
+/// * `Option<F::Output>`: `Some` with the output of the future, or `None` if it was cancelled.
+///   If the token is already cancelled, the future is not polled at all.
+pub async fn run_until_cancelled<F: Future>(
+    future: F,
+    token: &CancellationToken,
+) -> Option<F::Output> {
+    if token.is_cancelled() {
+        return None;
+    }
+    match trpl::race(future, token.cancelled()).await {
+        Either::Left(output) => Some(output),
+        Either::Right(()) => None,
+    }
+}
@@ -0,0 +1,236 @@
+//! Combinators working on many futures at once, generalizing the `join` and `race` functions of `trpl`.
+
+use std::{error::Error, fmt, future::Future, time::Duration};
+
+use futures::{future, stream::FuturesUnordered};
+use trpl::{Either, StreamExt};
+
+/// Awaits all the futures, polling at most `max_in_flight` of them at the same time.
+///
+/// As soon as a future completes, the next one is started, so a slow future doesn't hold back the others
+/// like it would when awaiting the futures in chunks. The futures not started yet are not polled at all,
+/// so pointing the page-title scraper at hundreds of URLs only opens `max_in_flight` connections at a time.
+///
+/// # Arguments
+///
+/// * `futures: IntoIterator<Item = Future>` - The futures to await, of the same type as in `trpl::join_all`.
+/// * `max_in_flight: usize` - Maximum number of futures polled at the same time.
+///
+/// # Returns
+///
+/// * `Vec<Output>`: The outputs of the futures, in the same order as the input, like `trpl::join_all`.
+///
+/// # Panics
+///
+/// Panics if `max_in_flight` is 0, since no future could ever make progress.
+pub async fn join_all_limited<I>(
+    futures: I,
+    max_in_flight: usize,
+) -> Vec<<I::Item as Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    assert!(max_in_flight > 0, "at least one future must be in flight");
+    // Each future is tagged with its index, so the outputs can be put back in order
+    let mut pending = futures
+        .into_iter()
+        .enumerate()
+        .map(|(index, future)| async move { (index, future.await) });
+    let mut in_flight: FuturesUnordered<_> = pending.by_ref().take(max_in_flight).collect();
+    let mut outputs = Vec::new();
+
+    while let Some(output) = in_flight.next().await {
+        outputs.push(output);
+        if let Some(future) = pending.next() {
+            in_flight.push(future);
+        }
+    }
+
+    outputs.sort_by_key(|(index, _)| *index);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Resolves when the first of many futures completes, and drops the others.
+///
+/// Like `trpl::race` the futures are polled in order, so when more of them are ready the first one wins.
+/// The futures don't need to be pinned by the caller, they are pinned on the heap here.
+///
+/// # Arguments
+///
+/// * `futures: IntoIterator<Item = Future>` - The futures to race, of the same type as in `trpl::join_all`.
+///
+/// # Returns
+///
+/// * `(usize, Output)`: The index of the first future to complete, with its output.
+///
+/// # Panics
+///
+/// Panics if there are no futures, since there would be no output to return.
+pub async fn race_all<I>(futures: I) -> (usize, <I::Item as Future>::Output)
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    let futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+    assert!(!futures.is_empty(), "at least one future is needed to race");
+    // The futures still running are returned alongside the output, dropping them cancels them
+    let (output, index, _others) = future::select_all(futures).await;
+    (index, output)
+}
+
+/// Awaits all the futures returning a `Result`, stopping at the first error.
+///
+/// The futures are polled concurrently like in `trpl::join_all`, and as soon as one of them fails
+/// the others are dropped, without waiting for them.
+///
+/// # Arguments
+///
+/// * `futures: IntoIterator<Item = Future<Output = Result<T, E>>>` - The futures to await.
+///
+/// # Returns
+///
+/// * `Result<Vec<T>, E>`: The outputs in the same order as the input, or the first error to happen.
+pub async fn try_join_all<I, T, E>(futures: I) -> Result<Vec<T>, E>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    let mut in_flight: FuturesUnordered<_> = futures
+        .into_iter()
+        .enumerate()
+        .map(|(index, future)| async move { (index, future.await) })
+        .collect();
+    let mut outputs = Vec::with_capacity(in_flight.len());
+
+    while let Some((index, result)) = in_flight.next().await {
+        outputs.push((index, result?));
+    }
+
+    outputs.sort_by_key(|(index, _)| *index);
+    Ok(outputs.into_iter().map(|(_, output)| output).collect())
+}
+
+/// Awaits all the futures returning a `Result`, even when some of them fail.
+///
+/// This is the common fan-out-then-collect pattern: every future gets to complete, the successful outputs
+/// can be used, and the failures are summarized in a single report, e.g. to log them all at once.
+///
+/// # Arguments
+///
+/// * `futures: IntoIterator<Item = Future<Output = Result<T, E>>>` - The futures to await.
+///
+/// # Returns
+///
+/// * `(Vec<Result<T, E>>, Option<ErrorReport>)`: The results in the same order as the input,
+///   and a report of the errors if any future failed
+pub async fn try_join_all_settled<I, T, E>(futures: I) -> (Vec<Result<T, E>>, Option<ErrorReport>)
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, E>>,
+    E: fmt::Display,
+{
+    let results = trpl::join_all(futures).await;
+    let failures: Vec<(usize, String)> = results
+        .iter()
+        .enumerate()
+        .filter_map(|(index, result)| {
+            result
+                .as_ref()
+                .err()
+                .map(|error| (index, error.to_string()))
+        })
+        .collect();
+    let report = (!failures.is_empty()).then_some(ErrorReport {
+        failures,
+        total: results.len(),
+    });
+    (results, report)
+}
+
+/// The errors of the futures awaited by [`try_join_all_settled`], with their index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorReport {
+    failures: Vec<(usize, String)>,
+    total: usize,
+}
+
+impl ErrorReport {
+    /// Returns the index of each failed future with its error message, in the order of the input.
+    pub fn failures(&self) -> &[(usize, String)] {
+        &self.failures
+    }
+
+    /// Returns the number of futures awaited, including the successful ones.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+}
+
+impl fmt::Display for ErrorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} futures failed",
+            self.failures.len(),
+            self.total
+        )?;
+        for (index, message) in &self.failures {
+            write!(f, "\n  #{index}: {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ErrorReport {}
+
+/// One of three values, like `trpl::Either` for two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either3<A, B, C> {
+    First(A),
+    Second(B),
+    Third(C),
+}
+
+/// Returned by [`select_timeout`] when neither future completed in time, with the duration it waited for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut(pub Duration);
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timed out after {:?}", self.0)
+    }
+}
+
+impl Error for TimedOut {}
+
+/// Races two futures against a deadline, returning the output of the first to complete.
+///
+/// It's the same as `race(race(a, b), sleep(max_time))`, without matching the nested `Either` to find which one won.
+/// Like `trpl::race` it's not fair: when more of them are ready, `a` wins over `b`, and both over the deadline.
+///
+/// # Arguments
+///
+/// * `a: Future` - The first future.
+/// * `b: Future` - The second future, its output can have a different type.
+/// * `max_time: Duration` - Maximum time to wait.
+///
+/// # Returns
+///
+/// * `Either3<A::Output, B::Output, TimedOut>`: `First` or `Second` with the output of the future completing first,
+///   or `Third` if the deadline elapsed before
+pub async fn select_timeout<A, B>(
+    a: A,
+    b: B,
+    max_time: Duration,
+) -> Either3<A::Output, B::Output, TimedOut>
+where
+    A: Future,
+    B: Future,
+{
+    match trpl::race(trpl::race(a, b), trpl::sleep(max_time)).await {
+        Either::Left(Either::Left(output)) => Either3::First(output),
+        Either::Left(Either::Right(output)) => Either3::Second(output),
+        Either::Right(()) => Either3::Third(TimedOut(max_time)),
+    }
+}
@@ -0,0 +1,107 @@
+//! A concurrent crawler collecting the titles of many web pages, built on [`page_title`](crate::page_title).
+
+use std::{collections::HashSet, error::Error, fmt, future::Future, time::Duration};
+
+use futures::stream::{self, StreamExt};
+use trpl::Stream;
+
+use crate::{timeout, title_of};
+
+/// The URL of a page, as passed to `trpl::get`.
+pub type Url = String;
+
+/// The time given to each request by [`crawl`] before giving up.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The reasons why the title of a page couldn't be fetched.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FetchError {
+    /// The request or the download of the body failed, with the message of the HTTP client.
+    Request(String),
+    /// The page didn't arrive before the timeout elapsed.
+    TimedOut(Duration),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Request(message) => write!(f, "request failed: {message}"),
+            FetchError::TimedOut(duration) => write!(f, "request timed out after {duration:?}"),
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Fetches the title of a page like [`page_title`](crate::page_title), but returns the errors instead of panicking.
+pub async fn fetch_title(url: Url) -> Result<Option<String>, FetchError> {
+    let request_error = |error: reqwest::Error| FetchError::Request(error.to_string());
+    let response = reqwest::get(&url).await.map_err(request_error)?;
+    let text = response.text().await.map_err(request_error)?;
+    Ok(title_of(&text))
+}
+
+/// Fetches the titles of the pages, at most `max_concurrency` at a time, yielding the results as they complete.
+///
+/// Each URL is fetched only once, even if it appears more times, and each request is given [`DEFAULT_REQUEST_TIMEOUT`].
+///
+/// # Arguments
+///
+/// * `urls: IntoIterator<Item = Url>` - The pages to fetch.
+/// * `max_concurrency: usize` - Maximum number of requests in flight at the same time.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = (Url, Result<Option<String>, FetchError>)>`: Each URL with its title, or the reason it couldn't be fetched,
+///   in the order the requests complete
+///
+/// # Panics
+///
+/// Panics if `max_concurrency` is 0.
+pub fn crawl<I>(
+    urls: I,
+    max_concurrency: usize,
+) -> impl Stream<Item = (Url, Result<Option<String>, FetchError>)>
+where
+    I: IntoIterator<Item = Url>,
+{
+    crawl_with(urls, max_concurrency, DEFAULT_REQUEST_TIMEOUT, fetch_title)
+}
+
+/// Like [`crawl`], with a custom timeout for each request and a custom function fetching the titles,
+/// for example to go through a cache, or to test the crawler without a network.
+pub fn crawl_with<I, F, Fut>(
+    urls: I,
+    max_concurrency: usize,
+    request_timeout: Duration,
+    mut fetch: F,
+) -> impl Stream<Item = (Url, Result<Option<String>, FetchError>)>
+where
+    I: IntoIterator<Item = Url>,
+    F: FnMut(Url) -> Fut,
+    Fut: Future<Output = Result<Option<String>, FetchError>>,
+{
+    assert!(
+        max_concurrency > 0,
+        "at least one request must be in flight"
+    );
+    let mut seen = HashSet::new();
+    let urls: Vec<Url> = urls
+        .into_iter()
+        .filter(|url| seen.insert(url.clone()))
+        .collect();
+
+    stream::iter(urls)
+        .map(move |url| {
+            let request = fetch(url.clone());
+            async move {
+                let result = match timeout(request, request_timeout).await {
+                    Ok(result) => result,
+                    Err(duration) => Err(FetchError::TimedOut(duration)),
+                };
+                (url, result)
+            }
+        })
+        // Like `join_all_limited`, but yielding each output as soon as it's ready instead of collecting them
+        .buffer_unordered(max_concurrency)
+}
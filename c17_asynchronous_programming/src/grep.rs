@@ -0,0 +1,77 @@
+//! An async front-end for `minigrep` from chapter 12, searching many files concurrently.
+//!
+//! The files are read with the async `trpl::read_to_string`, so a search over many files
+//! doesn't block the other futures, and the lines are matched by the functions of `c12_minigrep`.
+
+use std::path::PathBuf;
+
+use futures::stream::{self, StreamExt};
+use trpl::Stream;
+
+/// The maximum number of files read at the same time.
+pub const MAX_OPEN_FILES: usize = 8;
+
+/// A line containing the query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+    /// The file containing the line.
+    pub path: PathBuf,
+    /// The line, without the line terminator.
+    pub line: String,
+}
+
+/// Searches the query in the files, yielding the matching lines as the files are read.
+///
+/// Up to [`MAX_OPEN_FILES`] files are read at the same time, but the matches keep the order of the files,
+/// and the order of the lines in each file, like running `minigrep` on each file in turn.
+/// A file that can't be read is logged as a warning with `book_log` and skipped, like `minigrep` reports its errors,
+/// so one missing file doesn't stop the search in the others.
+///
+/// # Arguments
+///
+/// * `query: &str` - The text to search.
+/// * `paths: IntoIterator<Item = Into<PathBuf>>` - The files to search.
+/// * `ignore_case: bool` - Whether to use `search_case_insensitive` instead of `search`.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = Match>`: The matching lines with their file.
+pub fn search_files<I>(query: &str, paths: I, ignore_case: bool) -> impl Stream<Item = Match>
+where
+    I: IntoIterator,
+    I::Item: Into<PathBuf>,
+{
+    let query = query.to_string();
+    let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+
+    stream::iter(paths)
+        .map(move |path| {
+            let query = query.clone();
+            async move { search_file(&query, path, ignore_case).await }
+        })
+        .buffered(MAX_OPEN_FILES)
+        .flat_map(stream::iter)
+}
+
+/// Reads a file and returns its matching lines, or none if it can't be read.
+async fn search_file(query: &str, path: PathBuf, ignore_case: bool) -> Vec<Match> {
+    let contents = match trpl::read_to_string(&path).await {
+        Ok(contents) => contents,
+        Err(error) => {
+            book_log::warn!("can't read the file"; path = path, error = error);
+            return Vec::new();
+        }
+    };
+    let lines = if ignore_case {
+        c12_minigrep::search_case_insensitive(query, &contents)
+    } else {
+        c12_minigrep::search(query, &contents)
+    };
+    lines
+        .into_iter()
+        .map(|line| Match {
+            path: path.clone(),
+            line: line.to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,183 @@
+//! # Asynchronous programming
+//!
+//! Reusable async utilities built in the chapter on futures, tasks and streams.
+//!
+//! All the functions work with the runtime provided by `trpl`: the futures are awaited inside `trpl::run`,
+//! and the streams spawn their producers with `trpl::spawn_task`, so they must be created while the runtime is running.
+
+pub mod aggregator;
+pub mod broadcast;
+pub mod cancellation;
+pub mod combinators;
+pub mod crawler;
+pub mod grep;
+pub mod metrics;
+pub mod progress;
+pub mod rate_limit;
+pub mod retry;
+pub mod stream_ext;
+pub mod task_group;
+pub mod time;
+pub mod work_queue;
+
+use std::{future::Future, pin::pin, time::Duration};
+
+use trpl::{Either, Html, ReceiverStream, Stream, StreamExt};
+
+pub use aggregator::{StreamMessage, feed_from_stream, feed_messages};
+pub use broadcast::{BroadcastReceiver, BroadcastSender, LagPolicy, broadcast, spawn_broadcast};
+pub use cancellation::{CancellationToken, run_until_cancelled};
+pub use combinators::{
+    Either3, ErrorReport, TimedOut, join_all_limited, race_all, select_timeout, try_join_all,
+    try_join_all_settled,
+};
+pub use crawler::{FetchError, Url, crawl};
+pub use grep::{Match, search_files};
+pub use metrics::{MetricsRegistry, TaskMetrics};
+pub use progress::{Progress, ProgressEvent, ProgressHandle};
+pub use rate_limit::RateLimiter;
+pub use retry::{RetryError, RetryPolicy, retry_with_backoff, with_retries};
+pub use stream_ext::{EitherItem, StreamTimeExt, merge_tagged};
+pub use task_group::{ShutdownReport, TaskGroup};
+pub use time::{interval, timer};
+pub use work_queue::{AsyncWorkQueue, DrainReport};
+
+/// Extracts the title of a web page.
+///
+/// First of all it performs a GET to obtain the web page and awaits the response,
+/// then the whole text is awaited and parsed as `Html` to select the first `title` element.
+///
+/// # Arguments
+///
+/// * `url: &str` - The URL of the page.
+///
+/// # Returns
+///
+/// * `Option<String>`: The content of the `title` element, or `None` if the page has no title.
+///
+/// # Panics
+///
+/// Panics if the request fails, for example when the network is not available.
+pub async fn page_title(url: &str) -> Option<String> {
+    // The keyword `await` goes after the expression to make the chains of method nicer to work with
+    let response_text = trpl::get(url).await.text().await;
+    title_of(&response_text)
+}
+
+/// Returns the content of the first `title` element of an HTML document.
+fn title_of(html: &str) -> Option<String> {
+    Html::parse(html)
+        .select_first("title")
+        .map(|title_element| title_element.inner_html())
+}
+
+/// Tries to run a future before the timeout elapses.
+///
+/// # Arguments
+///
+/// * `future_to_try: Future` - Generic future to run.
+/// * `max_time: Duration` - Maximum time to wait.
+///
+/// # Returns
+///
+/// * `Result<F::Output, Duration>`: If the future completes successfully it returns `Ok` with the value produced by the future,
+///   otherwise, if the timeout elapses, `Err` with the duration that the timeout waited for
+pub async fn timeout<F: Future>(
+    future_to_try: F,
+    max_time: Duration,
+) -> Result<F::Output, Duration> {
+    // Race the future passed against the duration, created using `trpl::sleep`
+    // The future is passed first so it gets the chance to complete even if `max_time` is very short.
+    // If `future_to_try` finishes first, the `race` will return Left, otherwise `Right`
+    match trpl::race(future_to_try, trpl::sleep(max_time)).await {
+        Either::Left(output) => Ok(output),
+        Either::Right(_) => Err(max_time),
+    }
+}
+
+/// Sends the first 10 letters of the english alphabet across an async channel, with a delay before each of them.
+///
+/// The delay is 100ms for the letters at even indexes and 300ms for the ones at odd indexes,
+/// so a timeout of 200ms on the stream affects half the messages.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = String>`: stream of the messages, such as `Message: 'a'`
+pub fn get_messages() -> impl Stream<Item = String> {
+    let (tx, rx) = trpl::channel();
+    let messages = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+
+    trpl::spawn_task(async move {
+        for (index, message) in messages.into_iter().enumerate() {
+            let time_to_sleep = if index % 2 == 0 { 100 } else { 300 };
+            // `get_messages` can't be async, otherwise the caller would have to await all the messages, including the delays, before getting the stream
+            // Instead the spawned task handles the `sleep` calls, and the stream is returned right away
+            trpl::sleep(Duration::from_millis(time_to_sleep)).await;
+            // `send` fails when the receiver has been dropped, in that case there is nobody left to read the messages
+            if let Err(send_error) = tx.send(format!("Message: '{message}'")) {
+                eprintln!("Cannot send message '{message}': {send_error}");
+                break;
+            }
+        }
+    });
+
+    // The type `ReceiverStream` converts the `rx` receiver from `trpl::channel` into a `Stream` with a `next` method.
+    ReceiverStream::new(rx)
+}
+
+/// Emits the count of intervals every millisecond, using an [`interval`] to send a message on a delay.
+///
+/// The stream never ends by itself: the spawned task stops when the stream is dropped, or with the runtime.
+/// Use [`get_intervals_until_cancelled`] to stop it from another task.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = u32>`: stream of the count of intervals, starting from 1
+pub fn get_intervals() -> impl Stream<Item = u32> {
+    let (tx, rx) = trpl::channel();
+
+    trpl::spawn_task(async move {
+        let mut count = 0;
+        let mut ticks = pin!(interval(Duration::from_millis(1)));
+        // The loop doesn't block anything else, as long as there is an await point in each iteration
+        while ticks.next().await.is_some() {
+            count += 1;
+            if let Err(send_error) = tx.send(count) {
+                eprintln!("Could not send interval {count}: {send_error}");
+                break;
+            };
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
+
+/// Emits the count of intervals every millisecond like [`get_intervals`], until the token is cancelled.
+///
+/// The spawned task stops as soon as the token is cancelled, even while it's sleeping,
+/// and the stream ends once the intervals sent before are consumed.
+///
+/// # Arguments
+///
+/// * `token: &CancellationToken` - The token stopping the stream, cloned into the spawned task.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = u32>`: stream of the count of intervals, starting from 1
+pub fn get_intervals_until_cancelled(token: &CancellationToken) -> impl Stream<Item = u32> {
+    let (tx, rx) = trpl::channel();
+    let token = token.clone();
+
+    trpl::spawn_task(async move {
+        let mut count = 0;
+        let mut ticks = pin!(interval(Duration::from_millis(1)));
+        while let Some(Some(_)) = run_until_cancelled(ticks.next(), &token).await {
+            count += 1;
+            if tx.send(count).is_err() {
+                break;
+            }
+        }
+    });
+
+    ReceiverStream::new(rx)
+}
@@ -39,26 +39,20 @@ fn futures_async() {
 
         use trpl::{Either, Html};
 
-        /// Async function that extracts the tiltle of a web page
-        ///
-        /// First of all it performs a GET to obtain the web page and awaits the response
-        /// Once the response is available the whole text is awaited and extracted
-        /// The await keyword needs to be explicitly asked,since Rust futures are lazy: they don't anything until asked
-        /// Once the response_text is available, it can be parsed in an instance of `Html` type
-        /// The `Html` type allows to navigate and query the DOM
-        /// The `select_first` method returns an `Option<ElementRef>` containing the first elmement requested (in this case `title`) if it exists
-        /// Then the `Option::map` method, similarly to |match|, is used to work with the item in the option
-        /// In the body of the map `inner_html` is called to get the content of `title_element`
-        /// The result is an `Option<String>` containing the page title (if it doesn't exist `None`)
-        async fn page_title(url: &str) -> Option<String> {
-            // let response = trpl::get(url).await;
-            // let response_text = response.text().await;
-            // The keyword `await` goes after the expression to make the chains of method nicer to work with
-            let response_text = trpl::get(url).await.text().await;
-            Html::parse(&response_text)
-                .select_first("title")
-                .map(|title_element| title_element.inner_html())
-        }
+        // Async function that extracts the tiltle of a web page
+        // First of all it performs a GET to obtain the web page and awaits the response
+        // Once the response is available the whole text is awaited and extracted
+        // The await keyword needs to be explicitly asked,since Rust futures are lazy: they don't anything until asked
+        // Once the response_text is available, it can be parsed in an instance of `Html` type
+        // The `Html` type allows to navigate and query the DOM
+        // The `select_first` method returns an `Option<ElementRef>` containing the first elmement requested (in this case `title`) if it exists
+        // Then the `Option::map` method, similarly to |match|, is used to work with the item in the option
+        // In the body of the map `inner_html` is called to get the content of `title_element`
+        // The result is an `Option<String>` containing the page title (if it doesn't exist `None`)
+        // The keyword `await` goes after the expression to make the chains of method nicer to work with
+        // The function is defined in `lib.rs`, so other crates in the workspace can use it too
+        use c17_asynchronous_programming::page_title;
+
         // When Rust sees a block with the `async` keyword, it compiles it into a unique, anonymous data type that implements the `Future` trait
         // When RUst sees a function marked with `async` it compiles it into a non-async function whose body is an async block, the return type is the type of the anonymous data type
         {
@@ -333,7 +327,6 @@ fn multiple_futures() {
         thread,
         time::{Duration, Instant},
     };
-    use trpl::Either;
     trpl::run(async {
         {
             let (tx, mut rx) = trpl::channel();
@@ -464,6 +457,35 @@ fn multiple_futures() {
             println!("{a_result}, {b_result}, {c_result}");
             // Here `trpl::join_all` can't be used because it requires all of the futures to have the same type.
             // So the tradeoff is: `join_all` for a dynamic number of futures with the same type, `join!` with a set number of futures with different types, which is the same scenario as working with any other type in Rust.
+            // `join_all` polls all the futures at the same time, which is a problem when there are many of them, e.g. hundreds of web pages to download
+            // `join_all_limited` in `lib.rs` only polls a limited number of them at a time, and starts the next one as soon as any of them completes
+            use c17_asynchronous_programming::join_all_limited;
+
+            let futures = (1..=5).map(|i| async move {
+                trpl::sleep(Duration::from_millis(100 * i)).await;
+                i
+            });
+            let results = join_all_limited(futures, 2).await;
+            println!("Results in order: {results:?}");
+            // `crawl` in `lib.rs` uses the same idea to fetch the titles of many pages, yielding each of them as soon as it's ready
+            // It fetches each URL once, gives each request a timeout, and reports the errors instead of panicking like `page_title`
+            use c17_asynchronous_programming::crawl;
+            use trpl::StreamExt;
+
+            let urls = [
+                "https://www.rust-lang.org",
+                "https://doc.rust-lang.org/",
+                "https://www.rust-lang.org",
+            ];
+            // The stream holds the futures of the requests, so it needs to be pinned before calling `next`, like the streams with a timeout
+            let mut titles = pin!(crawl(urls.map(String::from), 2));
+            while let Some((url, result)) = titles.next().await {
+                match result {
+                    Ok(Some(title)) => println!("The title of {url} is {title}"),
+                    Ok(None) => println!("{url} has no title"),
+                    Err(error) => println!("Could not fetch {url}: {error}"),
+                }
+            }
         }
         {
             // When futures are joined with the `join` family of functions and macros,each of them are required to finish, but sometimes only few of them need to finish before moving on
@@ -482,6 +504,15 @@ fn multiple_futures() {
             trpl::race(slow, fast).await;
             // With `trpl::race` it is possible to ignore the ending of one future, changing the order of the arguments changes the print, but fast will always complete first.
             // This implementation of `trpl::race` is not fair as it always runs the futures passed as argument in the order they are passed, other implemenations are fair and choose randomly which future to run first.
+            // `trpl::race` only accepts two futures, `race_all` in `lib.rs` accepts any number of them and returns the index of the first one to complete, with its output
+            use c17_asynchronous_programming::race_all;
+
+            let futures = [300, 100, 200].map(|ms| async move {
+                trpl::sleep(Duration::from_millis(ms)).await;
+                ms
+            });
+            let (index, ms) = race_all(futures).await;
+            println!("Future {index} finished first after {ms}ms");
             // Rust gives a runtime the chance to pause the task and switch to another one if the future awaited isn't ready.
             // The inverse is also true: Rust only pauses async blocks and hands control back to a runtime at an await point, everything between await points is synchronous.
             // This means that the work in an async block without an await point, the future will block any other futures. This is referred as starving other features, so, in a complex or long case it is useful to think about handing control back to the runtime.
@@ -608,9 +639,24 @@ fn multiple_futures() {
             // Each future has the responsibility to avoid blocking for too long and in some Rust-based embedded OSs it is the only kind of multitasking
             // In real-world code usually functions are not alternated with await calls on every single line, even because `yieald_now` is not too expansive but neither free.
             // In many cases breaking up compute bound tasks might be significantly slower compared to let an operation block intact, better measure the overall performances.
+            // The await points of a long-running task are also the places where it can tell how far it got, `Progress` in `lib.rs` pairs the task with a stream of progress events
+            use c17_asynchronous_programming::Progress;
+
+            let progress = Progress::new(|handle| async move {
+                for step in 1..=4 {
+                    slow("step", 50);
+                    handle.report(step * 25, format!("Step {step} of 4"));
+                    trpl::yield_now().await;
+                }
+            });
+            progress
+                .run_reporting(|event| println!("{}% - {}", event.percent, event.message))
+                .await;
         }
         {
             // It is possible to compose multiple futures together to create new patterns, such as a `timeout` function with async blocks, the result will be another building block that can be use to create more async abstractions.
+            // The `timeout` function races the future against a `trpl::sleep`, it's defined in the library of this package so other crates can use it too.
+            use c17_asynchronous_programming::timeout;
 
             let slow = async {
                 trpl::sleep(Duration::from_millis(100)).await;
@@ -624,34 +670,50 @@ fn multiple_futures() {
                 }
             }
             // Because futures compose with other futures, powerful tools can be built using smaller async building blocks, e.g. timeouts with retries for network calls for example
+            // Such as `retry_with_backoff` in `lib.rs`, which creates a new future for every attempt, with a timeout on each of them, and waits longer and longer between the attempts
+            use c17_asynchronous_programming::{RetryPolicy, retry_with_backoff};
+
+            let policy = RetryPolicy::new(3)
+                .with_backoff(Duration::from_millis(10), Duration::from_millis(50))
+                .with_attempt_timeout(Duration::from_millis(50));
+            let mut attempts = 0;
+            let result = retry_with_backoff(
+                || {
+                    attempts += 1;
+                    let attempt = attempts;
+                    async move {
+                        if attempt < 3 {
+                            Err(format!("attempt {attempt} failed"))
+                        } else {
+                            Ok(attempt)
+                        }
+                    }
+                },
+                &policy,
+            )
+            .await;
+            println!("Retried until: {result:?}");
+            // Racing two futures with a timeout needs two nested `race`, and matching the nested `Either` to find which one fired
+            // `select_timeout` in `lib.rs` does it in one call, returning an `Either3` with three cases
+            use c17_asynchronous_programming::{Either3, select_timeout};
+
+            let message = async {
+                trpl::sleep(Duration::from_millis(50)).await;
+                "a message"
+            };
+            let count = async {
+                trpl::sleep(Duration::from_millis(80)).await;
+                42
+            };
+            match select_timeout(message, count, Duration::from_millis(100)).await {
+                Either3::First(message) => println!("Got {message} first"),
+                Either3::Second(count) => println!("Got {count} first"),
+                Either3::Third(timed_out) => println!("Nothing arrived: {timed_out}"),
+            }
             // The most common tools are `async`, `await` with macros such as `join`, `join_all`, and `race`.
             // Multiple futures in a sequence over time build a stream.
         }
     });
-
-    /// Tries to run a future before the timeout elapses.
-    ///
-    /// # Arguments
-    ///
-    /// * `future_to_try: Future` - Generic future to run.
-    /// * `max_time: Duration` - Maximum time to wait.
-    ///
-    /// # Returns
-    ///
-    /// * `Result<F::Output, Duration>`: If the future completes successfully it returns `Ok` with the value produced by the future,
-    /// otherwise, if the timeout elapses, `Err` with the duration that the timeout waited for
-    async fn timeout<F: Future>(
-        future_to_try: F,
-        max_time: Duration,
-    ) -> Result<F::Output, Duration> {
-        // Race the future passed gainst the duration, created using `thread::sleep`
-        // The feature is passed first so it gets the chance to complete even if `max_time` is very short.
-        // If `future_to_try` sinishes first, the `race` will return Left, otherwise `Right`
-        match trpl::race(future_to_try, trpl::sleep(max_time)).await {
-            Either::Left(output) => Ok(output),
-            Either::Right(_) => Err(max_time),
-        }
-    }
 }
 
 fn streams() {
@@ -660,6 +722,7 @@ fn streams() {
     // Another difference is the APIs: with iterators the synchronous method `next` is used, while with `trpl::Receiver` the asynchronous method `recv` is used.
     // These APIs are similar since a steram is basically an asynchronous form of iteration where the `trpl::Receiver` waits to receive a message and provides the next element as the `Iterator`, but asynchronously.
 
+    use c17_asynchronous_programming::{get_intervals, get_messages};
     use std::{pin::pin, time::Duration};
     use trpl::{ReceiverStream, Stream, StreamExt};
 
@@ -749,84 +812,101 @@ fn streams() {
             // Now `throttle` produces a new stream wrapping the original, limiting the number of intervals since the orignial stream is polled at throttle rate
             // And `take` limits the numebr of messages to 20 so the program stops.
         }
-    });
-
-    /// Create an async channel over the first 10 letters of the english alphabet and send them across the channel.
-    ///
-    /// # Returns
-    ///
-    /// * `impl Stream<Item = String>`: stream of the chars generated from the channel
-    fn get_messages() -> impl Stream<Item = String> {
         {
-            let (tx, rx) = trpl::channel();
-            let messages = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
-            for message in messages {
-                tx.send(format!("Message: '{message}'")).unwrap();
+            // Instead of mapping the intervals into strings, the two streams can be merged keeping their types, tagging each item with the stream it comes from
+            // `merge_tagged` in `lib.rs` does that with the `EitherItem` enum, similar to the `Either` returned by `trpl::race`
+            use c17_asynchronous_programming::{EitherItem, merge_tagged};
+
+            let messages = get_messages();
+            let intervals = get_intervals().throttle(Duration::from_millis(100));
+            let mut merged = pin!(merge_tagged(messages, intervals).take(20));
+
+            while let Some(item) = merged.next().await {
+                match item {
+                    EitherItem::Left(message) => println!("{message}"),
+                    EitherItem::Right(count) => println!("Interval #{count}"),
+                }
             }
-            // The type `ReceiverStream` converts the `rx`receiver from `trpl::channel` into a `Stream` with a `next` method.
-            ReceiverStream::new(rx);
-            // In this case, since there are no delays between messages, the timeout in the caller does not change the behaviour
         }
         {
-            let (tx, rx) = trpl::channel();
-            let messages = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
-
-            trpl::spawn_task(async move {
-                for (index, message) in messages.into_iter().enumerate() {
-                    // Introduce a delay of 100ms for even indexes, and 300ms for odd indexes
-                    // Since the timeout is 200ms it should affect half the messages
-                    let time_to_sleep = if index % 2 == 0 { 100 } else { 300 };
-                    // To sleep between messages in the `get_messages` function `async` is needed but `get_messages` can't be made async because it would chnage te return type in a `Future<Output = Stream<Item = String>>` but a `Stream` is needed
-                    // In this case the caller would have to await `get_messages` to get access to the stream, but this would mean require to send all the messages, including the delay, before returning the receiver stream because inside of a future everything is linear
-                    // As a result the timeout would be useless, without delays in the steram itself because they would happen before the stream was even available
-                    // Instead `get_messages` returns a stream and the spawned task handles the `sleep` calls. `spawn_task` works because the runtime is already spawned, otherwise it would cause a panic.
-                    trpl::sleep(Duration::from_millis(time_to_sleep)).await;
-                    // tx.send(format!("Message: {message}")).unwrap();
-                    // To properly send data on channel based streams errors needs to be handled, because `send` could just fail when the other channel closes,and that depends on the runtime
-                    // This is handled implicitly by `unwrap` but, in a well written program, it should be managed explicitly and at minimum ending the loop
-                    if let Err(send_error) = tx.send(format!("Message: '{message}'")) {
-                        eprintln!("Cannot send message '{message}': {send_error}");
-                        break;
+            // Each message of a channel goes to a single receiver, a broadcast channel instead sends each message to every receiver
+            // `spawn_broadcast` in `lib.rs` feeds the messages of one stream to several consumers, which run concurrently
+            use c17_asynchronous_programming::{LagPolicy, spawn_broadcast};
+
+            let receivers = spawn_broadcast(get_messages(), 2, 16, LagPolicy::Error);
+            let consumers = receivers
+                .into_iter()
+                .enumerate()
+                .map(|(id, receiver)| async move {
+                    let mut messages = pin!(receiver.into_stream());
+                    while let Some(message) = messages.next().await {
+                        match message {
+                            Ok(message) => println!("Consumer {id}: {message}"),
+                            Err(lagged) => eprintln!("Consumer {id}: {lagged}"),
+                        }
                     }
+                });
+            trpl::join_all(consumers).await;
+        }
+        {
+            // The messages of a stream can become items of the media aggregator of chapter 10, which only needs them to implement `Summary`
+            // `feed_messages` in `lib.rs` pushes each message into a `Feed` as soon as it arrives, so its subscribers see them while the stream is still running
+            use c10_generics_traits_lifetimes::Feed;
+            use c17_asynchronous_programming::feed_messages;
+
+            let mut feed = Feed::new();
+            feed.subscribe(|item| println!("New in the feed: {}", item.summarise()));
+            let pushed = feed_messages(get_messages(), "messages", &mut feed).await;
+            println!("{pushed} messages in the feed");
+        }
+        {
+            // Instead of limiting the number of items, the loop in `get_intervals` can be stopped from the outside with a `CancellationToken` defined in `lib.rs`
+            // The spawned task races each `sleep` against the token, so it stops as soon as the token is cancelled, and the stream ends
+            use c17_asynchronous_programming::{CancellationToken, get_intervals_until_cancelled};
+
+            let token = CancellationToken::new();
+            let mut intervals = get_intervals_until_cancelled(&token);
+            while let Some(count) = intervals.next().await {
+                if count == 10 {
+                    token.cancel();
                 }
-            });
-
-            ReceiverStream::new(rx)
+            }
+            println!("Intervals cancelled");
         }
-    }
-
-    /// Emit the count of intervals every millisecond using a `sleep` to send a message on a delay
-    ///
-    /// # Returns
-    ///
-    /// * `impl Stream<Item = String>`: stream of the count of intervals
-    fn get_intervals() -> impl Stream<Item = u32> {
-        let (tx, rx) = trpl::channel();
-
-        trpl::spawn_task(async move {
-            // Create the counter
-            let mut count = 0;
-            // Create an infinite loop
-            loop {
-                // Sleep for 1ms
-                trpl::sleep(Duration::from_millis(1)).await;
-                // Increment the count of intervals by one
-                count += 1;
-                // Send it over the channel
-                // tx.send(count).unwrap();
-                // To properly send data on channel based streams errors needs to be handled, because `send` could just fail when the other channel closes,and that depends on the runtime
-                // This is handled implicitly by `unwrap` but, in a well written program, it should be managed explicitly and at minimum ending the loop
-                if let Err(send_error) = tx.send(count) {
-                    eprintln!("Could not send interval {count}: {send_error}");
-                    break;
-                };
+        {
+            // Besides `throttle`, another common adapter for streams of events is `debounce`: it only emits an item once the stream has been quiet for a while, e.g. to react to the last key pressed
+            // `StreamTimeExt` in `lib.rs` implements both by hand, polling the stream and a `sleep` future in the `poll_next` method of the `Stream` trait
+            // `debounce` is not in `trpl::StreamExt`, so it can be called as a method even with both traits in scope, unlike `throttle`
+            use c17_asynchronous_programming::StreamTimeExt;
+
+            // The messages arrive every 100ms or 300ms, so only the ones followed by a pause longer than 200ms are emitted
+            let mut debounced = get_messages().debounce(Duration::from_millis(200));
+            while let Some(message) = debounced.next().await {
+                println!("Debounced: {message}");
             }
-        });
-        // Since the operation is in a `trpl::spawn_task` all of it, including the loop, will be cleaned up along the runtime
-        // This kind of loop is pretty common in Rust because  many programs need to run indefinitely, but with async it doesn't block anything else, as long as there is an await point in each iteration
+        }
+    });
 
+    // The first version of `get_messages` sent all the messages without delays:
+    fn _get_messages() -> impl Stream<Item = String> {
+        let (tx, rx) = trpl::channel();
+        let messages = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"];
+        for message in messages {
+            tx.send(format!("Message: '{message}'")).unwrap();
+        }
+        // The type `ReceiverStream` converts the `rx`receiver from `trpl::channel` into a `Stream` with a `next` method.
+        // In this case, since there are no delays between messages, the timeout in the caller does not change the behaviour
         ReceiverStream::new(rx)
     }
+    // The version in `lib.rs` spawns a task sending the messages with a delay of 100ms for even indexes, and 300ms for odd indexes, so a timeout of 200ms affects half the messages
+    // To sleep between messages `async` is needed but `get_messages` can't be made async because it would change the return type in a `Future<Output = Stream<Item = String>>` but a `Stream` is needed
+    // In this case the caller would have to await `get_messages` to get access to the stream, but this would mean require to send all the messages, including the delay, before returning the receiver stream because inside of a future everything is linear
+    // As a result the timeout would be useless, without delays in the steram itself because they would happen before the stream was even available
+    // Instead `get_messages` returns a stream and the spawned task handles the `sleep` calls. `spawn_task` works because the runtime is already spawned, otherwise it would cause a panic.
+    // To properly send data on channel based streams errors needs to be handled, because `send` could just fail when the other channel closes,and that depends on the runtime
+    // This is handled implicitly by `unwrap` but, in a well written program, it should be managed explicitly and at minimum ending the loop, as `get_messages` and `get_intervals` do
+    // `get_intervals` emits the count of intervals every millisecond in an infinite loop, which is pretty common in Rust because many programs need to run indefinitely
+    // With async it doesn't block anything else, as long as there is an await point in each iteration, and the spawned task is cleaned up along the runtime
 }
 
 fn traits_async() {
@@ -1020,4 +1100,28 @@ fn futures_tasks_threads() {
     });
 
     // An example of scenario is runnig a set of video encoding tasks using a dedicated thread but notifying th UI that the operations are done with an async channel
+
+    // Unlike threads, tasks are dropped with the runtime wherever they are, so a background loop doesn't get the chance to clean up
+    // `TaskGroup` in `lib.rs` keeps the handles of the tasks and gives them a `CancellationToken`, so they can be asked to stop and awaited before exiting
+    use c17_asynchronous_programming::{TaskGroup, run_until_cancelled};
+
+    trpl::run(async {
+        let mut group = TaskGroup::new();
+        for id in 1..=2 {
+            group.spawn(move |token| async move {
+                let mut count = 0;
+                while run_until_cancelled(trpl::sleep(Duration::from_millis(100)), &token)
+                    .await
+                    .is_some()
+                {
+                    count += 1;
+                }
+                println!("Background task {id} stopped after {count} iterations");
+            });
+        }
+
+        trpl::sleep(Duration::from_millis(350)).await;
+        let report = group.shutdown(Duration::from_secs(1)).await;
+        println!("{report:?}");
+    });
 }
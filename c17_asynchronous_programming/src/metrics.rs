@@ -0,0 +1,105 @@
+//! Counters of the tasks spawned through a [`MetricsRegistry`], to observe long-running async programs.
+
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use trpl::JoinHandle;
+
+#[derive(Debug, Default)]
+struct Counters {
+    spawned: AtomicU64,
+    completed: AtomicU64,
+    panicked: AtomicU64,
+    aborted: AtomicU64,
+    running: AtomicU64,
+}
+
+/// A snapshot of the counters of a [`MetricsRegistry`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskMetrics {
+    /// The tasks spawned so far.
+    pub spawned: u64,
+    /// The tasks that returned.
+    pub completed: u64,
+    /// The tasks that panicked.
+    pub panicked: u64,
+    /// The tasks dropped before completing, e.g. aborted through their handle or dropped with the runtime.
+    pub aborted: u64,
+    /// The tasks spawned and not ended yet.
+    pub running: u64,
+}
+
+/// Counts the tasks spawned through it, and how they end.
+///
+/// The clones of a registry share the same counters, so a clone can be given to every part of the program spawning tasks.
+#[derive(Debug, Clone, Default)]
+pub struct MetricsRegistry {
+    counters: Arc<Counters>,
+}
+
+impl MetricsRegistry {
+    /// Creates a registry with all the counters at zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns a task like `trpl::spawn_task`, counting it until it ends.
+    pub fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.counters.spawned.fetch_add(1, Ordering::SeqCst);
+        self.counters.running.fetch_add(1, Ordering::SeqCst);
+        // The guard is moved into the task, so it's dropped however the task ends: when it returns,
+        // while unwinding from a panic, or with the future when the task is aborted
+        let guard = Tracked {
+            counters: Arc::clone(&self.counters),
+            completed: false,
+        };
+        trpl::spawn_task(async move {
+            // Binding the whole guard inside the block, otherwise only its `completed` field would be captured
+            let mut guard = guard;
+            let output = future.await;
+            guard.completed = true;
+            output
+        })
+    }
+
+    /// Returns the current value of the counters.
+    pub fn metrics(&self) -> TaskMetrics {
+        let counters = &self.counters;
+        TaskMetrics {
+            spawned: counters.spawned.load(Ordering::SeqCst),
+            completed: counters.completed.load(Ordering::SeqCst),
+            panicked: counters.panicked.load(Ordering::SeqCst),
+            aborted: counters.aborted.load(Ordering::SeqCst),
+            running: counters.running.load(Ordering::SeqCst),
+        }
+    }
+}
+
+/// Updates the counters when a task ends.
+struct Tracked {
+    counters: Arc<Counters>,
+    completed: bool,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        let ended = if self.completed {
+            &self.counters.completed
+        } else if std::thread::panicking() {
+            &self.counters.panicked
+        } else {
+            &self.counters.aborted
+        };
+        ended.fetch_add(1, Ordering::SeqCst);
+        self.counters.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
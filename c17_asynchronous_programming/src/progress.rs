@@ -0,0 +1,81 @@
+//! Progress reporting for long-running futures.
+//!
+//! The task receives a [`ProgressHandle`] to report how far it got, and the events are received
+//! from a stream alongside the future, e.g. by a UI drawing a progress bar while the work goes on.
+
+use std::future::Future;
+
+use trpl::{ReceiverStream, Sender, StreamExt};
+
+/// How far a task got, reported through a [`ProgressHandle`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgressEvent {
+    /// The percentage of the work done, between 0 and 100.
+    pub percent: u8,
+    /// What the task is doing.
+    pub message: String,
+}
+
+/// The sending side of the progress events, given to the task.
+///
+/// The handle can be cloned to report from more futures of the same task.
+/// Once all the clones are dropped, usually when the task completes, the stream of events ends.
+#[derive(Debug, Clone)]
+pub struct ProgressHandle {
+    tx: Sender<ProgressEvent>,
+}
+
+impl ProgressHandle {
+    /// Reports the progress, the percentage is capped at 100.
+    ///
+    /// The event is discarded if nobody is listening anymore, the task can go on without checking it.
+    pub fn report(&self, percent: u8, message: impl Into<String>) {
+        let event = ProgressEvent {
+            percent: percent.min(100),
+            message: message.into(),
+        };
+        let _ = self.tx.send(event);
+    }
+}
+
+/// A future paired with the stream of the progress events it emits.
+pub struct Progress<F> {
+    future: F,
+    events: ReceiverStream<ProgressEvent>,
+}
+
+impl<F: Future> Progress<F> {
+    /// Creates the future of the task, passing it the handle to report its progress.
+    ///
+    /// Like any future, the task doesn't start until it's awaited.
+    pub fn new<T>(task: T) -> Self
+    where
+        T: FnOnce(ProgressHandle) -> F,
+    {
+        let (tx, rx) = trpl::channel();
+        Progress {
+            future: task(ProgressHandle { tx }),
+            events: ReceiverStream::new(rx),
+        }
+    }
+
+    /// Splits the future and the stream of events, to await them in different places.
+    ///
+    /// The future must be awaited for the stream to receive anything, e.g. with `trpl::join`.
+    pub fn into_parts(self) -> (F, ReceiverStream<ProgressEvent>) {
+        (self.future, self.events)
+    }
+
+    /// Runs the task, calling `on_event` for each event as it arrives, and returns the output of the task.
+    pub async fn run_reporting(self, mut on_event: impl FnMut(ProgressEvent)) -> F::Output {
+        let (future, mut events) = self.into_parts();
+        let report = async {
+            while let Some(event) = events.next().await {
+                on_event(event);
+            }
+        };
+        // The stream ends when the task completes and drops its handle, so both futures finish
+        let (output, ()) = trpl::join(future, report).await;
+        output
+    }
+}
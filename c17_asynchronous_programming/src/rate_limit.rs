@@ -0,0 +1,110 @@
+//! A rate limiter shared by many tasks, such as the requests of a crawler or the messages sent on a channel.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::time::Instant;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Limits how often an operation can be performed, with a token bucket.
+///
+/// The bucket holds up to `permits` tokens and is refilled continuously, `permits` tokens every `interval`.
+/// Each [`acquire`](RateLimiter::acquire) takes a token, waiting for the next one if the bucket is empty,
+/// so a burst of up to `permits` operations goes through right away, and then the operations are spread evenly.
+///
+/// The clones of a limiter share the same bucket, so a clone can be moved into each task.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use c17_asynchronous_programming::{RateLimiter, crawler::{crawl_with, fetch_title}};
+///
+/// // At most 2 requests per second, whatever the number of requests in flight
+/// let limiter = RateLimiter::new(2, Duration::from_secs(1));
+/// let urls = (1..=10).map(|page| format!("https://example.com/{page}"));
+/// let titles = crawl_with(urls, 4, Duration::from_secs(10), |url| {
+///     let limiter = limiter.clone();
+///     async move {
+///         limiter.acquire().await;
+///         fetch_title(url).await
+///     }
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+    permits: f64,
+    interval: Duration,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `permits` operations every `interval`, with a full bucket.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `permits` is 0 or `interval` is zero, no rate could be computed.
+    pub fn new(permits: u32, interval: Duration) -> Self {
+        assert!(permits > 0, "at least one permit per interval is needed");
+        assert!(!interval.is_zero(), "the interval must be longer than zero");
+        RateLimiter {
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: permits as f64,
+                last_refill: Instant::now(),
+            })),
+            permits: permits as f64,
+            interval,
+        }
+    }
+
+    /// Waits until a token is available, and takes it.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = match self.take_or_wait() {
+                Ok(()) => return,
+                Err(wait) => wait,
+            };
+            // Another task may take the token in the meantime, in that case the loop waits again
+            trpl::sleep(wait).await;
+        }
+    }
+
+    /// Takes a token if one is available right away, without waiting.
+    pub fn try_acquire(&self) -> bool {
+        self.take_or_wait().is_ok()
+    }
+
+    /// Returns the number of whole tokens currently in the bucket.
+    pub fn available(&self) -> u32 {
+        let mut bucket = self.bucket.lock().unwrap();
+        self.refill(&mut bucket);
+        bucket.tokens as u32
+    }
+
+    /// Takes a token, or returns how long to wait for the next one.
+    fn take_or_wait(&self) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        self.refill(&mut bucket);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - bucket.tokens;
+            Err(self.interval.mul_f64(missing / self.permits))
+        }
+    }
+
+    fn refill(&self, bucket: &mut Bucket) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.last_refill);
+        let refilled = elapsed.as_secs_f64() / self.interval.as_secs_f64() * self.permits;
+        bucket.tokens = (bucket.tokens + refilled).min(self.permits);
+        bucket.last_refill = now;
+    }
+}
@@ -0,0 +1,252 @@
+//! Retrying fallible futures and streams, built on top of [`timeout`](crate::timeout).
+//!
+//! A future can't be restarted once it has been polled, so the retry functions take a closure
+//! creating a new future for every attempt, such as `|| page_title(url)`, and the same goes for streams.
+
+use std::{error::Error, fmt, future::Future, pin::Pin, time::Duration};
+
+use futures::stream;
+use trpl::{Stream, StreamExt};
+
+use crate::timeout;
+
+/// How many times an operation is attempted, and how long to wait between the attempts.
+///
+/// The wait starts from `initial_backoff` and is multiplied by `multiplier` after every failed attempt,
+/// up to `max_backoff`. The jitter randomly shortens each wait by up to the given fraction,
+/// so many clients failing at the same time don't retry all together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: f64,
+    attempt_timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Creates a policy making at most `max_attempts` attempts, waiting 100ms after the first failure,
+    /// then doubling the wait up to 10 seconds, with a jitter of 20% and no timeout on the attempts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_attempts` is 0.
+    pub fn new(max_attempts: u32) -> Self {
+        assert!(max_attempts > 0, "at least one attempt is needed");
+        RetryPolicy {
+            max_attempts,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+            attempt_timeout: None,
+        }
+    }
+
+    /// Sets the wait after the first failure, and the maximum wait.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Sets the factor the wait is multiplied by after every failure, 1.0 keeps it constant.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the multiplier is less than 1.0.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        assert!(multiplier >= 1.0, "the multiplier must be at least 1.0");
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Sets the fraction of each wait that can be randomly removed, clamped between 0.0 (no jitter) and 1.0.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Gives up on an attempt once the timeout elapses, counting it as failed.
+    pub fn with_attempt_timeout(mut self, attempt_timeout: Duration) -> Self {
+        self.attempt_timeout = Some(attempt_timeout);
+        self
+    }
+
+    /// Returns the maximum number of attempts.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the wait after the failed `attempt`, counting from 1, before the jitter is applied.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.powi(attempt.saturating_sub(1) as i32);
+        let backoff = self.initial_backoff.as_secs_f64() * factor;
+        Duration::from_secs_f64(backoff.min(self.max_backoff.as_secs_f64()))
+    }
+
+    pub(crate) fn backoff_with_jitter(&self, attempt: u32) -> Duration {
+        let backoff = self.backoff(attempt);
+        if self.jitter == 0.0 {
+            return backoff;
+        }
+        backoff.mul_f64(1.0 - self.jitter * rand::random::<f64>())
+    }
+}
+
+/// The error of the last attempt, returned once all the attempts have failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RetryError<E> {
+    /// The future of the last attempt returned an error.
+    Failed(E),
+    /// The last attempt didn't complete before the timeout of the policy elapsed.
+    TimedOut(Duration),
+}
+
+impl<E> RetryError<E> {
+    /// Returns the error of the future, or `None` if the last attempt timed out.
+    pub fn into_inner(self) -> Option<E> {
+        match self {
+            RetryError::Failed(error) => Some(error),
+            RetryError::TimedOut(_) => None,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for RetryError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryError::Failed(error) => write!(f, "last attempt failed: {error}"),
+            RetryError::TimedOut(duration) => {
+                write!(f, "last attempt timed out after {duration:?}")
+            }
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for RetryError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RetryError::Failed(error) => Some(error),
+            RetryError::TimedOut(_) => None,
+        }
+    }
+}
+
+/// Runs the futures created by `make_future` until one of them succeeds, following the policy.
+///
+/// # Arguments
+///
+/// * `make_future: FnMut() -> Future` - Creates the future of each attempt.
+/// * `policy: &RetryPolicy` - The number of attempts, the waits between them, and the timeout of each attempt.
+///
+/// # Returns
+///
+/// * `Result<T, RetryError<E>>`: The output of the first successful attempt,
+///   otherwise the error of the last attempt, or the timeout if it didn't complete
+pub async fn retry_with_backoff<F, Fut, T, E>(
+    mut make_future: F,
+    policy: &RetryPolicy,
+) -> Result<T, RetryError<E>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        let result = match policy.attempt_timeout {
+            Some(max_time) => match timeout(make_future(), max_time).await {
+                Ok(result) => result.map_err(RetryError::Failed),
+                Err(duration) => Err(RetryError::TimedOut(duration)),
+            },
+            None => make_future().await.map_err(RetryError::Failed),
+        };
+        match result {
+            Ok(output) => return Ok(output),
+            Err(error) if attempt >= policy.max_attempts => return Err(error),
+            Err(_) => {
+                trpl::sleep(policy.backoff_with_jitter(attempt)).await;
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Consumes a stream from an unreliable source, re-creating it when it fails or ends.
+///
+/// The source yields each item with its cursor, such as an offset or the id of a message, and when the stream
+/// is re-created `make_stream` receives the cursor of the last item delivered, so it can resume after it
+/// instead of starting over. The first stream is created with `None`.
+///
+/// A live source isn't supposed to end, so an ended stream is re-created like a failed one. The waits between
+/// the attempts follow the policy, and the attempts are counted again from 1 once an item is received.
+/// When they are exhausted the stream ends, yielding the last error first, if the last attempt failed with one.
+///
+/// # Arguments
+///
+/// * `make_stream: FnMut(Option<C>) -> Stream<Item = Result<(C, T), E>>` - Creates the stream, resuming after the cursor.
+/// * `policy: RetryPolicy` - The number of consecutive attempts, and the waits between them.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = Result<T, E>>`: The items of the sources without their cursor, and the error giving up.
+pub fn with_retries<F, S, C, T, E>(
+    make_stream: F,
+    policy: RetryPolicy,
+) -> impl Stream<Item = Result<T, E>>
+where
+    F: FnMut(Option<C>) -> S,
+    S: Stream<Item = Result<(C, T), E>>,
+    C: Clone,
+{
+    let state = Resume {
+        make_stream,
+        policy,
+        cursor: None,
+        current: None,
+        attempt: 1,
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if state.done {
+                return None;
+            }
+            let source = match state.current.as_mut() {
+                Some(source) => source,
+                None => state
+                    .current
+                    .insert(Box::pin((state.make_stream)(state.cursor.clone()))),
+            };
+            let failure = match source.next().await {
+                Some(Ok((cursor, item))) => {
+                    state.cursor = Some(cursor);
+                    state.attempt = 1;
+                    return Some((Ok(item), state));
+                }
+                Some(Err(error)) => Some(error),
+                None => None,
+            };
+
+            state.current = None;
+            if state.attempt >= state.policy.max_attempts {
+                state.done = true;
+                return failure.map(|error| (Err(error), state));
+            }
+            trpl::sleep(state.policy.backoff_with_jitter(state.attempt)).await;
+            state.attempt += 1;
+        }
+    })
+}
+
+/// The state of [`with_retries`] between two items.
+struct Resume<F, S, C> {
+    make_stream: F,
+    policy: RetryPolicy,
+    cursor: Option<C>,
+    current: Option<Pin<Box<S>>>,
+    attempt: u32,
+    done: bool,
+}
@@ -0,0 +1,166 @@
+//! Time-based stream adapters.
+//!
+//! `trpl::StreamExt` already has a `throttle` method, used in the chapter to slow down `get_intervals`,
+//! these adapters implement the `Stream` trait by hand to show how they work, and add `debounce`.
+//! Since both traits have a `throttle` method, when they are both in scope it must be called as
+//! `StreamTimeExt::throttle(stream, period)`.
+//!
+//! [`merge_tagged`] merges streams of different item types, which `trpl::StreamExt::merge` can't do.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use trpl::{Stream, StreamExt};
+
+/// A pending `trpl::sleep`, boxed so the adapters don't need to be pinned structurally.
+type Delay = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Extension trait adding time-based adapters to every stream.
+pub trait StreamTimeExt: Stream + Sized {
+    /// Emits an item only once the stream has been quiet for `period`.
+    ///
+    /// Each new item replaces the one waiting and restarts the wait, so a burst of items produces only the last one.
+    /// When the stream ends, the item waiting is emitted right away.
+    fn debounce(self, period: Duration) -> Debounce<Self> {
+        Debounce {
+            stream: Box::pin(self),
+            period,
+            pending: None,
+            delay: None,
+            done: false,
+        }
+    }
+
+    /// Waits `period` after each item before polling the stream again, so at most one item is emitted every `period`.
+    ///
+    /// Unlike `debounce` no item is dropped: the items produced in the meantime wait in the stream.
+    fn throttle(self, period: Duration) -> Throttle<Self> {
+        Throttle {
+            stream: Box::pin(self),
+            period,
+            delay: None,
+        }
+    }
+}
+
+impl<S: Stream> StreamTimeExt for S {}
+
+/// Stream returned by [`StreamTimeExt::debounce`].
+pub struct Debounce<S: Stream> {
+    stream: Pin<Box<S>>,
+    period: Duration,
+    pending: Option<S::Item>,
+    delay: Option<Delay>,
+    done: bool,
+}
+
+// The item waiting is never pinned, and the stream and the delay are pinned in their boxes.
+impl<S: Stream> Unpin for Debounce<S> {}
+
+impl<S: Stream> Stream for Debounce<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        // Take every item ready, only the last one is kept
+        while !this.done {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.pending = Some(item);
+                    this.delay = Some(Box::pin(trpl::sleep(this.period)));
+                }
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => break,
+            }
+        }
+        if this.done {
+            return Poll::Ready(this.pending.take());
+        }
+        if let Some(delay) = this.delay.as_mut()
+            && delay.as_mut().poll(cx).is_ready()
+        {
+            this.delay = None;
+            return Poll::Ready(this.pending.take());
+        }
+        Poll::Pending
+    }
+}
+
+/// Stream returned by [`StreamTimeExt::throttle`].
+pub struct Throttle<S: Stream> {
+    stream: Pin<Box<S>>,
+    period: Duration,
+    delay: Option<Delay>,
+}
+
+impl<S: Stream> Stream for Throttle<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if let Some(delay) = this.delay.as_mut() {
+            if delay.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            this.delay = None;
+        }
+        let item = this.stream.as_mut().poll_next(cx);
+        if let Poll::Ready(Some(_)) = item {
+            this.delay = Some(Box::pin(trpl::sleep(this.period)));
+        }
+        item
+    }
+}
+
+/// An item of the stream returned by [`merge_tagged`], telling which stream it comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EitherItem<A, B> {
+    /// An item of the first stream.
+    Left(A),
+    /// An item of the second stream.
+    Right(B),
+}
+
+impl<A, B> EitherItem<A, B> {
+    /// Returns the item if it comes from the first stream.
+    pub fn left(self) -> Option<A> {
+        match self {
+            EitherItem::Left(item) => Some(item),
+            EitherItem::Right(_) => None,
+        }
+    }
+
+    /// Returns the item if it comes from the second stream.
+    pub fn right(self) -> Option<B> {
+        match self {
+            EitherItem::Left(_) => None,
+            EitherItem::Right(item) => Some(item),
+        }
+    }
+}
+
+/// Merges two streams with different item types, tagging each item with the stream it comes from.
+///
+/// The items are emitted as soon as either stream produces them, and the merged stream ends when both have ended.
+/// Like in the chapter, where the intervals are mapped to `String` to be merged with the messages,
+/// the two streams are mapped to the same type, but the original items are kept.
+///
+/// # Arguments
+///
+/// * `left: Stream<Item = A>` - The first stream, whose items become `EitherItem::Left`.
+/// * `right: Stream<Item = B>` - The second stream, whose items become `EitherItem::Right`.
+///
+/// # Returns
+///
+/// * `impl Stream<Item = EitherItem<A, B>>`: The items of both streams, in the order they arrive.
+pub fn merge_tagged<A, B>(
+    left: impl Stream<Item = A>,
+    right: impl Stream<Item = B>,
+) -> impl Stream<Item = EitherItem<A, B>> {
+    left.map(EitherItem::Left)
+        .merge(right.map(EitherItem::Right))
+}
@@ -0,0 +1,112 @@
+//! Graceful shutdown of spawned tasks.
+//!
+//! A task spawned with `trpl::spawn_task` keeps running until it completes, or until the runtime is dropped,
+//! which stops it at whatever await point it reached. A [`TaskGroup`] keeps the handles of its tasks
+//! and gives them a shared [`CancellationToken`], so they can be asked to stop, and awaited, before exiting.
+
+use std::{future::Future, time::Duration};
+
+use trpl::JoinHandle;
+
+use crate::{
+    CancellationToken,
+    metrics::{MetricsRegistry, TaskMetrics},
+    time::Instant,
+    timeout,
+};
+
+/// How the tasks of a [`TaskGroup`] ended.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ShutdownReport {
+    /// The tasks that returned before the deadline.
+    pub completed: usize,
+    /// The tasks that panicked.
+    pub panicked: usize,
+    /// The tasks still running at the deadline, which have been aborted.
+    pub aborted: usize,
+}
+
+/// A group of spawned tasks, sharing a cancellation token.
+///
+/// The tasks are spawned through a [`MetricsRegistry`], its own or one shared with other groups.
+/// Dropping the group doesn't stop the tasks, they keep running detached like any spawned task.
+#[derive(Debug, Default)]
+pub struct TaskGroup {
+    token: CancellationToken,
+    handles: Vec<JoinHandle<()>>,
+    registry: MetricsRegistry,
+}
+
+impl TaskGroup {
+    /// Creates an empty group, with its own registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty group counting its tasks in the given registry, e.g. to observe all the groups of a program together.
+    pub fn with_registry(registry: MetricsRegistry) -> Self {
+        TaskGroup {
+            registry,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the counters of the registry of the group.
+    pub fn metrics(&self) -> TaskMetrics {
+        self.registry.metrics()
+    }
+
+    /// Returns the token cancelled by [`TaskGroup::shutdown`], e.g. to give it to tasks spawned elsewhere.
+    pub fn token(&self) -> &CancellationToken {
+        &self.token
+    }
+
+    /// Spawns a task on the runtime, passing it a clone of the token of the group.
+    ///
+    /// The task should stop when the token is cancelled, e.g. by awaiting its work with `run_until_cancelled`.
+    pub fn spawn<F, Fut>(&mut self, task: F)
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handle = self.registry.spawn(task(self.token.clone()));
+        self.handles.push(handle);
+    }
+
+    /// Returns the number of tasks spawned in the group.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if no task has been spawned in the group.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Waits for all the tasks to complete by themselves, aborting the ones still running after `deadline`.
+    pub async fn join_all(self, deadline: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + deadline;
+        let mut report = ShutdownReport::default();
+
+        for mut handle in self.handles {
+            // The deadline is shared: each task gets the time left by the ones awaited before it
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match timeout(&mut handle, remaining).await {
+                Ok(Ok(())) => report.completed += 1,
+                Ok(Err(error)) if error.is_panic() => report.panicked += 1,
+                Ok(Err(_)) => report.aborted += 1,
+                Err(_) => {
+                    handle.abort();
+                    report.aborted += 1;
+                }
+            }
+        }
+        report
+    }
+
+    /// Cancels the token of the group, then waits for the tasks like [`TaskGroup::join_all`].
+    pub async fn shutdown(self, deadline: Duration) -> ShutdownReport {
+        self.token.cancel();
+        self.join_all(deadline).await
+    }
+}
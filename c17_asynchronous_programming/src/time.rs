@@ -0,0 +1,38 @@
+//! Timers and intervals scheduled on the clock of the runtime.
+//!
+//! A loop sleeping for the period after each iteration, like the first version of `get_intervals`, drifts:
+//! every iteration takes the period plus the time spent in the body and waking up.
+//! Here each tick is scheduled from the original start instead, so the delays don't add up.
+
+use std::{future::Future, time::Duration};
+
+use futures::stream;
+use trpl::Stream;
+
+/// The `Instant` of the runtime used by `trpl`, which follows the paused clock in the tests, unlike `std::time::Instant`.
+pub use tokio::time::Instant;
+
+/// Returns a future completing at the given instant, right away if it's already passed.
+pub fn timer(at: Instant) -> impl Future<Output = ()> {
+    trpl::sleep(at.saturating_duration_since(Instant::now()))
+}
+
+/// Returns a stream ticking every `period`, yielding the instant each tick was scheduled for.
+///
+/// The first tick happens one period after the call, the n-th one n periods after it, whatever happens in between.
+/// When the consumer falls behind, the ticks missed are emitted right away to catch up with the schedule.
+///
+/// # Panics
+///
+/// Panics if the period is zero.
+pub fn interval(period: Duration) -> impl Stream<Item = Instant> {
+    assert!(!period.is_zero(), "the period must be longer than zero");
+    let start = Instant::now();
+
+    // The durations are exact integers, so adding the period to the previous tick keeps to the schedule,
+    // without a counter of the ticks that would overflow on a long-running stream
+    stream::unfold(start + period, move |at| async move {
+        timer(at).await;
+        Some((at, at + period))
+    })
+}
@@ -0,0 +1,117 @@
+//! An async worker queue, the counterpart of the `ThreadPool` of chapter 21 with tasks instead of threads.
+//!
+//! A fixed number of worker tasks pull the jobs from a shared channel. The channel is bounded,
+//! so submitting a job waits while the queue is full, slowing down the producers instead of
+//! piling up jobs in memory, which the unbounded `trpl::channel` can't do.
+
+use std::{future::Future, panic::AssertUnwindSafe, pin::Pin, sync::Arc};
+
+use futures::FutureExt;
+use tokio::sync::{Mutex, mpsc};
+use trpl::JoinHandle;
+
+type Job = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// How the jobs of an [`AsyncWorkQueue`] ended, returned once it's drained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DrainReport {
+    /// The jobs that completed.
+    pub completed: usize,
+    /// The jobs that panicked, a panic stops the job but not its worker.
+    pub panicked: usize,
+}
+
+/// A queue of jobs run by a fixed number of worker tasks.
+pub struct AsyncWorkQueue {
+    workers: Vec<JoinHandle<DrainReport>>,
+    sender: mpsc::Sender<Job>,
+}
+
+impl AsyncWorkQueue {
+    /// Creates a queue with `size` workers, holding up to `capacity` jobs waiting for a worker.
+    ///
+    /// It must be called while the runtime is running, since the workers are spawned right away.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `capacity` is zero.
+    pub fn new(size: usize, capacity: usize) -> AsyncWorkQueue {
+        assert!(size > 0, "at least one worker is needed");
+        assert!(capacity > 0, "the queue must hold at least one job");
+
+        let (sender, receiver) = mpsc::channel(capacity);
+        // Like in the `ThreadPool` the receiver is shared by the workers, but with the async `Mutex`,
+        // so a worker waiting for a job doesn't block the thread of the runtime
+        let receiver = Arc::new(Mutex::new(receiver));
+        let workers = (0..size)
+            .map(|_| trpl::spawn_task(work(Arc::clone(&receiver))))
+            .collect();
+
+        AsyncWorkQueue { workers, sender }
+    }
+
+    /// Submits a job, waiting for room in the queue if it's full.
+    pub async fn submit<F>(&self, job: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.sender
+            .send(Box::pin(job))
+            .await
+            .expect("the workers stop only when the queue is drained");
+    }
+
+    /// Submits a job only if there is room in the queue, otherwise the job is given back.
+    pub fn try_submit<F>(&self, job: F) -> Result<(), F>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        match self.sender.try_reserve() {
+            Ok(permit) => {
+                permit.send(Box::pin(job));
+                Ok(())
+            }
+            Err(_) => Err(job),
+        }
+    }
+
+    /// Returns the number of workers.
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Stops accepting jobs, and waits for the workers to run the jobs left in the queue.
+    ///
+    /// Dropping the queue without shutting it down has the same effect, but without waiting:
+    /// the workers finish the jobs left in the background, as long as the runtime is running.
+    pub async fn shutdown(self) -> DrainReport {
+        // Like the `Drop` of the `ThreadPool`, closing the channel makes the workers stop once it's empty
+        drop(self.sender);
+        let mut report = DrainReport::default();
+        for worker in self.workers {
+            let worker_report = worker
+                .await
+                .expect("the workers catch the panics of the jobs");
+            report.completed += worker_report.completed;
+            report.panicked += worker_report.panicked;
+        }
+        report
+    }
+}
+
+/// The loop of a worker: runs the jobs until the channel is closed and empty.
+async fn work(receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> DrainReport {
+    let mut report = DrainReport::default();
+    loop {
+        // The lock is released at the end of the statement, before running the job
+        let job = receiver.lock().await.recv().await;
+        let Some(job) = job else {
+            break;
+        };
+        match AssertUnwindSafe(job).catch_unwind().await {
+            Ok(()) => report.completed += 1,
+            Err(_) => report.panicked += 1,
+        }
+    }
+    report
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use c10_generics_traits_lifetimes::{Feed, Summary, Tweet};
+use c17_asynchronous_programming::{StreamMessage, feed_from_stream, feed_messages, get_messages};
+use common::run_paused;
+use tokio::time::Instant;
+
+#[test]
+fn messages_become_summaries() {
+    let message = StreamMessage::new("chat", "hello");
+
+    assert_eq!(message.summarise(), "[chat] hello");
+    assert_eq!(message.summarise_author(), "chat");
+}
+
+#[test]
+fn every_message_is_pushed_into_the_feed() {
+    trpl::run(async {
+        let mut feed = Feed::new();
+
+        let pushed = feed_messages(get_messages(), "letters", &mut feed).await;
+
+        assert_eq!(pushed, 10);
+        assert_eq!(feed.len(), 10);
+        assert_eq!(feed.summaries()[0], "[letters] Message: 'a'");
+        assert_eq!(feed.get(9).unwrap().summarise_author(), "letters");
+    });
+}
+
+#[test]
+fn subscribers_are_notified_as_messages_arrive() {
+    run_paused(async {
+        let start = Instant::now();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut feed = Feed::new();
+        let log = Arc::clone(&seen);
+        feed.subscribe(move |item| {
+            log.lock()
+                .unwrap()
+                .push((start.elapsed(), item.summarise()));
+        });
+
+        feed_messages(get_messages(), "letters", &mut feed).await;
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 10);
+        // The first message is sent after 100ms, not once the whole stream is over after 2s
+        assert_eq!(
+            seen[0],
+            (
+                Duration::from_millis(100),
+                "[letters] Message: 'a'".to_string()
+            )
+        );
+        assert_eq!(seen[9].0, Duration::from_millis(2000));
+    });
+}
+
+#[test]
+fn items_can_be_any_summary() {
+    trpl::run(async {
+        let mut feed = Feed::new();
+        feed.push(StreamMessage::new("chat", "first"));
+
+        let usernames = trpl::stream_from_iter(["ferris", "corro"]);
+        let pushed = feed_from_stream(usernames, &mut feed, |username| Tweet {
+            username: username.to_string(),
+            content: "Async!".to_string(),
+            reply: false,
+            retweet: false,
+        })
+        .await;
+
+        assert_eq!(pushed, 2);
+        assert_eq!(
+            feed.summaries(),
+            ["[chat] first", "ferris: Async!", "corro: Async!"]
+        );
+    });
+}
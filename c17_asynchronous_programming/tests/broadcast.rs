@@ -0,0 +1,89 @@
+mod common;
+
+use c17_asynchronous_programming::{
+    LagPolicy, broadcast, broadcast::Lagged, get_messages, spawn_broadcast,
+};
+use common::run_paused;
+use trpl::StreamExt;
+
+#[test]
+fn every_receiver_gets_every_message() {
+    trpl::run(async {
+        let (tx, mut first) = broadcast(8, LagPolicy::Error);
+        let second = tx.subscribe();
+
+        assert_eq!(tx.send(1), Ok(2));
+        assert_eq!(tx.send(2), Ok(2));
+        drop(tx);
+
+        assert_eq!(first.recv().await, Some(Ok(1)));
+        assert_eq!(first.recv().await, Some(Ok(2)));
+        assert_eq!(first.recv().await, None);
+        assert_eq!(
+            second.into_stream().collect::<Vec<_>>().await,
+            vec![Ok(1), Ok(2)]
+        );
+    });
+}
+
+#[test]
+fn lagging_receiver_skips_the_oldest_messages() {
+    trpl::run(async {
+        let (tx, rx) = broadcast(2, LagPolicy::DropOldest);
+        for message in 1..=5 {
+            tx.send(message).unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(
+            rx.into_stream().collect::<Vec<_>>().await,
+            vec![Ok(4), Ok(5)]
+        );
+    });
+}
+
+#[test]
+fn lagging_receiver_is_told_how_many_it_missed() {
+    trpl::run(async {
+        let (tx, rx) = broadcast(2, LagPolicy::Error);
+        for message in 1..=5 {
+            tx.send(message).unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(
+            rx.into_stream().collect::<Vec<_>>().await,
+            vec![Err(Lagged(3)), Ok(4), Ok(5)]
+        );
+    });
+}
+
+#[test]
+fn send_without_receivers_gives_the_message_back() {
+    let (tx, rx) = broadcast(1, LagPolicy::Error);
+    drop(rx);
+
+    assert_eq!(tx.receiver_count(), 0);
+    assert_eq!(tx.send("lost"), Err("lost"));
+}
+
+#[test]
+fn one_producer_feeds_several_consumers() {
+    run_paused(async {
+        let receivers = spawn_broadcast(get_messages(), 3, 16, LagPolicy::Error);
+        assert_eq!(receivers.len(), 3);
+
+        let consumers = receivers
+            .into_iter()
+            .map(|receiver| receiver.into_stream().collect::<Vec<_>>());
+        let received = trpl::join_all(consumers).await;
+
+        let expected: Vec<_> = "abcdefghij"
+            .chars()
+            .map(|letter| Ok(format!("Message: '{letter}'")))
+            .collect();
+        for messages in received {
+            assert_eq!(messages, expected);
+        }
+    });
+}
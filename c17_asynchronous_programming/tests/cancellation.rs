@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use c17_asynchronous_programming::{
+    CancellationToken, get_intervals_until_cancelled, run_until_cancelled,
+};
+use trpl::StreamExt;
+
+#[test]
+fn clones_share_the_cancellation() {
+    let token = CancellationToken::new();
+    let clone = token.clone();
+
+    assert!(!token.is_cancelled());
+    clone.cancel();
+    assert!(token.is_cancelled());
+    assert!(clone.is_cancelled());
+}
+
+#[test]
+fn cancelled_wakes_up_the_waiting_task() {
+    trpl::run(async {
+        let token = CancellationToken::new();
+        let waiting = trpl::spawn_task({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+
+        trpl::sleep(Duration::from_millis(10)).await;
+        token.cancel();
+
+        waiting.await.unwrap();
+    });
+}
+
+#[test]
+fn completed_future_returns_its_output() {
+    trpl::run(async {
+        let token = CancellationToken::new();
+
+        assert_eq!(run_until_cancelled(async { 42 }, &token).await, Some(42));
+    });
+}
+
+#[test]
+fn cancellation_drops_the_future() {
+    trpl::run(async {
+        let token = CancellationToken::new();
+        let slow = async {
+            trpl::sleep(Duration::from_secs(10)).await;
+            "finished"
+        };
+        let cancel = async {
+            trpl::sleep(Duration::from_millis(10)).await;
+            token.cancel();
+        };
+
+        let (output, ()) = trpl::join(run_until_cancelled(slow, &token), cancel).await;
+
+        assert_eq!(output, None);
+    });
+}
+
+#[test]
+fn already_cancelled_token_doesnt_poll_the_future() {
+    trpl::run(async {
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let output = run_until_cancelled(async { panic!("polled") }, &token).await;
+
+        assert_eq!(output, None::<()>);
+    });
+}
+
+#[test]
+fn intervals_stop_when_cancelled() {
+    trpl::run(async {
+        let token = CancellationToken::new();
+        let mut intervals = get_intervals_until_cancelled(&token);
+
+        assert_eq!(intervals.next().await, Some(1));
+        token.cancel();
+        // The intervals already sent can still be received, then the stream ends
+        let rest: Vec<u32> = intervals.collect().await;
+        assert!(rest.iter().all(|&count| count > 1));
+    });
+}
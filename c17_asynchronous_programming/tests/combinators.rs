@@ -0,0 +1,235 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use c17_asynchronous_programming::{
+    Either3, TimedOut, join_all_limited, race_all, select_timeout, try_join_all,
+    try_join_all_settled,
+};
+
+#[test]
+fn outputs_are_in_input_order() {
+    trpl::run(async {
+        let futures = [30, 10, 20].map(|ms| async move {
+            trpl::sleep(Duration::from_millis(ms)).await;
+            ms
+        });
+
+        assert_eq!(join_all_limited(futures, 2).await, vec![30, 10, 20]);
+    });
+}
+
+#[test]
+fn never_more_than_max_in_flight() {
+    trpl::run(async {
+        let in_flight = Cell::new(0);
+        let peak = Cell::new(0);
+        let futures = (0..10).map(|i| {
+            let (in_flight, peak) = (&in_flight, &peak);
+            async move {
+                in_flight.set(in_flight.get() + 1);
+                peak.set(peak.get().max(in_flight.get()));
+                trpl::sleep(Duration::from_millis(5)).await;
+                in_flight.set(in_flight.get() - 1);
+                i
+            }
+        });
+
+        let outputs = join_all_limited(futures, 3).await;
+
+        assert_eq!(outputs, (0..10).collect::<Vec<_>>());
+        assert_eq!(peak.get(), 3);
+    });
+}
+
+#[test]
+fn next_future_starts_when_a_slot_frees_up() {
+    trpl::run(async {
+        // In chunks of 2 this would take 200ms + 50ms + 200ms, instead the short futures keep the second slot busy and it takes 350ms
+        let durations = [200, 50, 50, 50, 200];
+        let futures = durations.map(|ms| trpl::sleep(Duration::from_millis(ms)));
+
+        let start = Instant::now();
+        join_all_limited(futures, 2).await;
+
+        assert!(start.elapsed() < Duration::from_millis(430));
+    });
+}
+
+#[test]
+fn empty_input() {
+    trpl::run(async {
+        let futures: Vec<std::future::Ready<u8>> = Vec::new();
+
+        assert!(join_all_limited(futures, 4).await.is_empty());
+    });
+}
+
+#[test]
+#[should_panic(expected = "at least one future must be in flight")]
+fn zero_in_flight_panics() {
+    trpl::run(join_all_limited([async {}], 0));
+}
+
+#[test]
+fn first_to_complete_wins_the_race() {
+    trpl::run(async {
+        let futures = [30, 10, 20].map(|ms| async move {
+            trpl::sleep(Duration::from_millis(ms)).await;
+            ms * 2
+        });
+
+        assert_eq!(race_all(futures).await, (1, 20));
+    });
+}
+
+#[test]
+fn ready_futures_win_in_order() {
+    trpl::run(async {
+        let futures = vec![std::future::ready('a'), std::future::ready('b')];
+
+        assert_eq!(race_all(futures).await, (0, 'a'));
+    });
+}
+
+#[test]
+fn losers_are_dropped() {
+    trpl::run(async {
+        let finished = Cell::new(0);
+        let futures = [10, 50, 50].map(|ms| {
+            let finished = &finished;
+            async move {
+                trpl::sleep(Duration::from_millis(ms)).await;
+                finished.set(finished.get() + 1);
+            }
+        });
+
+        race_all(futures).await;
+        trpl::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(finished.get(), 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "at least one future is needed to race")]
+fn empty_race_panics() {
+    trpl::run(race_all(Vec::<std::future::Ready<()>>::new()));
+}
+
+async fn parse_after(ms: u64, input: &str) -> Result<i32, String> {
+    trpl::sleep(Duration::from_millis(ms)).await;
+    input
+        .parse()
+        .map_err(|_| format!("'{input}' is not a number"))
+}
+
+#[test]
+fn try_join_all_collects_in_order() {
+    trpl::run(async {
+        let futures = [(30, "1"), (10, "2"), (20, "3")].map(|(ms, input)| parse_after(ms, input));
+
+        assert_eq!(try_join_all(futures).await, Ok(vec![1, 2, 3]));
+    });
+}
+
+#[test]
+fn try_join_all_fails_fast() {
+    trpl::run(async {
+        let start = Instant::now();
+        let futures =
+            [(1000, "1"), (10, "two"), (1000, "three")].map(|(ms, input)| parse_after(ms, input));
+
+        assert_eq!(
+            try_join_all(futures).await,
+            Err(String::from("'two' is not a number"))
+        );
+        assert!(start.elapsed() < Duration::from_millis(500));
+    });
+}
+
+#[test]
+fn settled_waits_for_every_future() {
+    trpl::run(async {
+        let futures = [(10, "1"), (20, "two"), (5, "3"), (1, "four")]
+            .map(|(ms, input)| parse_after(ms, input));
+
+        let (results, report) = try_join_all_settled(futures).await;
+
+        assert_eq!(
+            results,
+            vec![
+                Ok(1),
+                Err(String::from("'two' is not a number")),
+                Ok(3),
+                Err(String::from("'four' is not a number")),
+            ]
+        );
+        let report = report.unwrap();
+        assert_eq!(report.total(), 4);
+        assert_eq!(
+            report
+                .failures()
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(
+            report.to_string(),
+            "2 of 4 futures failed\n  #1: 'two' is not a number\n  #3: 'four' is not a number"
+        );
+    });
+}
+
+#[test]
+fn settled_without_errors_has_no_report() {
+    trpl::run(async {
+        let (results, report) = try_join_all_settled([parse_after(1, "7")]).await;
+
+        assert_eq!(results, vec![Ok(7)]);
+        assert_eq!(report, None);
+    });
+}
+
+#[test]
+fn select_timeout_returns_the_first_to_complete() {
+    trpl::run(async {
+        let word = async {
+            trpl::sleep(Duration::from_millis(30)).await;
+            "word"
+        };
+        let number = async {
+            trpl::sleep(Duration::from_millis(10)).await;
+            7
+        };
+
+        assert_eq!(
+            select_timeout(word, number, Duration::from_secs(1)).await,
+            Either3::Second(7)
+        );
+        assert_eq!(
+            select_timeout(
+                async { 'a' },
+                std::future::pending::<()>(),
+                Duration::from_secs(1)
+            )
+            .await,
+            Either3::First('a')
+        );
+    });
+}
+
+#[test]
+fn select_timeout_reports_the_deadline() {
+    trpl::run(async {
+        let slow = trpl::sleep(Duration::from_secs(10));
+        let never = std::future::pending::<u8>();
+
+        assert_eq!(
+            select_timeout(slow, never, Duration::from_millis(10)).await,
+            Either3::Third(TimedOut(Duration::from_millis(10)))
+        );
+    });
+}
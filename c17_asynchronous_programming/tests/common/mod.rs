@@ -0,0 +1,12 @@
+use std::future::Future;
+
+/// Runs the future on a runtime with a paused clock: time only advances when every task is waiting on a timer,
+/// jumping straight to the next one, so the tests are fast and the times are exact.
+pub fn run_paused<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .enable_time()
+        .start_paused(true)
+        .build()
+        .unwrap()
+        .block_on(future)
+}
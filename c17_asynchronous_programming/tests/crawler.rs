@@ -0,0 +1,133 @@
+use std::{cell::RefCell, time::Duration};
+
+use c17_asynchronous_programming::{FetchError, Url, crawl, crawler::crawl_with};
+use trpl::StreamExt;
+
+/// Pretends to fetch a page: the title is the URL in uppercase, after waiting the milliseconds in the URL path.
+async fn fake_fetch(url: Url) -> Result<Option<String>, FetchError> {
+    let ms: u64 = url.rsplit('/').next().unwrap().parse().unwrap();
+    trpl::sleep(Duration::from_millis(ms)).await;
+    if url.starts_with("bad") {
+        Err(FetchError::Request(String::from("connection refused")))
+    } else {
+        Ok(Some(url.to_uppercase()))
+    }
+}
+
+fn urls(urls: &[&str]) -> Vec<Url> {
+    urls.iter().map(|url| url.to_string()).collect()
+}
+
+#[test]
+fn results_arrive_as_they_complete() {
+    trpl::run(async {
+        let crawled = crawl_with(
+            urls(&["a/30", "b/10", "c/20"]),
+            3,
+            Duration::from_secs(1),
+            fake_fetch,
+        );
+        let crawled: Vec<_> = crawled.collect().await;
+
+        assert_eq!(
+            crawled,
+            vec![
+                (String::from("b/10"), Ok(Some(String::from("B/10")))),
+                (String::from("c/20"), Ok(Some(String::from("C/20")))),
+                (String::from("a/30"), Ok(Some(String::from("A/30")))),
+            ]
+        );
+    });
+}
+
+#[test]
+fn duplicate_urls_are_fetched_once() {
+    trpl::run(async {
+        let fetched = RefCell::new(Vec::new());
+        let crawled = crawl_with(
+            urls(&["a/1", "b/1", "a/1", "b/1"]),
+            2,
+            Duration::from_secs(1),
+            |url| {
+                fetched.borrow_mut().push(url.clone());
+                fake_fetch(url)
+            },
+        );
+
+        assert_eq!(crawled.collect::<Vec<_>>().await.len(), 2);
+        assert_eq!(*fetched.borrow(), urls(&["a/1", "b/1"]));
+    });
+}
+
+#[test]
+fn errors_and_timeouts_are_reported_per_url() {
+    trpl::run(async {
+        let crawled = crawl_with(
+            urls(&["bad/1", "slow/500", "ok/1"]),
+            3,
+            Duration::from_millis(50),
+            fake_fetch,
+        );
+        let mut crawled: Vec<_> = crawled.collect().await;
+        crawled.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            crawled,
+            vec![
+                (
+                    String::from("bad/1"),
+                    Err(FetchError::Request(String::from("connection refused")))
+                ),
+                (String::from("ok/1"), Ok(Some(String::from("OK/1")))),
+                (
+                    String::from("slow/500"),
+                    Err(FetchError::TimedOut(Duration::from_millis(50)))
+                ),
+            ]
+        );
+    });
+}
+
+#[test]
+fn concurrency_is_limited() {
+    trpl::run(async {
+        let in_flight = RefCell::new((0, 0));
+        let crawled = crawl_with(
+            (0..10).map(|i| format!("page{i}/5")),
+            4,
+            Duration::from_secs(1),
+            |url| {
+                let in_flight = &in_flight;
+                async move {
+                    {
+                        let (current, peak) = &mut *in_flight.borrow_mut();
+                        *current += 1;
+                        *peak = (*peak).max(*current);
+                    }
+                    let result = fake_fetch(url).await;
+                    in_flight.borrow_mut().0 -= 1;
+                    result
+                }
+            },
+        );
+
+        assert_eq!(crawled.collect::<Vec<_>>().await.len(), 10);
+        assert_eq!(in_flight.borrow().1, 4);
+    });
+}
+
+#[test]
+#[ignore = "requires network access"]
+fn crawl_real_pages() {
+    trpl::run(async {
+        let crawled: Vec<_> = crawl(
+            urls(&["https://www.rust-lang.org", "https://www.rust-lang.org"]),
+            2,
+        )
+        .collect()
+        .await;
+
+        assert_eq!(crawled.len(), 1);
+        assert!(crawled[0].1.as_ref().is_ok_and(Option::is_some));
+    });
+}
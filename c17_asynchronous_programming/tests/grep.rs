@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use c17_asynchronous_programming::{Match, search_files};
+use trpl::StreamExt;
+
+/// Writes the files in a new directory for the test, returning their paths.
+fn write_files(test: &str, files: &[(&str, &str)]) -> Vec<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("c17_grep_{test}_{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    files
+        .iter()
+        .map(|(name, contents)| {
+            let path = dir.join(name);
+            fs::write(&path, contents).unwrap();
+            path
+        })
+        .collect()
+}
+
+fn matched(path: &Path, line: &str) -> Match {
+    Match {
+        path: path.to_path_buf(),
+        line: String::from(line),
+    }
+}
+
+#[test]
+fn matches_keep_the_order_of_files_and_lines() {
+    let paths = write_files(
+        "order",
+        &[
+            (
+                "poem.txt",
+                "I'm nobody! Who are you?\nAre you nobody, too?\nThen there's a pair of us",
+            ),
+            ("rust.txt", "Rust:\nsafe, fast, productive.\nPick three."),
+            (
+                "frog.txt",
+                "How dreary to be somebody!\nHow public, like a frog",
+            ),
+        ],
+    );
+
+    let matches: Vec<Match> = trpl::run(search_files("body", &paths, false).collect());
+
+    assert_eq!(
+        matches,
+        vec![
+            matched(&paths[0], "I'm nobody! Who are you?"),
+            matched(&paths[0], "Are you nobody, too?"),
+            matched(&paths[2], "How dreary to be somebody!"),
+        ]
+    );
+}
+
+#[test]
+fn ignore_case_uses_the_case_insensitive_search() {
+    let paths = write_files(
+        "ignore_case",
+        &[("rust.txt", "Rust:\nsafe, fast, productive.\nTrust me.")],
+    );
+
+    let sensitive: Vec<Match> = trpl::run(search_files("rUsT", &paths, false).collect());
+    let insensitive: Vec<Match> = trpl::run(search_files("rUsT", &paths, true).collect());
+
+    assert!(sensitive.is_empty());
+    assert_eq!(
+        insensitive,
+        vec![matched(&paths[0], "Rust:"), matched(&paths[0], "Trust me.")]
+    );
+}
+
+#[test]
+fn unreadable_files_are_skipped() {
+    let mut paths = write_files("missing", &[("found.txt", "the needle")]);
+    paths.insert(0, paths[0].with_file_name("missing.txt"));
+
+    let matches: Vec<Match> = trpl::run(search_files("needle", &paths, false).collect());
+
+    assert_eq!(matches, vec![matched(&paths[1], "the needle")]);
+}
+
+#[test]
+fn many_files() {
+    let files: Vec<(String, String)> = (0..50)
+        .map(|i| (format!("{i}.txt"), format!("line {i}\nneedle {i}\n")))
+        .collect();
+    let files: Vec<(&str, &str)> = files
+        .iter()
+        .map(|(name, contents)| (name.as_str(), contents.as_str()))
+        .collect();
+    let paths = write_files("many", &files);
+
+    let matches: Vec<Match> = trpl::run(search_files("needle", &paths, false).collect());
+
+    let lines: Vec<String> = matches.into_iter().map(|found| found.line).collect();
+    let expected: Vec<String> = (0..50).map(|i| format!("needle {i}")).collect();
+    assert_eq!(lines, expected);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use std::time::Duration;
+
+use c17_asynchronous_programming::{MetricsRegistry, TaskGroup, TaskMetrics};
+use common::run_paused;
+
+#[test]
+fn counts_how_tasks_end() {
+    run_paused(async {
+        let registry = MetricsRegistry::new();
+
+        let completed = registry.spawn(async { 42 });
+        let panicked = registry.spawn(async { panic!("task failed") });
+        let aborted = registry.spawn(trpl::sleep(Duration::from_secs(60)));
+        let running = registry.spawn(trpl::sleep(Duration::from_secs(60)));
+        assert_eq!(registry.metrics().running, 4);
+
+        assert_eq!(completed.await.unwrap(), 42);
+        assert!(panicked.await.unwrap_err().is_panic());
+        aborted.abort();
+        assert!(aborted.await.unwrap_err().is_cancelled());
+
+        assert_eq!(
+            registry.metrics(),
+            TaskMetrics {
+                spawned: 4,
+                completed: 1,
+                panicked: 1,
+                aborted: 1,
+                running: 1,
+            }
+        );
+        drop(running);
+    });
+}
+
+#[test]
+fn clones_share_the_counters() {
+    run_paused(async {
+        let registry = MetricsRegistry::new();
+        let clone = registry.clone();
+
+        clone.spawn(async {}).await.unwrap();
+
+        assert_eq!(registry.metrics().completed, 1);
+    });
+}
+
+#[test]
+fn groups_report_to_their_registry() {
+    run_paused(async {
+        let registry = MetricsRegistry::new();
+        let mut first = TaskGroup::with_registry(registry.clone());
+        let mut second = TaskGroup::with_registry(registry.clone());
+        first.spawn(|token| async move { token.cancelled().await });
+        second.spawn(|_token| async { trpl::sleep(Duration::from_secs(60)).await });
+        second.spawn(|_token| async {});
+
+        assert_eq!(registry.metrics().spawned, 3);
+        first.shutdown(Duration::from_secs(1)).await;
+        second.shutdown(Duration::from_millis(10)).await;
+        // The aborted task is dropped by the runtime after the abort, at its next poll
+        trpl::yield_now().await;
+
+        assert_eq!(
+            registry.metrics(),
+            TaskMetrics {
+                spawned: 3,
+                completed: 2,
+                panicked: 0,
+                aborted: 1,
+                running: 0,
+            }
+        );
+    });
+}
+
+#[test]
+fn group_has_its_own_registry_by_default() {
+    run_paused(async {
+        let mut group = TaskGroup::new();
+        group.spawn(|_token| async {});
+        group.spawn(|_token| async {
+            trpl::sleep(Duration::from_millis(10)).await;
+        });
+
+        trpl::sleep(Duration::from_millis(5)).await;
+        let metrics = group.metrics();
+
+        assert_eq!(
+            (metrics.spawned, metrics.completed, metrics.running),
+            (2, 1, 1)
+        );
+    });
+}
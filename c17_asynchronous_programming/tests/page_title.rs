@@ -0,0 +1,9 @@
+use c17_asynchronous_programming::page_title;
+
+#[test]
+#[ignore = "requires network access"]
+fn rust_lang_has_a_title() {
+    let title = trpl::run(page_title("https://www.rust-lang.org"));
+
+    assert!(title.is_some_and(|title| title.contains("Rust")));
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use c17_asynchronous_programming::{Progress, ProgressEvent};
+use trpl::StreamExt;
+
+fn event(percent: u8, message: &str) -> ProgressEvent {
+    ProgressEvent {
+        percent,
+        message: String::from(message),
+    }
+}
+
+#[test]
+fn events_are_received_while_the_task_runs() {
+    trpl::run(async {
+        let progress = Progress::new(|handle| async move {
+            for step in 1..=4 {
+                trpl::sleep(Duration::from_millis(5)).await;
+                handle.report(step * 25, format!("step {step}"));
+            }
+            "done"
+        });
+
+        let mut events = Vec::new();
+        let output = progress.run_reporting(|event| events.push(event)).await;
+
+        assert_eq!(output, "done");
+        assert_eq!(
+            events,
+            vec![
+                event(25, "step 1"),
+                event(50, "step 2"),
+                event(75, "step 3"),
+                event(100, "step 4"),
+            ]
+        );
+    });
+}
+
+#[test]
+fn events_stream_ends_with_the_task() {
+    trpl::run(async {
+        let (future, events) = Progress::new(|handle| async move {
+            handle.report(50, "halfway");
+            handle.report(200, "more than done");
+            1 + 1
+        })
+        .into_parts();
+
+        let (output, events) = trpl::join(future, events.collect::<Vec<_>>()).await;
+
+        assert_eq!(output, 2);
+        assert_eq!(
+            events,
+            vec![event(50, "halfway"), event(100, "more than done")]
+        );
+    });
+}
+
+#[test]
+fn task_goes_on_without_listeners() {
+    trpl::run(async {
+        let (future, events) = Progress::new(|handle| async move {
+            handle.report(10, "nobody is listening");
+            "finished anyway"
+        })
+        .into_parts();
+        drop(events);
+
+        assert_eq!(future.await, "finished anyway");
+    });
+}
@@ -0,0 +1,80 @@
+mod common;
+
+use std::time::Duration;
+
+use c17_asynchronous_programming::RateLimiter;
+use common::run_paused;
+use tokio::time::Instant;
+
+#[test]
+fn burst_goes_through_then_operations_are_spread() {
+    run_paused(async {
+        let limiter = RateLimiter::new(2, Duration::from_secs(1));
+        let start = Instant::now();
+        let mut times = Vec::new();
+
+        for _ in 0..5 {
+            limiter.acquire().await;
+            times.push(start.elapsed().as_millis());
+        }
+
+        assert_eq!(times, vec![0, 0, 500, 1000, 1500]);
+    });
+}
+
+#[test]
+fn clones_share_the_bucket_across_tasks() {
+    run_paused(async {
+        let limiter = RateLimiter::new(1, Duration::from_millis(100));
+        let start = Instant::now();
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let limiter = limiter.clone();
+                trpl::spawn_task(async move {
+                    limiter.acquire().await;
+                    start.elapsed().as_millis()
+                })
+            })
+            .collect();
+        let mut times = Vec::new();
+        for task in tasks {
+            times.push(task.await.unwrap());
+        }
+        times.sort();
+
+        assert_eq!(times, vec![0, 100, 200, 300]);
+    });
+}
+
+#[test]
+fn try_acquire_doesnt_wait() {
+    run_paused(async {
+        let limiter = RateLimiter::new(2, Duration::from_secs(1));
+
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+        assert_eq!(limiter.available(), 0);
+
+        trpl::sleep(Duration::from_millis(500)).await;
+        assert_eq!(limiter.available(), 1);
+    });
+}
+
+#[test]
+fn bucket_doesnt_overflow() {
+    run_paused(async {
+        let limiter = RateLimiter::new(3, Duration::from_secs(1));
+
+        trpl::sleep(Duration::from_secs(10)).await;
+
+        assert_eq!(limiter.available(), 3);
+    });
+}
+
+#[test]
+#[should_panic(expected = "at least one permit per interval is needed")]
+fn zero_permits_panics() {
+    RateLimiter::new(0, Duration::from_secs(1));
+}
@@ -0,0 +1,141 @@
+use std::time::Duration;
+
+use c17_asynchronous_programming::{RetryError, RetryPolicy, retry_with_backoff, with_retries};
+use trpl::{Stream, StreamExt};
+
+fn fast_policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::new(max_attempts)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(5))
+        .with_jitter(0.0)
+}
+
+#[test]
+fn succeeds_after_failures() {
+    trpl::run(async {
+        let mut attempts = 0;
+        let result = retry_with_backoff(
+            || {
+                attempts += 1;
+                let attempt = attempts;
+                async move {
+                    if attempt < 3 {
+                        Err(attempt)
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            },
+            &fast_policy(5),
+        )
+        .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(attempts, 3);
+    });
+}
+
+#[test]
+fn returns_the_last_error_on_exhaustion() {
+    trpl::run(async {
+        let mut attempts = 0;
+        let result: Result<(), _> = retry_with_backoff(
+            || {
+                attempts += 1;
+                let attempt = attempts;
+                async move { Err(format!("attempt {attempt}")) }
+            },
+            &fast_policy(4),
+        )
+        .await;
+
+        assert_eq!(result, Err(RetryError::Failed(String::from("attempt 4"))));
+        assert_eq!(attempts, 4);
+    });
+}
+
+#[test]
+fn slow_attempts_time_out() {
+    trpl::run(async {
+        let policy = fast_policy(2).with_attempt_timeout(Duration::from_millis(10));
+        let result: Result<(), RetryError<()>> = retry_with_backoff(
+            || async {
+                trpl::sleep(Duration::from_secs(1)).await;
+                Ok(())
+            },
+            &policy,
+        )
+        .await;
+
+        assert_eq!(result, Err(RetryError::TimedOut(Duration::from_millis(10))));
+    });
+}
+
+#[test]
+fn backoff_grows_up_to_the_maximum() {
+    let policy = RetryPolicy::new(10)
+        .with_backoff(Duration::from_millis(100), Duration::from_secs(1))
+        .with_multiplier(3.0);
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(300));
+    assert_eq!(policy.backoff(3), Duration::from_millis(900));
+    assert_eq!(policy.backoff(4), Duration::from_secs(1));
+}
+
+#[test]
+#[should_panic(expected = "at least one attempt is needed")]
+fn zero_attempts_panics() {
+    RetryPolicy::new(0);
+}
+
+/// A source of the numbers from 0 to 9, disconnecting after sending 3 of them.
+fn flaky_source(after: Option<u32>) -> impl Stream<Item = Result<(u32, String), String>> {
+    let start = after.map_or(0, |cursor| cursor + 1);
+    let numbers = (start..10).take(3).map(|n| Ok((n, format!("message {n}"))));
+    let disconnect = (start + 3 < 10).then(|| Err(String::from("disconnected")));
+    trpl::stream_from_iter(numbers.chain(disconnect))
+}
+
+#[test]
+fn stream_resumes_after_the_last_item() {
+    trpl::run(async {
+        let mut created = Vec::new();
+        let messages = with_retries(
+            |cursor| {
+                created.push(cursor);
+                flaky_source(cursor)
+            },
+            fast_policy(3),
+        );
+
+        let messages: Vec<Result<String, String>> = messages.collect().await;
+
+        let expected: Vec<Result<String, String>> =
+            (0..10).map(|n| Ok(format!("message {n}"))).collect();
+        assert_eq!(messages, expected);
+        // After the last item the source keeps ending, until the attempts are exhausted
+        assert_eq!(
+            created,
+            vec![None, Some(2), Some(5), Some(8), Some(9), Some(9)]
+        );
+    });
+}
+
+#[test]
+fn stream_yields_the_last_error_when_giving_up() {
+    trpl::run(async {
+        let mut attempts = 0;
+        let messages = with_retries(
+            |_cursor: Option<u32>| {
+                attempts += 1;
+                let attempt = attempts;
+                trpl::stream_from_iter([Err::<(u32, ()), _>(format!("attempt {attempt} failed"))])
+            },
+            fast_policy(4),
+        );
+
+        let messages: Vec<_> = messages.collect().await;
+
+        assert_eq!(messages, vec![Err(String::from("attempt 4 failed"))]);
+    });
+}
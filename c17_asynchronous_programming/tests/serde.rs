@@ -0,0 +1,30 @@
+//! Run with `cargo test --features serde`
+#![cfg(feature = "serde")]
+
+use c10_generics_traits_lifetimes::Tweet;
+use c17_asynchronous_programming::aggregator::StreamMessage;
+
+#[test]
+fn stream_messages_round_trip() {
+    let message = StreamMessage::new("news", "Rust 2024 is out");
+    let json = serde_json::to_string(&message).unwrap();
+    assert_eq!(json, r#"{"source":"news","content":"Rust 2024 is out"}"#);
+    assert_eq!(
+        serde_json::from_str::<StreamMessage>(&json).unwrap(),
+        message
+    );
+}
+
+#[test]
+fn items_of_chapter_10_are_enabled_too() {
+    let tweet: Tweet = serde_json::from_str(
+        r#"{"username":"horse_ebooks","content":"of course","reply":false,"retweet":false}"#,
+    )
+    .unwrap();
+    assert_eq!(tweet.username, "horse_ebooks");
+    assert!(
+        serde_json::to_string(&tweet)
+            .unwrap()
+            .contains(r#""reply":false"#)
+    );
+}
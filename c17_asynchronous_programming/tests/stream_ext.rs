@@ -0,0 +1,129 @@
+mod common;
+
+use std::time::Duration;
+
+use c17_asynchronous_programming::{EitherItem, StreamTimeExt, merge_tagged};
+use common::run_paused;
+// `futures::StreamExt` has no `throttle` method, so it doesn't clash with `StreamTimeExt` like `trpl::StreamExt`
+use futures::{Stream, StreamExt, stream};
+use tokio::time::Instant;
+
+/// A stream emitting each item after waiting the given milliseconds since the previous one.
+fn timed<T>(items: Vec<(u64, T)>) -> impl Stream<Item = T> {
+    stream::iter(items).then(|(ms, item)| async move {
+        trpl::sleep(Duration::from_millis(ms)).await;
+        item
+    })
+}
+
+/// Collects the items with the milliseconds elapsed when they were emitted.
+async fn timestamped<T>(stream: impl Stream<Item = T>) -> Vec<(u128, T)> {
+    let start = Instant::now();
+    stream
+        .map(|item| (start.elapsed().as_millis(), item))
+        .collect()
+        .await
+}
+
+#[test]
+fn debounce_keeps_the_last_item_of_a_burst() {
+    run_paused(async {
+        let items = timed(vec![(0, 'a'), (10, 'b'), (10, 'c'), (100, 'd'), (200, 'e')]);
+
+        let debounced = timestamped(items.debounce(Duration::from_millis(50))).await;
+
+        assert_eq!(debounced, vec![(70, 'c'), (170, 'd'), (320, 'e')]);
+    });
+}
+
+#[test]
+fn debounce_emits_the_pending_item_when_the_stream_ends() {
+    run_paused(async {
+        let items = timed(vec![(0, 1), (10, 2), (10, 3)]);
+
+        let debounced = timestamped(items.debounce(Duration::from_secs(1))).await;
+
+        assert_eq!(debounced, vec![(20, 3)]);
+    });
+}
+
+#[test]
+fn throttle_spaces_out_ready_items() {
+    run_paused(async {
+        let items = stream::iter(['a', 'b', 'c']);
+
+        let throttled = timestamped(items.throttle(Duration::from_millis(100))).await;
+
+        assert_eq!(throttled, vec![(0, 'a'), (100, 'b'), (200, 'c')]);
+    });
+}
+
+#[test]
+fn throttle_doesnt_delay_slow_items() {
+    run_paused(async {
+        // The items are produced by a task, like in `get_messages`, so their delays don't depend on when the stream is polled
+        let (tx, rx) = trpl::channel();
+        trpl::spawn_task(async move {
+            for item in ['a', 'b'] {
+                trpl::sleep(Duration::from_millis(150)).await;
+                tx.send(item).unwrap();
+            }
+        });
+        let items = trpl::ReceiverStream::new(rx);
+
+        let throttled = timestamped(items.throttle(Duration::from_millis(100))).await;
+
+        assert_eq!(throttled, vec![(150, 'a'), (300, 'b')]);
+    });
+}
+
+#[test]
+fn empty_streams_end_right_away() {
+    run_paused(async {
+        let debounced = stream::empty::<u8>().debounce(Duration::from_secs(1));
+        let throttled = stream::empty::<u8>().throttle(Duration::from_secs(1));
+
+        assert!(timestamped(debounced).await.is_empty());
+        assert!(timestamped(throttled).await.is_empty());
+    });
+}
+
+#[test]
+fn merge_tagged_keeps_the_item_types() {
+    run_paused(async {
+        let words = timed(vec![(10, "one"), (20, "two")]);
+        let numbers = timed(vec![(15, 1.5), (20, 3.5)]);
+
+        let merged = timestamped(merge_tagged(words, numbers)).await;
+
+        assert_eq!(
+            merged,
+            vec![
+                (10, EitherItem::Left("one")),
+                (15, EitherItem::Right(1.5)),
+                (30, EitherItem::Left("two")),
+                (35, EitherItem::Right(3.5)),
+            ]
+        );
+    });
+}
+
+#[test]
+fn merge_tagged_ends_when_both_streams_end() {
+    run_paused(async {
+        let short = stream::iter([1, 2]);
+        let long = timed(vec![(100, 'a')]);
+
+        let merged: Vec<_> = merge_tagged(short, long).collect().await;
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[2].right(), Some('a'));
+        assert_eq!(
+            merged
+                .into_iter()
+                .filter_map(EitherItem::left)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+    });
+}
@@ -0,0 +1,42 @@
+use std::{pin::pin, time::Duration};
+
+use c17_asynchronous_programming::{get_intervals, get_messages};
+use trpl::StreamExt;
+
+#[test]
+fn messages_arrive_in_order() {
+    trpl::run(async {
+        let messages: Vec<String> = get_messages().collect().await;
+
+        assert_eq!(messages.len(), 10);
+        assert_eq!(messages[0], "Message: 'a'");
+        assert_eq!(messages[9], "Message: 'j'");
+    });
+}
+
+#[test]
+fn timeout_affects_the_slow_messages() {
+    trpl::run(async {
+        let mut messages = pin!(get_messages().timeout(Duration::from_millis(200)));
+        let mut received = 0;
+        let mut elapsed = 0;
+        while let Some(result) = messages.next().await {
+            match result {
+                Ok(_) => received += 1,
+                Err(_) => elapsed += 1,
+            }
+        }
+
+        assert_eq!(received, 10);
+        assert!(elapsed > 0);
+    });
+}
+
+#[test]
+fn intervals_count_from_one() {
+    trpl::run(async {
+        let intervals: Vec<u32> = get_intervals().take(5).collect().await;
+
+        assert_eq!(intervals, vec![1, 2, 3, 4, 5]);
+    });
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::Duration,
+};
+
+use c17_asynchronous_programming::{
+    ShutdownReport, TaskGroup, interval, run_until_cancelled, time::Instant,
+};
+use common::run_paused;
+use trpl::StreamExt;
+
+#[test]
+fn shutdown_stops_the_background_loops() {
+    run_paused(async {
+        let ticks = Arc::new(AtomicU32::new(0));
+        let mut group = TaskGroup::new();
+        for _ in 0..3 {
+            let ticks = Arc::clone(&ticks);
+            group.spawn(|token| async move {
+                let mut interval = Box::pin(interval(Duration::from_millis(10)));
+                while let Some(Some(_)) = run_until_cancelled(interval.next(), &token).await {
+                    ticks.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+        assert_eq!(group.len(), 3);
+
+        trpl::sleep(Duration::from_millis(55)).await;
+        let start = Instant::now();
+        let report = group.shutdown(Duration::from_secs(1)).await;
+
+        assert_eq!(
+            report,
+            ShutdownReport {
+                completed: 3,
+                panicked: 0,
+                aborted: 0
+            }
+        );
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        assert_eq!(ticks.load(Ordering::SeqCst), 15);
+    });
+}
+
+#[test]
+fn tasks_ignoring_the_token_are_aborted_at_the_deadline() {
+    run_paused(async {
+        let mut group = TaskGroup::new();
+        group.spawn(|_token| async {
+            trpl::sleep(Duration::from_secs(60)).await;
+        });
+        group.spawn(|token| async move { token.cancelled().await });
+
+        let start = Instant::now();
+        let report = group.shutdown(Duration::from_millis(100)).await;
+
+        assert_eq!(report.completed, 1);
+        assert_eq!(report.aborted, 1);
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+    });
+}
+
+#[test]
+fn join_all_doesnt_cancel() {
+    run_paused(async {
+        let mut group = TaskGroup::new();
+        group.spawn(|token| async move {
+            trpl::sleep(Duration::from_millis(50)).await;
+            assert!(!token.is_cancelled());
+        });
+        group.spawn(|_token| async { panic!("task failed") });
+
+        let report = group.join_all(Duration::from_secs(1)).await;
+
+        assert_eq!(
+            report,
+            ShutdownReport {
+                completed: 1,
+                panicked: 1,
+                aborted: 0
+            }
+        );
+    });
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use std::{pin::pin, time::Duration};
+
+use c17_asynchronous_programming::{get_intervals, interval, time::Instant, timer};
+use common::run_paused;
+use trpl::StreamExt;
+
+#[test]
+fn ticks_follow_the_original_schedule() {
+    run_paused(async {
+        let start = Instant::now();
+        let mut ticks = pin!(interval(Duration::from_millis(10)));
+        let mut times = Vec::new();
+
+        while let Some(tick) = ticks.next().await {
+            // The work in the body doesn't delay the next ticks, unlike a `sleep` after each iteration
+            trpl::sleep(Duration::from_millis(3)).await;
+            times.push((tick - start).as_millis());
+            if times.len() == 4 {
+                break;
+            }
+        }
+
+        assert_eq!(times, vec![10, 20, 30, 40]);
+        assert_eq!(start.elapsed().as_millis(), 43);
+    });
+}
+
+#[test]
+fn missed_ticks_catch_up() {
+    run_paused(async {
+        let start = Instant::now();
+        let mut ticks = pin!(interval(Duration::from_millis(10)));
+        let mut received = Vec::new();
+
+        ticks.next().await;
+        trpl::sleep(Duration::from_millis(25)).await;
+        for _ in 0..3 {
+            ticks.next().await;
+            received.push(start.elapsed().as_millis());
+        }
+
+        assert_eq!(received, vec![35, 35, 40]);
+    });
+}
+
+#[test]
+fn timer_completes_at_the_instant() {
+    run_paused(async {
+        let start = Instant::now();
+
+        timer(start + Duration::from_millis(250)).await;
+        assert_eq!(start.elapsed().as_millis(), 250);
+
+        // An instant already passed completes right away
+        timer(start).await;
+        assert_eq!(start.elapsed().as_millis(), 250);
+    });
+}
+
+#[test]
+fn intervals_are_counted_on_schedule() {
+    run_paused(async {
+        let start = Instant::now();
+
+        let counts: Vec<u32> = get_intervals().take(100).collect().await;
+
+        assert_eq!(counts, (1..=100).collect::<Vec<_>>());
+        assert_eq!(start.elapsed().as_millis(), 100);
+    });
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use c17_asynchronous_programming::timeout;
+
+#[test]
+fn completes_before_the_timeout() {
+    trpl::run(async {
+        let fast = async {
+            trpl::sleep(Duration::from_millis(10)).await;
+            "I finished!"
+        };
+
+        assert_eq!(
+            timeout(fast, Duration::from_secs(1)).await,
+            Ok("I finished!")
+        );
+    });
+}
+
+#[test]
+fn elapses_with_the_waited_duration() {
+    trpl::run(async {
+        let slow = async {
+            trpl::sleep(Duration::from_secs(1)).await;
+            "I finished!"
+        };
+
+        assert_eq!(
+            timeout(slow, Duration::from_millis(10)).await,
+            Err(Duration::from_millis(10))
+        );
+    });
+}
+
+#[test]
+fn ready_future_wins_a_zero_timeout() {
+    trpl::run(async {
+        assert_eq!(timeout(async { 42 }, Duration::ZERO).await, Ok(42));
+    });
+}
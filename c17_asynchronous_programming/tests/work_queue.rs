@@ -0,0 +1,110 @@
+mod common;
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use c17_asynchronous_programming::{AsyncWorkQueue, DrainReport, time::Instant};
+use common::run_paused;
+
+#[test]
+fn shutdown_drains_every_job() {
+    run_paused(async {
+        let queue = AsyncWorkQueue::new(4, 2);
+        let done = Arc::new(Mutex::new(Vec::new()));
+
+        for i in 0..10 {
+            let done = Arc::clone(&done);
+            queue
+                .submit(async move {
+                    trpl::sleep(Duration::from_millis(10)).await;
+                    done.lock().unwrap().push(i);
+                })
+                .await;
+        }
+        let report = queue.shutdown().await;
+
+        assert_eq!(
+            report,
+            DrainReport {
+                completed: 10,
+                panicked: 0
+            }
+        );
+        let mut done = done.lock().unwrap().clone();
+        done.sort();
+        assert_eq!(done, (0..10).collect::<Vec<_>>());
+    });
+}
+
+#[test]
+fn workers_limit_the_jobs_running() {
+    run_paused(async {
+        let queue = AsyncWorkQueue::new(3, 10);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let start = Instant::now();
+
+        for _ in 0..9 {
+            let (running, peak) = (Arc::clone(&running), Arc::clone(&peak));
+            queue
+                .submit(async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    trpl::sleep(Duration::from_millis(100)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+                .await;
+        }
+        queue.shutdown().await;
+
+        assert_eq!(peak.load(Ordering::SeqCst), 3);
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+    });
+}
+
+#[test]
+fn full_queue_makes_submit_wait() {
+    run_paused(async {
+        let queue = AsyncWorkQueue::new(1, 1);
+        // The sleep is created when the job starts, as its deadline is fixed when it's created
+        let slow = || async { trpl::sleep(Duration::from_millis(100)).await };
+        let start = Instant::now();
+
+        // The worker takes the first job, the second one waits in the queue
+        queue.submit(slow()).await;
+        trpl::yield_now().await;
+        queue.submit(slow()).await;
+        assert!(queue.try_submit(slow()).is_err());
+
+        // The third job waits for the worker to take the second one
+        queue.submit(slow()).await;
+        assert_eq!(start.elapsed(), Duration::from_millis(100));
+
+        queue.shutdown().await;
+        assert_eq!(start.elapsed(), Duration::from_millis(300));
+    });
+}
+
+#[test]
+fn panicking_jobs_dont_stop_the_workers() {
+    run_paused(async {
+        let queue = AsyncWorkQueue::new(1, 4);
+
+        queue.submit(async { panic!("job failed") }).await;
+        queue.submit(async {}).await;
+        assert!(queue.try_submit(async {}).is_ok());
+
+        assert_eq!(
+            queue.shutdown().await,
+            DrainReport {
+                completed: 2,
+                panicked: 1
+            }
+        );
+    });
+}
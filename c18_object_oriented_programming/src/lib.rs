@@ -1,10 +1,38 @@
+//! # c18_object_oriented_programming
+//!
+//! Without the `std` feature, enabled by default, the library is `no_std` and only needs `alloc`:
+//! none of the patterns of the chapter do any I/O or use threads.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::collections::{VecDeque, vec_deque};
+use alloc::vec::Vec;
+
+// `core` doesn't have the square root for the standard deviation, the art module of the cargo chapter replaces it
+#[cfg(not(any(feature = "std", test)))]
+use c14_cargo_crates::art::float::F64Ext;
+
 /// A collection that maintains a list of `i32` values and keeps track of their average.
 ///
 /// The `AveragedCollection` struct provides a way to store a list of integers and
 /// automatically update the average value whenever the collection is modified.
+/// Alongside the average it keeps a sorted copy of the values for the median and the percentiles,
+/// and the sum of the values and of their squares for the average and the variance.
+/// The sums are updated incrementally as exact integers, so they don't drift however long the stream of values is.
+/// The minimum and maximum are tracked on every addition, and only recomputed when the removed value was one of them.
+///
+/// A collection created with [`AveragedCollection::with_window`] works as a ring buffer:
+/// it only keeps the last N values, so the average becomes a moving average.
 pub struct AveragedCollection {
-    list: Vec<i32>,
-    average: f64,
+    list: VecDeque<i32>,
+    window: Option<usize>,
+    sorted: Vec<i32>,
+    sum: i128,
+    sum_of_squares: i128,
+    min: Option<i32>,
+    max: Option<i32>,
 }
 
 impl AveragedCollection {
@@ -15,18 +43,61 @@ impl AveragedCollection {
     /// An instance of `AveragedCollection` with an empty list and an average of `0.0`.
     pub fn new() -> Self {
         AveragedCollection {
-            list: Vec::new(),
-            average: 0.0,
+            list: VecDeque::new(),
+            window: None,
+            sorted: Vec::new(),
+            sum: 0,
+            sum_of_squares: 0,
+            min: None,
+            max: None,
         }
     }
+
+    /// Creates a new, empty `AveragedCollection` with room for at least `capacity` values without reallocating.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of values to allocate space for.
+    pub fn with_capacity(capacity: usize) -> Self {
+        AveragedCollection {
+            list: VecDeque::with_capacity(capacity),
+            sorted: Vec::with_capacity(capacity),
+            ..AveragedCollection::new()
+        }
+    }
+
+    /// Creates a new, empty `AveragedCollection` that keeps only the last `size` values.
+    ///
+    /// Once the window is full, every new value evicts the oldest one, and `average` returns the moving average.
+    /// Nothing is allocated up front, the collection grows with the values like one created with `new`.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The maximum number of values kept in the collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    pub fn with_window(size: usize) -> Self {
+        assert!(size > 0, "window size must be greater than zero");
+        AveragedCollection {
+            window: Some(size),
+            ..AveragedCollection::new()
+        }
+    }
+
     /// Adds an integer to the collection and updates the average.
     ///
+    /// In a windowed collection that is already full, the oldest integer is evicted first.
+    ///
     /// # Arguments
     ///
     /// * `value` - The integer to add to the collection.
     pub fn add(&mut self, value: i32) {
-        self.list.push(value);
-        self.update_average();
+        self.list.push_back(value);
+        self.insert_sorted(value);
+        self.update_average_added(value);
+        self.evict_overflow();
     }
 
     /// Removes the last integer from the collection and updates the average.
@@ -35,31 +106,377 @@ impl AveragedCollection {
     ///
     /// * `Option<i32>` - The removed integer if the collection is not empty, or `None` if it is empty.
     pub fn remove(&mut self) -> Option<i32> {
-        let result = self.list.pop();
+        let result = self.list.pop_back();
         match result {
             Some(value) => {
-                self.update_average();
+                self.remove_sorted(value);
+                self.update_average_removed(value);
                 Some(value)
             }
             None => None,
         }
     }
 
+    /// Inserts an integer at the given position and updates the average.
+    ///
+    /// In a windowed collection that is already full, the oldest integer (at position `0` after the insertion) is evicted.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position at which the integer is inserted.
+    /// * `value` - The integer to add to the collection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is greater than the number of elements in the collection.
+    pub fn insert(&mut self, index: usize, value: i32) {
+        self.list.insert(index, value);
+        self.insert_sorted(value);
+        self.update_average_added(value);
+        self.evict_overflow();
+    }
+
+    /// Removes the integer at the given position and updates the average.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The position of the integer to remove.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The removed integer, or `None` if `index` is out of bounds.
+    pub fn remove_at(&mut self, index: usize) -> Option<i32> {
+        let value = self.list.remove(index)?;
+        self.remove_sorted(value);
+        self.update_average_removed(value);
+        Some(value)
+    }
+
+    /// Removes the first occurrence of an integer from the collection and updates the average.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The integer to remove from the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the integer was found and removed, `false` otherwise.
+    pub fn remove_value(&mut self, value: i32) -> bool {
+        match self.list.iter().position(|&v| v == value) {
+            Some(index) => self.remove_at(index).is_some(),
+            None => false,
+        }
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    ///
+    /// # Arguments
+    ///
+    /// * `additional` - The number of values to allocate space for, on top of the current length.
+    pub fn reserve(&mut self, additional: usize) {
+        self.list.reserve(additional);
+        self.sorted.reserve(additional);
+    }
+
+    /// Returns the number of integers in the collection.
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Returns `true` if the collection contains no integers.
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
     /// Returns the current average of the collection.
     ///
     /// # Returns
     ///
     /// * `f64` - The average value of the integers in the collection.
-    pub fn average(&mut self) -> f64 {
-        self.average
+    pub fn average(&self) -> f64 {
+        if self.list.is_empty() {
+            0.0
+        } else {
+            self.sum as f64 / self.list.len() as f64
+        }
     }
 
-    /// Recalculates and updates the average value based on the current contents of the collection.
+    /// Returns the median of the collection.
+    ///
+    /// With an even number of elements the median is the mean of the two middle values.
     ///
-    /// This method is called internally whenever the collection is modified.
-    fn update_average(&mut self) {
-        let total: i32 = self.list.iter().sum();
-        self.average = total as f64 / self.list.len() as f64;
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The median value, or `None` if the collection is empty.
+    pub fn median(&self) -> Option<f64> {
+        self.percentile(50.0)
+    }
+
+    /// Returns the value below which the given percentage of the integers falls.
+    ///
+    /// The percentile is read from the sorted copy of the list, so repeated queries don't need to sort the values.
+    /// When the rank falls between two values the result is linearly interpolated between them.
+    ///
+    /// # Arguments
+    ///
+    /// * `p` - The percentile to compute, between `0.0` and `100.0`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The percentile value, or `None` if the collection is empty or `p` is out of range.
+    pub fn percentile(&self, p: f64) -> Option<f64> {
+        if self.sorted.is_empty() || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let rank = p / 100.0 * (self.sorted.len() - 1) as f64;
+        // The rank is never negative, so the cast rounds it down, and the next value is only needed if there's a fraction
+        let lower = rank as usize;
+        let upper = lower + usize::from(rank > lower as f64);
+        let fraction = rank - lower as f64;
+        let low = self.sorted[lower] as f64;
+        let high = self.sorted[upper] as f64;
+
+        Some(low + (high - low) * fraction)
+    }
+
+    /// Returns the population variance of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The variance, or `None` if the collection is empty.
+    pub fn variance(&self) -> Option<f64> {
+        if self.list.is_empty() {
+            None
+        } else {
+            // n² times the variance is an integer, only the last divisions round
+            let n = self.list.len() as i128;
+            let scaled = n * self.sum_of_squares - self.sum * self.sum;
+            Some(scaled as f64 / n as f64 / n as f64)
+        }
+    }
+
+    /// Returns the population standard deviation of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The square root of the variance, or `None` if the collection is empty.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Returns the smallest integer in the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The minimum value, or `None` if the collection is empty.
+    pub fn min(&self) -> Option<i32> {
+        self.min
+    }
+
+    /// Returns the largest integer in the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<i32>` - The maximum value, or `None` if the collection is empty.
+    pub fn max(&self) -> Option<i32> {
+        self.max
+    }
+
+    /// Returns an iterator over the integers of the collection, in insertion order.
+    ///
+    /// The iterator only yields shared references, so the values can't be modified and the average stays in sync.
+    pub fn iter(&self) -> vec_deque::Iter<'_, i32> {
+        self.list.iter()
+    }
+
+    /// Returns the size of the window, if the collection was created with [`AveragedCollection::with_window`].
+    ///
+    /// # Returns
+    ///
+    /// * `Option<usize>` - The maximum number of values kept, or `None` if the collection is unbounded.
+    pub fn window_capacity(&self) -> Option<usize> {
+        self.window
+    }
+
+    /// Checks whether a windowed collection holds as many values as its window allows.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if the window is full, always `false` for an unbounded collection.
+    pub fn window_full(&self) -> bool {
+        self.window.is_some_and(|size| self.list.len() >= size)
+    }
+
+    /// Evicts the oldest values while the collection holds more values than its window.
+    fn evict_overflow(&mut self) {
+        let Some(size) = self.window else {
+            return;
+        };
+        while self.list.len() > size {
+            if let Some(value) = self.list.pop_front() {
+                self.remove_sorted(value);
+                self.update_average_removed(value);
+            }
+        }
+    }
+
+    /// Inserts a value in the sorted copy of the list, keeping it ordered.
+    fn insert_sorted(&mut self, value: i32) {
+        let index = self.sorted.partition_point(|&v| v < value);
+        self.sorted.insert(index, value);
+    }
+
+    /// Removes one occurrence of a value from the sorted copy of the list.
+    fn remove_sorted(&mut self, value: i32) {
+        if let Ok(index) = self.sorted.binary_search(&value) {
+            self.sorted.remove(index);
+        }
+    }
+
+    /// Updates the sums and the extremes after a value has been added to the list.
+    ///
+    /// This method is called internally whenever a value is added to the collection.
+    fn update_average_added(&mut self, value: i32) {
+        self.min = Some(self.min.map_or(value, |min| min.min(value)));
+        self.max = Some(self.max.map_or(value, |max| max.max(value)));
+
+        let value = i128::from(value);
+        self.sum += value;
+        self.sum_of_squares += value * value;
+    }
+
+    /// Updates the sums and the extremes after a value has been removed from the list.
+    ///
+    /// This method is called internally whenever a value is removed from the collection, after the sorted copy has been updated.
+    /// The sums are exact, so removing a value leaves them as if it had never been added.
+    fn update_average_removed(&mut self, value: i32) {
+        // The extremes only change if the removed value was one of them
+        if self.min == Some(value) {
+            self.min = self.sorted.first().copied();
+        }
+        if self.max == Some(value) {
+            self.max = self.sorted.last().copied();
+        }
+
+        let value = i128::from(value);
+        self.sum -= value;
+        self.sum_of_squares -= value * value;
+    }
+}
+
+impl Default for AveragedCollection {
+    /// Creates an empty `AveragedCollection`, same as [`AveragedCollection::new`].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<i32> for AveragedCollection {
+    /// Builds an `AveragedCollection` from the values of an iterator, allowing the use of `collect()`.
+    fn from_iter<I: IntoIterator<Item = i32>>(iter: I) -> Self {
+        let mut collection = AveragedCollection::new();
+        collection.extend(iter);
+        collection
+    }
+}
+
+impl Extend<i32> for AveragedCollection {
+    /// Adds every value of an iterator to the collection, updating the average for each of them.
+    fn extend<I: IntoIterator<Item = i32>>(&mut self, iter: I) {
+        for value in iter {
+            self.add(value);
+        }
+    }
+}
+
+impl IntoIterator for AveragedCollection {
+    type Item = i32;
+    type IntoIter = vec_deque::IntoIter<i32>;
+
+    /// Consumes the collection, returning its integers in insertion order.
+    fn into_iter(self) -> Self::IntoIter {
+        self.list.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a AveragedCollection {
+    type Item = &'a i32;
+    type IntoIter = vec_deque::Iter<'a, i32>;
+
+    /// Iterates over the integers of the collection without consuming it.
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_support {
+    //! `Serialize`/`Deserialize` for `AveragedCollection`, enabled by the `serde` feature.
+    //!
+    //! Only the values, the window, and the average are stored: the other statistics are rebuilt on deserialization.
+    //! The stored average is revalidated against the values, so a tampered or stale record is rejected.
+    use super::AveragedCollection;
+    use alloc::collections::VecDeque;
+    use alloc::format;
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    #[derive(Serialize)]
+    struct SerializedCollection<'a> {
+        values: &'a VecDeque<i32>,
+        window: Option<usize>,
+        average: f64,
+    }
+
+    #[derive(Deserialize)]
+    struct DeserializedCollection {
+        values: Vec<i32>,
+        #[serde(default)]
+        window: Option<usize>,
+        #[serde(default)]
+        average: Option<f64>,
+    }
+
+    impl Serialize for AveragedCollection {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            SerializedCollection {
+                values: &self.list,
+                window: self.window,
+                average: self.average(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for AveragedCollection {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let stored = DeserializedCollection::deserialize(deserializer)?;
+
+            let mut collection = match stored.window {
+                Some(0) => return Err(D::Error::custom("window size must be greater than zero")),
+                Some(size) if stored.values.len() > size => {
+                    return Err(D::Error::custom(format!(
+                        "{} values don't fit in a window of {size}",
+                        stored.values.len()
+                    )));
+                }
+                Some(size) => AveragedCollection::with_window(size),
+                None => AveragedCollection::with_capacity(stored.values.len()),
+            };
+            collection.extend(stored.values);
+
+            // The tolerance is relative, the rounding of the average grows with its magnitude
+            let expected = collection.average();
+            if let Some(average) = stored.average
+                && (average - expected).abs() > expected.abs() * 1e-12
+            {
+                return Err(D::Error::custom(format!(
+                    "stored average {average} doesn't match the values, expected {expected}"
+                )));
+            }
+
+            Ok(collection)
+        }
     }
 }
 
@@ -70,6 +487,10 @@ pub mod gui {
     //!
     //! This module provides the `Draw` trait for drawable UI components and the `Screen` struct to manage and render a collection of such components.
 
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use alloc::vec::Vec;
+
     /// A trait for drawable UI components.
     ///
     /// Types implementing this trait can be drawn onto a screen.
@@ -107,6 +528,7 @@ pub mod gui {
     /// * `width` - The width of the button in pixels.
     /// * `height` - The height of the button in pixels.
     /// * `label` - The text label displayed on the button.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Button {
         pub width: u32,
         pub height: u32,
@@ -121,6 +543,43 @@ pub mod gui {
             // Draw the button
         }
     }
+
+    /// A text label that can be drawn on the screen.
+    ///
+    /// # Fields
+    ///
+    /// * `text` - The text displayed by the label.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Label {
+        pub text: String,
+    }
+
+    impl Draw for Label {
+        /// Draws the label component.
+        fn draw(&self) {
+            // Draw the label
+        }
+    }
+
+    /// A component grouping other components, drawn in order.
+    ///
+    /// Since the children are trait objects too, containers can be nested to build a tree of components.
+    ///
+    /// # Fields
+    ///
+    /// * `children` - The components inside the container.
+    pub struct Container {
+        pub children: Vec<Box<dyn Draw>>,
+    }
+
+    impl Draw for Container {
+        /// Draws each child of the container.
+        fn draw(&self) {
+            for child in self.children.iter() {
+                child.draw();
+            }
+        }
+    }
 }
 
 pub mod blog {
@@ -132,6 +591,32 @@ pub mod blog {
     //! The post transitions through different states (draft, pending review, published) using internal state objects.
     //! State transitions and content visibility are controlled through the public API.
 
+    use alloc::boxed::Box;
+    use alloc::string::String;
+    use core::error::Error;
+    use core::fmt;
+
+    /// Error returned by the `try_` transitions when the post can't take that step from its current state.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct WorkflowError {
+        /// Name of the state the post was in, e.g. `"draft"`.
+        pub state: &'static str,
+        /// Transition that was attempted, worded to fit the message, e.g. `"approve"`.
+        pub action: &'static str,
+    }
+
+    impl fmt::Display for WorkflowError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(
+                f,
+                "can't {} a post in the {} state",
+                self.action, self.state
+            )
+        }
+    }
+
+    impl Error for WorkflowError {}
+
     /// Represents a blog post that has an internal state and associated content.
     ///
     /// The `Post` struct uses the state pattern to manage its publishing workflow.
@@ -189,6 +674,35 @@ pub mod blog {
                 self.state = Some(s.approve())
             }
         }
+
+        /// Name of the current state: `"draft"`, `"pending review"`, or `"published"`.
+        pub fn state(&self) -> &'static str {
+            self.state.as_ref().unwrap().name()
+        }
+
+        /// Like `request_review`, but returns an error if the post stays in the same state.
+        pub fn try_request_review(&mut self) -> Result<(), WorkflowError> {
+            self.try_transition("request a review of", Post::request_review)
+        }
+
+        /// Like `approve`, but returns an error if the post stays in the same state.
+        pub fn try_approve(&mut self) -> Result<(), WorkflowError> {
+            self.try_transition("approve", Post::approve)
+        }
+
+        fn try_transition(
+            &mut self,
+            action: &'static str,
+            transition: fn(&mut Post),
+        ) -> Result<(), WorkflowError> {
+            let state = self.state();
+            transition(self);
+            if self.state() == state {
+                Err(WorkflowError { state, action })
+            } else {
+                Ok(())
+            }
+        }
     }
 
     trait State {
@@ -218,6 +732,9 @@ pub mod blog {
         fn content<'a>(&self, _post: &'a Post) -> &'a str {
             ""
         }
+
+        /// Returns the name of the state, used by `Post::state` and in the errors.
+        fn name(&self) -> &'static str;
     }
 
     /// Represents the draft state of a blog post.
@@ -236,6 +753,10 @@ pub mod blog {
         fn approve(self: Box<Self>) -> Box<dyn State> {
             self
         }
+
+        fn name(&self) -> &'static str {
+            "draft"
+        }
     }
 
     /// Represents the pending review state of a blog post.
@@ -254,6 +775,10 @@ pub mod blog {
         fn approve(self: Box<Self>) -> Box<dyn State> {
             Box::new(Published {})
         }
+
+        fn name(&self) -> &'static str {
+            "pending review"
+        }
     }
 
     /// Represents the published state of a blog post.
@@ -275,6 +800,10 @@ pub mod blog {
         fn content<'a>(&self, post: &'a Post) -> &'a str {
             &post.content
         }
+
+        fn name(&self) -> &'static str {
+            "published"
+        }
     }
 }
 
@@ -284,9 +813,14 @@ pub mod blog_no_state {
     //! The `blog_no_state` module provides a simple blog post workflow without using the state pattern.
     //! It defines types representing the different stages of a blog post's lifecycle: draft, pending review, and published.
 
+    use alloc::string::String;
+
     /// Represents a published blog post.
     ///
     /// Use [`Post::new`] to start creating a new post as a draft.
+    ///
+    /// With the `serde` feature it can be serialized but not deserialized, so a published post always went through the review.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct Post {
         content: String,
     }
@@ -294,6 +828,7 @@ pub mod blog_no_state {
     /// Represents a blog post in draft state.
     ///
     /// Use [`DraftPost::add_text`] to add content, and [`DraftPost::request_review`] to move to the pending review state.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct DraftPost {
         content: String,
     }
@@ -341,6 +876,9 @@ pub mod blog_no_state {
     /// Represents a blog post that is pending review.
     ///
     /// Use [`PendingReviewPost::approve`] to publish the post.
+    ///
+    /// Like [`Post`], with the `serde` feature it can be serialized but not deserialized.
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     pub struct PendingReviewPost {
         content: String,
     }
@@ -358,3 +896,470 @@ pub mod blog_no_state {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use c11_automated_tests::assert_approx_eq;
+
+    #[test]
+    fn empty_collection_has_no_statistics() {
+        let collection = AveragedCollection::new();
+
+        assert_eq!(collection.average(), 0.0);
+        assert_eq!(collection.median(), None);
+        assert_eq!(collection.variance(), None);
+        assert_eq!(collection.std_dev(), None);
+        assert_eq!(collection.min(), None);
+        assert_eq!(collection.max(), None);
+    }
+
+    #[test]
+    fn median_of_odd_and_even_lengths() {
+        let mut collection = AveragedCollection::new();
+        collection.add(60);
+        collection.add(10);
+        collection.add(20);
+        assert_eq!(collection.median(), Some(20.0));
+
+        collection.add(30);
+        assert_eq!(collection.median(), Some(25.0));
+    }
+
+    #[test]
+    fn variance_and_std_dev() {
+        let mut collection = AveragedCollection::new();
+        for value in [2, 4, 4, 4, 5, 5, 7, 9] {
+            collection.add(value);
+        }
+
+        assert_approx_eq!(collection.average(), 5.0);
+        assert_approx_eq!(collection.variance().unwrap(), 4.0);
+        assert_approx_eq!(collection.std_dev().unwrap(), 2.0);
+    }
+
+    #[test]
+    fn streaming_updates_match_recomputed_statistics() {
+        let mut collection = AveragedCollection::new();
+        let mut values = Vec::new();
+
+        for value in [13, -7, 42, 0, 5, 5, 99, -31, 8, 21] {
+            collection.add(value);
+            values.push(value);
+            check_against(&mut collection, &values);
+        }
+        while let Some(value) = collection.remove() {
+            assert_eq!(values.pop(), Some(value));
+            check_against(&mut collection, &values);
+        }
+    }
+
+    #[test]
+    fn insert_keeps_statistics_consistent() {
+        let mut collection = AveragedCollection::new();
+        collection.add(10);
+        collection.add(30);
+        collection.insert(1, 20);
+        collection.insert(0, -5);
+
+        check_against(&mut collection, &[-5, 10, 20, 30]);
+        assert_eq!(collection.remove(), Some(30));
+        assert_eq!(collection.remove(), Some(20));
+    }
+
+    #[test]
+    #[should_panic]
+    fn insert_out_of_bounds() {
+        let mut collection = AveragedCollection::new();
+        collection.insert(1, 10);
+    }
+
+    #[test]
+    fn remove_at_position() {
+        let mut collection = AveragedCollection::new();
+        for value in [10, 20, 60] {
+            collection.add(value);
+        }
+
+        assert_eq!(collection.remove_at(3), None);
+        assert_eq!(collection.remove_at(0), Some(10));
+        check_against(&mut collection, &[20, 60]);
+        assert_eq!(collection.remove_at(1), Some(60));
+        assert_eq!(collection.remove_at(0), Some(20));
+        check_against(&mut collection, &[]);
+    }
+
+    #[test]
+    fn remove_by_value() {
+        let mut collection = AveragedCollection::new();
+        for value in [5, 7, 5, 9] {
+            collection.add(value);
+        }
+
+        assert!(collection.remove_value(5));
+        check_against(&mut collection, &[7, 5, 9]);
+        assert!(!collection.remove_value(42));
+        check_against(&mut collection, &[7, 5, 9]);
+        assert_eq!(collection.remove(), Some(9));
+        assert_eq!(collection.remove(), Some(5));
+    }
+
+    #[test]
+    fn min_and_max_follow_updates() {
+        let mut collection = AveragedCollection::new();
+        for value in [20, 5, 60, 5] {
+            collection.add(value);
+        }
+        assert_eq!(collection.min(), Some(5));
+        assert_eq!(collection.max(), Some(60));
+
+        // A duplicated minimum is still the minimum after removing one occurrence
+        assert_eq!(collection.remove(), Some(5));
+        assert_eq!(collection.min(), Some(5));
+
+        assert!(collection.remove_value(60));
+        assert_eq!(collection.max(), Some(20));
+        assert_eq!(collection.remove_at(1), Some(5));
+        assert_eq!(collection.min(), Some(20));
+        assert_eq!(collection.max(), Some(20));
+
+        collection.remove();
+        assert_eq!(collection.min(), None);
+        assert_eq!(collection.max(), None);
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut collection: AveragedCollection = [10, 20, 60].into_iter().collect();
+        check_against(&mut collection, &[10, 20, 60]);
+
+        collection.extend(vec![-4, 4]);
+        check_against(&mut collection, &[10, 20, 60, -4, 4]);
+    }
+
+    #[test]
+    fn iterate_by_reference_and_by_value() {
+        let collection: AveragedCollection = (1..=4).collect();
+
+        let mut total = 0;
+        for value in &collection {
+            total += value;
+        }
+        assert_eq!(total, 10);
+        assert_eq!(collection.iter().max(), Some(&4));
+
+        let values: Vec<i32> = collection.into_iter().collect();
+        assert_eq!(values, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn window_keeps_last_values() {
+        let mut collection = AveragedCollection::with_window(3);
+        assert_eq!(collection.window_capacity(), Some(3));
+        assert!(!collection.window_full());
+
+        for value in [10, 20, 30] {
+            collection.add(value);
+        }
+        assert!(collection.window_full());
+        check_against(&mut collection, &[10, 20, 30]);
+
+        collection.add(60);
+        check_against(&mut collection, &[20, 30, 60]);
+        collection.add(-10);
+        check_against(&mut collection, &[30, 60, -10]);
+        assert_eq!(
+            collection.iter().copied().collect::<Vec<_>>(),
+            vec![30, 60, -10]
+        );
+
+        assert_eq!(collection.remove(), Some(-10));
+        assert!(!collection.window_full());
+        check_against(&mut collection, &[30, 60]);
+    }
+
+    #[test]
+    fn moving_average_over_stream() {
+        let mut collection = AveragedCollection::with_window(2);
+        let averages: Vec<f64> = [2, 4, 6, 8]
+            .into_iter()
+            .map(|value| {
+                collection.add(value);
+                collection.average()
+            })
+            .collect();
+
+        assert_eq!(averages, vec![2.0, 3.0, 5.0, 7.0]);
+    }
+
+    #[test]
+    fn window_insert_evicts_oldest() {
+        let mut collection = AveragedCollection::with_window(3);
+        collection.extend([1, 2, 3]);
+        collection.insert(1, 9);
+
+        check_against(&mut collection, &[9, 2, 3]);
+    }
+
+    #[test]
+    fn unbounded_collection_is_never_full() {
+        let collection: AveragedCollection = (0..100).collect();
+
+        assert_eq!(collection.window_capacity(), None);
+        assert!(!collection.window_full());
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be greater than zero")]
+    fn empty_window() {
+        AveragedCollection::with_window(0);
+    }
+
+    #[test]
+    fn huge_window_is_not_allocated_up_front() {
+        let mut collection = AveragedCollection::with_window(usize::MAX);
+        collection.extend([1, 2, 3]);
+
+        assert_eq!(collection.window_capacity(), Some(usize::MAX));
+        check_against(&mut collection, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn moving_average_does_not_drift() {
+        let mut collection = AveragedCollection::with_window(3);
+        for i in 0..1_000_000 {
+            collection.add(if i % 2 == 0 { i32::MAX } else { i32::MIN + i });
+        }
+        collection.extend([7, 8, 9]);
+
+        assert_eq!(collection.average(), 8.0);
+        assert_eq!(collection.variance(), Some(2.0 / 3.0));
+    }
+
+    #[test]
+    fn percentile_interpolates_between_values() {
+        let collection: AveragedCollection = [40, 10, 30, 20].into_iter().collect();
+
+        assert_eq!(collection.percentile(0.0), Some(10.0));
+        assert_eq!(collection.percentile(100.0), Some(40.0));
+        assert_eq!(collection.percentile(50.0), Some(25.0));
+        assert_approx_eq!(collection.percentile(25.0).unwrap(), 17.5);
+        assert_approx_eq!(collection.percentile(90.0).unwrap(), 37.0);
+    }
+
+    #[test]
+    fn percentile_out_of_range() {
+        let collection: AveragedCollection = [1, 2, 3].into_iter().collect();
+
+        assert_eq!(collection.percentile(-1.0), None);
+        assert_eq!(collection.percentile(100.5), None);
+        assert_eq!(collection.percentile(f64::NAN), None);
+        assert_eq!(AveragedCollection::new().percentile(50.0), None);
+    }
+
+    #[test]
+    fn percentile_follows_mutations() {
+        let mut collection: AveragedCollection = [5, 1, 3].into_iter().collect();
+        assert_eq!(collection.percentile(100.0), Some(5.0));
+
+        collection.add(9);
+        assert_eq!(collection.percentile(100.0), Some(9.0));
+        assert!(collection.remove_value(1));
+        assert_eq!(collection.percentile(0.0), Some(3.0));
+        assert_eq!(collection.percentile(50.0), Some(5.0));
+    }
+
+    #[test]
+    fn capacity_and_length() {
+        let mut collection = AveragedCollection::with_capacity(10);
+        assert!(collection.is_empty());
+        assert_eq!(collection.len(), 0);
+
+        collection.extend([1, 2, 3]);
+        collection.reserve(100);
+        assert!(!collection.is_empty());
+        assert_eq!(collection.len(), 3);
+        check_against(&mut collection, &[1, 2, 3]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let collection: AveragedCollection = [10, 20, 60].into_iter().collect();
+
+        let json = serde_json::to_string(&collection).unwrap();
+        assert_eq!(
+            json,
+            r#"{"values":[10,20,60],"window":null,"average":30.0}"#
+        );
+
+        let mut restored: AveragedCollection = serde_json::from_str(&json).unwrap();
+        check_against(&mut restored, &[10, 20, 60]);
+        assert_eq!(restored.window_capacity(), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_keeps_window() {
+        let mut collection = AveragedCollection::with_window(2);
+        collection.extend([1, 2, 3]);
+
+        let json = serde_json::to_string(&collection).unwrap();
+        let mut restored: AveragedCollection = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.window_capacity(), Some(2));
+        restored.add(7);
+        check_against(&mut restored, &[3, 7]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_revalidates_average() {
+        let mut restored: AveragedCollection = serde_json::from_str(r#"{"values":[1,2]}"#).unwrap();
+        check_against(&mut restored, &[1, 2]);
+
+        let err = serde_json::from_str::<AveragedCollection>(r#"{"values":[1,2],"average":5.0}"#)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("doesn't match the values"));
+
+        let err = serde_json::from_str::<AveragedCollection>(r#"{"values":[1,2,3],"window":2}"#)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("don't fit in a window of 2"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_of_large_values() {
+        let values = [
+            i32::MAX,
+            1_234_567_890,
+            i32::MAX - 1,
+            2,
+            i32::MIN,
+            999_999_999,
+        ];
+        let collection: AveragedCollection = values.into_iter().collect();
+        let json = serde_json::to_string(&collection).unwrap();
+        let mut restored: AveragedCollection = serde_json::from_str(&json).unwrap();
+        check_against(&mut restored, &values);
+
+        let mut collection = AveragedCollection::with_window(6);
+        for i in 0..10_000_000 {
+            collection.add(i32::MAX - i);
+        }
+        let json = serde_json::to_string(&collection).unwrap();
+        let restored: AveragedCollection = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.average(), collection.average());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_invalid_windows() {
+        let err = serde_json::from_str::<AveragedCollection>(r#"{"values":[],"window":0}"#)
+            .err()
+            .unwrap();
+        assert!(
+            err.to_string()
+                .contains("window size must be greater than zero")
+        );
+
+        let restored: AveragedCollection =
+            serde_json::from_str(r#"{"values":[1],"window":18446744073709551615}"#).unwrap();
+        assert_eq!(restored.window_capacity(), Some(usize::MAX));
+    }
+
+    /// Check the incremental statistics against values computed from scratch
+    fn check_against(collection: &mut AveragedCollection, values: &[i32]) {
+        if values.is_empty() {
+            assert_eq!(collection.average(), 0.0);
+            assert_eq!(collection.variance(), None);
+            return;
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().map(|&v| v as f64).sum::<f64>() / n;
+        let variance = values
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[sorted.len() / 2 - 1] as f64 + sorted[sorted.len() / 2] as f64) / 2.0
+        } else {
+            sorted[sorted.len() / 2] as f64
+        };
+
+        assert_approx_eq!(collection.average(), mean);
+        assert_approx_eq!(collection.variance().unwrap(), variance);
+        assert_eq!(collection.median(), Some(median));
+        assert_eq!(collection.min(), sorted.first().copied());
+        assert_eq!(collection.max(), sorted.last().copied());
+    }
+
+    #[test]
+    fn blog_transitions_report_invalid_steps() {
+        use blog::{Post, WorkflowError};
+
+        let mut post = Post::new();
+        post.add_text("I ate a salad for lunch today");
+        assert_eq!(
+            post.try_approve(),
+            Err(WorkflowError {
+                state: "draft",
+                action: "approve"
+            })
+        );
+        assert_eq!(post.try_request_review(), Ok(()));
+        assert_eq!(post.state(), "pending review");
+        assert_eq!(post.try_approve(), Ok(()));
+        assert_eq!(post.content(), "I ate a salad for lunch today");
+
+        let error = post.try_request_review().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "can't request a review of a post in the published state"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_gui_components() {
+        use gui::{Button, Label};
+
+        let button = Button {
+            width: 50,
+            height: 10,
+            label: String::from("OK"),
+        };
+        let json = serde_json::to_string(&button).unwrap();
+        assert_eq!(json, r#"{"width":50,"height":10,"label":"OK"}"#);
+        let restored: Button = serde_json::from_str(&json).unwrap();
+        assert_eq!((restored.width, restored.label), (50, String::from("OK")));
+
+        let label: Label = serde_json::from_str(r#"{"text":"Name"}"#).unwrap();
+        assert_eq!(label.text, "Name");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_blog_posts() {
+        use blog_no_state::{DraftPost, Post};
+
+        let mut draft = Post::new();
+        draft.add_text("I ate a salad");
+        let json = serde_json::to_string(&draft).unwrap();
+        assert_eq!(json, r#"{"content":"I ate a salad"}"#);
+
+        // A draft can be restored and go through the review, a published post can only be serialized
+        let mut restored: DraftPost = serde_json::from_str(&json).unwrap();
+        restored.add_text(" for lunch");
+        let post = restored.request_review().approve();
+        assert_eq!(
+            serde_json::to_string(&post).unwrap(),
+            r#"{"content":"I ate a salad for lunch"}"#
+        );
+    }
+}
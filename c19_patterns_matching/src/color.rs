@@ -0,0 +1,201 @@
+//! The colors of the messages, in RGB or HSV, with the conversions between the two
+//! The components are `i32` like in the examples of the chapter, so a color can hold values out of range:
+//! - red, green, blue, saturation and value go from 0 to 255, and are clamped to that range
+//! - the hue is an angle in degrees, and wraps around 360, so -90 is 270
+//!
+//! The conversions compute in `f64` and round to the nearest integer
+//! A degree of hue spans up to 4 values of a component, so converting back can be off by 2
+
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    Rgb(i32, i32, i32),
+    Hsv(i32, i32, i32),
+}
+
+impl Default for Color {
+    fn default() -> Color {
+        Color::Rgb(0, 0, 0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseColorError {
+    // Not `#rrggbb`, `rgb(r, g, b)`, or `hsv(h, s, v)`
+    UnknownFormat(String),
+    // A component that is not a number, or is out of its range
+    InvalidComponent(String),
+    WrongComponentCount(usize),
+}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseColorError::UnknownFormat(text) => write!(f, "'{text}' is not a color"),
+            ParseColorError::InvalidComponent(text) => {
+                write!(f, "'{text}' is not a valid color component")
+            }
+            ParseColorError::WrongComponentCount(count) => {
+                write!(f, "a color has 3 components, found {count}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseColorError {}
+
+fn clamp_component(component: i32) -> i32 {
+    component.clamp(0, 255)
+}
+
+// From a component between 0 and 255 to a fraction between 0 and 1
+fn fraction(component: i32) -> f64 {
+    f64::from(clamp_component(component)) / 255.0
+}
+
+// From a fraction between 0 and 1 to a component between 0 and 255, rounded
+fn component(fraction: f64) -> i32 {
+    (fraction * 255.0).round() as i32
+}
+
+impl Color {
+    pub fn parse(text: &str) -> Result<Color, ParseColorError> {
+        let text = text.trim();
+        if let Some(hex) = text.strip_prefix('#') {
+            return parse_hex(hex);
+        }
+        let Some((kind, rest)) = text.split_once('(') else {
+            return Err(ParseColorError::UnknownFormat(String::from(text)));
+        };
+        let Some(arguments) = rest.strip_suffix(')') else {
+            return Err(ParseColorError::UnknownFormat(String::from(text)));
+        };
+        let components: Vec<&str> = arguments.split(',').map(str::trim).collect();
+        let &[first, second, third] = components.as_slice() else {
+            return Err(ParseColorError::WrongComponentCount(components.len()));
+        };
+        match kind.trim().to_lowercase().as_str() {
+            "rgb" => Ok(Color::Rgb(
+                parse_component(first, 0..=255)?,
+                parse_component(second, 0..=255)?,
+                parse_component(third, 0..=255)?,
+            )),
+            "hsv" => Ok(Color::Hsv(
+                parse_component(first, 0..=359)?,
+                parse_component(second, 0..=255)?,
+                parse_component(third, 0..=255)?,
+            )),
+            _ => Err(ParseColorError::UnknownFormat(String::from(text))),
+        }
+    }
+
+    // The same color, with the components in their range
+    pub fn normalized(&self) -> Color {
+        match *self {
+            Color::Rgb(r, g, b) => {
+                Color::Rgb(clamp_component(r), clamp_component(g), clamp_component(b))
+            }
+            Color::Hsv(h, s, v) => {
+                Color::Hsv(h.rem_euclid(360), clamp_component(s), clamp_component(v))
+            }
+        }
+    }
+
+    // The color as `Color::Rgb`, normalized
+    pub fn to_rgb(&self) -> Color {
+        match self.normalized() {
+            rgb @ Color::Rgb(..) => rgb,
+            Color::Hsv(h, s, v) => {
+                let (s, v) = (fraction(s), fraction(v));
+                let chroma = v * s;
+                // The hue picks one of six sectors of 60 degrees, where one of the components grows or shrinks linearly
+                let sector = f64::from(h) / 60.0;
+                let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+                let (r, g, b) = match h {
+                    0..60 => (chroma, x, 0.0),
+                    60..120 => (x, chroma, 0.0),
+                    120..180 => (0.0, chroma, x),
+                    180..240 => (0.0, x, chroma),
+                    240..300 => (x, 0.0, chroma),
+                    _ => (chroma, 0.0, x),
+                };
+                let m = v - chroma;
+                Color::Rgb(component(r + m), component(g + m), component(b + m))
+            }
+        }
+    }
+
+    // The color as `Color::Hsv`, normalized
+    // The greys have no hue, and the black has no saturation either, so they are 0
+    pub fn to_hsv(&self) -> Color {
+        match self.normalized() {
+            hsv @ Color::Hsv(..) => hsv,
+            Color::Rgb(r, g, b) => {
+                let max = r.max(g).max(b);
+                let min = r.min(g).min(b);
+                let delta = f64::from(max - min);
+                // The hue depends on which component is the largest, each one is 120 degrees from the others
+                let hue = if max == min {
+                    0.0
+                } else if max == r {
+                    60.0 * (f64::from(g - b) / delta)
+                } else if max == g {
+                    60.0 * (f64::from(b - r) / delta + 2.0)
+                } else {
+                    60.0 * (f64::from(r - g) / delta + 4.0)
+                };
+                let saturation = if max == 0 {
+                    0.0
+                } else {
+                    delta / f64::from(max)
+                };
+                Color::Hsv(
+                    (hue.round() as i32).rem_euclid(360),
+                    component(saturation),
+                    max,
+                )
+            }
+        }
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Color, ParseColorError> {
+    if hex.chars().count() != 6 {
+        return Err(ParseColorError::UnknownFormat(format!("#{hex}")));
+    }
+    // `from_str_radix` accepts a sign too, so the digits are checked first
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ParseColorError::InvalidComponent(format!("#{hex}")));
+    }
+    let channel = |start: usize| i32::from_str_radix(&hex[start..start + 2], 16).unwrap();
+    Ok(Color::Rgb(channel(0), channel(2), channel(4)))
+}
+
+fn parse_component(text: &str, range: RangeInclusive<i32>) -> Result<i32, ParseColorError> {
+    match text.parse() {
+        Ok(value) if range.contains(&value) => Ok(value),
+        _ => Err(ParseColorError::InvalidComponent(String::from(text))),
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(text: &str) -> Result<Color, ParseColorError> {
+        Color::parse(text)
+    }
+}
+
+// Written in the format of `parse`, so a color can be parsed back
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Rgb(r, g, b) => write!(f, "rgb({r}, {g}, {b})"),
+            Color::Hsv(h, s, v) => write!(f, "hsv({h}, {s}, {v})"),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+//! The types destructured in the examples of the chapter, with the methods that make them usable outside of a `match`
+//! A `Point` lives on an integer grid, a `Message` is a command that changes a `State`, and a `Color` is one of its fields
+//! The methods are written with patterns too, so they are examples of the chapter as well
+
+// A point on the integer grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    // The quadrant from 1 to 4, counterclockwise from the one with both coordinates positive
+    // The points on the axes are in no quadrant
+    pub fn quadrant(&self) -> Option<u8> {
+        match classify(*self) {
+            Location::Quadrant(quadrant) => Some(quadrant),
+            _ => None,
+        }
+    }
+
+    // The grid has edges: a point moved past one of them stops on it, instead of overflowing
+    pub fn translate(&self, dx: i32, dy: i32) -> Point {
+        Point::new(self.x.saturating_add(dx), self.y.saturating_add(dy))
+    }
+
+    // The euclidean distance, computed in `f64` so it can't overflow
+    pub fn distance(&self, other: &Point) -> f64 {
+        let dx = f64::from(self.x) - f64::from(other.x);
+        let dy = f64::from(self.y) - f64::from(other.y);
+        dx.hypot(dy)
+    }
+}
+
+// The colors in RGB or HSV, with the conversions between the two and the parsing from strings
+pub mod color;
+pub use color::{Color, ParseColorError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(Color),
+}
+
+// What the messages act on: a cursor that moves, writes and changes color, until it quits
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct State {
+    pub position: Point,
+    pub color: Color,
+    // Every text written, in order
+    pub written: Vec<String>,
+    pub running: bool,
+}
+
+impl Default for State {
+    fn default() -> State {
+        State {
+            position: Point::ORIGIN,
+            color: Color::default(),
+            written: Vec::new(),
+            running: true,
+        }
+    }
+}
+
+impl State {
+    pub fn new() -> State {
+        State::default()
+    }
+}
+
+impl Message {
+    // Executes the message on the state, the messages after a `Quit` are ignored
+    pub fn apply(&self, state: &mut State) {
+        if !state.running {
+            return;
+        }
+        match self {
+            Message::Quit => state.running = false,
+            Message::Move { x, y } => state.position = state.position.translate(*x, *y),
+            Message::Write(text) => state.written.push(text.clone()),
+            Message::ChangeColor(color) => state.color = *color,
+        }
+    }
+}
+
+// The settings of the example on ignoring values, as a map that can be merged with a policy
+pub mod settings;
+pub use settings::{Conflict, MergePolicy, Settings};
+
+// The parser of scripts of messages, with the line and the column of the errors
+pub mod parser;
+pub use parser::{ParseError, ParseErrorKind, parse_script};
+
+// Where a point is: at the origin, on an axis or in a quadrant, with the grid snapping and the bounding boxes
+pub mod location;
+pub use location::{BoundingBox, Location, classify, snap};
@@ -0,0 +1,110 @@
+//! Where a point is on the grid, the match of the chapter that tells the axes apart as a reusable function
+//! `classify` matches the literal `0` in the struct pattern for the axes, and a match guard on the signs for the quadrants
+//! The grid snapping and the bounding boxes work on the same integer grid, taking care of the limits of `i32`
+
+use std::fmt;
+
+use crate::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Location {
+    Origin,
+    OnXAxis,
+    OnYAxis,
+    // From 1 to 4, counterclockwise from the one with both coordinates positive
+    Quadrant(u8),
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Origin => write!(f, "at the origin"),
+            Location::OnXAxis => write!(f, "on the x axis"),
+            Location::OnYAxis => write!(f, "on the y axis"),
+            Location::Quadrant(quadrant) => write!(f, "in quadrant {quadrant}"),
+        }
+    }
+}
+
+pub fn classify(point: Point) -> Location {
+    match point {
+        Point { x: 0, y: 0 } => Location::Origin,
+        Point { y: 0, .. } => Location::OnXAxis,
+        Point { x: 0, .. } => Location::OnYAxis,
+        Point { x, y } if x > 0 && y > 0 => Location::Quadrant(1),
+        Point { x, y } if x < 0 && y > 0 => Location::Quadrant(2),
+        Point { x, y } if x < 0 && y < 0 => Location::Quadrant(3),
+        // The guards are not checked for exhaustiveness, so the last case has none
+        Point { .. } => Location::Quadrant(4),
+    }
+}
+
+// Moves the point to the nearest one whose coordinates are multiples of `step`
+// A coordinate halfway between two multiples goes up, unless the multiple above doesn't fit in an `i32`
+pub fn snap(point: Point, step: u32) -> Point {
+    assert!(step > 0, "the step of the grid must be positive");
+    Point::new(
+        snap_coordinate(point.x, step),
+        snap_coordinate(point.y, step),
+    )
+}
+
+fn snap_coordinate(value: i32, step: u32) -> i32 {
+    // In `i64` the coordinate and the step can't overflow
+    let (value, step) = (i64::from(value), i64::from(step));
+    let below = value - value.rem_euclid(step);
+    let above = below + step;
+    let candidates = match (value - below, above - value) {
+        (0, _) => [below, below],
+        (down, up) if up <= down => [above, below],
+        _ => [below, above],
+    };
+    // Near the limits of `i32` the nearest multiple may not fit, then the other one does
+    candidates
+        .into_iter()
+        .find_map(|candidate| i32::try_from(candidate).ok())
+        .expect("one of two multiples of a `u32` around an `i32` fits in an `i32`")
+}
+
+// The smallest rectangle with the sides parallel to the axes that contains some points, borders included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    // `None` if there are no points
+    pub fn of(points: impl IntoIterator<Item = Point>) -> Option<BoundingBox> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(
+            BoundingBox {
+                min: first,
+                max: first,
+            },
+            |bounding_box, point| bounding_box.including(point),
+        ))
+    }
+
+    // The bounding box grown to contain the point too
+    pub fn including(&self, point: Point) -> BoundingBox {
+        BoundingBox {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    // The sides are `u32`, as the distance between two `i32` doesn't always fit in an `i32`
+    pub fn width(&self) -> u32 {
+        self.max.x.abs_diff(self.min.x)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.max.y.abs_diff(self.min.y)
+    }
+}
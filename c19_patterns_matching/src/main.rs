@@ -232,11 +232,8 @@ fn pattern_syntax() {
         // Patterns can also be used to destructure structs, enums, and tuples to use different parts of these values
         {
             // Destructuring Structs
-            // The following struct `Point` has two fields: `x`, and `y`
-            struct Point {
-                x: i32,
-                y: i32,
-            }
+            // The struct `Point` of the library has two fields: `x`, and `y`
+            use c19_patterns_matching::Point;
 
             // Create a point with `x = 0` and `y = 7`
             let p = Point { x: 0, y: 7 };
@@ -297,20 +294,9 @@ fn pattern_syntax() {
         }
         {
             // Destructuring Nested Structs and Enums
-            // Since now the structs and enums were only one level deep, but matching works on nested items too:
-            #[allow(dead_code)]
-            enum Color {
-                Rgb(i32, i32, i32),
-                Hsv(i32, i32, i32),
-            }
-
-            #[allow(dead_code)]
-            enum Message {
-                Quit,
-                Move { x: i32, y: i32 },
-                Write(String),
-                ChangeColor(Color),
-            }
+            // Since now the structs and enums were only one level deep, but matching works on nested items too
+            // The `Message` of the library has a `ChangeColor` variant with a `Color` inside, that can be `Rgb` or `Hsv`:
+            use c19_patterns_matching::{Color, Message, Point, State};
 
             let msg = Message::ChangeColor(Color::Hsv(0, 160, 255));
 
@@ -326,6 +312,22 @@ fn pattern_syntax() {
             // In this case the first arm of `match` matches the `Message::ChangeColor` variant that contains the `Color::Rgb` variant
             // The pattern then binds to the three inner `i32` values
             // The second arm, used in this example, instead matches the `Color::Hsv` instead in the same way.
+            // The library uses the same match to execute each message on a `State`:
+            let mut state = State::new();
+            for msg in [
+                Message::Move { x: 3, y: -4 },
+                Message::Write(String::from("hello")),
+                msg,
+                Message::Quit,
+            ] {
+                msg.apply(&mut state);
+            }
+            println!(
+                "The cursor is at {:?}, in quadrant {:?}, {} from the origin",
+                state.position,
+                state.position.quadrant(),
+                state.position.distance(&Point::ORIGIN)
+            );
         }
     }
     {
@@ -362,6 +364,14 @@ fn pattern_syntax() {
 
             println!("setting is {setting_value:?}");
             // In this case, if the `setting_value` isn't `None` it won't be overwritten, otherwise is set as `new_setting_value`
+            // The library generalises it to whole maps of settings, with a policy for the values set on both sides:
+            use c19_patterns_matching::{MergePolicy, Settings};
+
+            let mut settings: Settings<i32> =
+                [("volume", 5), ("brightness", 70)].into_iter().collect();
+            let new_settings = [("volume", 10), ("contrast", 50)].into_iter().collect();
+            let conflicts = settings.merge(new_settings, MergePolicy::KeepExisting);
+            println!("settings are {settings:?}, with conflicts {conflicts:?}");
             // Underscores can alse be used in multiple places to ignore particular values:
             let numbers = (2, 4, 8, 16, 32);
 
@@ -0,0 +1,187 @@
+//! A parser of scripts of messages, one per line: `move 3 4`, `write hello`, `color 0 160 255`, `quit`
+//! Parsing a line is refutable: any line can be given, but only some of them match a message
+//! So every step returns a `Result`, and the lines are matched with slice patterns on their words
+//! The errors point to the line and the column of the word that didn't match, counted from 1 like in an editor
+//! Empty lines and lines starting with `#` are skipped, so a script can have comments
+
+use std::fmt;
+
+use crate::{Color, Message, ParseColorError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    // An empty line, where a single message was expected
+    MissingCommand,
+    UnknownCommand(String),
+    // The command needs more words, e.g. `move 3`
+    MissingArgument { command: String, expected: usize },
+    // The command doesn't take this word, e.g. `quit now`
+    UnexpectedArgument(String),
+    InvalidNumber(String),
+    InvalidColor(ParseColorError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: ", self.line, self.column)?;
+        match &self.kind {
+            ParseErrorKind::MissingCommand => write!(f, "missing command"),
+            ParseErrorKind::UnknownCommand(command) => write!(f, "unknown command '{command}'"),
+            ParseErrorKind::MissingArgument { command, expected } => {
+                write!(f, "'{command}' needs {expected} arguments")
+            }
+            ParseErrorKind::UnexpectedArgument(word) => write!(f, "unexpected '{word}'"),
+            ParseErrorKind::InvalidNumber(word) => write!(f, "'{word}' is not a number"),
+            ParseErrorKind::InvalidColor(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// A word of a line, with the column of its first character
+#[derive(Debug, Clone, Copy)]
+struct Word<'a> {
+    column: usize,
+    text: &'a str,
+}
+
+fn words(line: &str) -> Vec<Word<'_>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    // The columns count the characters, not the bytes, so a word after an accented letter is where it looks
+    for (column, (index, c)) in line.char_indices().enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((column + 1, index)),
+            (Some((column, begin)), true) => {
+                words.push(Word {
+                    column,
+                    text: &line[begin..index],
+                });
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some((column, begin)) = start {
+        words.push(Word {
+            column,
+            text: &line[begin..],
+        });
+    }
+    words
+}
+
+impl Message {
+    // Parses a single line, the errors are on line 1
+    pub fn parse(line: &str) -> Result<Message, ParseError> {
+        parse_line(line, 1)
+    }
+}
+
+pub fn parse_script(script: &str) -> Result<Vec<Message>, ParseError> {
+    script
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim_start();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(index, line)| parse_line(line, index + 1))
+        .collect()
+}
+
+fn parse_line(line: &str, line_number: usize) -> Result<Message, ParseError> {
+    let error = |column, kind| ParseError {
+        line: line_number,
+        column,
+        kind,
+    };
+    // The column just after the end of the line, where a missing word would be
+    let end = line.trim_end().chars().count() + 1;
+    let words = words(line);
+    let [command, arguments @ ..] = words.as_slice() else {
+        return Err(error(end, ParseErrorKind::MissingCommand));
+    };
+    let missing = |expected| {
+        error(
+            end,
+            ParseErrorKind::MissingArgument {
+                command: String::from(command.text),
+                expected,
+            },
+        )
+    };
+    let unexpected = |word: &Word| {
+        error(
+            word.column,
+            ParseErrorKind::UnexpectedArgument(String::from(word.text)),
+        )
+    };
+    let number = |word: &Word| {
+        word.text.parse::<i32>().map_err(|_| {
+            error(
+                word.column,
+                ParseErrorKind::InvalidNumber(String::from(word.text)),
+            )
+        })
+    };
+
+    match (command.text, arguments) {
+        ("quit", []) => Ok(Message::Quit),
+        ("quit", [extra, ..]) => Err(unexpected(extra)),
+        ("move", [x, y]) => Ok(Message::Move {
+            x: number(x)?,
+            y: number(y)?,
+        }),
+        ("move", [_, _, extra, ..]) => Err(unexpected(extra)),
+        ("move", _) => Err(missing(2)),
+        // The text is the rest of the line as it was written, spaces included
+        ("write", [first, ..]) => {
+            let start = line
+                .char_indices()
+                .nth(first.column - 1)
+                .map_or(0, |(index, _)| index);
+            Ok(Message::Write(String::from(line[start..].trim_end())))
+        }
+        ("write", []) => Err(missing(1)),
+        ("color", [r, g, b]) => Ok(Message::ChangeColor(Color::Rgb(
+            component(r, &error)?,
+            component(g, &error)?,
+            component(b, &error)?,
+        ))),
+        // A single word is a color in any of the formats of `Color::parse`, e.g. `#00a0ff`
+        ("color", [color]) => Color::parse(color.text)
+            .map(Message::ChangeColor)
+            .map_err(|parse_error| error(color.column, ParseErrorKind::InvalidColor(parse_error))),
+        ("color", [_, _, _, extra, ..]) => Err(unexpected(extra)),
+        ("color", _) => Err(missing(3)),
+        (other, _) => Err(error(
+            command.column,
+            ParseErrorKind::UnknownCommand(String::from(other)),
+        )),
+    }
+}
+
+fn component(
+    word: &Word,
+    error: &impl Fn(usize, ParseErrorKind) -> ParseError,
+) -> Result<i32, ParseError> {
+    // The same rules as `Color::parse`, so the errors are the same too
+    match word.text.parse() {
+        Ok(value @ 0..=255) => Ok(value),
+        _ => Err(error(
+            word.column,
+            ParseErrorKind::InvalidColor(ParseColorError::InvalidComponent(String::from(
+                word.text,
+            ))),
+        )),
+    }
+}
@@ -0,0 +1,111 @@
+//! Settings that can be merged, generalising the example of the chapter that doesn't overwrite a customized value
+//! There, a single setting was matched with `(Some(_), Some(_))` to ignore the values and only check that both were set
+//! Here a whole map of settings is merged into another one, and the policy decides what happens when a key is set on both sides
+//! Every key set on both sides with different values is reported as a conflict, whatever the policy
+
+use std::collections::BTreeMap;
+
+// What to do with a key set on both sides with different values
+#[derive(Debug, Clone, Copy)]
+pub enum MergePolicy<T> {
+    // The value already set stays, like in the example of the chapter
+    KeepExisting,
+    Overwrite,
+    // The function gets the existing value and the incoming one, and returns the merged one
+    Combine(fn(&T, &T) -> T),
+}
+
+// A key set on both sides with different values, before the policy was applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conflict<T> {
+    pub key: String,
+    pub existing: T,
+    pub incoming: T,
+}
+
+// The settings are sorted by key, so the conflicts are reported in a predictable order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings<T> {
+    values: BTreeMap<String, T>,
+}
+
+impl<T> Default for Settings<T> {
+    fn default() -> Settings<T> {
+        Settings {
+            values: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> Settings<T> {
+    pub fn new() -> Settings<T> {
+        Settings::default()
+    }
+
+    // Sets a value, returning the previous one if it was set
+    pub fn set(&mut self, key: &str, value: T) -> Option<T> {
+        self.values.insert(String::from(key), value)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&T> {
+        self.values.get(key)
+    }
+
+    // Unsets a value, so it can be set again by a merge with any policy
+    pub fn unset(&mut self, key: &str) -> Option<T> {
+        self.values.remove(key)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &T)> {
+        self.values.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl<T: Clone + PartialEq> Settings<T> {
+    // Merges the other settings into these ones
+    // The keys set only on the other side are always added, the conflicts are resolved with the policy and returned
+    pub fn merge(&mut self, other: Settings<T>, policy: MergePolicy<T>) -> Vec<Conflict<T>> {
+        let mut conflicts = Vec::new();
+        for (key, incoming) in other.values {
+            match self.values.get_mut(&key) {
+                None => {
+                    self.values.insert(key, incoming);
+                }
+                // The same value on both sides is not a conflict
+                Some(existing) if *existing == incoming => {}
+                Some(existing) => {
+                    conflicts.push(Conflict {
+                        key,
+                        existing: existing.clone(),
+                        incoming: incoming.clone(),
+                    });
+                    match &policy {
+                        MergePolicy::KeepExisting => {}
+                        MergePolicy::Overwrite => *existing = incoming,
+                        MergePolicy::Combine(combine) => *existing = combine(existing, &incoming),
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+impl<K: Into<String>, T> FromIterator<(K, T)> for Settings<T> {
+    fn from_iter<I: IntoIterator<Item = (K, T)>>(iter: I) -> Settings<T> {
+        Settings {
+            values: iter
+                .into_iter()
+                .map(|(key, value)| (key.into(), value))
+                .collect(),
+        }
+    }
+}
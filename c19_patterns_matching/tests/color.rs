@@ -0,0 +1,113 @@
+use c19_patterns_matching::{Color, ParseColorError};
+
+#[test]
+fn primary_colors_convert_both_ways() {
+    let colors = [
+        (Color::Rgb(255, 0, 0), Color::Hsv(0, 255, 255)),
+        (Color::Rgb(0, 255, 0), Color::Hsv(120, 255, 255)),
+        (Color::Rgb(0, 0, 255), Color::Hsv(240, 255, 255)),
+        (Color::Rgb(255, 255, 0), Color::Hsv(60, 255, 255)),
+        (Color::Rgb(255, 0, 255), Color::Hsv(300, 255, 255)),
+        (Color::Rgb(255, 255, 255), Color::Hsv(0, 0, 255)),
+        (Color::Rgb(0, 0, 0), Color::Hsv(0, 0, 0)),
+    ];
+    for (rgb, hsv) in colors {
+        assert_eq!(rgb.to_hsv(), hsv, "{rgb} to hsv");
+        assert_eq!(hsv.to_rgb(), rgb, "{hsv} to rgb");
+        // Converting to the same variant only normalizes
+        assert_eq!(rgb.to_rgb(), rgb);
+        assert_eq!(hsv.to_hsv(), hsv);
+    }
+}
+
+#[test]
+fn conversions_round_to_the_nearest_component() {
+    // The color of the examples of the chapter
+    assert_eq!(Color::Hsv(0, 160, 255).to_rgb(), Color::Rgb(255, 95, 95));
+    assert_eq!(Color::Rgb(0, 160, 255).to_hsv(), Color::Hsv(202, 255, 255));
+    assert_eq!(Color::Rgb(128, 128, 128).to_hsv(), Color::Hsv(0, 0, 128));
+}
+
+#[test]
+fn round_trips_are_off_by_two_at_most() {
+    for r in (0..=255).step_by(15) {
+        for g in (0..=255).step_by(15) {
+            for b in (0..=255).step_by(15) {
+                let Color::Rgb(r2, g2, b2) = Color::Rgb(r, g, b).to_hsv().to_rgb() else {
+                    panic!("to_rgb returned a Color::Hsv");
+                };
+                let error = (r - r2).abs().max((g - g2).abs()).max((b - b2).abs());
+                assert!(
+                    error <= 2,
+                    "rgb({r}, {g}, {b}) came back as rgb({r2}, {g2}, {b2})"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn out_of_range_components_are_clamped_and_hues_wrap() {
+    assert_eq!(
+        Color::Rgb(300, -20, 128).normalized(),
+        Color::Rgb(255, 0, 128)
+    );
+    assert_eq!(
+        Color::Hsv(-90, 300, -1).normalized(),
+        Color::Hsv(270, 255, 0)
+    );
+    assert_eq!(Color::Hsv(480, 255, 255).to_rgb(), Color::Rgb(0, 255, 0));
+    assert_eq!(Color::Rgb(300, -20, 0).to_hsv(), Color::Hsv(0, 255, 255));
+}
+
+#[test]
+fn colors_are_parsed_in_every_format() {
+    assert_eq!(Color::parse("#00a0ff"), Ok(Color::Rgb(0, 160, 255)));
+    assert_eq!(Color::parse("#FFFFFF"), Ok(Color::Rgb(255, 255, 255)));
+    assert_eq!(
+        Color::parse("rgb(0, 160, 255)"),
+        Ok(Color::Rgb(0, 160, 255))
+    );
+    assert_eq!(" HSV( 0,160,255 ) ".parse(), Ok(Color::Hsv(0, 160, 255)));
+    // What is displayed can be parsed back
+    let color = Color::Hsv(202, 255, 255);
+    assert_eq!(color.to_string().parse(), Ok(color));
+}
+
+#[test]
+fn invalid_colors_are_rejected() {
+    let error = |text: &str| Color::parse(text).unwrap_err();
+    assert_eq!(
+        error("red"),
+        ParseColorError::UnknownFormat(String::from("red"))
+    );
+    assert_eq!(
+        error("#fff"),
+        ParseColorError::UnknownFormat(String::from("#fff"))
+    );
+    assert_eq!(
+        error("cmyk(0, 0, 0)"),
+        ParseColorError::UnknownFormat(String::from("cmyk(0, 0, 0)"))
+    );
+    assert_eq!(
+        error("rgb(0, 0"),
+        ParseColorError::UnknownFormat(String::from("rgb(0, 0"))
+    );
+    assert_eq!(
+        error("#+fffff"),
+        ParseColorError::InvalidComponent(String::from("#+fffff"))
+    );
+    assert_eq!(
+        error("rgb(0, 256, 0)"),
+        ParseColorError::InvalidComponent(String::from("256"))
+    );
+    assert_eq!(
+        error("hsv(360, 0, 0)"),
+        ParseColorError::InvalidComponent(String::from("360"))
+    );
+    assert_eq!(error("rgb(0, 0)"), ParseColorError::WrongComponentCount(2));
+    assert_eq!(
+        error("rgb(a, 0, 0)").to_string(),
+        "'a' is not a valid color component"
+    );
+}
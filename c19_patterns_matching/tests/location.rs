@@ -0,0 +1,94 @@
+use c19_patterns_matching::{BoundingBox, Location, Point, classify, snap};
+
+#[test]
+fn points_are_classified() {
+    assert_eq!(classify(Point::ORIGIN), Location::Origin);
+    assert_eq!(classify(Point::new(7, 0)), Location::OnXAxis);
+    assert_eq!(classify(Point::new(-7, 0)), Location::OnXAxis);
+    assert_eq!(classify(Point::new(0, 7)), Location::OnYAxis);
+    assert_eq!(classify(Point::new(1, 1)), Location::Quadrant(1));
+    assert_eq!(classify(Point::new(-1, 1)), Location::Quadrant(2));
+    assert_eq!(classify(Point::new(-1, -1)), Location::Quadrant(3));
+    assert_eq!(classify(Point::new(1, -1)), Location::Quadrant(4));
+    assert_eq!(
+        classify(Point::new(i32::MIN, i32::MAX)),
+        Location::Quadrant(2)
+    );
+    assert_eq!(Location::Quadrant(3).to_string(), "in quadrant 3");
+    assert_eq!(Location::OnYAxis.to_string(), "on the y axis");
+}
+
+#[test]
+fn quadrant_agrees_with_classify() {
+    for x in -3..=3 {
+        for y in -3..=3 {
+            let point = Point::new(x, y);
+            match (classify(point), point.quadrant()) {
+                (Location::Quadrant(a), Some(b)) => assert_eq!(a, b),
+                (_, None) => assert!(x == 0 || y == 0),
+                (location, quadrant) => panic!("{point:?} is {location} and in {quadrant:?}"),
+            }
+        }
+    }
+}
+
+#[test]
+fn points_snap_to_the_nearest_multiple() {
+    assert_eq!(snap(Point::new(7, 12), 5), Point::new(5, 10));
+    assert_eq!(snap(Point::new(8, 13), 5), Point::new(10, 15));
+    assert_eq!(snap(Point::new(-7, -8), 5), Point::new(-5, -10));
+    // Halfway goes up, also below zero
+    assert_eq!(snap(Point::new(5, -5), 10), Point::new(10, 0));
+    assert_eq!(snap(Point::new(3, -4), 1), Point::new(3, -4));
+}
+
+#[test]
+fn snapping_stays_in_range() {
+    assert_eq!(snap(Point::new(i32::MAX, i32::MIN), 10).x, 2_147_483_640);
+    assert_eq!(snap(Point::new(i32::MAX, i32::MIN), 10).y, -2_147_483_640);
+    // The nearest multiple of `u32::MAX` to `i32::MAX` is 0, the next one doesn't fit
+    assert_eq!(
+        snap(Point::new(i32::MAX, i32::MIN), u32::MAX),
+        Point::ORIGIN
+    );
+}
+
+#[test]
+#[should_panic(expected = "must be positive")]
+fn snapping_needs_a_step() {
+    snap(Point::ORIGIN, 0);
+}
+
+#[test]
+fn bounding_boxes_contain_all_the_points() {
+    let points = [Point::new(3, -1), Point::new(-2, 4), Point::new(0, 0)];
+    let bounding_box = BoundingBox::of(points).unwrap();
+
+    assert_eq!(
+        bounding_box,
+        BoundingBox {
+            min: Point::new(-2, -1),
+            max: Point::new(3, 4)
+        }
+    );
+    assert_eq!((bounding_box.width(), bounding_box.height()), (5, 5));
+    assert!(points.iter().all(|point| bounding_box.contains(*point)));
+    assert!(!bounding_box.contains(Point::new(4, 0)));
+
+    let grown = bounding_box.including(Point::new(10, 0));
+    assert_eq!(grown.width(), 12);
+    assert!(grown.contains(Point::new(4, 0)));
+}
+
+#[test]
+fn bounding_boxes_of_nothing_or_everything() {
+    assert_eq!(BoundingBox::of([]), None);
+    let single = BoundingBox::of([Point::new(1, 1)]).unwrap();
+    assert_eq!((single.width(), single.height()), (0, 0));
+
+    let everything = BoundingBox::of([
+        Point::new(i32::MIN, i32::MIN),
+        Point::new(i32::MAX, i32::MAX),
+    ]);
+    assert_eq!(everything.unwrap().width(), u32::MAX);
+}
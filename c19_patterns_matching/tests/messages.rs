@@ -0,0 +1,83 @@
+use c19_patterns_matching::{Color, Message, Point, State};
+
+#[test]
+fn points_have_a_quadrant() {
+    assert_eq!(Point::new(3, 4).quadrant(), Some(1));
+    assert_eq!(Point::new(-3, 4).quadrant(), Some(2));
+    assert_eq!(Point::new(-3, -4).quadrant(), Some(3));
+    assert_eq!(Point::new(3, -4).quadrant(), Some(4));
+    // The points on the axes are in no quadrant
+    assert_eq!(Point::ORIGIN.quadrant(), None);
+    assert_eq!(Point::new(0, 7).quadrant(), None);
+    assert_eq!(Point::new(-7, 0).quadrant(), None);
+}
+
+#[test]
+fn points_translate_and_measure() {
+    let p = Point::new(0, 7);
+    assert_eq!(p.translate(3, -3), Point::new(3, 4));
+    assert_eq!(p.translate(3, -3).distance(&Point::ORIGIN), 5.0);
+    assert_eq!(p.distance(&p), 0.0);
+    // The distance is computed in `f64`, so the extremes don't overflow
+    let far = Point::new(i32::MIN, 0).distance(&Point::new(i32::MAX, 0));
+    assert_eq!(far, f64::from(u32::MAX));
+}
+
+#[test]
+fn moves_stop_at_the_edges_of_the_grid() {
+    let corner = Point::new(i32::MAX, i32::MIN);
+    assert_eq!(corner.translate(1, -1), corner);
+    assert_eq!(
+        Point::new(-5, 5).translate(i32::MIN, i32::MAX),
+        Point::new(i32::MIN, i32::MAX)
+    );
+
+    // The moves of a parsed script can't overflow the position either
+    let mut state = State::new();
+    for _ in 0..3 {
+        Message::Move {
+            x: i32::MAX,
+            y: i32::MIN,
+        }
+        .apply(&mut state);
+    }
+    assert_eq!(state.position, Point::new(i32::MAX, i32::MIN));
+}
+
+#[test]
+fn messages_change_the_state() {
+    let mut state = State::new();
+    let messages = [
+        Message::Move { x: 3, y: 4 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(Color::Hsv(0, 160, 255)),
+        Message::Move { x: -1, y: 1 },
+        Message::Write(String::from("world")),
+    ];
+    for message in &messages {
+        message.apply(&mut state);
+    }
+
+    assert_eq!(
+        state,
+        State {
+            position: Point::new(2, 5),
+            color: Color::Hsv(0, 160, 255),
+            written: vec![String::from("hello"), String::from("world")],
+            running: true,
+        }
+    );
+}
+
+#[test]
+fn messages_after_quit_are_ignored() {
+    let mut state = State::new();
+    Message::Write(String::from("bye")).apply(&mut state);
+    Message::Quit.apply(&mut state);
+    Message::Move { x: 1, y: 1 }.apply(&mut state);
+    Message::Write(String::from("ignored")).apply(&mut state);
+
+    assert!(!state.running);
+    assert_eq!(state.position, Point::ORIGIN);
+    assert_eq!(state.written, ["bye"]);
+}
@@ -0,0 +1,139 @@
+use c19_patterns_matching::{
+    Color, Message, ParseColorError, ParseError, ParseErrorKind, Point, State, parse_script,
+};
+
+fn error(line: usize, column: usize, kind: ParseErrorKind) -> ParseError {
+    ParseError { line, column, kind }
+}
+
+#[test]
+fn every_command_is_parsed() {
+    assert_eq!(Message::parse("quit"), Ok(Message::Quit));
+    assert_eq!(
+        Message::parse("move 3 -4"),
+        Ok(Message::Move { x: 3, y: -4 })
+    );
+    assert_eq!(
+        Message::parse("color 0 160 255"),
+        Ok(Message::ChangeColor(Color::Rgb(0, 160, 255)))
+    );
+    assert_eq!(
+        Message::parse("color #00a0ff"),
+        Ok(Message::ChangeColor(Color::Rgb(0, 160, 255)))
+    );
+    assert_eq!(
+        Message::parse("  color hsv(0,160,255)  "),
+        Ok(Message::ChangeColor(Color::Hsv(0, 160, 255)))
+    );
+}
+
+#[test]
+fn write_keeps_the_rest_of_the_line() {
+    assert_eq!(
+        Message::parse("write  hello,   world  "),
+        Ok(Message::Write(String::from("hello,   world")))
+    );
+    assert_eq!(
+        Message::parse("\twrite ¡hola! qué tal"),
+        Ok(Message::Write(String::from("¡hola! qué tal")))
+    );
+}
+
+#[test]
+fn scripts_skip_blank_lines_and_comments() {
+    let script =
+        "# go to the corner\nmove 3 4\n\n   # and say hello\nwrite hello\ncolor 0 160 255\nquit\n";
+    let messages = parse_script(script).unwrap();
+    assert_eq!(messages.len(), 4);
+
+    let mut state = State::new();
+    for message in &messages {
+        message.apply(&mut state);
+    }
+    assert_eq!(state.position, Point::new(3, 4));
+    assert_eq!(state.written, ["hello"]);
+    assert_eq!(state.color, Color::Rgb(0, 160, 255));
+    assert!(!state.running);
+}
+
+#[test]
+fn errors_point_to_the_word() {
+    assert_eq!(
+        Message::parse("jump 3 4"),
+        Err(error(
+            1,
+            1,
+            ParseErrorKind::UnknownCommand(String::from("jump"))
+        ))
+    );
+    assert_eq!(
+        Message::parse("move 3 four"),
+        Err(error(
+            1,
+            8,
+            ParseErrorKind::InvalidNumber(String::from("four"))
+        ))
+    );
+    assert_eq!(
+        Message::parse("quit now"),
+        Err(error(
+            1,
+            6,
+            ParseErrorKind::UnexpectedArgument(String::from("now"))
+        ))
+    );
+    assert_eq!(
+        Message::parse("move 1 2 3"),
+        Err(error(
+            1,
+            10,
+            ParseErrorKind::UnexpectedArgument(String::from("3"))
+        ))
+    );
+    assert_eq!(
+        Message::parse("color 0 300 0"),
+        Err(error(
+            1,
+            9,
+            ParseErrorKind::InvalidColor(ParseColorError::InvalidComponent(String::from("300")))
+        ))
+    );
+    assert_eq!(
+        Message::parse("   "),
+        Err(error(1, 1, ParseErrorKind::MissingCommand))
+    );
+}
+
+#[test]
+fn missing_arguments_point_to_the_end_of_the_line() {
+    let missing = |command: &str, expected| ParseErrorKind::MissingArgument {
+        command: String::from(command),
+        expected,
+    };
+    assert_eq!(
+        Message::parse("move 3"),
+        Err(error(1, 7, missing("move", 2)))
+    );
+    assert_eq!(
+        Message::parse("write "),
+        Err(error(1, 6, missing("write", 1)))
+    );
+    assert_eq!(
+        Message::parse("color 0 0"),
+        Err(error(1, 10, missing("color", 3)))
+    );
+}
+
+#[test]
+fn scripts_stop_at_the_first_error() {
+    let script = "move 1 1\n# a comment\nwrite ok\n  move x 1\nquit 1\n";
+    let parse_error = parse_script(script).unwrap_err();
+    assert_eq!(
+        parse_error,
+        error(4, 8, ParseErrorKind::InvalidNumber(String::from("x")))
+    );
+    assert_eq!(
+        parse_error.to_string(),
+        "line 4, column 8: 'x' is not a number"
+    );
+}
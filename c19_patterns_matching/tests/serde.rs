@@ -0,0 +1,38 @@
+//! Run with `cargo test --features serde`
+#![cfg(feature = "serde")]
+
+use c19_patterns_matching::{Color, Message, Point, State};
+
+#[test]
+fn messages_round_trip() {
+    let messages = [
+        Message::Quit,
+        Message::Move { x: 3, y: -4 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(Color::Hsv(200, 128, 255)),
+    ];
+    for message in messages {
+        let json = serde_json::to_string(&message).unwrap();
+        assert_eq!(serde_json::from_str::<Message>(&json).unwrap(), message);
+    }
+    assert_eq!(
+        serde_json::to_string(&Message::Move { x: 3, y: -4 }).unwrap(),
+        r#"{"Move":{"x":3,"y":-4}}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&Color::Rgb(0, 160, 255)).unwrap(),
+        r#"{"Rgb":[0,160,255]}"#
+    );
+}
+
+#[test]
+fn state_round_trips() {
+    let mut state = State::new();
+    Message::Move { x: 1, y: 2 }.apply(&mut state);
+    Message::Write(String::from("saved")).apply(&mut state);
+
+    let json = serde_json::to_string(&state).unwrap();
+    let restored: State = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, state);
+    assert_eq!(restored.position, Point::new(1, 2));
+}
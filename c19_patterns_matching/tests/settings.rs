@@ -0,0 +1,90 @@
+use c19_patterns_matching::{Conflict, MergePolicy, Settings};
+
+fn existing() -> Settings<i32> {
+    [("volume", 5), ("brightness", 70), ("contrast", 50)]
+        .into_iter()
+        .collect()
+}
+
+fn incoming() -> Settings<i32> {
+    [("volume", 10), ("contrast", 50), ("zoom", 2)]
+        .into_iter()
+        .collect()
+}
+
+fn values(settings: &Settings<i32>) -> Vec<(&str, i32)> {
+    settings.iter().map(|(key, value)| (key, *value)).collect()
+}
+
+#[test]
+fn keep_existing_only_adds_the_new_keys() {
+    let mut settings = existing();
+    let conflicts = settings.merge(incoming(), MergePolicy::KeepExisting);
+
+    assert_eq!(
+        values(&settings),
+        [
+            ("brightness", 70),
+            ("contrast", 50),
+            ("volume", 5),
+            ("zoom", 2)
+        ]
+    );
+    // The same value on both sides is not a conflict
+    assert_eq!(
+        conflicts,
+        [Conflict {
+            key: String::from("volume"),
+            existing: 5,
+            incoming: 10
+        }]
+    );
+}
+
+#[test]
+fn overwrite_takes_the_incoming_values() {
+    let mut settings = existing();
+    let conflicts = settings.merge(incoming(), MergePolicy::Overwrite);
+
+    assert_eq!(settings.get("volume"), Some(&10));
+    assert_eq!(settings.get("zoom"), Some(&2));
+    assert_eq!(settings.len(), 4);
+    assert_eq!(conflicts.len(), 1);
+}
+
+#[test]
+fn combine_merges_both_values() {
+    let mut settings = existing();
+    let conflicts = settings.merge(incoming(), MergePolicy::Combine(|a, b| (a + b) / 2));
+
+    assert_eq!(settings.get("volume"), Some(&7));
+    assert_eq!(conflicts[0].existing, 5);
+    assert_eq!(conflicts[0].incoming, 10);
+
+    // Works with values that are not `Copy` too
+    let mut tags: Settings<Vec<&str>> = [("fruit", vec!["apple"])].into_iter().collect();
+    let new_tags = [("fruit", vec!["pear"]), ("vegetable", vec!["leek"])]
+        .into_iter()
+        .collect();
+    tags.merge(
+        new_tags,
+        MergePolicy::Combine(|a, b| a.iter().chain(b).copied().collect()),
+    );
+    assert_eq!(tags.get("fruit"), Some(&vec!["apple", "pear"]));
+    assert_eq!(tags.get("vegetable"), Some(&vec!["leek"]));
+}
+
+#[test]
+fn unset_values_can_be_set_again() {
+    // Like in the example of the chapter: a value can be unset and set again, but not overwritten
+    let mut settings = existing();
+    assert_eq!(settings.unset("volume"), Some(5));
+
+    let conflicts = settings.merge(incoming(), MergePolicy::KeepExisting);
+    assert!(conflicts.is_empty());
+    assert_eq!(settings.get("volume"), Some(&10));
+
+    assert_eq!(settings.set("volume", 3), Some(10));
+    assert!(!settings.is_empty());
+    assert!(Settings::<i32>::new().is_empty());
+}
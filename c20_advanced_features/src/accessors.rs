@@ -0,0 +1,115 @@
+//! `#[derive(Getters)]` and `#[derive(Setters)]`: generate `field()` accessors and `set_field()` mutators.
+//!
+//! Getters return a reference to the field, or a copy with `#[getter(copy)]`. Fields marked with `#[getter(skip)]`
+//! or `#[setter(skip)]` don't get the method. The methods have the same visibility as the struct.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// The options of a `#[getter(...)]` or `#[setter(...)]` attribute on a field.
+#[derive(Default)]
+struct Options {
+    skip: bool,
+    copy: bool,
+}
+
+/// Reads the options of the attribute named `attribute`, accepting `copy` only if `allow_copy` is set.
+fn field_options(field: &syn::Field, attribute: &str, allow_copy: bool) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(attribute))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if allow_copy && meta.path.is_ident("copy") {
+                options.copy = true;
+            } else if allow_copy {
+                return Err(meta.error("expected `skip` or `copy`"));
+            } else {
+                return Err(meta.error("expected `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+/// Returns the named fields of the struct, or an error mentioning the derive.
+fn named_fields<'a>(
+    ast: &'a syn::DeriveInput,
+    derive: &str,
+) -> syn::Result<impl Iterator<Item = &'a syn::Field>> {
+    match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => Ok(fields.named.iter()),
+        _ => Err(syn::Error::new_spanned(
+            &ast.ident,
+            format!("`{derive}` can only be derived for structs with named fields"),
+        )),
+    }
+}
+
+pub fn impl_getters(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let vis = &ast.vis;
+    let mut getters = Vec::new();
+    for field in named_fields(ast, "Getters")? {
+        let options = field_options(field, "getter", true)?;
+        if options.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named fields have a name");
+        let ty = &field.ty;
+        getters.push(if options.copy {
+            quote! {
+                #vis fn #ident(&self) -> #ty {
+                    self.#ident
+                }
+            }
+        } else {
+            quote! {
+                #vis fn #ident(&self) -> &#ty {
+                    &self.#ident
+                }
+            }
+        });
+    }
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#getters)*
+        }
+    })
+}
+
+pub fn impl_setters(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let vis = &ast.vis;
+    let mut setters = Vec::new();
+    for field in named_fields(ast, "Setters")? {
+        if field_options(field, "setter", false)?.skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named fields have a name");
+        let setter = format_ident!("set_{}", ident);
+        let ty = &field.ty;
+        setters.push(quote! {
+            #vis fn #setter(&mut self, #ident: #ty) {
+                self.#ident = #ident;
+            }
+        });
+    }
+
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#setters)*
+        }
+    })
+}
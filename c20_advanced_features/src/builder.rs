@@ -0,0 +1,154 @@
+//! `#[derive(Builder)]`: generates a `FooBuilder` to create a `Foo` one field at a time.
+//!
+//! The builder has a setter for each field and a `build` method that checks every required field has been set.
+//! Like the `HelloMacro` trait, the error type is up to the user: `build` returns `Result<Foo, BuilderError>`,
+//! and the generated code creates it with `BuilderError::MissingField("field_name")`, so it must be in scope.
+//!
+//! * `Option<T>` fields are optional: their setter takes a `T`, and they are `None` if not set.
+//! * `#[builder(default)]` fields are optional too, and fall back to `Default::default()`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+/// A field of the struct and how the builder handles it.
+struct BuilderField<'a> {
+    ident: &'a syn::Ident,
+    /// The type the setter takes, which is `T` for an `Option<T>` field.
+    ty: &'a syn::Type,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    Required,
+    Optional,
+    Default,
+}
+
+pub fn impl_builder(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let builder = format_ident!("{}Builder", name);
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`Builder` can only be derived for structs with named fields",
+            ));
+        }
+    };
+    let fields = fields
+        .iter()
+        .map(builder_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let idents: Vec<_> = fields.iter().map(|field| field.ident).collect();
+    let types: Vec<_> = fields.iter().map(|field| field.ty).collect();
+    let values = fields.iter().map(|field| {
+        let ident = field.ident;
+        let missing = ident.to_string();
+        match field.kind {
+            FieldKind::Required => {
+                quote! { self.#ident.ok_or(BuilderError::MissingField(#missing))? }
+            }
+            FieldKind::Optional => quote! { self.#ident },
+            FieldKind::Default => quote! { self.#ident.unwrap_or_default() },
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let generics = &ast.generics;
+    let builder_doc = format!("Builder for [`{name}`], created with `{name}::builder()`.");
+    Ok(quote! {
+        #[doc = #builder_doc]
+        #vis struct #builder #generics #where_clause {
+            #(#idents: Option<#types>,)*
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Creates a builder with none of the fields set.
+            #vis fn builder() -> #builder #ty_generics {
+                #builder {
+                    #(#idents: None,)*
+                }
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            #(
+                #vis fn #idents(mut self, #idents: #types) -> Self {
+                    self.#idents = Some(#idents);
+                    self
+                }
+            )*
+
+            /// Creates the value, failing with the first required field that hasn't been set.
+            #vis fn build(self) -> Result<#name #ty_generics, BuilderError> {
+                Ok(#name {
+                    #(#idents: #values,)*
+                })
+            }
+        }
+    })
+}
+
+fn builder_field(field: &syn::Field) -> syn::Result<BuilderField<'_>> {
+    let ident = field.ident.as_ref().expect("named fields have a name");
+    let mut default = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("builder"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `default`"))
+            }
+        })?;
+    }
+
+    let field = match (default, option_inner(&field.ty)) {
+        (true, _) => BuilderField {
+            ident,
+            ty: &field.ty,
+            kind: FieldKind::Default,
+        },
+        (false, Some(inner)) => BuilderField {
+            ident,
+            ty: inner,
+            kind: FieldKind::Optional,
+        },
+        (false, None) => BuilderField {
+            ident,
+            ty: &field.ty,
+            kind: FieldKind::Required,
+        },
+    };
+    Ok(field)
+}
+
+/// Returns `T` if the type is written as `Option<T>`.
+///
+/// Macros only see tokens, so a type alias of `Option` isn't recognised, and is treated as a required field.
+fn option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(inner) if arguments.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
@@ -0,0 +1,60 @@
+//! `#[derive(Display)]`: implements `std::fmt::Display` from a format string naming the fields.
+//!
+//! `#[display("{headline}, by {author}")]` on a struct with named fields generates a `fmt` writing the string
+//! with the fields interpolated. Format specs work as in `format!`, such as `{price:.2}`, and `{{` escapes a brace.
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+
+use crate::template;
+
+pub fn impl_display(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`Display` can only be derived for structs with named fields",
+            ));
+        }
+    };
+
+    let mut template = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("display"))
+    {
+        if template.is_some() {
+            return Err(syn::Error::new_spanned(
+                attr,
+                "duplicate `#[display]` attribute",
+            ));
+        }
+        template = Some(attr.parse_args::<syn::LitStr>()?);
+    }
+    let Some(template) = template else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "missing `#[display(\"...\")]` attribute with the format string",
+        ));
+    };
+
+    let used = template::fields_in_template(&template, fields, name)?;
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    // The span of the format string is used for the generated code, so type errors such as a field
+    // without a `Display` implementation are reported on the attribute.
+    Ok(quote_spanned! { template.span() =>
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                let Self { #(#used,)* .. } = self;
+                write!(f, #template)
+            }
+        }
+    })
+}
@@ -0,0 +1,55 @@
+//! `#[derive(Draw)]`: implements the `Draw` trait of the `gui` module of chapter 18 from a template.
+//!
+//! `#[draw(template = "[{label}]")]` makes `draw` print the template with the fields interpolated,
+//! so simple widgets can be added to a `Screen` without writing the implementation.
+//! The `Draw` trait must be in scope, like `Summary` for its derive.
+
+use proc_macro2::TokenStream;
+use quote::quote_spanned;
+
+use crate::template;
+
+pub fn impl_draw(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let fields = match &ast.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                name,
+                "`Draw` can only be derived for structs with named fields",
+            ));
+        }
+    };
+
+    let mut template = None;
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident("draw")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("template") {
+                template = Some(meta.value()?.parse::<syn::LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `template = \"...\"`"))
+            }
+        })?;
+    }
+    let Some(template) = template else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "missing `#[draw(template = \"...\")]` attribute",
+        ));
+    };
+    let used = template::fields_in_template(&template, fields, name)?;
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote_spanned! { template.span() =>
+        impl #impl_generics Draw for #name #ty_generics #where_clause {
+            fn draw(&self) {
+                let Self { #(#used,)* .. } = self;
+                println!(#template);
+            }
+        }
+    })
+}
@@ -0,0 +1,40 @@
+//! `#[derive(FieldNames)]`: lists the names of the fields of a struct, such as the headers of a table.
+//!
+//! Generates an associated `FIELDS` constant and a `field_count` function. Tuple struct fields are named by
+//! their index, like `self.0`, and a unit struct has no fields.
+
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+pub fn impl_field_names(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let syn::Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`FieldNames` can only be derived for structs",
+        ));
+    };
+    // Each name keeps the span of its field, so the literals in the expansion point back at the declaration.
+    let names = data.fields.iter().enumerate().map(|(index, field)| {
+        let member = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        let lit = syn::LitStr::new(&member, field.span());
+        quote_spanned! { field.span() => #lit }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// The names of the fields, in declaration order.
+            pub const FIELDS: &'static [&'static str] = &[#(#names),*];
+
+            /// Returns the number of fields.
+            pub fn field_count() -> usize {
+                Self::FIELDS.len()
+            }
+        }
+    })
+}
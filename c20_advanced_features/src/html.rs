@@ -0,0 +1,233 @@
+//! `html!`: builds a tree of `gui` components from chapter 18 with an element syntax.
+//!
+//! ```ignore
+//! let screen = html! {
+//!     <screen>
+//!         <label text="Name" />
+//!         <container>
+//!             <button width=50 height=10 label="OK" />
+//!             <button width=50 height=10 label={cancel} />
+//!         </container>
+//!     </screen>
+//! };
+//! ```
+//!
+//! A `<screen>` expands to a `Screen`, any other element to a `Box<dyn Draw>`, so a single component can be built too.
+//! Attribute values are literals or Rust expressions between braces. Unknown elements and attributes,
+//! missing attributes and mismatched closing tags are reported while compiling, at the offending token.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Expr, Ident, Lit, Token, braced,
+    parse::{Parse, ParseStream},
+};
+
+/// The elements known by the macro, one for each component of the `gui` module.
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Screen,
+    Container,
+    Button,
+    Label,
+}
+
+impl Kind {
+    fn from_name(name: &Ident) -> syn::Result<Kind> {
+        match name.to_string().as_str() {
+            "screen" => Ok(Kind::Screen),
+            "container" => Ok(Kind::Container),
+            "button" => Ok(Kind::Button),
+            "label" => Ok(Kind::Label),
+            _ => Err(syn::Error::new_spanned(
+                name,
+                format!(
+                    "unknown element `<{name}>`, expected one of `<screen>`, `<container>`, `<button>`, `<label>`"
+                ),
+            )),
+        }
+    }
+
+    /// The attributes of the element, all of them are required.
+    fn attributes(self) -> &'static [&'static str] {
+        match self {
+            Kind::Screen | Kind::Container => &[],
+            Kind::Button => &["width", "height", "label"],
+            Kind::Label => &["text"],
+        }
+    }
+
+    fn has_children(self) -> bool {
+        matches!(self, Kind::Screen | Kind::Container)
+    }
+}
+
+/// An element with its attributes and children, such as `<button width=50 height=10 label="OK" />`.
+pub struct Element {
+    name: Ident,
+    kind: Kind,
+    attributes: Vec<(Ident, Expr)>,
+    children: Vec<Element>,
+}
+
+impl Element {
+    /// Returns the value of a required attribute, which has already been checked while parsing.
+    fn attribute(&self, name: &str) -> &Expr {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value)
+            .expect("required attributes are checked while parsing")
+    }
+}
+
+impl Parse for Element {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![<]>()?;
+        let name: Ident = input.parse()?;
+        let kind = Kind::from_name(&name)?;
+
+        let mut attributes: Vec<(Ident, Expr)> = Vec::new();
+        while !input.peek(Token![/]) && !input.peek(Token![>]) {
+            let attribute: Ident = input.parse()?;
+            if !kind.attributes().iter().any(|known| attribute == known) {
+                return Err(syn::Error::new_spanned(
+                    &attribute,
+                    format!("unknown attribute `{attribute}` for `<{name}>`"),
+                ));
+            }
+            if attributes.iter().any(|(seen, _)| seen == &attribute) {
+                return Err(syn::Error::new_spanned(
+                    &attribute,
+                    format!("duplicate attribute `{attribute}`"),
+                ));
+            }
+            input.parse::<Token![=]>()?;
+            let value = if input.peek(syn::token::Brace) {
+                let content;
+                braced!(content in input);
+                content.parse::<Expr>()?
+            } else {
+                let lit: Lit = input.parse()?;
+                syn::parse_quote!(#lit)
+            };
+            attributes.push((attribute, value));
+        }
+        for required in kind.attributes() {
+            if !attributes
+                .iter()
+                .any(|(attribute, _)| attribute == required)
+            {
+                return Err(syn::Error::new_spanned(
+                    &name,
+                    format!("missing attribute `{required}` for `<{name}>`"),
+                ));
+            }
+        }
+
+        let mut children = Vec::new();
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+        } else {
+            input.parse::<Token![>]>()?;
+            while !(input.peek(Token![<]) && input.peek2(Token![/])) {
+                if input.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        &name,
+                        format!("unclosed element `<{name}>`"),
+                    ));
+                }
+                let child: Element = input.parse()?;
+                if !kind.has_children() {
+                    return Err(syn::Error::new_spanned(
+                        &child.name,
+                        format!("`<{name}>` cannot have children"),
+                    ));
+                }
+                if child.kind == Kind::Screen {
+                    return Err(syn::Error::new_spanned(
+                        &child.name,
+                        "`<screen>` can only be the root element",
+                    ));
+                }
+                children.push(child);
+            }
+            input.parse::<Token![<]>()?;
+            input.parse::<Token![/]>()?;
+            let closing: Ident = input.parse()?;
+            if closing != name {
+                return Err(syn::Error::new_spanned(
+                    &closing,
+                    format!("closing tag `</{closing}>` does not match `<{name}>`"),
+                ));
+            }
+            input.parse::<Token![>]>()?;
+        }
+
+        Ok(Element {
+            name,
+            kind,
+            attributes,
+            children,
+        })
+    }
+}
+
+/// The input of the macro: a single root element.
+pub struct Html {
+    root: Element,
+}
+
+impl Parse for Html {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let root: Element = input.parse()?;
+        if !input.is_empty() {
+            return Err(
+                input.error("expected a single root element, wrap the elements in a `<container>`")
+            );
+        }
+        Ok(Html { root })
+    }
+}
+
+pub fn impl_html(html: Html) -> TokenStream {
+    expand(&html.root)
+}
+
+fn expand(element: &Element) -> TokenStream {
+    let gui = quote! { ::c18_object_oriented_programming::gui };
+    let children = element.children.iter().map(expand);
+    match element.kind {
+        Kind::Screen => quote! {
+            #gui::Screen {
+                components: ::std::vec![#(#children),*],
+            }
+        },
+        Kind::Container => quote! {
+            ::std::boxed::Box::new(#gui::Container {
+                children: ::std::vec![#(#children),*],
+            }) as ::std::boxed::Box<dyn #gui::Draw>
+        },
+        Kind::Button => {
+            let width = element.attribute("width");
+            let height = element.attribute("height");
+            let label = element.attribute("label");
+            quote! {
+                ::std::boxed::Box::new(#gui::Button {
+                    width: #width,
+                    height: #height,
+                    label: ::std::string::String::from(#label),
+                }) as ::std::boxed::Box<dyn #gui::Draw>
+            }
+        }
+        Kind::Label => {
+            let text = element.attribute("text");
+            quote! {
+                ::std::boxed::Box::new(#gui::Label {
+                    text: ::std::string::String::from(#text),
+                }) as ::std::boxed::Box<dyn #gui::Draw>
+            }
+        }
+    }
+}
@@ -1,37 +1,232 @@
 use proc_macro::TokenStream;
 use quote::quote;
+use syn::{DeriveInput, parse_macro_input};
 
-#[proc_macro_derive(HelloMacro)]
+mod accessors;
+mod builder;
+mod display;
+mod draw;
+mod field_names;
+mod html;
+mod route;
+mod sql;
+mod summary;
+mod template;
+
+// The `attributes(hello_macro)` part declares `#[hello_macro]` as an inert helper attribute of the derive,
+// so the compiler accepts it on the annotated type and leaves it to the macro to read it.
+#[proc_macro_derive(HelloMacro, attributes(hello_macro))]
 pub fn hello_macro_derive(input: TokenStream) -> TokenStream {
     // Construct a representation of Rust code as a syntax tree that we can manipulate.
-    // Use unwrap to get a `TokenStream` instead of `Result`, which is not compliant with the macro API.
-    let ast = syn::parse(input).unwrap();
+    // The `parse_macro_input!` macro returns early with a compile error pointing at the offending tokens if parsing fails,
+    // since the macro API requires a `TokenStream` instead of `Result`, and panicking would only show a proc-macro backtrace.
+    let ast = parse_macro_input!(input as DeriveInput);
 
     // Build the trait implementation.
+    // The errors found while generating the code are turned into `compile_error!` invocations with the span of the code causing them,
+    // so the compiler underlines the user's code like for any other error.
     impl_hello_macro(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
-fn impl_hello_macro(ast: &syn::DeriveInput) -> TokenStream {
+fn impl_hello_macro(ast: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     // Get an `Ident` struct instance containing the name (identifier) of the annotated type using `ast.ident`
     // In this example, when the `impl_hello_macro` function is called, the `ident` will have the field with value `"Pancakes"`.
     // The `name` variable will contain an `Ident` struct such that, when printed, will be the string `"Pancakes"`: the name of the struct.
     let name = &ast.ident;
+    // The greeting can be customised with `#[hello_macro(message = "Howdy from {}!")]`.
+    // If the attribute is malformed, the `?` returns the error pointing at the attribute.
+    let template = hello_macro_message(ast)?;
+    let greeting = match &template {
+        // The `{}` in the template is replaced with the type name while expanding, so the generated code prints a plain string literal.
+        Some(template) => print_template(template, &name.to_string()),
+        // The `stringify!` macro used here is built into Rust, and it takes a Rust expression, and converts it into a string literal. (`1 + 2` becomes `"1 + 2"`)
+        // This is different from `format!` and `println!` macros, which evaluate the expression and turn the result into `String`
+        // there is the possibility that `#name` input might be an expression to print literally.
+        // using `stringify!` also saves an allocation by converting `#name` to  astring literal at compile time.
+        None => quote! { println!("Hello, Macro! My name is {}!", stringify!(#name)); },
+    };
     // The `quote!` macro let's defining the Rust code that will return.
     // The compiler expects something different to the direct result of the `quote!` macro's execution, so it needs to be converted to `TokenStream`
-    // This is done by calling the `into` method, that consumes the intermediate representation and returns the value of the required `TokenStream` type.
+    // This is done by calling the `into` method in `hello_macro_derive`, that consumes the intermediate representation and returns the value of the required `TokenStream` type.
     // The `qupte!` macro also provides some templating mechanics: such as entering `#name`, and `quote!` will replace it with the calue in the variable `name`.
+    // A generic type such as `Wrapper<T: Display>` needs its generics repeated in the implementation: `impl<T: Display> HelloMacro for Wrapper<T>`.
+    // `split_for_impl` splits them in the three places they go: the parameters with their bounds after `impl`,
+    // the parameters alone after the type name, and the `where` clause, if any.
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
     let generated = quote! {
         // The procedural macro needs to generate an implementation of the `HelloMacro` trait for the type the user annotated, and can be get by using `#name`.
         // The trait implementation has the one funciton `hello_macro`, whose body contains the functionality to provide: printing `Hello, Macro! My name is`, and the name of the annotated type.
-        impl HelloMacro for #name {
+        impl #impl_generics HelloMacro for #name #ty_generics #where_clause {
             fn hello_macro() {
-                // The `stringify!` macro used here is built into Rust, and it takes a Rust expression, and converts it into a string literal. (`1 + 2` becomes `"1 + 2"`)
-                // This is different from `format!` and `println!` macros, which evaluate the expression and turn the result into `String`
-                // there is the possibility that `#name` input might be an expression to print literally.
-                // using `stringify!` also saves an allocation by converting `#name` to  astring literal at compile time.
-                println!("Hello, Macro! My name is {}!", stringify!(#name));
+                #greeting
+            }
+        }
+    };
+    // Enums also get a `hello_variant` method greeting with the name of the variant of a value, such as `Pancakes::Fluffy`.
+    // It takes `&self` to know the variant, so it's an inherent method rather than part of the `HelloMacro` trait,
+    // whose `hello_macro` is an associated function without `self`.
+    let hello_variant = match &ast.data {
+        syn::Data::Enum(data) => {
+            let arms = data.variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                // The pattern depends on the kind of fields of the variant, none of them are needed to greet.
+                let pattern = match &variant.fields {
+                    syn::Fields::Unit => quote! { Self::#ident },
+                    syn::Fields::Unnamed(_) => quote! { Self::#ident(..) },
+                    syn::Fields::Named(_) => quote! { Self::#ident { .. } },
+                };
+                let subject = format!("{name}::{ident}");
+                let greeting = match &template {
+                    Some(template) => print_template(template, &subject),
+                    None => quote! { println!("Hello, Macro! My name is {}!", #subject); },
+                };
+                quote! { #pattern => { #greeting } }
+            });
+            quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    pub fn hello_variant(&self) {
+                        // Matching on `*self` rather than `self` also works for enums without variants.
+                        match *self {
+                            #(#arms)*
+                        }
+                    }
+                }
             }
         }
+        _ => quote! {},
     };
-    generated.into()
+    Ok(quote! {
+        #generated
+        #hello_variant
+    })
+}
+
+/// Generates the code printing a greeting template, with `{}` replaced by `subject`.
+fn print_template(template: &syn::LitStr, subject: &str) -> proc_macro2::TokenStream {
+    let text = template.value().replace("{}", subject);
+    // The literal keeps the span of the template, so errors in the generated code point at the attribute.
+    let literal = syn::LitStr::new(&text, template.span());
+    quote! { println!("{}", #literal); }
+}
+
+/// Reads the `message` template of the `#[hello_macro(...)]` attribute, if there is one.
+fn hello_macro_message(ast: &DeriveInput) -> syn::Result<Option<syn::LitStr>> {
+    let mut message = None;
+    for attr in ast
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("hello_macro"))
+    {
+        // `parse_nested_meta` walks the comma separated `key = value` pairs inside the parentheses.
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("message") {
+                message = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported hello_macro attribute, expected `message = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(message)
+}
+
+// A derive macro for a trait defined in another crate: `Summary` from chapter 10.
+// The fields to use are marked with the `#[summary(author)]` and `#[summary(content)]` helper attributes.
+#[proc_macro_derive(Summary, attributes(summary))]
+pub fn summary_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    // Mistakes in the annotations are reported as compile errors on the offending code.
+    summary::impl_summary(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// The builder pattern without the boilerplate: a `FooBuilder` with a setter per field, and a checked `build`.
+#[proc_macro_derive(Builder, attributes(builder))]
+pub fn builder_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    builder::impl_builder(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// An attribute-like macro, like the `route` sketched in `main.rs`: `attr` contains `GET, "/"`, and `item` the annotated function.
+#[proc_macro_attribute]
+pub fn route(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as route::RouteArgs);
+    let handler = parse_macro_input!(item as syn::ItemFn);
+
+    route::impl_route(args, handler)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// A function-like macro, like the `sql!` sketched in `main.rs`: the tokens between the parentheses are parsed as a query.
+// Since `Select` implements `syn::parse::Parse`, syntax errors are reported at the offending token like the other macros.
+#[proc_macro]
+pub fn sql(input: TokenStream) -> TokenStream {
+    let select = parse_macro_input!(input as sql::Select);
+
+    sql::impl_sql(select).into()
+}
+
+// Another function-like macro, with a syntax closer to HTML: the elements become the components of the `gui` module of chapter 18.
+#[proc_macro]
+pub fn html(input: TokenStream) -> TokenStream {
+    let html = parse_macro_input!(input as html::Html);
+
+    html::impl_html(html).into()
+}
+
+// Two derives generating inherent methods, configured per field with the `#[getter]` and `#[setter]` helper attributes.
+#[proc_macro_derive(Getters, attributes(getter))]
+pub fn getters_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    accessors::impl_getters(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[proc_macro_derive(Setters, attributes(setter))]
+pub fn setters_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    accessors::impl_setters(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// A derive for a standard library trait: `Display`, from the format string in `#[display("...")]`.
+#[proc_macro_derive(Display, attributes(display))]
+pub fn display_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    display::impl_display(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// Connects the macros to the trait objects of chapter 18: simple widgets get their `Draw` implementation from a template.
+#[proc_macro_derive(Draw, attributes(draw))]
+pub fn draw_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    draw::impl_draw(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+// A derive generating associated items instead of a trait implementation, with the same span handling as the others.
+#[proc_macro_derive(FieldNames)]
+pub fn field_names_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    field_names::impl_field_names(&ast)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
@@ -962,9 +962,12 @@ fn macros() {
         // The fields of this struct shows that the parsed Rust code  is a unit struct with the `ident` (identifier) of `Pancakes`, but there are more fields.
         // in the `impl_hello_macro` function there is the Rust code that needs to be included, but before the output of `derive` is also a `TokenStream`
         // The returned `TokenStream` is added to the code written by the user so, when the crate is compiled, it will contain the extra funcitonality provided in the modified `TokenStream`.
-        // The `unwrap` function is used to cause the `hello_macro_derive` funciton to panic if the call `syn::parse` fails.
-        // It's necessary for the procedural macro to panic on errors because `proc_macro_derive` functions must return `TokenStream` rather than `Result` to conform to the procedural macro API.
-        // For this reason it has been simplified by using `unwrap;`, in produciton code a more specific error message should be provided by using `panic!` or `expect`.
+        // The book uses `unwrap` to cause the `hello_macro_derive` funciton to panic if the call `syn::parse` fails.
+        // `proc_macro_derive` functions must return `TokenStream` rather than `Result` to conform to the procedural macro API, so errors can't be returned with `?`.
+        // Panicking works, but the user gets a proc-macro panic message without any indication of where the problem is in their code.
+        // Instead, `syn::parse_macro_input!` returns early with a `TokenStream` containing a `compile_error!` when parsing fails.
+        // The same is done for the errors found while generating the code: `syn::Error` carries the span of the offending tokens,
+        // and `to_compile_error` turns it into a `compile_error!` that the compiler reports on the user's code like any other error.
         // At this point `cargo build` should complete successfully in both `hello_macro`, and `hello_macro_derive`
         // Runnoing the following code, it successfully prints: `Hello, Macro! My name is Pancakes!` by using the implementeation of the `HelloMacro` trait from the procedural macro.
         use c20_advanced_features::HelloMacro;
@@ -974,6 +977,23 @@ fn macros() {
 
         Pancakes::hello_macro();
         // The `#[derive(HelloMacro)]`  added to the trait implementation allows to use the macro on `Pancakes`
+        // A derive macro can also declare helper attributes, that it reads from the syntax tree to customise the generated code.
+        // The `#[hello_macro(message = "...")]` attribute replaces the greeting, with `{}` substituted by the type name at compile time.
+        // This prints `Howdy from Waffles!`
+        #[derive(HelloMacro)]
+        #[hello_macro(message = "Howdy from {}!")]
+        struct Waffles;
+
+        Waffles::hello_macro();
+        // The macro can inspect the whole syntax tree: for enums `ast.data` is `syn::Data::Enum`, with the list of variants.
+        // Enums get an additional `hello_variant` method that matches on the value, and prints `Hello, Macro! My name is Breakfast::Eggs!`
+        #[derive(HelloMacro)]
+        enum Breakfast {
+            _Toast,
+            Eggs,
+        }
+
+        Breakfast::Eggs.hello_variant();
     }
     {
         // Attribute-Like macros
@@ -989,6 +1009,11 @@ fn macros() {
         // - The contest of attribute: the `GET, "/"` part
         // - The body of the attribute is attached too: in this case `fn index {}`, and the resto of the function's body.
         // Other than that, attribute-like macros work the same way as custom `derive` macros: a crate `proc-macro`crate type can be created aimplementing a function that generates the wanted code.
+        // The `route` attribute is implemented in lib.rs for the router of the web server of chapter 21:
+        // it leaves the function as it is, and generates a `register_index` function that adds it to a `c21_web_server::router::Router`.
+        // #[route(GET, "/")]
+        // fn index(request: &Request) -> Response { Response::ok("Hello!") }
+        // router.register(register_index);
     }
     {
         // Function-Like macros
@@ -1003,5 +1028,7 @@ fn macros() {
         // #[proc_macro]
         // pub fn sql(input: TokenStream) -> TokenStream {}
         // The deifnition is similar to the custom `derive` macro's signature: the tokens inside the parentheses are received, and the generated code is returned.
+        // lib.rs implements it for a small subset of SQL: the tokens are parsed with `syn` into a `Select`, and syntax errors are reported with their span.
+        // The query is expanded into a `Query` struct literal, so `sql!(SELECT * FROM posts WHERE id = 1)` costs nothing at runtime.
     }
 }
@@ -0,0 +1,73 @@
+//! `#[route(GET, "/path")]`: registers a handler function with the router of `c21_web_server`.
+//!
+//! The function is left as it is, and a `register_<name>` function is generated next to it,
+//! that adds the route to a `c21_web_server::router::Router`, usually through `Router::register`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    Ident, ItemFn, LitStr, Token,
+    parse::{Parse, ParseStream},
+};
+
+/// The arguments of the attribute: the method and the path, separated by a comma.
+pub struct RouteArgs {
+    method: Ident,
+    path: LitStr,
+}
+
+impl Parse for RouteArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let method: Ident = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path: LitStr = input.parse()?;
+        // A trailing comma is allowed, anything else is an error pointing at the extra tokens.
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        if !input.is_empty() {
+            return Err(input.error("expected `#[route(METHOD, \"/path\")]`"));
+        }
+        Ok(RouteArgs { method, path })
+    }
+}
+
+pub fn impl_route(args: RouteArgs, handler: ItemFn) -> syn::Result<TokenStream> {
+    // The method is checked while compiling, so a typo is reported on the attribute instead of at runtime.
+    let method = match args.method.to_string().as_str() {
+        "GET" => quote! { Get },
+        "POST" => quote! { Post },
+        "PUT" => quote! { Put },
+        "DELETE" => quote! { Delete },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &args.method,
+                "unknown method, expected one of GET, POST, PUT, DELETE",
+            ));
+        }
+    };
+    let path = &args.path;
+    if !path.value().starts_with('/') {
+        return Err(syn::Error::new_spanned(
+            path,
+            "the path must start with `/`",
+        ));
+    }
+
+    let name = &handler.sig.ident;
+    let vis = &handler.vis;
+    let register = format_ident!("register_{}", name);
+    let doc = format!(
+        "Registers `{name}` as the handler of `{} {}`.",
+        args.method,
+        path.value()
+    );
+    Ok(quote! {
+        #handler
+
+        #[doc = #doc]
+        #vis fn #register(router: &mut ::c21_web_server::router::Router) {
+            router.route(::c21_web_server::router::Method::#method, #path, #name);
+        }
+    })
+}
@@ -0,0 +1,196 @@
+//! `sql!`: parses a small subset of SQL at compile time, and expands into a `Query` value.
+//!
+//! The supported statements look like `SELECT id, title FROM posts WHERE id = 1 AND draft = false LIMIT 10`:
+//! either `*` or a list of columns, a table, optional conditions joined by `AND`, and an optional limit.
+//! Keywords are case insensitive, values are integer, string, or boolean literals.
+//!
+//! Like the `HelloMacro` trait, the types are defined by the user, and must be in scope where the macro is called:
+//!
+//! ```text
+//! struct Query {
+//!     columns: &'static [&'static str], // empty for `*`
+//!     table: &'static str,
+//!     conditions: &'static [Condition],
+//!     limit: Option<u64>,
+//! }
+//! struct Condition { column: &'static str, operator: &'static str, value: Value }
+//! enum Value { Int(i64), Str(&'static str), Bool(bool) }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    Ident, Lit, Token,
+    parse::{Parse, ParseStream},
+};
+
+pub struct Select {
+    columns: Vec<Ident>,
+    table: Ident,
+    conditions: Vec<Condition>,
+    limit: Option<u64>,
+}
+
+struct Condition {
+    column: Ident,
+    operator: &'static str,
+    value: Lit,
+}
+
+/// Parses the next token as the given keyword, in any case.
+fn keyword(input: ParseStream, keyword: &str) -> syn::Result<()> {
+    input.step(|cursor| match cursor.ident() {
+        Some((ident, rest)) if ident.to_string().eq_ignore_ascii_case(keyword) => Ok(((), rest)),
+        _ => Err(cursor.error(format!("expected `{keyword}`"))),
+    })
+}
+
+/// Checks whether the next token is the given keyword, without consuming it.
+fn peek_keyword(input: ParseStream, keyword: &str) -> bool {
+    input
+        .cursor()
+        .ident()
+        .is_some_and(|(ident, _)| ident.to_string().eq_ignore_ascii_case(keyword))
+}
+
+/// Parses a column or table name, rejecting the keywords so a missing name isn't silently taken from them.
+fn name(input: ParseStream) -> syn::Result<Ident> {
+    const KEYWORDS: [&str; 5] = ["SELECT", "FROM", "WHERE", "AND", "LIMIT"];
+    let ident: Ident = input.parse()?;
+    if KEYWORDS
+        .iter()
+        .any(|keyword| ident.to_string().eq_ignore_ascii_case(keyword))
+    {
+        return Err(syn::Error::new(
+            ident.span(),
+            "expected a name, found a keyword",
+        ));
+    }
+    Ok(ident)
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let column = name(input)?;
+        // The two characters operators are checked first, otherwise `<=` would be parsed as `<`.
+        let operator = if input.peek(Token![!=]) {
+            input.parse::<Token![!=]>()?;
+            "!="
+        } else if input.peek(Token![<=]) {
+            input.parse::<Token![<=]>()?;
+            "<="
+        } else if input.peek(Token![>=]) {
+            input.parse::<Token![>=]>()?;
+            ">="
+        } else if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            "="
+        } else if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            "<"
+        } else if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            ">"
+        } else {
+            return Err(input.error("expected one of `=`, `!=`, `<`, `<=`, `>`, `>=`"));
+        };
+        let value: Lit = input.parse()?;
+        match &value {
+            Lit::Int(_) | Lit::Str(_) | Lit::Bool(_) => {}
+            _ => {
+                return Err(syn::Error::new(
+                    value.span(),
+                    "expected an integer, string, or boolean value",
+                ));
+            }
+        }
+        Ok(Condition {
+            column,
+            operator,
+            value,
+        })
+    }
+}
+
+impl Parse for Select {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        keyword(input, "SELECT")?;
+        let mut columns = Vec::new();
+        if input.peek(Token![*]) {
+            input.parse::<Token![*]>()?;
+        } else {
+            loop {
+                columns.push(name(input)?);
+                if !input.peek(Token![,]) {
+                    break;
+                }
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        keyword(input, "FROM")?;
+        let table = name(input)?;
+
+        let mut conditions = Vec::new();
+        if peek_keyword(input, "WHERE") {
+            keyword(input, "WHERE")?;
+            conditions.push(input.parse()?);
+            while peek_keyword(input, "AND") {
+                keyword(input, "AND")?;
+                conditions.push(input.parse()?);
+            }
+        }
+
+        let mut limit = None;
+        if peek_keyword(input, "LIMIT") {
+            keyword(input, "LIMIT")?;
+            limit = Some(input.parse::<syn::LitInt>()?.base10_parse()?);
+        }
+
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the end of the query"));
+        }
+        Ok(Select {
+            columns,
+            table,
+            conditions,
+            limit,
+        })
+    }
+}
+
+pub fn impl_sql(select: Select) -> TokenStream {
+    let columns = select.columns.iter().map(|column| column.to_string());
+    let table = select.table.to_string();
+    let conditions = select.conditions.iter().map(|condition| {
+        let column = condition.column.to_string();
+        let operator = condition.operator;
+        // The type of the value is known while compiling, so it's expanded into the matching variant.
+        let value = match &condition.value {
+            Lit::Int(int) => quote! { Value::Int(#int) },
+            Lit::Str(string) => quote! { Value::Str(#string) },
+            Lit::Bool(boolean) => quote! { Value::Bool(#boolean) },
+            _ => unreachable!("only integer, string, and boolean values are parsed"),
+        };
+        quote! {
+            Condition {
+                column: #column,
+                operator: #operator,
+                value: #value,
+            }
+        }
+    });
+    let limit = match select.limit {
+        Some(limit) => quote! { Some(#limit) },
+        None => quote! { None },
+    };
+
+    quote! {
+        Query {
+            columns: &[#(#columns),*],
+            table: #table,
+            conditions: &[#(#conditions),*],
+            limit: #limit,
+        }
+    }
+}
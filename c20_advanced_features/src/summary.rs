@@ -0,0 +1,73 @@
+//! `#[derive(Summary)]`: implements the `Summary` trait of chapter 10 from marked fields.
+//!
+//! The field marked with `#[summary(author)]` is returned by `summarise_author`, and is required.
+//! The field marked with `#[summary(content)]`, if any, makes `summarise` print `author: content` like `Tweet` does,
+//! otherwise the default implementation of the trait is used.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+pub fn impl_summary(ast: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &ast.ident;
+    let syn::Data::Struct(data) = &ast.data else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "`Summary` can only be derived for structs",
+        ));
+    };
+
+    let mut author = None;
+    let mut content = None;
+    for (index, field) in data.fields.iter().enumerate() {
+        // Named fields are accessed by name, tuple struct fields by position: `self.0`
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(index)),
+        };
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("summary"))
+        {
+            attr.parse_nested_meta(|meta| {
+                let slot = if meta.path.is_ident("author") {
+                    &mut author
+                } else if meta.path.is_ident("content") {
+                    &mut content
+                } else {
+                    return Err(meta.error("expected `author` or `content`"));
+                };
+                if slot.is_some() {
+                    return Err(meta.error("only one field can be marked with this attribute"));
+                }
+                *slot = Some(member.clone());
+                Ok(())
+            })?;
+        }
+    }
+
+    let Some(author) = author else {
+        return Err(syn::Error::new_spanned(
+            name,
+            "missing `#[summary(author)]` on one of the fields",
+        ));
+    };
+    let summarise = content.map(|content| {
+        quote! {
+            fn summarise(&self) -> String {
+                format!("{}: {}", self.summarise_author(), self.#content)
+            }
+        }
+    });
+
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics Summary for #name #ty_generics #where_clause {
+            #summarise
+
+            fn summarise_author(&self) -> String {
+                self.#author.to_string()
+            }
+        }
+    })
+}
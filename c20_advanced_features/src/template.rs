@@ -0,0 +1,66 @@
+//! Format strings naming the fields of a struct, shared by the `Display` and `Draw` derives.
+//!
+//! The generated code binds the fields used by the template to variables with the same name,
+//! so the format string can capture them like in `format!("{x}")`: `let Self { label, .. } = self;`.
+
+use quote::format_ident;
+
+/// Checks that the placeholders of the template are fields of the struct, and returns them without duplicates.
+pub fn fields_in_template(
+    template: &syn::LitStr,
+    fields: &syn::punctuated::Punctuated<syn::Field, syn::Token![,]>,
+    name: &syn::Ident,
+) -> syn::Result<Vec<syn::Ident>> {
+    let mut used = Vec::new();
+    for placeholder in placeholders(&template.value()) {
+        if placeholder.is_empty() || placeholder.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(syn::Error::new_spanned(
+                template,
+                format!(
+                    "positional placeholder `{{{placeholder}}}`, name a field instead, such as `{{field}}`"
+                ),
+            ));
+        }
+        if !fields.iter().any(|field| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == &placeholder)
+        }) {
+            return Err(syn::Error::new_spanned(
+                template,
+                format!(
+                    "`{placeholder}` is not a field of `{name}`, only named fields can be used"
+                ),
+            ));
+        }
+        if !used.contains(&placeholder) {
+            used.push(placeholder);
+        }
+    }
+    Ok(used
+        .iter()
+        .map(|field| format_ident!("{}", field))
+        .collect())
+}
+
+/// Returns the names between braces in a format string, skipping the escaped `{{` and the format specs after `:`.
+///
+/// An empty or positional placeholder, such as `{}` or `{0}`, is returned as it is, to be reported by the caller.
+fn placeholders(template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '{' {
+            continue;
+        }
+        if chars.peek() == Some(&'{') {
+            chars.next();
+            continue;
+        }
+        let placeholder: String = chars.by_ref().take_while(|&c| c != '}').collect();
+        let name = placeholder.split(':').next().unwrap_or_default().trim();
+        names.push(name.to_string());
+    }
+    names
+}
@@ -0,0 +1,66 @@
+use c20_advanced_features::{Getters, Setters};
+
+#[derive(Getters, Setters)]
+pub struct Account {
+    name: String,
+    #[getter(copy)]
+    balance: u64,
+    #[getter(skip)]
+    #[setter(skip)]
+    password: String,
+}
+
+impl Account {
+    fn check(&self, password: &str) -> bool {
+        self.password == password
+    }
+}
+
+#[derive(Getters, Setters)]
+struct Labelled<T> {
+    label: &'static str,
+    value: T,
+}
+
+#[test]
+fn getters_borrow_or_copy() {
+    let account = Account {
+        name: String::from("ferris"),
+        balance: 100,
+        password: String::from("crab"),
+    };
+
+    let name: &String = account.name();
+    let balance: u64 = account.balance();
+    assert_eq!("ferris", name);
+    assert_eq!(100, balance);
+    assert!(account.check("crab"));
+}
+
+#[test]
+fn setters_replace_the_field() {
+    let mut account = Account {
+        name: String::from("ferris"),
+        balance: 100,
+        password: String::from("crab"),
+    };
+
+    account.set_name(String::from("corro"));
+    account.set_balance(42);
+
+    assert_eq!("corro", account.name());
+    assert_eq!(42, account.balance());
+}
+
+#[test]
+fn works_with_generics() {
+    let mut labelled = Labelled {
+        label: "answer",
+        value: vec![4, 2],
+    };
+
+    labelled.set_value(vec![42]);
+
+    assert_eq!(&"answer", labelled.label());
+    assert_eq!(&vec![42], labelled.value());
+}
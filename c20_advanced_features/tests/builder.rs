@@ -0,0 +1,68 @@
+use c20_advanced_features::Builder;
+
+// The error type of `build` is defined by the user of the derive.
+#[derive(Debug, PartialEq)]
+enum BuilderError {
+    MissingField(&'static str),
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Command {
+    executable: String,
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default)]
+    retries: u32,
+}
+
+#[derive(Debug, PartialEq, Builder)]
+struct Pair<T: Clone> {
+    left: T,
+    right: T,
+}
+
+#[test]
+fn builds_with_every_field() {
+    let command = Command::builder()
+        .executable(String::from("cargo"))
+        .args(vec![String::from("build")])
+        .current_dir(String::from(".."))
+        .retries(2)
+        .build();
+
+    assert_eq!(
+        Ok(Command {
+            executable: String::from("cargo"),
+            args: vec![String::from("build")],
+            current_dir: Some(String::from("..")),
+            retries: 2,
+        }),
+        command
+    );
+}
+
+#[test]
+fn optional_fields_can_be_skipped() {
+    let command = Command::builder()
+        .executable(String::from("ls"))
+        .args(Vec::new())
+        .build()
+        .unwrap();
+
+    assert_eq!(None, command.current_dir);
+    assert_eq!(0, command.retries);
+}
+
+#[test]
+fn required_fields_are_checked() {
+    let command = Command::builder().executable(String::from("ls")).build();
+
+    assert_eq!(Err(BuilderError::MissingField("args")), command);
+}
+
+#[test]
+fn works_with_generics() {
+    let pair = Pair::builder().left(1).right(2).build();
+
+    assert_eq!(Ok(Pair { left: 1, right: 2 }), pair);
+}
@@ -0,0 +1,7 @@
+// Each file in `tests/ui` must fail to compile with the error message saved in the `.stderr` file next to it.
+// After changing an error message, run `TRYBUILD=overwrite cargo test -p c20_advanced_features` to update them.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,46 @@
+use c20_advanced_features::Display;
+
+// The same shape as the `NewsArticle` of the aggregator in chapter 10.
+#[derive(Display)]
+#[display("{headline}, by {author} ({location})")]
+struct NewsArticle {
+    headline: String,
+    location: String,
+    author: String,
+    #[allow(dead_code)]
+    content: String,
+}
+
+#[derive(Display)]
+#[display("[{label}] costs {price:.2}, {{{label}}}")]
+struct Item<'a, T: std::fmt::Display> {
+    label: &'a str,
+    price: T,
+}
+
+#[test]
+fn interpolates_the_fields() {
+    let article = NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+        content: String::from(
+            "The Pittsburgh Penguins once again are the best hockey team in the NHL.",
+        ),
+    };
+
+    assert_eq!(
+        "Penguins win the Stanley Cup Championship!, by Iceburgh (Pittsburgh, PA, USA)",
+        article.to_string()
+    );
+}
+
+#[test]
+fn supports_format_specs_and_generics() {
+    let item = Item {
+        label: "tea",
+        price: 2.5,
+    };
+
+    assert_eq!("[tea] costs 2.50, {tea}", item.to_string());
+}
@@ -0,0 +1,50 @@
+use c18_object_oriented_programming::gui::{Button, Draw, Screen};
+use c20_advanced_features::Draw;
+
+#[derive(Draw)]
+#[draw(template = "[{label}]")]
+struct Label {
+    label: String,
+}
+
+#[derive(Draw)]
+#[draw(template = "<{selected}> of {options:?} ({width}x{height})")]
+struct SelectBox {
+    width: u32,
+    height: u32,
+    options: Vec<String>,
+    selected: usize,
+}
+
+#[test]
+fn derived_widgets_are_components() {
+    let screen = Screen {
+        components: vec![
+            Box::new(Label {
+                label: String::from("Name"),
+            }),
+            Box::new(SelectBox {
+                width: 75,
+                height: 10,
+                options: vec![String::from("Yes"), String::from("No")],
+                selected: 0,
+            }),
+            Box::new(Button {
+                width: 50,
+                height: 10,
+                label: String::from("OK"),
+            }),
+        ],
+    };
+
+    screen.run();
+}
+
+#[test]
+fn draw_can_be_called_directly() {
+    let label = Label {
+        label: String::from("Hello"),
+    };
+
+    label.draw();
+}
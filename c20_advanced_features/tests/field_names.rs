@@ -0,0 +1,47 @@
+use c20_advanced_features::FieldNames;
+
+#[derive(FieldNames)]
+struct Employee {
+    _name: String,
+    _department: String,
+    _salary: u32,
+}
+
+#[derive(FieldNames)]
+struct Point(i32, i32);
+
+#[derive(FieldNames)]
+struct Marker;
+
+#[derive(FieldNames)]
+struct Wrapper<T: Clone> {
+    _value: T,
+}
+
+#[test]
+fn named_fields_in_declaration_order() {
+    assert_eq!(Employee::FIELDS, &["_name", "_department", "_salary"]);
+    assert_eq!(Employee::field_count(), 3);
+}
+
+#[test]
+fn tuple_fields_are_named_by_index() {
+    let point = Point(1, 2);
+
+    assert_eq!(point.0 + point.1, 3);
+    assert_eq!(Point::FIELDS, &["0", "1"]);
+    assert_eq!(Point::field_count(), 2);
+}
+
+#[test]
+fn unit_struct_has_no_fields() {
+    let _ = Marker;
+
+    assert!(Marker::FIELDS.is_empty());
+    assert_eq!(Marker::field_count(), 0);
+}
+
+#[test]
+fn generic_struct() {
+    assert_eq!(Wrapper::<String>::FIELDS, &["_value"]);
+}
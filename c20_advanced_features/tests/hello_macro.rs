@@ -0,0 +1,88 @@
+use c20_advanced_features::HelloMacro;
+
+// The trait lives with the user of the derive, the generated implementation refers to it by name.
+trait HelloMacro {
+    fn hello_macro();
+}
+
+#[derive(HelloMacro)]
+struct Pancakes;
+
+#[derive(HelloMacro)]
+#[hello_macro(message = "Howdy from {}!")]
+struct Waffles;
+
+#[derive(HelloMacro)]
+#[hello_macro(message = "No name here")]
+struct Crepes;
+
+#[derive(HelloMacro)]
+#[allow(dead_code)]
+struct Wrapper<T: std::fmt::Display>(T);
+
+#[derive(HelloMacro)]
+#[allow(dead_code)]
+struct Borrowed<'a> {
+    name: &'a str,
+}
+
+#[derive(HelloMacro)]
+#[hello_macro(message = "{} has everything")]
+#[allow(dead_code)]
+struct Everything<'a, T, const N: usize>
+where
+    T: Clone + 'a,
+{
+    items: [&'a T; N],
+}
+
+#[derive(HelloMacro)]
+#[allow(dead_code)]
+enum Breakfast {
+    Toast,
+    Eggs(u8),
+    Pancakes { syrup: bool },
+}
+
+#[derive(HelloMacro)]
+#[hello_macro(message = "Bonjour from {}")]
+#[allow(dead_code)]
+enum Drink<T> {
+    Coffee(T),
+}
+
+#[derive(HelloMacro)]
+#[allow(dead_code)]
+enum Nothing {}
+
+#[test]
+fn default_greeting() {
+    Pancakes::hello_macro();
+}
+
+#[test]
+fn custom_greeting() {
+    Waffles::hello_macro();
+    Crepes::hello_macro();
+}
+
+#[test]
+fn generic_types() {
+    Wrapper::<i32>::hello_macro();
+    Borrowed::hello_macro();
+    Everything::<String, 3>::hello_macro();
+}
+
+#[test]
+fn enum_variants() {
+    Breakfast::hello_macro();
+    for breakfast in [
+        Breakfast::Toast,
+        Breakfast::Eggs(2),
+        Breakfast::Pancakes { syrup: true },
+    ] {
+        breakfast.hello_variant();
+    }
+    Drink::Coffee("espresso").hello_variant();
+    Nothing::hello_macro();
+}
@@ -0,0 +1,34 @@
+use c20_advanced_features::html;
+
+#[test]
+fn screen_holds_the_root_components() {
+    let cancel = String::from("Cancel");
+    let screen = html! {
+        <screen>
+            <label text="Name" />
+            <container>
+                <button width=50 height=10 label="OK" />
+                <button width={25 * 2} height=10 label={cancel.as_str()} />
+            </container>
+        </screen>
+    };
+
+    assert_eq!(screen.components.len(), 2);
+    screen.run();
+}
+
+#[test]
+fn single_component_is_boxed() {
+    let mut components = vec![html! { <label text="Title" /> }];
+    components.push(html! {
+        <container>
+            <label text="Nested" />
+            <container></container>
+        </container>
+    });
+
+    assert_eq!(components.len(), 2);
+    for component in components.iter() {
+        component.draw();
+    }
+}
@@ -0,0 +1,80 @@
+use c20_advanced_features::sql;
+
+// The types the `sql!` macro expands into are defined by its user.
+#[derive(Debug, PartialEq)]
+struct Query {
+    columns: &'static [&'static str],
+    table: &'static str,
+    conditions: &'static [Condition],
+    limit: Option<u64>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Condition {
+    column: &'static str,
+    operator: &'static str,
+    value: Value,
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Int(i64),
+    Str(&'static str),
+    Bool(bool),
+}
+
+#[test]
+fn select_all() {
+    let query = sql!(SELECT * FROM posts WHERE id = 1);
+
+    assert_eq!(
+        Query {
+            columns: &[],
+            table: "posts",
+            conditions: &[Condition {
+                column: "id",
+                operator: "=",
+                value: Value::Int(1),
+            }],
+            limit: None,
+        },
+        query
+    );
+}
+
+#[test]
+fn select_columns_with_conditions_and_limit() {
+    let query = sql!(select id, title from posts where author != "ferris" and draft = false and likes >= 10 limit 5);
+
+    assert_eq!(&["id", "title"], query.columns);
+    assert_eq!(
+        &[
+            Condition {
+                column: "author",
+                operator: "!=",
+                value: Value::Str("ferris"),
+            },
+            Condition {
+                column: "draft",
+                operator: "=",
+                value: Value::Bool(false),
+            },
+            Condition {
+                column: "likes",
+                operator: ">=",
+                value: Value::Int(10),
+            },
+        ],
+        query.conditions
+    );
+    assert_eq!(Some(5), query.limit);
+}
+
+#[test]
+fn queries_are_constant() {
+    // Everything is known at compile time, so a query can even be a constant.
+    const ALL_USERS: Query = sql!(SELECT * FROM users);
+
+    assert_eq!("users", ALL_USERS.table);
+    assert!(ALL_USERS.conditions.is_empty());
+}
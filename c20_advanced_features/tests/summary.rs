@@ -0,0 +1,47 @@
+use c10_generics_traits_lifetimes::Summary;
+use c20_advanced_features::Summary;
+
+#[derive(Summary)]
+struct Post {
+    #[summary(author)]
+    author: String,
+    #[summary(content)]
+    body: String,
+    #[allow(dead_code)]
+    likes: u32,
+}
+
+#[derive(Summary)]
+struct Letter {
+    #[summary(author)]
+    sender: &'static str,
+}
+
+#[derive(Summary)]
+struct Note(#[summary(content)] String, #[summary(author)] u32);
+
+#[test]
+fn summarises_author_and_content() {
+    let post = Post {
+        author: String::from("ferris"),
+        body: String::from("Crabs are great"),
+        likes: 3,
+    };
+
+    assert_eq!("ferris", post.summarise_author());
+    assert_eq!("ferris: Crabs are great", post.summarise());
+}
+
+#[test]
+fn uses_default_summary_without_content() {
+    let letter = Letter { sender: "Alice" };
+
+    assert_eq!("(Read more from Alice...)", letter.summarise());
+}
+
+#[test]
+fn works_on_tuple_structs() {
+    let note = Note(String::from("Remember the milk"), 42);
+
+    assert_eq!("42: Remember the milk", note.summarise());
+}
@@ -0,0 +1,18 @@
+use c20_advanced_features::{Getters, Setters};
+
+#[derive(Getters)]
+struct Account {
+    #[getter(clone)]
+    name: String,
+}
+
+#[derive(Setters)]
+struct Wallet {
+    #[setter(copy)]
+    balance: u64,
+}
+
+#[derive(Getters)]
+struct Point(i32, i32);
+
+fn main() {}
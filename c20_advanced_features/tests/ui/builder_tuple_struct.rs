@@ -0,0 +1,6 @@
+use c20_advanced_features::Builder;
+
+#[derive(Builder)]
+struct Command(String);
+
+fn main() {}
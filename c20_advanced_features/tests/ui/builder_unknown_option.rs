@@ -0,0 +1,13 @@
+use c20_advanced_features::Builder;
+
+enum BuilderError {
+    MissingField(&'static str),
+}
+
+#[derive(Builder)]
+struct Command {
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use c20_advanced_features::Display;
+
+#[derive(Display)]
+#[display("{headline} by {writer}")]
+struct Article {
+    headline: String,
+    author: String,
+}
+
+#[derive(Display)]
+struct Untitled {
+    headline: String,
+}
+
+#[derive(Display)]
+#[display("{}")]
+struct Positional {
+    headline: String,
+}
+
+#[derive(Display)]
+#[display("{data}")]
+struct NotDisplayable {
+    data: Vec<u8>,
+}
+
+fn main() {}
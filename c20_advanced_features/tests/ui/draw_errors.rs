@@ -0,0 +1,15 @@
+use c20_advanced_features::Draw;
+
+#[derive(Draw)]
+#[draw(template = "[{text}]")]
+struct Label {
+    label: String,
+}
+
+#[derive(Draw)]
+#[draw(format = "[{label}]")]
+struct Button {
+    label: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use c20_advanced_features::FieldNames;
+
+#[derive(FieldNames)]
+enum Shape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use c20_advanced_features::Getters;
+
+#[derive(Getters)]
+struct Account {
+    name: String,
+    #[getter(skip)]
+    password: String,
+}
+
+fn main() {
+    let account = Account {
+        name: String::from("ferris"),
+        password: String::from("crab"),
+    };
+    println!("{}", account.name());
+    println!("{}", account.password());
+}
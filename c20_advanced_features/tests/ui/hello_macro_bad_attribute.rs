@@ -0,0 +1,15 @@
+use c20_advanced_features::HelloMacro;
+
+trait HelloMacro {
+    fn hello_macro();
+}
+
+#[derive(HelloMacro)]
+#[hello_macro(greeting = "Hi!")]
+struct Pancakes;
+
+#[derive(HelloMacro)]
+#[hello_macro(message = 42)]
+struct Waffles;
+
+fn main() {}
@@ -0,0 +1,9 @@
+use c20_advanced_features::html;
+
+fn main() {
+    let _ = html! { <screen><image src="logo.png" /></screen> };
+    let _ = html! { <button width=50 height=10 label="OK" color="red" /> };
+    let _ = html! { <button width=50 label="OK" /> };
+    let _ = html! { <container><label text="Name" /></screen> };
+    let _ = html! { <label text="Name"><label text="Inner" /></label> };
+}
@@ -0,0 +1,12 @@
+use c20_advanced_features::route;
+
+#[route(FETCH, "/")]
+fn index() {}
+
+#[route(GET, "index")]
+fn relative() {}
+
+#[route(GET)]
+fn no_path() {}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use c20_advanced_features::sql;
+
+struct Query {
+    columns: &'static [&'static str],
+    table: &'static str,
+    conditions: &'static [Condition],
+    limit: Option<u64>,
+}
+
+struct Condition {
+    column: &'static str,
+    operator: &'static str,
+    value: Value,
+}
+
+enum Value {
+    Int(i64),
+    Str(&'static str),
+    Bool(bool),
+}
+
+fn main() {
+    let _ = sql!(SELECT * posts);
+    let _ = sql!(SELECT id, FROM posts);
+    let _ = sql!(SELECT * FROM posts WHERE id == 1);
+    let _ = sql!(SELECT * FROM posts WHERE score = 1.5);
+    let _ = sql!(SELECT * FROM posts LIMIT 10 OFFSET 5);
+}
@@ -0,0 +1,11 @@
+use c20_advanced_features::Summary;
+
+#[derive(Summary)]
+struct Post {
+    #[summary(author)]
+    author: String,
+    #[summary(author)]
+    editor: String,
+}
+
+fn main() {}
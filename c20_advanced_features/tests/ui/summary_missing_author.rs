@@ -0,0 +1,10 @@
+use c20_advanced_features::Summary;
+
+#[derive(Summary)]
+struct Post {
+    author: String,
+    #[summary(content)]
+    body: String,
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use c20_advanced_features::Summary;
+
+#[derive(Summary)]
+enum Post {
+    Draft,
+}
+
+fn main() {}
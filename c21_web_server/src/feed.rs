@@ -0,0 +1,198 @@
+//! Serving the Media Aggregator
+//!
+//! The routes registered by `routes` expose the `Feed` of chapter 10:
+//! - `GET /feed`: the summaries of all the items.
+//! - `GET /feed/item?id=0`: a single item, by its position in the feed.
+//! - `POST /feed/tweets`: a new `Tweet`, with the `username` and `content` fields, and optionally `reply` and `retweet`.
+//! - `POST /feed/articles`: a new `NewsArticle`, with the `headline`, `location`, `author`, and `content` fields.
+//!
+//! The bodies of the `POST` requests are form-encoded, as sent by the forms of the HTML page.
+//! Every route answers with JSON, or with HTML if the query string has `format=html`.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use c10_generics_traits_lifetimes::{Feed, NewsArticle, Summary, Tweet};
+
+use crate::{
+    html, json,
+    router::{Method, Request, Response, Router},
+};
+
+/// Registers the routes of the feed, shared by the threads handling the connections.
+///
+/// The caller keeps its own handle to the feed, e.g. to push items or subscribe to them outside of the server.
+pub fn routes(router: &mut Router, feed: Arc<Mutex<Feed>>) -> &mut Router {
+    let list_feed = Arc::clone(&feed);
+    let item_feed = Arc::clone(&feed);
+    let tweet_feed = Arc::clone(&feed);
+    router
+        .route(Method::Get, "/feed", move |request| {
+            answer(list(&list_feed, request))
+        })
+        .route(Method::Get, "/feed/item", move |request| {
+            answer(item(&item_feed, request))
+        })
+        .route(Method::Post, "/feed/tweets", move |request| {
+            answer(post_tweet(&tweet_feed, request))
+        })
+        .route(Method::Post, "/feed/articles", move |request| {
+            answer(post_article(&feed, request))
+        })
+}
+
+/// The handlers return the requests they reject as errors, to use `?`, but they are answered all the same.
+fn answer(result: Result<Response, Response>) -> Response {
+    result.unwrap_or_else(|rejected| rejected)
+}
+
+// A handler that panicked while holding the lock has pushed a whole item or none, so the poisoning is ignored
+fn lock(feed: &Mutex<Feed>) -> MutexGuard<'_, Feed> {
+    feed.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Whether the request asks for HTML instead of JSON, with the `format` parameter.
+fn wants_html(request: &Request) -> Result<bool, Response> {
+    match request.param("format").as_deref() {
+        None | Some("json") => Ok(false),
+        Some("html") => Ok(true),
+        Some(format) => Err(Response::bad_request(format!(
+            "Unknown format `{format}`, expected `json` or `html`"
+        ))),
+    }
+}
+
+fn item_json(id: usize, item: &dyn Summary) -> String {
+    json::object(&[
+        ("id", id.to_string()),
+        ("author", json::string(&item.summarise_author())),
+        ("summary", json::string(&item.summarise())),
+    ])
+}
+
+fn item_html(id: usize, item: &dyn Summary) -> String {
+    let body = format!(
+        "    <h1>{}</h1>\n    <p>By {}, item {id} of the feed.</p>\n    <p><a href=\"/feed?format=html\">Back to the feed</a></p>\n",
+        html::escape(&item.summarise()),
+        html::escape(&item.summarise_author())
+    );
+    html::page(&item.summarise_author(), &body)
+}
+
+/// The forms of the feed page, posting the new items.
+const FORMS: &str = r#"    <h2>New tweet</h2>
+    <form method="post" action="/feed/tweets?format=html">
+      <input name="username" placeholder="Username" required>
+      <input name="content" placeholder="What's happening?" required>
+      <label><input type="checkbox" name="reply"> Reply</label>
+      <label><input type="checkbox" name="retweet"> Retweet</label>
+      <button>Tweet</button>
+    </form>
+    <h2>New article</h2>
+    <form method="post" action="/feed/articles?format=html">
+      <input name="headline" placeholder="Headline" required>
+      <input name="location" placeholder="Location" required>
+      <input name="author" placeholder="Author" required>
+      <textarea name="content" placeholder="Content" required></textarea>
+      <button>Publish</button>
+    </form>
+"#;
+
+fn list(feed: &Mutex<Feed>, request: &Request) -> Result<Response, Response> {
+    let html = wants_html(request)?;
+    let feed = lock(feed);
+
+    if html {
+        let items: String = feed
+            .iter()
+            .enumerate()
+            .map(|(id, item)| {
+                format!(
+                    "      <li><a href=\"/feed/item?id={id}&amp;format=html\">{}</a></li>\n",
+                    html::escape(&item.summarise())
+                )
+            })
+            .collect();
+        let body = format!("    <h1>Feed</h1>\n    <ul>\n{items}    </ul>\n{FORMS}");
+        Ok(Response::html(html::page("Feed", &body)))
+    } else {
+        let items = feed
+            .iter()
+            .enumerate()
+            .map(|(id, item)| item_json(id, item));
+        Ok(Response::json(json::object(&[
+            ("items", json::array(items)),
+            ("count", feed.len().to_string()),
+        ])))
+    }
+}
+
+fn item(feed: &Mutex<Feed>, request: &Request) -> Result<Response, Response> {
+    let html = wants_html(request)?;
+    let id = request
+        .param("id")
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| {
+            Response::bad_request("Missing the `id` parameter, the position of the item")
+        })?;
+    let feed = lock(feed);
+
+    match feed.get(id) {
+        Some(item) if html => Ok(Response::html(item_html(id, item))),
+        Some(item) => Ok(Response::json(item_json(id, item))),
+        None => Err(Response::not_found(format!(
+            "No item {id}, the feed has {}",
+            feed.len()
+        ))),
+    }
+}
+
+/// The value of a required field of the form, which can't be empty.
+fn required(request: &Request, name: &str) -> Result<String, Response> {
+    request
+        .form(name)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| Response::bad_request(format!("Missing the `{name}` field")))
+}
+
+/// Whether a checkbox of the form is checked, sent as `on` by the browsers, or `true`.
+fn checked(request: &Request, name: &str) -> bool {
+    matches!(request.form(name).as_deref(), Some("on" | "true"))
+}
+
+/// Pushes the item in the feed, answering `201 CREATED` with it.
+fn push<T: Summary + Send + 'static>(feed: &Mutex<Feed>, html: bool, item: T) -> Response {
+    let mut feed = lock(feed);
+    feed.push(item);
+    let id = feed.len() - 1;
+    let item = feed.get(id).expect("the item was just pushed");
+    book_log::info!("item posted"; id = id, author = item.summarise_author());
+
+    let response = if html {
+        Response::html(item_html(id, item))
+    } else {
+        Response::json(item_json(id, item))
+    };
+    response.with_status(201)
+}
+
+fn post_tweet(feed: &Mutex<Feed>, request: &Request) -> Result<Response, Response> {
+    let html = wants_html(request)?;
+    let tweet = Tweet {
+        username: required(request, "username")?,
+        content: required(request, "content")?,
+        reply: checked(request, "reply"),
+        retweet: checked(request, "retweet"),
+    };
+    Ok(push(feed, html, tweet))
+}
+
+fn post_article(feed: &Mutex<Feed>, request: &Request) -> Result<Response, Response> {
+    let html = wants_html(request)?;
+    let article = NewsArticle {
+        headline: required(request, "headline")?,
+        location: required(request, "location")?,
+        author: required(request, "author")?,
+        content: required(request, "content")?,
+    };
+    Ok(push(feed, html, article))
+}
@@ -0,0 +1,60 @@
+//! Writing HTML Pages
+//!
+//! The pages in `utils` are static, the ones built by the handlers include text from the requests and the files.
+//! That text has to be escaped, or a search for `<script>` would add a script to the page.
+
+/// Escapes the characters with a meaning in HTML, so the text is shown as it is.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The beginning of a page, up to the opening of its body, with the title already escaped.
+///
+/// Split from `page` for the bodies sent in chunks, which end with `PAGE_END`.
+pub fn page_start(title: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n  <head>\n    <meta charset=\"utf-8\">\n    <title>{}</title>\n  </head>\n  <body>\n",
+        escape(title)
+    )
+}
+
+/// The end of a page started with `page_start`.
+pub const PAGE_END: &str = "  </body>\n</html>\n";
+
+/// A whole page, with the title escaped and the body as it is, since it's HTML already.
+pub fn page(title: &str, body: &str) -> String {
+    format!("{}{body}{PAGE_END}", page_start(title))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_is_escaped() {
+        assert_eq!(escape("nobody"), "nobody");
+        assert_eq!(
+            escape(r#"<script>alert("Tom & 'Jerry'")</script>"#),
+            "&lt;script&gt;alert(&quot;Tom &amp; &#39;Jerry&#39;&quot;)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn pages_have_an_escaped_title() {
+        let page = page("<Feed>", "<h1>Feed</h1>\n");
+        assert!(page.starts_with("<!DOCTYPE html>\n"));
+        assert!(page.contains("<title>&lt;Feed&gt;</title>"));
+        assert!(page.ends_with("<body>\n<h1>Feed</h1>\n  </body>\n</html>\n"));
+    }
+}
@@ -0,0 +1,74 @@
+//! Writing JSON Bodies
+//!
+//! The responses of the server are small and their shape is known, so they are written with `format!` instead of a serializer.
+//! These functions take care of what `format!` can't: quoting and escaping the strings, and joining objects and arrays.
+
+use std::fmt::Write;
+
+/// Quotes the string, escaping the characters JSON doesn't allow inside it, e.g. `say "hi"` becomes `"say \"hi\""`.
+pub fn string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            // The other control characters have no short escape
+            c if c.is_control() => {
+                let _ = write!(quoted, "\\u{:04x}", c as u32);
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Joins the names and the values, already written as JSON, in an object.
+///
+/// `object(&[("status", String::from("200"))])` is `{"status":200}`.
+pub fn object(fields: &[(&str, String)]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|(name, value)| format!("{}:{value}", string(name)))
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Joins the values, already written as JSON, in an array.
+pub fn array(values: impl IntoIterator<Item = String>) -> String {
+    let values: Vec<String> = values.into_iter().collect();
+    format!("[{}]", values.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(string("nobody"), r#""nobody""#);
+        assert_eq!(string("say \"hi\"\n"), r#""say \"hi\"\n""#);
+        assert_eq!(string(r"C:\book"), r#""C:\\book""#);
+        assert_eq!(string("bell\u{7}"), r#""bell\u0007""#);
+        assert_eq!(string("¿Quién?"), r#""¿Quién?""#);
+    }
+
+    #[test]
+    fn objects_and_arrays_are_joined() {
+        assert_eq!(object(&[]), "{}");
+        assert_eq!(array([]), "[]");
+        let matches = array([string("I'm nobody!"), string("Are you nobody, too?")]);
+        assert_eq!(
+            object(&[
+                ("query", string("body")),
+                ("matches", matches),
+                ("count", 2.to_string())
+            ]),
+            r#"{"query":"body","matches":["I'm nobody!","Are you nobody, too?"],"count":2}"#
+        );
+    }
+}
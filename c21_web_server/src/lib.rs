@@ -2,9 +2,19 @@
 
 // Currently the `ThreadPool` type or module doesn't exist, so it needs to be built, it will be independent from the web server
 
+pub mod feed;
+pub mod html;
+pub mod json;
+pub mod router;
+pub mod search;
+
 use std::{
+    error::Error,
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering},
     sync::{Arc, Mutex, mpsc}, // [5] Bring into scope `Arc`, `Mutex`, and `mpsc` to create the channel, and manage the shared ownership.
     thread, // [3] Bring into scope `std::thread` since the type used is `thread::JoinHandle`
+    time::Instant,
 };
 
 /// Struct that represents the ThreadPool
@@ -16,9 +26,31 @@ pub struct ThreadPool {
     // [5] Add the sender of the channel created in the `ThreadPool`
     // sender: mpsc::Sender<Job>,
     // [8] TO explicitly drop the `sender` an `Option` is needed to move `sender` out of `ThreadPool` with `Option::take`
-    sender: Option<mpsc::Sender<Job>>,
+    // sender: Option<mpsc::Sender<Job>>,
+    // Each job is sent with an id, so its events can be followed from the queue to the worker running it
+    sender: Option<mpsc::Sender<(usize, Job)>>,
+    // Number of jobs queued so far, the id of the next one
+    // Atomic since `execute` only borrows the pool, which can be shared by several threads
+    jobs: AtomicUsize,
+}
+
+/// Error returned by `ThreadPool::build`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolCreationError {
+    /// A pool needs at least one thread to run the jobs
+    ZeroThreads,
 }
 
+impl fmt::Display for PoolCreationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PoolCreationError::ZeroThreads => write!(f, "a thread pool needs at least one thread"),
+        }
+    }
+}
+
+impl Error for PoolCreationError {}
+
 // [5] Currently the structu `Job` doesn't hold anything, but will be the type to send down the channel.
 // struct Job;
 // [6] `Job` must become a type alias for a trait object that holds the type of closure that `execute` receives
@@ -83,10 +115,20 @@ impl ThreadPool {
         // [5] Return the `ThreadPool` with workers and the sender of the channel
         // ThreadPool { workers, sender }
         // [8] The `ThreadPool` needs to return the sender in an `Option` to move the `sender` out
+        book_log::info!("thread pool started"; threads = size);
         ThreadPool {
             workers,
             sender: Some(sender),
+            jobs: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a new ThreadPool, like `new` but returning an error instead of panicking if the size is zero.
+    pub fn build(size: usize) -> Result<ThreadPool, PoolCreationError> {
+        if size == 0 {
+            return Err(PoolCreationError::ZeroThreads);
         }
+        Ok(ThreadPool::new(size))
     }
     // After creating the `new` method, the compiler tells that the `execute` method on `ThreadPool` is missing
     // `execute` should have a similar interface to `thread::spawn`, and it takes a closure that is given to an idle thread in the pool
@@ -111,7 +153,10 @@ impl ThreadPool {
         let job = Box::new(f);
         // self.sender.send(job).unwrap();
         // [8] Since sender is now an `Option` it needs to be taken as a reference using `as_ref`
-        self.sender.as_ref().unwrap().send(job).unwrap();
+        // self.sender.as_ref().unwrap().send(job).unwrap();
+        let id = self.jobs.fetch_add(1, Ordering::Relaxed);
+        book_log::debug!("job queued"; job = id);
+        self.sender.as_ref().unwrap().send((id, job)).unwrap();
     }
     // Now the code compiles, but it gives error in the browser, since the library isn't calling the closure passed to `execute` yet.
     // [2] Validating the Number of Threads in new
@@ -179,6 +224,7 @@ impl Drop for ThreadPool {
 
             worker.thread.join().unwrap();
         }
+        book_log::info!("thread pool shut down"; jobs = self.jobs.load(Ordering::Relaxed));
     }
 }
 
@@ -191,7 +237,7 @@ struct Worker {
 }
 
 impl Worker {
-    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<Job>>>) -> Worker {
+    fn new(id: usize, receiver: Arc<Mutex<mpsc::Receiver<(usize, Job)>>>) -> Worker {
         // [4] The `new` spawns a thread with an empty closure and stores it in `thread`
         // [5] Pass the receiver side of the channel to the Worker instances, so the `receiver` parameter can be referenced in the closure.
         // The signature needs to be `receiver: Arc<Mutex<mpsc::Receiver<Job>>>` instead of `receiver: mpsc::Receiver<Job>` because the receiver side of the channel is shared between multiple workers
@@ -219,12 +265,21 @@ impl Worker {
                 let message = receiver.lock().unwrap().recv();
 
                 match message {
-                    Ok(job) => {
+                    Ok((job_id, job)) => {
                         println!("Worker {id} got a job; executing.");
+                        book_log::debug!("job started"; worker = id, job = job_id);
+                        let start = Instant::now();
                         job();
+                        book_log::debug!(
+                            "job finished";
+                            worker = id,
+                            job = job_id,
+                            elapsed = start.elapsed(),
+                        );
                     }
                     Err(_) => {
                         println!("Worker {id} disconnected; shutting down.");
+                        book_log::debug!("worker shut down"; worker = id);
                         break;
                     }
                 }
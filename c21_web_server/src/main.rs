@@ -396,5 +396,116 @@ fn multi_threaded() {
             println!("Shutting down.");
             // This code will compile once the `ThreadPool` is completed in `src/lib.rs`, it is built using a compiler driven development
         }
+        {
+            // Routing with a Router
+            // The `match` in `handle_connection` grows with every page, a router keeps a list of routes registered at runtime instead.
+            // The `#[route]` attribute macro of chapter 20 generates a `register_*` function for each handler, to add it to the router.
+            use c20_advanced_features::route;
+            use c21_web_server::ThreadPool;
+            use c21_web_server::router::{Request, Response, Router};
+            use std::sync::Arc;
+
+            #[route(GET, "/")]
+            fn index(_: &Request) -> Response {
+                Response::ok(fs::read_to_string("utils/hello.html").unwrap())
+            }
+
+            #[route(GET, "/sleep")]
+            fn sleep(_: &Request) -> Response {
+                thread::sleep(Duration::from_secs(5));
+                Response::ok(fs::read_to_string("utils/hello.html").unwrap())
+            }
+
+            let mut router = Router::new();
+            router
+                .register(register_index)
+                .register(register_sleep)
+                .fallback(|_| Response::not_found(fs::read_to_string("utils/404.html").unwrap()));
+            // The router is shared by all the threads of the pool, and it's never modified after this point, so `Arc` is enough.
+            let router = Arc::new(router);
+
+            let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
+            let pool = ThreadPool::new(4);
+
+            for stream in listener.incoming().take(10) {
+                let stream = stream.unwrap();
+                let router = Arc::clone(&router);
+
+                pool.execute(move || {
+                    router.handle_connection(stream);
+                });
+            }
+        }
+        {
+            // Searching with Minigrep
+            // The handlers can be closures keeping some state, here the `Search` of `src/search.rs` with its own pool.
+            // `/search?q=nobody&path=poem.txt` runs the `search` function of chapter 12 on `utils/poem.txt`,
+            // add `&format=html` for a page instead of JSON, and `&ignore_case` to search like `IGNORE_CASE=1 cargo run`.
+            // The matches of each file are sent in a chunk as soon as they are found, so a large search doesn't wait until the end.
+            use c21_web_server::ThreadPool;
+            use c21_web_server::router::{Method, Router};
+            use c21_web_server::search::Search;
+            use std::sync::Arc;
+
+            let search = Search::new("utils", 2);
+            let mut router = Router::new();
+            router.stream_route(Method::Get, "/search", move |request| {
+                search.handle(request)
+            });
+            let router = Arc::new(router);
+
+            let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
+            let pool = ThreadPool::new(4);
+
+            for stream in listener.incoming().take(10) {
+                let stream = stream.unwrap();
+                let router = Arc::clone(&router);
+
+                pool.execute(move || {
+                    router.handle_connection(stream);
+                });
+            }
+        }
+        {
+            // Serving the Feed
+            // The `Feed` of chapter 10 is shared by the routes of `src/feed.rs`, behind a `Mutex` since the `POST` requests push new items.
+            // `/feed?format=html` lists the summaries with the forms to post tweets and articles, `/feed` returns the same list as JSON.
+            // The subscriber prints every item posted, the feed still notifies it when the item comes from a request.
+            use c10_generics_traits_lifetimes::{Feed, Tweet};
+            use c21_web_server::ThreadPool;
+            use c21_web_server::feed;
+            use c21_web_server::router::Router;
+            use std::sync::{Arc, Mutex};
+
+            let mut items = Feed::new();
+            items.subscribe(|item| println!("New item: {}", item.summarise()));
+            items.push(Tweet {
+                username: String::from("horse_ebooks"),
+                content: String::from("of course, as you probably already know, people"),
+                reply: false,
+                retweet: false,
+            });
+            let items = Arc::new(Mutex::new(items));
+
+            let mut router = Router::new();
+            feed::routes(&mut router, Arc::clone(&items));
+            let router = Arc::new(router);
+
+            let listener = TcpListener::bind("127.0.0.1:7878").unwrap();
+            let pool = ThreadPool::new(4);
+
+            for stream in listener.incoming().take(10) {
+                let stream = stream.unwrap();
+                let router = Arc::clone(&router);
+
+                pool.execute(move || {
+                    router.handle_connection(stream);
+                });
+            }
+
+            // Dropping the pool waits for the last requests, then the feed has all the items posted
+            drop(pool);
+            println!("{} items in the feed", items.lock().unwrap().len());
+        }
     }
 }
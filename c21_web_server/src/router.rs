@@ -0,0 +1,640 @@
+//! Routing Requests to Handlers
+//!
+//! The `handle_connection` functions in `main.rs` match on the whole request line to choose the response.
+//! A router does the same matching, but the routes are registered at runtime, so adding a page doesn't mean changing a `match`.
+//! With the `#[route(GET, "/path")]` attribute of `c20_advanced_features` the handlers register themselves.
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Read, Write},
+    sync::mpsc,
+    time::Instant,
+};
+
+/// The HTTP methods the router knows about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Method {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+impl Method {
+    /// Parses a method as written in the request line, such as `GET`.
+    pub fn parse(method: &str) -> Option<Method> {
+        match method {
+            "GET" => Some(Method::Get),
+            "POST" => Some(Method::Post),
+            "PUT" => Some(Method::Put),
+            "DELETE" => Some(Method::Delete),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let method = match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+        };
+        write!(f, "{method}")
+    }
+}
+
+/// The part of a request the router uses: the method and the path of the request line, and the body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    /// The path without the query string, the part matched against the routes.
+    pub path: String,
+    /// The query string after the `?`, as sent, empty if there is none.
+    pub query: String,
+    /// The body, as long as its `Content-Length` header, empty without the header.
+    pub body: String,
+}
+
+impl Request {
+    /// Parses a request line such as `GET / HTTP/1.1`.
+    ///
+    /// Returns `None` if the line doesn't have a known method, a path, and the HTTP version.
+    pub fn parse(request_line: &str) -> Option<Request> {
+        let mut parts = request_line.split_whitespace();
+        let method = Method::parse(parts.next()?)?;
+        let target = parts.next()?;
+        let _version = parts
+            .next()
+            .filter(|version| version.starts_with("HTTP/"))?;
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        Some(Request {
+            method,
+            path: path.to_string(),
+            query: query.to_string(),
+            body: String::new(),
+        })
+    }
+
+    /// Returns the decoded value of the first parameter of the query string with the given name.
+    ///
+    /// In `/search?q=safe+fast&path=poem.txt` the `q` parameter is `safe fast`.
+    pub fn param(&self, name: &str) -> Option<String> {
+        field(&self.query, name)
+    }
+
+    /// Returns the decoded value of a field of a body encoded like a query string,
+    /// as sent by the HTML forms with the `application/x-www-form-urlencoded` content type.
+    pub fn form(&self, name: &str) -> Option<String> {
+        field(&self.body, name)
+    }
+}
+
+/// Finds the value of the first field with the given name in `name=value` pairs separated by `&`.
+fn field(pairs: &str, name: &str) -> Option<String> {
+    pairs
+        .split('&')
+        .filter_map(|pair| pair.split_once('=').or(Some((pair, ""))))
+        .find(|(key, _)| decode(key) == name)
+        .map(|(_, value)| decode(value))
+}
+
+/// Decodes a component of a query string: `+` is a space, and `%` followed by two hexadecimal digits is a byte.
+///
+/// A `%` without two digits after it is kept as it is, and the bytes that are not UTF-8 are replaced with `�`.
+fn decode(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+            (b'+', _) => decoded.push(b' '),
+            (byte, _) => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The reason phrase of the status line, after the status code.
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "CREATED",
+        400 => "BAD REQUEST",
+        404 => "NOT FOUND",
+        405 => "METHOD NOT ALLOWED",
+        413 => "PAYLOAD TOO LARGE",
+        500 => "INTERNAL SERVER ERROR",
+        _ => "UNKNOWN",
+    }
+}
+
+/// A response with its status code and body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    /// The `Content-Type` header, not sent if `None`, as in the pages of `main.rs`.
+    pub content_type: Option<&'static str>,
+    pub body: String,
+}
+
+impl Response {
+    /// A `200 OK` response.
+    pub fn ok(body: impl Into<String>) -> Response {
+        Response {
+            status: 200,
+            content_type: None,
+            body: body.into(),
+        }
+    }
+
+    /// A `404 NOT FOUND` response.
+    pub fn not_found(body: impl Into<String>) -> Response {
+        Response {
+            status: 404,
+            content_type: None,
+            body: body.into(),
+        }
+    }
+
+    /// A `400 BAD REQUEST` response, for a request missing something the handler needs.
+    pub fn bad_request(body: impl Into<String>) -> Response {
+        Response {
+            status: 400,
+            content_type: None,
+            body: body.into(),
+        }
+    }
+
+    /// A `200 OK` response with a JSON body, such as the ones built with the `json` module.
+    pub fn json(body: impl Into<String>) -> Response {
+        Response::ok(body).with_content_type(JSON)
+    }
+
+    /// A `200 OK` response with an HTML page, such as the ones built with the `html` module.
+    pub fn html(body: impl Into<String>) -> Response {
+        Response::ok(body).with_content_type(HTML)
+    }
+
+    /// Changes the status code, e.g. `Response::json(body).with_status(201)`.
+    pub fn with_status(mut self, status: u16) -> Response {
+        self.status = status;
+        self
+    }
+
+    pub fn with_content_type(mut self, content_type: &'static str) -> Response {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// Formats the response as it is sent down the connection, like the `format!` in `main.rs`.
+    pub fn to_http(&self) -> String {
+        let reason = reason(self.status);
+        let length = self.body.len();
+        let content_type = self
+            .content_type
+            .map(|content_type| format!("Content-Type: {content_type}\r\n"))
+            .unwrap_or_default();
+        format!(
+            "HTTP/1.1 {} {reason}\r\n{content_type}Content-Length: {length}\r\n\r\n{}",
+            self.status, self.body
+        )
+    }
+}
+
+/// The content type of the JSON responses.
+pub const JSON: &str = "application/json";
+/// The content type of the HTML responses.
+pub const HTML: &str = "text/html; charset=utf-8";
+
+/// The number of chunks a `Stream` holds before the sender waits for the client to receive them.
+pub const STREAM_BUFFER: usize = 16;
+
+/// A response whose body is sent in chunks while it is produced, instead of all at once with its length.
+///
+/// The chunks arrive from a channel, so they can be produced by another thread, such as a job of a `ThreadPool`,
+/// and the client gets the first ones before the last ones exist. The body ends when all the senders are dropped.
+/// The channel holds at most `STREAM_BUFFER` chunks, so a producer faster than the client waits instead of
+/// keeping the whole body in memory.
+#[derive(Debug)]
+pub struct Stream {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub chunks: mpsc::Receiver<String>,
+}
+
+impl Stream {
+    /// A `200 OK` stream, and the sender of its chunks.
+    pub fn new(content_type: &'static str) -> (mpsc::SyncSender<String>, Stream) {
+        let (sender, chunks) = mpsc::sync_channel(STREAM_BUFFER);
+        let stream = Stream {
+            status: 200,
+            content_type,
+            chunks,
+        };
+        (sender, stream)
+    }
+
+    /// Writes the response with the `chunked` transfer encoding: each chunk after its length in hexadecimal,
+    /// then a chunk of length zero at the end. Returns the number of chunks written.
+    pub fn write_to<W: Write>(self, mut writer: W) -> io::Result<usize> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nTransfer-Encoding: chunked\r\n\r\n",
+            self.status,
+            reason(self.status),
+            self.content_type
+        )?;
+        let mut written = 0;
+        // An empty chunk would end the body, so there is nothing to send for it.
+        for chunk in self.chunks.iter().filter(|chunk| !chunk.is_empty()) {
+            write!(writer, "{:X}\r\n{chunk}\r\n", chunk.len())?;
+            // Each chunk is sent right away, instead of waiting for a buffer to fill up.
+            writer.flush()?;
+            written += 1;
+        }
+        writer.write_all(b"0\r\n\r\n")?;
+        writer.flush()?;
+        Ok(written)
+    }
+
+    /// Waits for all the chunks and joins them in the body of a plain response.
+    pub fn collect(self) -> Response {
+        Response {
+            status: self.status,
+            content_type: Some(self.content_type),
+            body: self.chunks.iter().collect(),
+        }
+    }
+}
+
+/// The type of the functions handling the requests.
+///
+/// Function pointers are enough since the handlers don't capture anything, and they can be created by macros.
+/// The handlers that need some state, such as a `ThreadPool`, can be closures registered with `Router::route`.
+pub type Handler = fn(&Request) -> Response;
+
+// The handlers as stored by the router, either function pointers or closures
+type BoxedHandler = Box<dyn Fn(&Request) -> Response + Send + Sync>;
+type BoxedStreamHandler = Box<dyn Fn(&Request) -> Result<Stream, Response> + Send + Sync>;
+
+/// What a route does with the request: answer at once, or stream the body.
+/// A streaming handler can still answer at once, e.g. to reject an invalid request.
+enum Action {
+    Respond(BoxedHandler),
+    Stream(BoxedStreamHandler),
+}
+
+struct Route {
+    method: Method,
+    path: String,
+    action: Action,
+}
+
+/// Chooses the handler of each request from its method and path.
+pub struct Router {
+    routes: Vec<Route>,
+    fallback: Handler,
+}
+
+impl Default for Router {
+    fn default() -> Self {
+        Router::new()
+    }
+}
+
+impl Router {
+    /// Creates a router without routes, answering `404 NOT FOUND` to every request.
+    pub fn new() -> Router {
+        Router {
+            routes: Vec::new(),
+            fallback: |_| Response::not_found("Not Found"),
+        }
+    }
+
+    /// Registers the handler of the requests with the given method and path.
+    ///
+    /// If the same route is registered twice the first handler is used.
+    /// The router is shared by the threads handling the connections, so the handler needs to be `Send` and `Sync`.
+    pub fn route<H>(&mut self, method: Method, path: &str, handler: H) -> &mut Router
+    where
+        H: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.add(method, path, Action::Respond(Box::new(handler)))
+    }
+
+    /// Registers a handler sending the body in chunks, for large bodies or ones that take long to produce.
+    pub fn stream_route<H>(&mut self, method: Method, path: &str, handler: H) -> &mut Router
+    where
+        H: Fn(&Request) -> Result<Stream, Response> + Send + Sync + 'static,
+    {
+        self.add(method, path, Action::Stream(Box::new(handler)))
+    }
+
+    fn add(&mut self, method: Method, path: &str, action: Action) -> &mut Router {
+        self.routes.push(Route {
+            method,
+            path: path.to_string(),
+            action,
+        });
+        self
+    }
+
+    /// Calls a registration function, such as the ones generated by the `#[route]` attribute, to add its routes.
+    pub fn register(&mut self, registration: fn(&mut Router)) -> &mut Router {
+        registration(self);
+        self
+    }
+
+    /// Replaces the handler of the requests that don't match any route.
+    pub fn fallback(&mut self, handler: Handler) -> &mut Router {
+        self.fallback = handler;
+        self
+    }
+
+    fn respond(&self, request: &Request) -> Result<Stream, Response> {
+        let route = self
+            .routes
+            .iter()
+            .find(|route| route.method == request.method && route.path == request.path);
+        match route.map(|route| &route.action) {
+            Some(Action::Respond(handler)) => Err(handler(request)),
+            Some(Action::Stream(handler)) => handler(request),
+            None => Err((self.fallback)(request)),
+        }
+    }
+
+    /// Returns the response of the handler matching the request.
+    ///
+    /// A streamed response is collected in a single body, waiting for all of it.
+    pub fn dispatch(&self, request: &Request) -> Response {
+        match self.respond(request) {
+            Ok(stream) => stream.collect(),
+            Err(response) => response,
+        }
+    }
+
+    /// Reads the request from the stream, and writes back the response.
+    ///
+    /// A request line that can't be parsed gets a `400 BAD REQUEST` response.
+    /// Every request is logged with its status and how long the handler took.
+    pub fn handle_connection<S: Read + Write>(&self, mut stream: S) {
+        let start = Instant::now();
+        // The `BufReader` only borrows the stream, so it's still available to write the response.
+        let request = read_request(&mut BufReader::new(&mut stream));
+
+        let request = match request {
+            Ok(request) => request,
+            Err(response) => {
+                // The client might have disconnected, there is nobody to tell about it but the log.
+                if let Err(error) = stream.write_all(response.to_http().as_bytes()) {
+                    book_log::debug!("response not sent"; error = error);
+                }
+                return;
+            }
+        };
+
+        let (status, sent) = match self.respond(&request) {
+            Err(response) => (
+                response.status,
+                stream.write_all(response.to_http().as_bytes()),
+            ),
+            Ok(body) => (body.status, body.write_to(&mut stream).map(|_| ())),
+        };
+        book_log::info!(
+            "request handled";
+            method = request.method.to_string(),
+            path = request.path,
+            status = status,
+            elapsed = start.elapsed(),
+        );
+        if let Err(error) = sent {
+            book_log::debug!("response not sent"; error = error);
+        }
+    }
+}
+
+/// The longest body the server accepts, the ones announcing a longer `Content-Length` are not read.
+pub const MAX_BODY: usize = 64 * 1024;
+
+/// Reads the request line, the headers, and the body, answering at once the requests that can't be handled.
+fn read_request<R: BufRead>(reader: &mut R) -> Result<Request, Response> {
+    let mut request_line = String::new();
+    let read = reader.read_line(&mut request_line);
+    let Some(mut request) = read.ok().and_then(|_| Request::parse(&request_line)) else {
+        book_log::warn!("bad request"; line = request_line.trim_end());
+        return Err(Response::bad_request("Bad Request"));
+    };
+
+    // The headers end with an empty line, only the length of the body is needed
+    let mut length = 0;
+    loop {
+        let mut header = String::new();
+        match reader.read_line(&mut header) {
+            Ok(0) => break,
+            Ok(_) if header.trim_end().is_empty() => break,
+            Ok(_) => {}
+            Err(_) => return Err(Response::bad_request("Bad Request")),
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            length = value
+                .trim()
+                .parse()
+                .map_err(|_| Response::bad_request("Invalid Content-Length"))?;
+        }
+    }
+    if length > MAX_BODY {
+        book_log::warn!("body too large"; path = request.path, length = length);
+        return Err(Response::bad_request("Payload Too Large").with_status(413));
+    }
+
+    let mut body = Vec::with_capacity(length);
+    // A body shorter than announced ends with the connection, it's handled with what arrived
+    reader
+        .take(length as u64)
+        .read_to_end(&mut body)
+        .map_err(|_| Response::bad_request("Bad Request"))?;
+    request.body = String::from_utf8_lossy(&body).into_owned();
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::thread;
+
+    fn hello(_: &Request) -> Response {
+        Response::ok("Hello!")
+    }
+
+    #[test]
+    fn parses_request_lines() {
+        assert_eq!(
+            Some(Request {
+                method: Method::Get,
+                path: String::from("/sleep"),
+                query: String::new(),
+                body: String::new(),
+            }),
+            Request::parse("GET /sleep HTTP/1.1")
+        );
+        assert_eq!(None, Request::parse("FETCH / HTTP/1.1"));
+        assert_eq!(None, Request::parse("GET /"));
+    }
+
+    #[test]
+    fn decodes_the_query_parameters() {
+        let request =
+            Request::parse("GET /search?q=safe+fast%21&path=utils%2Fpoem.txt&ignore_case HTTP/1.1")
+                .unwrap();
+        assert_eq!(request.path, "/search");
+        assert_eq!(request.param("q").as_deref(), Some("safe fast!"));
+        assert_eq!(request.param("path").as_deref(), Some("utils/poem.txt"));
+        assert_eq!(request.param("ignore_case").as_deref(), Some(""));
+        assert_eq!(request.param("format"), None);
+        // Incomplete escapes are kept as they are
+        assert_eq!(decode("100%"), "100%");
+        assert_eq!(decode("%e2%9c%93%zz"), "✓%zz");
+    }
+
+    #[test]
+    fn dispatches_by_method_and_path() {
+        let mut router = Router::new();
+        router.route(Method::Get, "/", hello);
+
+        let get = Request::parse("GET /?name=Ann HTTP/1.1").unwrap();
+        let post = Request::parse("POST / HTTP/1.1").unwrap();
+        assert_eq!(Response::ok("Hello!"), router.dispatch(&get));
+        assert_eq!(404, router.dispatch(&post).status);
+
+        router.fallback(|request| Response::not_found(format!("No {}", request.path)));
+        assert_eq!(Response::not_found("No /"), router.dispatch(&post));
+    }
+
+    #[test]
+    fn handlers_can_be_closures() {
+        let greeting = String::from("Hi");
+        let mut router = Router::new();
+        router.route(Method::Get, "/", move |request| {
+            let name = request.param("name").unwrap_or_default();
+            Response::json(format!(r#"{{"greeting":"{greeting} {name}"}}"#))
+        });
+
+        let response = router.dispatch(&Request::parse("GET /?name=Ann HTTP/1.1").unwrap());
+        assert_eq!(response.body, r#"{"greeting":"Hi Ann"}"#);
+        assert!(
+            response
+                .to_http()
+                .starts_with("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\n")
+        );
+    }
+
+    #[test]
+    fn handles_a_connection() {
+        let mut router = Router::new();
+        router.route(Method::Get, "/", hello);
+        let mut stream = Cursor::new(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n".to_vec());
+
+        router.handle_connection(&mut stream);
+
+        let written = String::from_utf8(stream.into_inner()).unwrap();
+        assert!(written.ends_with("HTTP/1.1 200 OK\r\nContent-Length: 6\r\n\r\nHello!"));
+    }
+
+    #[test]
+    fn reads_the_body() {
+        let mut router = Router::new();
+        router.route(Method::Post, "/echo", |request| {
+            Response::ok(request.form("text").unwrap_or_default())
+        });
+        let request =
+            b"POST /echo HTTP/1.1\r\ncontent-length: 23\r\n\r\ntext=Hello%2C+world%21&more";
+        let mut stream = Cursor::new(request.to_vec());
+
+        router.handle_connection(&mut stream);
+
+        let written = String::from_utf8(stream.into_inner()).unwrap();
+        assert!(written.ends_with("\r\nContent-Length: 13\r\n\r\nHello, world!"));
+
+        let request = format!(
+            "POST /echo HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY + 1
+        );
+        let mut stream = Cursor::new(request.into_bytes());
+        router.handle_connection(&mut stream);
+        let written = String::from_utf8(stream.into_inner()).unwrap();
+        assert!(written.contains("HTTP/1.1 413 PAYLOAD TOO LARGE\r\n"));
+    }
+
+    #[test]
+    fn streams_are_sent_in_chunks() {
+        let mut router = Router::new();
+        router.stream_route(Method::Get, "/count", |request| {
+            let to: u32 = request
+                .param("to")
+                .and_then(|to| to.parse().ok())
+                .ok_or_else(|| Response::bad_request("Missing `to`"))?;
+            let (sender, stream) = Stream::new("text/plain");
+            // Another thread produces the chunks, the stream is returned before it's done
+            thread::spawn(move || {
+                for n in 1..=to {
+                    sender.send(format!("{n},")).unwrap();
+                }
+            });
+            Ok(stream)
+        });
+
+        let mut stream = Cursor::new(b"GET /count?to=3 HTTP/1.1\r\n\r\n".to_vec());
+        router.handle_connection(&mut stream);
+        let written = String::from_utf8(stream.into_inner()).unwrap();
+        assert!(written.ends_with(
+            "Transfer-Encoding: chunked\r\n\r\n2\r\n1,\r\n2\r\n2,\r\n2\r\n3,\r\n0\r\n\r\n"
+        ));
+
+        // `dispatch` collects the chunks, and a streaming handler can still answer at once
+        let request = Request::parse("GET /count?to=12 HTTP/1.1").unwrap();
+        assert_eq!(
+            router.dispatch(&request).body,
+            "1,2,3,4,5,6,7,8,9,10,11,12,"
+        );
+        let request = Request::parse("GET /count HTTP/1.1").unwrap();
+        assert_eq!(
+            router.dispatch(&request),
+            Response::bad_request("Missing `to`")
+        );
+    }
+
+    #[test]
+    fn streams_hold_a_limited_number_of_chunks() {
+        let (sender, stream) = Stream::new("text/plain");
+        for n in 0..STREAM_BUFFER {
+            sender.try_send(n.to_string()).unwrap();
+        }
+        // The buffer is full until the client receives a chunk
+        assert!(matches!(
+            sender.try_send(String::from("more")),
+            Err(mpsc::TrySendError::Full(_))
+        ));
+        assert_eq!(stream.chunks.recv().unwrap(), "0");
+        sender.try_send(String::from("more")).unwrap();
+
+        drop(sender);
+        let body = stream.collect().body;
+        assert!(body.starts_with("123") && body.ends_with("15more"));
+    }
+}
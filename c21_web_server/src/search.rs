@@ -0,0 +1,272 @@
+//! Searching Files with Minigrep
+//!
+//! The `/search?q=...&path=...` endpoint runs the `search` functions of `c12_minigrep` on the files of a directory.
+//! The files are read and searched by a `ThreadPool`, and the matches of each file are sent as soon as they are found,
+//! so a search over many files doesn't have to fit in memory, and the client starts receiving the results right away.
+//!
+//! The parameters of the request are:
+//! - `q`: the text to search, required.
+//! - `path`: the file or directory to search, relative to the root of the `Search`, required.
+//! - `format`: `json`, the default, or `html`.
+//! - `ignore_case`: search ignoring the case, unless it's `false`, like the `IGNORE_CASE` variable of minigrep.
+
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::mpsc::SyncSender,
+    time::Instant,
+};
+
+use crate::{
+    ThreadPool, html, json,
+    router::{HTML, JSON, Request, Response, Stream},
+};
+
+/// How the results are written, chosen with the `format` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Html,
+}
+
+impl Format {
+    fn content_type(self) -> &'static str {
+        match self {
+            Format::Json => JSON,
+            Format::Html => HTML,
+        }
+    }
+
+    /// The beginning of the body, before the first match.
+    fn start(self, query: &str, path: &str) -> String {
+        match self {
+            Format::Json => format!(
+                r#"{{"query":{},"path":{},"matches":["#,
+                json::string(query),
+                json::string(path)
+            ),
+            Format::Html => format!(
+                "{}    <h1>Searching for <code>{}</code> in <code>{}</code></h1>\n    <ul>\n",
+                html::page_start(&format!("Search for {query}")),
+                html::escape(query),
+                html::escape(path)
+            ),
+        }
+    }
+
+    /// The matches of a file, `first` if no match has been written before them.
+    fn matches(self, file: &str, lines: &[&str], first: bool) -> String {
+        match self {
+            Format::Json => {
+                let matches: Vec<String> = lines
+                    .iter()
+                    .map(|line| {
+                        json::object(&[("file", json::string(file)), ("text", json::string(line))])
+                    })
+                    .collect();
+                let separator = if first { "" } else { "," };
+                format!("{separator}{}", matches.join(","))
+            }
+            Format::Html => lines
+                .iter()
+                .map(|line| {
+                    format!(
+                        "      <li><code>{}</code>: {}</li>\n",
+                        html::escape(file),
+                        html::escape(line)
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// The end of the body, after the last match.
+    fn end(self, count: usize) -> String {
+        match self {
+            Format::Json => format!(r#"],"count":{count}}}"#),
+            Format::Html => format!("    </ul>\n    <p>{count} matches</p>\n{}", html::PAGE_END),
+        }
+    }
+}
+
+/// The handler of the searches, with its own pool to run them.
+///
+/// The pool is not the one of the connections: a connection waits for the chunks of its search,
+/// so if the searches were queued behind the connections, a busy server would never run them.
+pub struct Search {
+    pool: ThreadPool,
+    root: PathBuf,
+}
+
+impl Search {
+    /// Creates the handler of the searches in the files under `root`, run by a pool of `threads` threads.
+    ///
+    /// # Panics
+    ///
+    /// Like `ThreadPool::new`, if `threads` is zero.
+    pub fn new(root: impl Into<PathBuf>, threads: usize) -> Search {
+        Search {
+            pool: ThreadPool::new(threads),
+            root: root.into(),
+        }
+    }
+
+    /// Starts the search of the request on the pool, returning the stream of its results.
+    ///
+    /// The request is rejected with `400 BAD REQUEST` if a parameter is missing or invalid,
+    /// or if the path leads outside of the root through a symbolic link,
+    /// and with `404 NOT FOUND` if there is nothing at the path.
+    pub fn handle(&self, request: &Request) -> Result<Stream, Response> {
+        let query = request
+            .param("q")
+            .filter(|query| !query.is_empty())
+            .ok_or_else(|| Response::bad_request("Missing the `q` parameter"))?;
+        let path = request
+            .param("path")
+            .ok_or_else(|| Response::bad_request("Missing the `path` parameter"))?;
+        let format = match request.param("format").as_deref() {
+            None | Some("json") => Format::Json,
+            Some("html") => Format::Html,
+            Some(format) => {
+                return Err(Response::bad_request(format!(
+                    "Unknown format `{format}`, expected `json` or `html`"
+                )));
+            }
+        };
+        let ignore_case = request
+            .param("ignore_case")
+            .is_some_and(|ignore_case| ignore_case != "false");
+
+        // Only plain names are allowed, an absolute path or a `..` could read any file of the server
+        let inside_root = Path::new(&path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+        if !inside_root {
+            return Err(Response::bad_request(
+                "The `path` must be relative, and can't go up with `..`",
+            ));
+        }
+        let target = self.root.join(&path);
+        // A symbolic link inside the root could still lead outside of it, so the resolved paths are compared too
+        let (Ok(resolved), Ok(root)) = (target.canonicalize(), self.root.canonicalize()) else {
+            return Err(Response::not_found(format!("Nothing found at `{path}`")));
+        };
+        if !resolved.starts_with(root) {
+            return Err(Response::bad_request(
+                "The `path` can't lead outside of the root",
+            ));
+        }
+
+        let (sender, stream) = Stream::new(format.content_type());
+        let root = self.root.clone();
+        self.pool.execute(move || {
+            let run = Run {
+                query,
+                path,
+                ignore_case,
+                format,
+                sender,
+            };
+            run.search(&root, &target);
+        });
+        Ok(stream)
+    }
+}
+
+/// A search running on the pool, sending its results down the channel of the stream.
+struct Run {
+    query: String,
+    path: String,
+    ignore_case: bool,
+    format: Format,
+    sender: SyncSender<String>,
+}
+
+impl Run {
+    fn search(&self, root: &Path, target: &Path) {
+        let start = Instant::now();
+        let mut count = 0;
+        // If the client disconnects the stream is dropped, and there is no reason to go on
+        if !self.send(self.format.start(&self.query, &self.path)) {
+            return;
+        }
+
+        for file in files(target) {
+            // A file that can't be read, or isn't text, has no lines to match
+            let contents = match fs::read_to_string(&file) {
+                Ok(contents) => contents,
+                Err(error) => {
+                    book_log::debug!("file skipped"; file = file, error = error);
+                    continue;
+                }
+            };
+            let lines = if self.ignore_case {
+                c12_minigrep::search_case_insensitive(&self.query, &contents)
+            } else {
+                c12_minigrep::search(&self.query, &contents)
+            };
+            if lines.is_empty() {
+                continue;
+            }
+
+            let name = file
+                .strip_prefix(root)
+                .unwrap_or(&file)
+                .display()
+                .to_string();
+            if !self.send(self.format.matches(&name, &lines, count == 0)) {
+                return;
+            }
+            count += lines.len();
+        }
+
+        self.send(self.format.end(count));
+        book_log::info!(
+            "search finished";
+            query = self.query,
+            path = self.path,
+            ignore_case = self.ignore_case,
+            matches = count,
+            elapsed = start.elapsed(),
+        );
+    }
+
+    /// Sends a chunk of the body, returning whether the client is still waiting for it.
+    fn send(&self, chunk: String) -> bool {
+        let sent = self.sender.send(chunk).is_ok();
+        if !sent {
+            book_log::debug!("search abandoned"; query = self.query, path = self.path);
+        }
+        sent
+    }
+}
+
+/// Lists the files at the path: the path itself if it's a file, or all the files under it, sorted, if it's a directory.
+///
+/// The symbolic links inside a directory are not followed, they could point outside of the root.
+fn files(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    let mut entries: Vec<PathBuf> = match fs::read_dir(path) {
+        Ok(entries) => entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .collect(),
+        Err(error) => {
+            book_log::debug!("directory skipped"; path = path, error = error);
+            return Vec::new();
+        }
+    };
+    entries.sort();
+
+    let mut files = Vec::new();
+    for entry in entries {
+        match fs::symlink_metadata(&entry) {
+            Ok(metadata) if metadata.is_dir() => files.extend(self::files(&entry)),
+            Ok(metadata) if metadata.is_file() => files.push(entry),
+            _ => {}
+        }
+    }
+    files
+}
@@ -0,0 +1,52 @@
+//! Benchmarks of the `ThreadPool`, run with `cargo test --test bench -- --include-ignored --show-output`
+//! They only check that the timings are consistent, as the actual values depend on the machine.
+//! The comparison of the parallel jobs is ignored by default as its result depends on the load of the machine
+
+use std::sync::mpsc;
+use std::time::Duration;
+
+use c11_automated_tests::bench::time_it;
+use c21_web_server::ThreadPool;
+
+#[test]
+fn dispatch_to_a_worker() {
+    let pool = ThreadPool::new(4);
+    let (sender, receiver) = mpsc::channel();
+
+    // Each run sends a job to the pool and waits for a worker to run it
+    let stats = time_it(500, || {
+        let sender = sender.clone();
+        pool.execute(move || sender.send(()).unwrap());
+        receiver.recv().unwrap();
+    });
+    println!("dispatch of a job, {stats}");
+
+    assert!(stats.min <= stats.p95);
+    // Even on a busy machine a worker picks the job long before a second
+    assert!(stats.p95 < Duration::from_secs(1));
+}
+
+#[test]
+#[ignore = "depends on the load of the machine"]
+fn jobs_run_in_parallel() {
+    let pool = ThreadPool::new(4);
+    let (sender, receiver) = mpsc::channel();
+
+    let run = |jobs: usize| {
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            pool.execute(move || {
+                std::thread::sleep(Duration::from_millis(5));
+                sender.send(()).unwrap();
+            });
+        }
+        receiver.iter().take(jobs).count()
+    };
+    let one = time_it(10, || run(1));
+    let four = time_it(10, || run(4));
+    println!("one job, {one}");
+    println!("four jobs on four workers, {four}");
+
+    // Four jobs on four workers take about as long as one, not four times as long
+    assert!(four.min < one.min * 3);
+}
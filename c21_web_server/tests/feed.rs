@@ -0,0 +1,120 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use c10_generics_traits_lifetimes::{Feed, Tweet};
+use c21_web_server::feed;
+use c21_web_server::router::{Request, Response, Router};
+
+fn router(feed: &Arc<Mutex<Feed>>) -> Router {
+    let mut router = Router::new();
+    feed::routes(&mut router, Arc::clone(feed));
+    router
+}
+
+fn send(router: &Router, request: &str) -> Response {
+    let (line, body) = request.split_once('\n').unwrap_or((request, ""));
+    let mut request = Request::parse(line).unwrap();
+    request.body = body.to_string();
+    router.dispatch(&request)
+}
+
+#[test]
+fn lists_and_fetches_the_items() {
+    let feed = Arc::new(Mutex::new(Feed::new()));
+    feed.lock().unwrap().push(Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        reply: false,
+        retweet: false,
+    });
+    let router = router(&feed);
+
+    let response = send(&router, "GET /feed HTTP/1.1");
+    assert_eq!(response.content_type, Some("application/json"));
+    assert_eq!(
+        response.body,
+        r#"{"items":[{"id":0,"author":"@horse_ebooks","summary":"horse_ebooks: of course, as you probably already know, people"}],"count":1}"#
+    );
+
+    let response = send(&router, "GET /feed/item?id=0 HTTP/1.1");
+    assert_eq!(
+        response.body,
+        r#"{"id":0,"author":"@horse_ebooks","summary":"horse_ebooks: of course, as you probably already know, people"}"#
+    );
+    let response = send(&router, "GET /feed/item?id=0&format=html HTTP/1.1");
+    assert_eq!(response.content_type, Some("text/html; charset=utf-8"));
+    assert!(
+        response
+            .body
+            .contains("<p>By @horse_ebooks, item 0 of the feed.</p>")
+    );
+
+    assert_eq!(send(&router, "GET /feed/item?id=1 HTTP/1.1").status, 404);
+    assert_eq!(
+        send(&router, "GET /feed/item?id=first HTTP/1.1").status,
+        400
+    );
+    assert_eq!(send(&router, "GET /feed?format=xml HTTP/1.1").status, 400);
+}
+
+#[test]
+fn posts_new_items() {
+    let feed = Arc::new(Mutex::new(Feed::new()));
+    let router = router(&feed);
+
+    let response = send(
+        &router,
+        "POST /feed/tweets HTTP/1.1\nusername=ferris&content=Hello%2C+%3Cworld%3E%21&reply=on",
+    );
+    assert_eq!(response.status, 201);
+    assert_eq!(
+        response.body,
+        r#"{"id":0,"author":"@ferris","summary":"ferris: Hello, <world>!"}"#
+    );
+
+    let response = send(
+        &router,
+        "POST /feed/articles?format=html HTTP/1.1\nheadline=Rust+2024&location=Online&author=Rust+Team&content=Released",
+    );
+    assert_eq!(response.status, 201);
+    assert!(
+        response
+            .body
+            .contains("<h1>(Read more from Rust Team...)</h1>")
+    );
+
+    let response = send(
+        &router,
+        "POST /feed/tweets HTTP/1.1\nusername=ferris&content=",
+    );
+    assert_eq!(
+        response,
+        Response::bad_request("Missing the `content` field")
+    );
+
+    // The page escapes the summaries, and the handle of the test sees the items pushed by the server
+    assert_eq!(feed.lock().unwrap().len(), 2);
+    let page = send(&router, "GET /feed?format=html HTTP/1.1").body;
+    assert!(page.contains(
+        "<li><a href=\"/feed/item?id=0&amp;format=html\">ferris: Hello, &lt;world&gt;!</a></li>"
+    ));
+    assert!(page.contains(r#"<form method="post" action="/feed/tweets?format=html">"#));
+}
+
+#[test]
+fn posts_over_a_connection() {
+    let feed = Arc::new(Mutex::new(Feed::new()));
+    let router = router(&feed);
+    let body = "username=ferris&content=Hi";
+    let request = format!(
+        "POST /feed/tweets HTTP/1.1\r\nContent-Type: application/x-www-form-urlencoded\r\nContent-Length: {}\r\n\r\n{body}",
+        body.len()
+    );
+    let mut stream = Cursor::new(request.into_bytes());
+
+    router.handle_connection(&mut stream);
+
+    let written = String::from_utf8(stream.into_inner()).unwrap();
+    assert!(written.contains("HTTP/1.1 201 CREATED\r\nContent-Type: application/json\r\n"));
+    assert_eq!(feed.lock().unwrap().summaries(), ["ferris: Hi"]);
+}
@@ -0,0 +1,67 @@
+//! The events of the server, in a single test as the sink of `book_log` is global
+
+use std::io::Cursor;
+use std::sync::Arc;
+
+use book_log::{Event, Level, MemorySink};
+use c21_web_server::ThreadPool;
+use c21_web_server::router::{Method, Response, Router};
+
+fn with_message<'a>(events: &'a [Event], message: &'a str) -> impl Iterator<Item = &'a Event> {
+    events.iter().filter(move |event| event.message == message)
+}
+
+#[test]
+fn requests_and_jobs_are_logged() {
+    let sink = Arc::new(MemorySink::new());
+    book_log::set_sink(sink.clone());
+    book_log::set_max_level(Level::Debug);
+
+    let pool = ThreadPool::new(2);
+    for _ in 0..3 {
+        let mut router = Router::new();
+        router.route(Method::Get, "/", |_| Response::ok("Hello!"));
+        pool.execute(move || {
+            router.handle_connection(Cursor::new(b"GET / HTTP/1.1\r\n\r\n".to_vec()));
+            router.handle_connection(Cursor::new(b"HELLO\r\n\r\n".to_vec()));
+        });
+    }
+    drop(pool);
+
+    let events = sink.take();
+    let messages = |message| with_message(&events, message);
+
+    assert_eq!(
+        events[0].to_string(),
+        "INFO  c21_web_server: thread pool started threads=2"
+    );
+    let queued: Vec<_> = messages("job queued")
+        .filter_map(|event| event.field("job"))
+        .collect();
+    assert_eq!(queued, ["0", "1", "2"]);
+    assert_eq!(messages("job started").count(), 3);
+    assert_eq!(messages("job finished").count(), 3);
+    assert_eq!(messages("worker shut down").count(), 2);
+    assert_eq!(
+        events.last().unwrap().to_string(),
+        "INFO  c21_web_server: thread pool shut down jobs=3"
+    );
+
+    // Every request is logged from the worker running its job, and each job is finished by the worker that started it
+    let handled: Vec<_> = messages("request handled").collect();
+    assert_eq!(handled.len(), 3);
+    assert_eq!(handled[0].target, "c21_web_server::router");
+    assert_eq!(handled[0].field("method"), Some(r#""GET""#));
+    assert_eq!(handled[0].field("status"), Some("200"));
+    assert_eq!(
+        messages("bad request").next().unwrap().field("line"),
+        Some(r#""HELLO""#)
+    );
+    for started in messages("job started") {
+        let job = started.field("job");
+        let finished = messages("job finished")
+            .find(|event| event.field("job") == job)
+            .unwrap();
+        assert_eq!(started.field("worker"), finished.field("worker"));
+    }
+}
@@ -0,0 +1,23 @@
+use std::sync::mpsc;
+
+use c21_web_server::{PoolCreationError, ThreadPool};
+
+#[test]
+fn build_rejects_an_empty_pool() {
+    assert_eq!(
+        ThreadPool::build(0).err(),
+        Some(PoolCreationError::ZeroThreads)
+    );
+    assert_eq!(
+        PoolCreationError::ZeroThreads.to_string(),
+        "a thread pool needs at least one thread"
+    );
+}
+
+#[test]
+fn built_pools_run_jobs() {
+    let pool = ThreadPool::build(2).unwrap();
+    let (sender, receiver) = mpsc::channel();
+    pool.execute(move || sender.send(42).unwrap());
+    assert_eq!(receiver.recv().unwrap(), 42);
+}
@@ -0,0 +1,45 @@
+use c20_advanced_features::route;
+use c21_web_server::router::{Request, Response, Router};
+
+#[route(GET, "/")]
+fn index(_: &Request) -> Response {
+    Response::ok("Hello!")
+}
+
+#[route(POST, "/echo")]
+fn echo(request: &Request) -> Response {
+    Response::ok(format!("{} {}", request.method, request.path))
+}
+
+fn router() -> Router {
+    let mut router = Router::new();
+    router.register(register_index).register(register_echo);
+    router
+}
+
+#[test]
+fn routes_are_registered() {
+    let router = router();
+
+    assert_eq!(
+        Response::ok("Hello!"),
+        router.dispatch(&Request::parse("GET / HTTP/1.1").unwrap())
+    );
+    assert_eq!(
+        Response::ok("POST /echo"),
+        router.dispatch(&Request::parse("POST /echo HTTP/1.1").unwrap())
+    );
+    assert_eq!(
+        404,
+        router
+            .dispatch(&Request::parse("GET /echo HTTP/1.1").unwrap())
+            .status
+    );
+}
+
+#[test]
+fn handlers_are_still_functions() {
+    let request = Request::parse("GET / HTTP/1.1").unwrap();
+
+    assert_eq!(Response::ok("Hello!"), index(&request));
+}
@@ -0,0 +1,142 @@
+use std::io::Cursor;
+use std::{env, fs, process};
+
+use c21_web_server::router::{Method, Request, Response, Router};
+use c21_web_server::search::Search;
+
+// The tests run in the directory of the crate, the files of the chapters are one level up
+fn router(root: &str) -> Router {
+    let search = Search::new(root, 2);
+    let mut router = Router::new();
+    router.stream_route(Method::Get, "/search", move |request| {
+        search.handle(request)
+    });
+    router
+}
+
+fn get(router: &Router, target: &str) -> Response {
+    router.dispatch(&Request::parse(&format!("GET {target} HTTP/1.1")).unwrap())
+}
+
+#[test]
+fn searches_a_file() {
+    let router = router("../utils");
+
+    let response = get(&router, "/search?q=body&path=poem.txt");
+    assert_eq!(response.status, 200);
+    assert_eq!(response.content_type, Some("application/json"));
+    assert_eq!(
+        response.body,
+        r#"{"query":"body","path":"poem.txt","matches":[{"file":"poem.txt","text":"I'm nobody! Who are you?"},{"file":"poem.txt","text":"Are you nobody, too?"},{"file":"poem.txt","text":"How dreary to be somebody!"}],"count":3}"#
+    );
+
+    let response = get(&router, "/search?q=to&path=poem.txt");
+    assert!(response.body.ends_with(r#""count":2}"#));
+    let response = get(&router, "/search?q=to&path=poem.txt&ignore_case");
+    assert!(response.body.ends_with(r#""count":4}"#));
+}
+
+#[test]
+fn searches_a_directory_as_a_page() {
+    let router = router("../utils");
+
+    let response = get(&router, "/search?q=Who+are+you%3F&path=.&format=html");
+    assert_eq!(response.content_type, Some("text/html; charset=utf-8"));
+    assert!(
+        response
+            .body
+            .contains("<title>Search for Who are you?</title>")
+    );
+    assert!(
+        response
+            .body
+            .contains("      <li><code>poem.txt</code>: I&#39;m nobody! Who are you?</li>\n")
+    );
+    assert!(response.body.contains("<p>1 matches</p>"));
+    assert!(response.body.ends_with("</html>\n"));
+}
+
+#[test]
+fn rejects_invalid_searches() {
+    let router = router("../utils");
+
+    assert_eq!(get(&router, "/search?path=poem.txt").status, 400);
+    assert_eq!(get(&router, "/search?q=&path=poem.txt").status, 400);
+    assert_eq!(get(&router, "/search?q=body").status, 400);
+    assert_eq!(
+        get(&router, "/search?q=body&path=poem.txt&format=xml").status,
+        400
+    );
+    assert_eq!(get(&router, "/search?q=body&path=poem.md").status, 404);
+    // Nothing outside of the root can be searched
+    assert_eq!(
+        get(&router, "/search?q=name&path=../Cargo.toml").status,
+        400
+    );
+    assert_eq!(
+        get(&router, "/search?q=root&path=%2Fetc%2Fpasswd").status,
+        400
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn symbolic_links_cannot_leave_the_root() {
+    use std::os::unix::fs::symlink;
+
+    let dir = env::temp_dir().join(format!("c21_search_links_{}", process::id()));
+    fs::create_dir_all(dir.join("root")).unwrap();
+    fs::create_dir_all(dir.join("outside")).unwrap();
+    fs::write(dir.join("outside/secret.txt"), "the password\n").unwrap();
+    fs::write(dir.join("root/notes.txt"), "no password here\n").unwrap();
+    symlink(dir.join("outside"), dir.join("root/outside")).unwrap();
+    symlink(dir.join("outside/secret.txt"), dir.join("root/secret.txt")).unwrap();
+    symlink(dir.join("root/notes.txt"), dir.join("root/alias.txt")).unwrap();
+    let router = router(dir.join("root").to_str().unwrap());
+
+    let escaping = [
+        get(&router, "/search?q=password&path=secret.txt"),
+        get(&router, "/search?q=password&path=outside"),
+        get(&router, "/search?q=password&path=outside%2Fsecret.txt"),
+    ];
+    // A link to another file of the root is still allowed
+    let alias = get(&router, "/search?q=password&path=alias.txt");
+    fs::remove_dir_all(&dir).unwrap();
+
+    for response in escaping {
+        assert_eq!(response.status, 400);
+    }
+    assert_eq!(alias.status, 200);
+    assert!(alias.body.contains("no password here"));
+}
+
+#[test]
+fn streams_large_results() {
+    let dir = env::temp_dir().join(format!("c21_search_{}", process::id()));
+    fs::create_dir_all(dir.join("chapters")).unwrap();
+    let lines: String = (0..10_000)
+        .map(|n| format!("line {n} of the book\n"))
+        .collect();
+    for file in ["a.txt", "b.txt", "chapters/c.txt"] {
+        fs::write(dir.join(file), &lines).unwrap();
+    }
+    let router = router(dir.to_str().unwrap());
+
+    let mut stream = Cursor::new(b"GET /search?q=book&path=. HTTP/1.1\r\n\r\n".to_vec());
+    router.handle_connection(&mut stream);
+    let written = String::from_utf8(stream.into_inner()).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    // The cursor holds the request too, the response is written after it
+    let response = &written[written.find("HTTP/1.1 200 OK").unwrap()..];
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    assert!(head.contains("Transfer-Encoding: chunked"));
+    assert!(!head.contains("Content-Length"));
+    // The start, a chunk for each file, in order, and the end
+    let chunks: Vec<&str> = body.split("\r\n").skip(1).step_by(2).collect();
+    assert_eq!(chunks.len(), 6);
+    assert!(chunks[1].starts_with(r#"{"file":"a.txt","text":"line 0 of the book"}"#));
+    assert!(chunks[3].starts_with(r#",{"file":"chapters/c.txt","text":"line 0 of the book"}"#));
+    assert_eq!(chunks[4], r#"],"count":30000}"#);
+    assert_eq!(chunks[5], "");
+}